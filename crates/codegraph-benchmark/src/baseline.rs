@@ -1,32 +1,33 @@
-//! SimpleVectorRAG - Baseline implementation using only Qdrant vector search
+//! SimpleVectorRAG - Baseline implementation using only vector search
 //!
 //! This baseline implementation provides a simple vector-only retrieval system
 //! without NARS reasoning or graph pattern matching. It serves as a reference
 //! for comparing the hybrid GraphRAG+NARS system.
 
 use async_trait::async_trait;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
-use codegraph_vector::{QdrantRepository, SearchFilter};
+use codegraph_vector::{SearchFilter, VectorStore};
 
-use crate::error::Result;
+use crate::error::{BenchmarkError, Result};
 use crate::models::{BenchmarkQuery, QueryResult};
 use crate::retriever::Retriever;
 
 /// SimpleVectorRAG baseline - vector-only retrieval without NARS or graph patterns
 #[derive(Clone)]
 pub struct SimpleVectorRAG {
-    /// Qdrant repository for vector operations
-    repository: QdrantRepository,
+    /// Vector store (Qdrant or in-memory)
+    repository: Arc<dyn VectorStore>,
     /// Default number of results to return
     default_limit: u64,
 }
 
 impl SimpleVectorRAG {
     /// Create a new SimpleVectorRAG instance
-    pub fn new(repository: QdrantRepository) -> Self {
+    pub fn new(repository: Arc<dyn VectorStore>) -> Self {
         Self {
             repository,
             default_limit: 10,
@@ -83,7 +84,8 @@ impl Retriever for SimpleVectorRAG {
         let results = self
             .repository
             .search_all(embedding, limit, filter)
-            .await?;
+            .await
+            .map_err(|e| BenchmarkError::QueryFailed(e.to_string()))?;
 
         let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
 
@@ -102,10 +104,30 @@ impl Retriever for SimpleVectorRAG {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use codegraph_core::MemoryVectorStore;
+    use codegraph_vector::EmbeddingPoint;
+    use codegraph_vector::models::PointPayload;
+
     #[test]
     fn test_simple_vector_rag_name() {
-        // We can't create a real SimpleVectorRAG without Qdrant, but we can test the name
-        // This is a placeholder for integration tests
-        assert_eq!("SimpleVectorRAG", "SimpleVectorRAG");
+        let baseline = SimpleVectorRAG::new(Arc::new(MemoryVectorStore::new()));
+        assert_eq!(baseline.name(), "SimpleVectorRAG");
+    }
+
+    #[tokio::test]
+    async fn test_search_against_memory_store() {
+        let store = Arc::new(MemoryVectorStore::new());
+        let id = Uuid::new_v4();
+        let payload = PointPayload::new("Primary Button", "button", "component", "material");
+        store
+            .upsert_point("ui_material", EmbeddingPoint::new(id, vec![1.0, 0.0], payload))
+            .await
+            .unwrap();
+
+        let baseline = SimpleVectorRAG::new(store);
+        let query = BenchmarkQuery::new("primary button", vec![id]).with_category("button");
+        let result = baseline.search(&query, vec![1.0, 0.0], 5).await.unwrap();
+        assert_eq!(result.returned_ids, vec![id]);
     }
 }
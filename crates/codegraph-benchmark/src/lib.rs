@@ -92,7 +92,7 @@ pub async fn run() -> anyhow::Result<()> {
     info!("Loaded {} benchmark queries", dataset.queries.len());
 
     // Create baseline retriever
-    let baseline = SimpleVectorRAG::new(qdrant_repo);
+    let baseline = SimpleVectorRAG::new(std::sync::Arc::new(qdrant_repo));
     info!("Running baseline (SimpleVectorRAG) benchmark...");
     let baseline_metrics = runner.run_single(&baseline, &dataset).await?;
 
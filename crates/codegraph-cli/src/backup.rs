@@ -0,0 +1,463 @@
+//! Backup archives
+//!
+//! A backup is one gzipped tar holding `manifest.json` (always the first
+//! entry), the graph as `graph.json`, the points of each vector collection as
+//! `vectors/<collection>.jsonl` and the feedback rows as `feedback.jsonl`.
+//! The manifest records the record count and SHA-256 of every other entry;
+//! restore checks both before writing anything. The Redis cache is not part
+//! of a backup: it only holds results derived from the other stores.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+use uuid::Uuid;
+
+use codegraph_core::{EmbeddingPoint, Feedback, FeedbackStore, GraphDump, GraphStore, VectorStore};
+
+/// Version of the archive layout, bumped on incompatible changes
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const GRAPH: &str = "graph.json";
+const FEEDBACK: &str = "feedback.jsonl";
+const VECTORS_DIR: &str = "vectors/";
+
+/// Records read from or written to a store per request
+const PAGE_SIZE: usize = 256;
+
+/// Stores a backup is taken from or restored into
+pub struct Stores {
+    pub graph: Arc<dyn GraphStore>,
+    pub vectors: Arc<dyn VectorStore>,
+    /// `None` when no feedback store is configured
+    pub feedback: Option<Arc<dyn FeedbackStore>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Version of the `codegraph` binary that wrote the archive
+    pub codegraph_version: String,
+    /// Storage backend the backup was taken from
+    pub storage: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    /// Number of records (graph nodes and relationships, points or feedback rows)
+    pub count: u64,
+    /// Hex SHA-256 of the entry's contents
+    pub sha256: String,
+}
+
+/// Contents of the stores, read one store after another
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    /// When reading started; feedback saved later is left out
+    pub taken_at: DateTime<Utc>,
+    pub graph: GraphDump,
+    /// Points by collection
+    pub vectors: BTreeMap<String, Vec<EmbeddingPoint>>,
+    /// `None` when the backup was taken without a feedback store
+    pub feedback: Option<Vec<Feedback>>,
+}
+
+impl Snapshot {
+    /// Read everything from `stores`
+    ///
+    /// The snapshot is not point-in-time across stores: the graph, each
+    /// collection and the feedback are read in turn, so a write made
+    /// meanwhile can reach the graph but miss the vectors (restore reports
+    /// such orphans). Feedback is paged by `(created_at, id)` up to
+    /// [`taken_at`](Self::taken_at), so rows saved during the backup are
+    /// neither repeated nor shift others out of it.
+    pub async fn take(stores: &Stores) -> Result<Self> {
+        let taken_at = Utc::now();
+        let graph = stores.graph.export_graph().await.context("failed to export the graph")?;
+
+        let mut vectors = BTreeMap::new();
+        for info in stores.vectors.all_collections_info().await? {
+            let mut points = Vec::new();
+            let mut offset = None;
+            loop {
+                let (page, next) = stores
+                    .vectors
+                    .scroll_points(&info.name, offset, PAGE_SIZE)
+                    .await
+                    .with_context(|| format!("failed to read collection {}", info.name))?;
+                points.extend(page);
+                match next {
+                    Some(next) => offset = Some(next),
+                    None => break,
+                }
+            }
+            if !points.is_empty() {
+                vectors.insert(info.name, points);
+            }
+        }
+
+        let feedback = match &stores.feedback {
+            Some(store) => {
+                let mut records: Vec<Feedback> = Vec::new();
+                loop {
+                    let after = records.last().map(|f| (f.created_at, f.id));
+                    let page = store.list_until(taken_at, after, PAGE_SIZE as i64).await?;
+                    let done = page.len() < PAGE_SIZE;
+                    records.extend(page);
+                    if done {
+                        break;
+                    }
+                }
+                Some(records)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            taken_at,
+            graph,
+            vectors,
+            feedback,
+        })
+    }
+
+    fn graph_count(&self) -> u64 {
+        let graph = &self.graph;
+        (graph.elements.len() + graph.snippets.len() + graph.design_systems.len() + graph.tokens.len() + graph.relations.len())
+            as u64
+    }
+
+    pub fn point_count(&self) -> u64 {
+        self.vectors.values().map(|points| points.len() as u64).sum()
+    }
+}
+
+/// Write `snapshot` as an archive, returning its manifest
+pub fn write(snapshot: &Snapshot, storage: &str, writer: impl Write) -> Result<Manifest> {
+    let mut files: Vec<(String, u64, Vec<u8>)> = Vec::new();
+    files.push((GRAPH.to_string(), snapshot.graph_count(), serde_json::to_vec(&snapshot.graph)?));
+    for (collection, points) in &snapshot.vectors {
+        files.push((format!("{}{}.jsonl", VECTORS_DIR, collection), points.len() as u64, json_lines(points)?));
+    }
+    if let Some(feedback) = &snapshot.feedback {
+        files.push((FEEDBACK.to_string(), feedback.len() as u64, json_lines(feedback)?));
+    }
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        created_at: snapshot.taken_at,
+        codegraph_version: env!("CARGO_PKG_VERSION").to_string(),
+        storage: storage.to_string(),
+        entries: files
+            .iter()
+            .map(|(path, count, contents)| ManifestEntry { path: path.clone(), count: *count, sha256: sha256(contents) })
+            .collect(),
+    };
+
+    let mut archive = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+    append(&mut archive, MANIFEST, &serde_json::to_vec_pretty(&manifest)?, manifest.created_at)?;
+    for (path, _, contents) in &files {
+        append(&mut archive, path, contents, manifest.created_at)?;
+    }
+    archive.into_inner()?.finish()?.flush()?;
+    Ok(manifest)
+}
+
+fn append<W: Write>(archive: &mut tar::Builder<W>, path: &str, contents: &[u8], mtime: DateTime<Utc>) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime.timestamp().max(0) as u64);
+    archive.append_data(&mut header, path, contents)?;
+    Ok(())
+}
+
+fn json_lines<T: Serialize>(records: &[T]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut out, record)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+fn sha256(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+/// Read an archive, checking its format version and every entry's checksum and count
+pub fn read(reader: impl Read) -> Result<(Manifest, Snapshot)> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+    for entry in archive.entries().context("not a backup archive")? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        files.insert(path, contents);
+    }
+
+    let manifest: Manifest = match files.remove(MANIFEST) {
+        Some(contents) => serde_json::from_slice(&contents).context("invalid manifest")?,
+        None => bail!("not a backup archive: no {}", MANIFEST),
+    };
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "backup format version {} is newer than the supported version {}",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+
+    let mut snapshot = Snapshot {
+        taken_at: manifest.created_at,
+        ..Snapshot::default()
+    };
+    for entry in &manifest.entries {
+        let contents = files
+            .remove(&entry.path)
+            .with_context(|| format!("{} is listed in the manifest but missing", entry.path))?;
+        if sha256(&contents) != entry.sha256 {
+            bail!("checksum mismatch in {}", entry.path);
+        }
+        let count = if entry.path == GRAPH {
+            snapshot.graph = serde_json::from_slice(&contents).context("invalid graph.json")?;
+            snapshot.graph_count()
+        } else if entry.path == FEEDBACK {
+            let feedback: Vec<Feedback> = parse_lines(&entry.path, &contents)?;
+            let count = feedback.len() as u64;
+            snapshot.feedback = Some(feedback);
+            count
+        } else if let Some(collection) = entry.path.strip_prefix(VECTORS_DIR).and_then(|p| p.strip_suffix(".jsonl")) {
+            let points: Vec<EmbeddingPoint> = parse_lines(&entry.path, &contents)?;
+            let count = points.len() as u64;
+            snapshot.vectors.insert(collection.to_string(), points);
+            count
+        } else {
+            bail!("unknown entry {} in the manifest", entry.path);
+        };
+        if count != entry.count {
+            bail!("{} holds {} records, the manifest says {}", entry.path, count, entry.count);
+        }
+    }
+    if let Some(path) = files.keys().next() {
+        bail!("{} is not listed in the manifest", path);
+    }
+    Ok((manifest, snapshot))
+}
+
+fn parse_lines<T: DeserializeOwned>(path: &str, contents: &[u8]) -> Result<Vec<T>> {
+    contents
+        .split(|&b| b == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| serde_json::from_slice(line).with_context(|| format!("{}:{}", path, i + 1)))
+        .collect()
+}
+
+/// Points and elements that do not match up
+#[derive(Debug, Default, PartialEq)]
+pub struct Orphans {
+    /// Points (collection, ID) whose element is not in the graph
+    pub vectors_without_node: Vec<(String, Uuid)>,
+    /// Elements with an embedding but no point in any collection
+    pub nodes_without_vector: Vec<Uuid>,
+}
+
+impl Orphans {
+    pub fn is_empty(&self) -> bool {
+        self.vectors_without_node.is_empty() && self.nodes_without_vector.is_empty()
+    }
+}
+
+/// Compare the points of `snapshot` against its graph elements
+pub fn orphans(snapshot: &Snapshot) -> Orphans {
+    let elements: HashSet<Uuid> = snapshot.graph.elements.iter().map(|e| e.id).collect();
+    let points: HashSet<Uuid> = snapshot.vectors.values().flatten().map(|p| p.id).collect();
+
+    let vectors_without_node = snapshot
+        .vectors
+        .iter()
+        .flat_map(|(collection, points)| points.iter().map(move |p| (collection, p.id)))
+        .filter(|(_, id)| !elements.contains(id))
+        .map(|(collection, id)| (collection.clone(), id))
+        .collect();
+    let nodes_without_vector = snapshot
+        .graph
+        .elements
+        .iter()
+        .filter(|e| e.embedding.is_some() && !points.contains(&e.id))
+        .map(|e| e.id)
+        .collect();
+    Orphans { vectors_without_node, nodes_without_vector }
+}
+
+/// Print the orphans found in a backup
+pub fn print_orphans(orphans: &Orphans) {
+    if orphans.is_empty() {
+        println!("Consistent: every point has an element and every embedded element a point");
+        return;
+    }
+    if !orphans.vectors_without_node.is_empty() {
+        println!("{} points without an element:", orphans.vectors_without_node.len());
+        for (collection, id) in &orphans.vectors_without_node {
+            println!("  {} {}", collection, id);
+        }
+    }
+    if !orphans.nodes_without_vector.is_empty() {
+        println!("{} embedded elements without a point:", orphans.nodes_without_vector.len());
+        for id in &orphans.nodes_without_vector {
+            println!("  {}", id);
+        }
+    }
+}
+
+/// Write `snapshot` into `stores`, merging with what they hold
+pub async fn restore(stores: &Stores, snapshot: &Snapshot) -> Result<()> {
+    let written = stores.graph.import_graph(&snapshot.graph).await.context("failed to import the graph")?;
+    info!("Restored {} graph nodes and relationships", written);
+
+    stores.vectors.init_collections().await?;
+    for (collection, points) in &snapshot.vectors {
+        for chunk in points.chunks(PAGE_SIZE) {
+            stores
+                .vectors
+                .upsert_batch(collection, chunk.to_vec())
+                .await
+                .with_context(|| format!("failed to restore collection {}", collection))?;
+        }
+        info!("Restored {} points into {}", points.len(), collection);
+    }
+
+    match (&snapshot.feedback, &stores.feedback) {
+        (Some(feedback), Some(store)) => {
+            let imported = store.import_feedback(feedback).await.context("failed to restore feedback")?;
+            info!("Restored {} of {} feedback records (the rest already existed)", imported, feedback.len());
+        }
+        (Some(feedback), None) if !feedback.is_empty() => {
+            bail!("the backup holds {} feedback records but no feedback store is configured", feedback.len())
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codegraph_core::{FeedbackType, PointPayload, UIElement};
+
+    fn snapshot() -> Snapshot {
+        let mut button = UIElement::new("Button", "button");
+        button.embedding = Some(vec![0.5, 0.5]);
+        let card = UIElement::new("Card", "card");
+        let mut orphan_element = UIElement::new("Modal", "modal");
+        orphan_element.embedding = Some(vec![1.0, 0.0]);
+
+        let point = |id| EmbeddingPoint::new(id, vec![0.5, 0.5], PointPayload::new("Button", "button", "component", "custom"));
+        let orphan_point = Uuid::from_u128(7);
+        Snapshot {
+            taken_at: Utc::now(),
+            graph: GraphDump { elements: vec![button.clone(), card, orphan_element.clone()], ..Default::default() },
+            vectors: BTreeMap::from([("ui_custom".to_string(), vec![point(button.id), point(orphan_point)])]),
+            feedback: Some(vec![Feedback {
+                id: Uuid::from_u128(1),
+                generation_id: Uuid::from_u128(2),
+                element_ids: vec![button.id],
+                feedback_type: FeedbackType::ThumbsUp,
+                query_context: None,
+                comment: Some("nice".into()),
+                confidence_delta: 0.1,
+                created_at: Utc::now(),
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let original = snapshot();
+        let mut archive = Vec::new();
+        let manifest = write(&original, "embedded", &mut archive).unwrap();
+        assert_eq!(manifest.entries.len(), 3);
+
+        let (read_manifest, restored) = read(archive.as_slice()).unwrap();
+        assert_eq!(read_manifest.format_version, FORMAT_VERSION);
+        assert_eq!(read_manifest.storage, "embedded");
+        assert_eq!(restored.graph.elements.len(), 3);
+        assert_eq!(restored.point_count(), 2);
+        assert_eq!(restored.feedback.unwrap()[0].comment.as_deref(), Some("nice"));
+    }
+
+    #[tokio::test]
+    async fn test_take_pages_feedback_oldest_first_up_to_start() {
+        use codegraph_core::{MemoryFeedbackStore, MemoryGraphStore, MemoryVectorStore};
+
+        let feedback = Arc::new(MemoryFeedbackStore::new());
+        let records: Vec<Feedback> = (0..PAGE_SIZE as u128 * 2 + 10)
+            .map(|i| Feedback {
+                id: Uuid::from_u128(i),
+                generation_id: Uuid::from_u128(i),
+                element_ids: Vec::new(),
+                feedback_type: FeedbackType::ThumbsUp,
+                query_context: None,
+                comment: None,
+                confidence_delta: 0.1,
+                // Pairs share a timestamp, so pages split ties by ID
+                created_at: DateTime::from_timestamp(1_700_000_000 + (i / 2) as i64, 0).unwrap(),
+            })
+            .collect();
+        feedback.import_feedback(&records).await.unwrap();
+        let mut late = records[0].clone();
+        late.id = Uuid::new_v4();
+        late.created_at = Utc::now() + chrono::Duration::hours(1);
+        feedback.import_feedback(&[late]).await.unwrap();
+
+        let stores = Stores {
+            graph: Arc::new(MemoryGraphStore::new()),
+            vectors: Arc::new(MemoryVectorStore::new()),
+            feedback: Some(feedback),
+        };
+        let snapshot = Snapshot::take(&stores).await.unwrap();
+        let ids: Vec<Uuid> = snapshot.feedback.unwrap().iter().map(|f| f.id).collect();
+        assert_eq!(ids, records.iter().map(|f| f.id).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_read_rejects_tampered_entries() {
+        let mut archive = Vec::new();
+        let mut manifest = write(&snapshot(), "embedded", &mut archive).unwrap();
+
+        // Re-pack with a manifest whose graph checksum no longer matches
+        manifest.entries[0].sha256 = sha256(b"other");
+        let (_, original) = read(archive.as_slice()).unwrap();
+        let mut tampered = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        append(&mut tampered, MANIFEST, &serde_json::to_vec(&manifest).unwrap(), Utc::now()).unwrap();
+        append(&mut tampered, GRAPH, &serde_json::to_vec(&original.graph).unwrap(), Utc::now()).unwrap();
+        let tampered = tampered.into_inner().unwrap().finish().unwrap();
+
+        let error = read(tampered.as_slice()).unwrap_err();
+        assert_eq!(error.to_string(), "checksum mismatch in graph.json");
+    }
+
+    #[test]
+    fn test_orphans_in_both_directions() {
+        let snapshot = snapshot();
+        let orphans = orphans(&snapshot);
+        assert_eq!(orphans.vectors_without_node, vec![("ui_custom".to_string(), Uuid::from_u128(7))]);
+        assert_eq!(orphans.nodes_without_vector, vec![snapshot.graph.elements[2].id]);
+        assert!(!orphans.is_empty());
+    }
+}
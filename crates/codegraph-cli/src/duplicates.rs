@@ -0,0 +1,142 @@
+//! Near-duplicate cluster report
+//!
+//! Groups elements connected by `SIMILAR_TO` relations into clusters so
+//! design-system owners can see which components to consolidate.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use codegraph_graph::{GraphStore, SimilarityBasis, UIElement};
+
+/// Elements connected by `SIMILAR_TO`
+#[derive(Debug)]
+pub struct Cluster {
+    pub members: Vec<UIElement>,
+    /// Lowest similarity among the edges inside the cluster
+    pub min_score: f32,
+}
+
+/// Connected components of the similarity graph, largest first
+pub fn clusters(edges: &[(Uuid, Uuid, f32)]) -> Vec<(Vec<Uuid>, f32)> {
+    let mut parent: HashMap<Uuid, Uuid> = HashMap::new();
+    fn find(parent: &mut HashMap<Uuid, Uuid>, id: Uuid) -> Uuid {
+        let next = *parent.entry(id).or_insert(id);
+        if next == id {
+            return id;
+        }
+        let root = find(parent, next);
+        parent.insert(id, root);
+        root
+    }
+
+    for (a, b, _) in edges {
+        let (root_a, root_b) = (find(&mut parent, *a), find(&mut parent, *b));
+        if root_a != root_b {
+            parent.insert(root_a.max(root_b), root_a.min(root_b));
+        }
+    }
+
+    let mut groups: HashMap<Uuid, (Vec<Uuid>, f32)> = HashMap::new();
+    let ids: Vec<Uuid> = parent.keys().copied().collect();
+    for id in ids {
+        let root = find(&mut parent, id);
+        groups.entry(root).or_insert_with(|| (Vec::new(), 1.0)).0.push(id);
+    }
+    for (a, _, score) in edges {
+        let root = find(&mut parent, *a);
+        if let Some(group) = groups.get_mut(&root) {
+            group.1 = group.1.min(*score);
+        }
+    }
+
+    let mut clusters: Vec<(Vec<Uuid>, f32)> = groups
+        .into_values()
+        .map(|(mut ids, score)| {
+            ids.sort();
+            (ids, score)
+        })
+        .collect();
+    clusters.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+    clusters
+}
+
+/// Load the structural duplicate clusters whose edges score at least `min_score`
+pub async fn find_clusters(repository: &dyn GraphStore, min_score: f32) -> anyhow::Result<Vec<Cluster>> {
+    let edges = repository
+        .similarity_edges(min_score, SimilarityBasis::Structure)
+        .await?;
+    let groups = clusters(&edges);
+
+    let ids: Vec<Uuid> = groups.iter().flat_map(|(ids, _)| ids.iter().copied()).collect();
+    let mut elements: HashMap<Uuid, UIElement> = repository
+        .find_by_ids(&ids)
+        .await?
+        .into_iter()
+        .map(|element| (element.id, element))
+        .collect();
+
+    Ok(groups
+        .into_iter()
+        .map(|(ids, min_score)| Cluster {
+            members: ids.iter().filter_map(|id| elements.remove(id)).collect(),
+            min_score,
+        })
+        .filter(|cluster| cluster.members.len() > 1)
+        .collect())
+}
+
+/// Print clusters for `codegraph duplicates`
+pub fn print(clusters: &[Cluster]) {
+    if clusters.is_empty() {
+        println!("No near-duplicate clusters found");
+        return;
+    }
+
+    println!("{} near-duplicate clusters", clusters.len());
+    for (index, cluster) in clusters.iter().enumerate() {
+        let shape = cluster
+            .members
+            .iter()
+            .find_map(|m| m.shape.as_deref())
+            .unwrap_or("-");
+        println!(
+            "\n#{} {} elements, similarity >= {:.2}, shape {}",
+            index + 1,
+            cluster.members.len(),
+            cluster.min_score,
+            shape
+        );
+        for member in &cluster.members {
+            println!(
+                "  {} [{}] {} {} ({})",
+                member.name,
+                member.category,
+                member.design_system.as_deref().unwrap_or("-"),
+                member.source_path.as_deref().unwrap_or("-"),
+                member.id
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clusters() {
+        let ids: Vec<Uuid> = (1..=5).map(Uuid::from_u128).collect();
+        let edges = vec![
+            (ids[0], ids[1], 0.9),
+            (ids[1], ids[0], 0.9),
+            (ids[1], ids[2], 0.85),
+            (ids[3], ids[4], 0.95),
+        ];
+
+        let clusters = clusters(&edges);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0], (vec![ids[0], ids[1], ids[2]], 0.85));
+        assert_eq!(clusters[1], (vec![ids[3], ids[4]], 0.95));
+    }
+}
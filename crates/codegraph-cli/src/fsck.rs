@@ -0,0 +1,357 @@
+//! Cross-store consistency check
+//!
+//! Extraction, deletion and re-embedding write the graph and the vector
+//! store separately, so the two drift apart. `fsck` compares a snapshot of
+//! both, treating the graph as authoritative, and `--repair` brings the
+//! vector store back in line with it. Elements whose embedding was never
+//! stored are embedded again from their HTML template.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::Result;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use codegraph_core::sparse;
+use codegraph_core::{EmbeddingPoint, GraphNodeRef, GraphRelation, PointPayload, UIElement};
+use codegraph_extraction::{ExtractionInput, ExtractionPipeline};
+use codegraph_vector::collection_name;
+
+use crate::backup::{self, Orphans, Snapshot, Stores};
+
+/// Payload confidence differing by less than this matches the graph
+const CONFIDENCE_TOLERANCE: f32 = 1e-4;
+
+/// A point whose payload disagrees with its element
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub collection: String,
+    pub id: Uuid,
+    /// `category`, `design_system`, `collection` or `confidence`
+    pub field: &'static str,
+    pub graph: String,
+    pub payload: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub orphans: Orphans,
+    /// Elements with an HTML template but neither a stored embedding nor a point
+    pub unembedded: Vec<Uuid>,
+    pub mismatches: Vec<Mismatch>,
+    /// Relationships with an endpoint that is not a node of the graph
+    pub dangling: Vec<GraphRelation>,
+}
+
+impl Report {
+    pub fn problems(&self) -> usize {
+        self.orphans.vectors_without_node.len()
+            + self.orphans.nodes_without_vector.len()
+            + self.unembedded.len()
+            + self.mismatches.len()
+            + self.dangling.len()
+    }
+}
+
+/// Compare the points of `snapshot` against its graph
+pub fn check(snapshot: &Snapshot) -> Report {
+    let graph = &snapshot.graph;
+    let elements: HashMap<Uuid, &UIElement> = graph.elements.iter().map(|e| (e.id, e)).collect();
+
+    let mut mismatches = Vec::new();
+    for (collection, points) in &snapshot.vectors {
+        for point in points {
+            let Some(element) = elements.get(&point.id) else { continue };
+            let mut mismatch = |field, graph: String, payload: String| {
+                mismatches.push(Mismatch { collection: collection.clone(), id: point.id, field, graph, payload });
+            };
+            let payload = &point.payload;
+            if element.category != payload.category {
+                mismatch("category", element.category.clone(), payload.category.clone());
+            }
+            if let Some(design_system) = &element.design_system {
+                if *design_system != payload.design_system {
+                    mismatch("design_system", design_system.clone(), payload.design_system.clone());
+                }
+                let expected = collection_name(design_system);
+                if expected != *collection {
+                    mismatch("collection", expected, collection.clone());
+                }
+            }
+            if let Some(confidence) = element.confidence
+                && (confidence - payload.confidence).abs() > CONFIDENCE_TOLERANCE
+            {
+                mismatch("confidence", confidence.to_string(), payload.confidence.to_string());
+            }
+        }
+    }
+
+    let points: HashSet<Uuid> = snapshot.vectors.values().flatten().map(|p| p.id).collect();
+    let unembedded = graph
+        .elements
+        .iter()
+        .filter(|e| e.embedding.is_none() && e.html_template.is_some() && !points.contains(&e.id))
+        .map(|e| e.id)
+        .collect();
+
+    let nodes: HashSet<GraphNodeRef> = graph
+        .elements
+        .iter()
+        .map(|e| GraphNodeRef::Element { id: e.id })
+        .chain(graph.snippets.iter().map(|s| GraphNodeRef::Snippet { id: s.id }))
+        .chain(graph.tokens.iter().map(|t| GraphNodeRef::DesignToken { name: t.name.clone(), theme: t.theme.clone() }))
+        .chain(graph.design_systems.iter().map(|d| GraphNodeRef::DesignSystem { name: d.name.clone() }))
+        .collect();
+    let dangling = graph
+        .relations
+        .iter()
+        .filter(|r| !nodes.contains(&r.from) || !nodes.contains(&r.to))
+        .cloned()
+        .collect();
+
+    Report { orphans: backup::orphans(snapshot), unembedded, mismatches, dangling }
+}
+
+/// Point of `element` with `vector`, its payload taken from the graph
+fn point_for(element: &UIElement, vector: Vec<f32>) -> (String, EmbeddingPoint) {
+    let design_system = element.design_system.as_deref().unwrap_or("custom");
+    let mut payload = PointPayload::new(&element.name, &element.category, &element.element_type, design_system)
+        .with_css_classes(element.css_classes.clone())
+        .with_tags(element.tags.clone());
+    if let Some(confidence) = element.confidence {
+        payload = payload.with_confidence(confidence);
+    }
+    let lexical = sparse::encode_document(&payload, element.html_template.as_deref());
+    (collection_name(design_system), EmbeddingPoint::new(element.id, vector, payload).with_sparse(lexical))
+}
+
+/// What `repair` changed
+#[derive(Debug, Default, PartialEq)]
+pub struct Repaired {
+    /// Points written from their element's stored embedding
+    pub reembedded: usize,
+    /// Elements embedded again from their HTML template, with their points
+    pub embedded: usize,
+    /// Elements whose template could not be embedded
+    pub unrepairable: Vec<Uuid>,
+    /// Points rewritten with the graph's payload
+    pub rewritten: usize,
+    pub deleted_points: usize,
+    pub deleted_relations: u64,
+}
+
+/// Fix the problems of `report`: missing points are written from the
+/// elements' stored embeddings, elements without one are embedded from their
+/// HTML template through `pipeline`, mismatched payloads are rewritten
+/// (moving points to their design system's collection), points without an
+/// element and dangling relationships are deleted
+pub async fn repair(
+    stores: &Stores,
+    snapshot: &Snapshot,
+    report: &Report,
+    pipeline: &mut ExtractionPipeline,
+) -> Result<Repaired> {
+    let elements: HashMap<Uuid, &UIElement> = snapshot.graph.elements.iter().map(|e| (e.id, e)).collect();
+    let mut repaired = Repaired::default();
+    let mut upserts: BTreeMap<String, Vec<EmbeddingPoint>> = BTreeMap::new();
+    let mut deletes: BTreeMap<String, Vec<Uuid>> = BTreeMap::new();
+
+    for id in &report.orphans.nodes_without_vector {
+        let element = elements[id];
+        let Some(embedding) = &element.embedding else { continue };
+        let (collection, point) = point_for(element, embedding.clone());
+        upserts.entry(collection).or_default().push(point);
+        repaired.reembedded += 1;
+    }
+
+    let mut embedded = Vec::new();
+    for id in &report.unembedded {
+        let element = elements[id];
+        let html = element.html_template.clone().unwrap_or_default();
+        let embedding = match pipeline.extract(ExtractionInput::new(html)).await {
+            Ok(result) => result.embedding,
+            Err(e) => {
+                warn!("Failed to extract element {} from its template: {:#}", id, e);
+                None
+            }
+        };
+        let Some(embedding) = embedding else {
+            repaired.unrepairable.push(*id);
+            continue;
+        };
+        let (collection, point) = point_for(element, embedding.embedding.clone());
+        upserts.entry(collection).or_default().push(point);
+        embedded.push(element.clone().with_embedding(embedding.embedding));
+    }
+    if !embedded.is_empty() {
+        repaired.embedded = stores.graph.save_batch(&embedded).await?;
+    }
+
+    let mismatched: HashSet<(&str, Uuid)> =
+        report.mismatches.iter().map(|m| (m.collection.as_str(), m.id)).collect();
+    for (collection, id) in mismatched {
+        let Some(point) = snapshot.vectors[collection].iter().find(|p| p.id == id) else { continue };
+        let (target, point) = point_for(elements[&id], point.vector.clone());
+        if target != collection {
+            deletes.entry(collection.to_string()).or_default().push(id);
+        }
+        upserts.entry(target).or_default().push(point);
+        repaired.rewritten += 1;
+    }
+
+    for (collection, id) in &report.orphans.vectors_without_node {
+        deletes.entry(collection.clone()).or_default().push(*id);
+    }
+
+    for (collection, ids) in deletes {
+        repaired.deleted_points += stores.vectors.delete_batch(&collection, ids).await?;
+    }
+    for (collection, points) in upserts {
+        stores.vectors.upsert_batch(&collection, points).await?;
+    }
+    if !report.dangling.is_empty() {
+        repaired.deleted_relations = stores.graph.delete_relations(&report.dangling).await?;
+    }
+
+    info!(
+        "Repaired: {} points re-embedded, {} elements embedded, {} points rewritten, {} deleted; {} relationships deleted",
+        repaired.reembedded, repaired.embedded, repaired.rewritten, repaired.deleted_points, repaired.deleted_relations
+    );
+    if !repaired.unrepairable.is_empty() {
+        println!("{} elements could not be embedded from their template:", repaired.unrepairable.len());
+        for id in &repaired.unrepairable {
+            println!("  {}", id);
+        }
+    }
+    Ok(repaired)
+}
+
+pub fn print(report: &Report) {
+    if report.problems() == 0 {
+        println!("No problems found");
+        return;
+    }
+
+    let orphans = &report.orphans;
+    if !orphans.nodes_without_vector.is_empty() {
+        println!("{} elements missing a vector:", orphans.nodes_without_vector.len());
+        for id in &orphans.nodes_without_vector {
+            println!("  {}", id);
+        }
+    }
+    if !report.unembedded.is_empty() {
+        println!("{} elements without an embedding:", report.unembedded.len());
+        for id in &report.unembedded {
+            println!("  {}", id);
+        }
+    }
+    if !orphans.vectors_without_node.is_empty() {
+        println!("{} vectors without an element:", orphans.vectors_without_node.len());
+        for (collection, id) in &orphans.vectors_without_node {
+            println!("  {} {}", collection, id);
+        }
+    }
+    if !report.mismatches.is_empty() {
+        println!("{} payload fields differing from the graph:", report.mismatches.len());
+        for m in &report.mismatches {
+            println!("  {} {} {}: graph {:?}, payload {:?}", m.collection, m.id, m.field, m.graph, m.payload);
+        }
+    }
+    if !report.dangling.is_empty() {
+        println!("{} dangling relationships:", report.dangling.len());
+        for r in &report.dangling {
+            println!("  ({})-[{}]->({})", r.from.key(), r.rel_type, r.to.key());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use codegraph_core::{GraphStore, MemoryGraphStore, MemoryVectorStore, VectorStore};
+
+    fn element(name: &str, design_system: &str) -> UIElement {
+        UIElement::new(name, "button")
+            .with_design_system(design_system)
+            .with_embedding(vec![1.0, 0.0])
+            .with_confidence(0.8)
+    }
+
+    #[tokio::test]
+    async fn test_check_and_repair() {
+        let graph = Arc::new(MemoryGraphStore::new());
+        let vectors = Arc::new(MemoryVectorStore::new());
+        let stores = Stores { graph: graph.clone(), vectors: vectors.clone(), feedback: None };
+
+        let consistent = element("Ok", "tailwind");
+        let unembedded = element("Missing", "tailwind");
+        let drifted = element("Drifted", "bootstrap");
+        let child = UIElement::new("Child", "icon");
+        // Stored before its embedding was generated
+        let template = UIElement::new("Banner", "alert")
+            .with_design_system("chakra")
+            .with_html_template("<div class=\"alert\">Saved</div>");
+        graph
+            .save_batch(&[consistent.clone(), unembedded.clone(), drifted.clone(), child.clone(), template.clone()])
+            .await
+            .unwrap();
+        graph.link_children(&[(consistent.id, child.id), (drifted.id, child.id)]).await.unwrap();
+
+        let (collection, point) = point_for(&consistent, vec![1.0, 0.0]);
+        vectors.upsert_point(&collection, point).await.unwrap();
+        // Left in Tailwind's collection with a stale design system, category and confidence
+        let (_, mut stale) = point_for(&drifted, vec![0.0, 1.0]);
+        stale.payload.design_system = "tailwind".into();
+        stale.payload.category = "link".into();
+        stale.payload.confidence = 0.3;
+        vectors.upsert_point("ui_tailwind", stale).await.unwrap();
+        let orphan = Uuid::new_v4();
+        let (_, mut deleted) = point_for(&consistent, vec![0.5, 0.5]);
+        deleted.id = orphan;
+        vectors.upsert_point("ui_tailwind", deleted).await.unwrap();
+
+        let mut snapshot = Snapshot::take(&stores).await.unwrap();
+        // The memory store never keeps a relationship to a missing node, so
+        // one is made up and, for one it does keep, the endpoint is dropped
+        snapshot.graph.relations.retain(|r| r.from == GraphNodeRef::Element { id: drifted.id });
+        let stored = snapshot.graph.relations[0].clone();
+        let mut made_up = stored.clone();
+        made_up.to = GraphNodeRef::Element { id: Uuid::new_v4() };
+        snapshot.graph.relations.push(made_up.clone());
+        snapshot.graph.elements.retain(|e| e.id != child.id);
+
+        let report = check(&snapshot);
+        assert_eq!(report.orphans.nodes_without_vector, vec![unembedded.id]);
+        assert_eq!(report.orphans.vectors_without_node, vec![("ui_tailwind".to_string(), orphan)]);
+        let fields: Vec<&str> = report.mismatches.iter().map(|m| m.field).collect();
+        assert_eq!(fields, vec!["category", "design_system", "collection", "confidence"]);
+        assert!(report.mismatches.iter().all(|m| m.id == drifted.id));
+        assert_eq!(report.unembedded, vec![template.id]);
+        assert_eq!(report.dangling, vec![stored, made_up]);
+        assert_eq!(report.problems(), 9);
+
+        let mut pipeline = ExtractionPipeline::new();
+        let repaired = repair(&stores, &snapshot, &report, &mut pipeline).await.unwrap();
+        assert_eq!(
+            repaired,
+            Repaired {
+                reembedded: 1,
+                embedded: 1,
+                unrepairable: Vec::new(),
+                rewritten: 1,
+                deleted_points: 2,
+                deleted_relations: 1,
+            }
+        );
+        let after = check(&Snapshot::take(&stores).await.unwrap());
+        assert_eq!(after.problems(), 0);
+        assert_eq!(graph.count_relationships().await.unwrap(), 1);
+        assert!(graph.find_by_id(template.id).await.unwrap().unwrap().embedding.is_some());
+        assert_eq!(vectors.scroll_points("ui_chakra", None, 10).await.unwrap().0.len(), 1);
+        let (moved, _) = vectors.scroll_points("ui_bootstrap", None, 10).await.unwrap();
+        assert_eq!(moved[0].payload.category, "button");
+        assert_eq!(moved[0].vector, vec![0.0, 1.0]);
+    }
+}
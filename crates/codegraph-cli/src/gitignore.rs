@@ -0,0 +1,197 @@
+//! Minimal `.gitignore` matcher used by the ingest walker
+//!
+//! Supports the subset of gitignore syntax found in front-end repositories:
+//! `*`, `?`, `**`, `[...]` classes, leading `/` anchoring, trailing `/`
+//! directory-only patterns and `!` negation. Rules from nested `.gitignore`
+//! files apply below their own directory and take precedence over parents.
+
+use std::path::{Path, PathBuf};
+
+/// Directories skipped even when no `.gitignore` mentions them
+const ALWAYS_IGNORED: &[&str] = &[".git", "node_modules"];
+
+#[derive(Debug, Clone)]
+struct Rule {
+    /// Directory of the `.gitignore` the rule came from (relative to the walk root)
+    base: PathBuf,
+    pattern: String,
+    negated: bool,
+    dir_only: bool,
+    /// Pattern contains a `/` and is matched against the full relative path
+    anchored: bool,
+}
+
+/// Accumulated ignore rules for a directory walk
+#[derive(Debug, Clone, Default)]
+pub struct GitIgnore {
+    rules: Vec<Rule>,
+}
+
+impl GitIgnore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the rules of a `.gitignore` located in `base` (relative to the walk root)
+    pub fn add_file(&mut self, base: &Path, contents: &str) {
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let anchored = line.contains('/');
+            let pattern = line.trim_start_matches('/').to_string();
+            if pattern.is_empty() {
+                continue;
+            }
+
+            self.rules.push(Rule {
+                base: base.to_path_buf(),
+                pattern,
+                negated,
+                dir_only,
+                anchored,
+            });
+        }
+    }
+
+    /// Whether `path` (relative to the walk root) is ignored; the last matching rule wins
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if is_dir && ALWAYS_IGNORED.contains(&name) {
+            return true;
+        }
+
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&rule.base) else {
+                continue;
+            };
+            let matched = if rule.anchored {
+                glob_match(&rule.pattern, &to_slash(relative))
+            } else {
+                glob_match(&rule.pattern, name)
+            };
+            if matched {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Render a relative path with `/` separators
+pub fn to_slash(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Match a gitignore glob against a `/`-separated path
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match_bytes(pattern.as_bytes(), text.as_bytes())
+}
+
+fn match_bytes(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let rest = &pattern[2..];
+            // `**/` also matches zero directories
+            if let Some(after_slash) = rest.strip_prefix(b"/")
+                && match_bytes(after_slash, text)
+            {
+                return true;
+            }
+            (0..=text.len()).any(|i| match_bytes(rest, &text[i..]))
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if match_bytes(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => match text.first() {
+            Some(&c) if c != b'/' => match_bytes(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some(b'[') => {
+            let Some(close) = pattern.iter().skip(1).position(|&c| c == b']').map(|p| p + 1) else {
+                return text.first() == Some(&b'[') && match_bytes(&pattern[1..], &text[1..]);
+            };
+            let Some(&c) = text.first() else {
+                return false;
+            };
+            let class = &pattern[1..close];
+            let (negated, class) = match class.first() {
+                Some(b'!') | Some(b'^') => (true, &class[1..]),
+                _ => (false, class),
+            };
+            let mut matched = false;
+            let mut i = 0;
+            while i < class.len() {
+                if i + 2 < class.len() && class[i + 1] == b'-' {
+                    matched |= (class[i]..=class[i + 2]).contains(&c);
+                    i += 3;
+                } else {
+                    matched |= class[i] == c;
+                    i += 1;
+                }
+            }
+            matched != negated && match_bytes(&pattern[close + 1..], &text[1..])
+        }
+        Some(&p) => text.first() == Some(&p) && match_bytes(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.min.css", "app.min.css"));
+        assert!(!glob_match("*.css", "styles/app.css"));
+        assert!(glob_match("dist/**/*.js", "dist/a/b/c.js"));
+        assert!(glob_match("**/fixtures", "fixtures"));
+        assert!(glob_match("build?", "build2"));
+        assert!(glob_match("[ab].html", "b.html"));
+        assert!(!glob_match("[!ab].html", "a.html"));
+    }
+
+    #[test]
+    fn test_rules_nested_and_negated() {
+        let mut ignore = GitIgnore::new();
+        ignore.add_file(Path::new(""), "dist/\n*.log\n/coverage\n");
+        ignore.add_file(Path::new("packages/ui"), "*.css\n!keep.css\n");
+
+        assert!(ignore.is_ignored(Path::new("dist"), true));
+        assert!(!ignore.is_ignored(Path::new("dist"), false));
+        assert!(ignore.is_ignored(Path::new("src/debug.log"), false));
+        assert!(ignore.is_ignored(Path::new("coverage"), true));
+        assert!(!ignore.is_ignored(Path::new("src/coverage"), true));
+        assert!(ignore.is_ignored(Path::new("packages/ui/button.css"), false));
+        assert!(!ignore.is_ignored(Path::new("packages/ui/keep.css"), false));
+        assert!(!ignore.is_ignored(Path::new("src/button.css"), false));
+        assert!(ignore.is_ignored(Path::new("web/node_modules"), true));
+    }
+}
@@ -0,0 +1,1103 @@
+//! Bulk ingestion of a repository or directory
+//!
+//! Walks the tree honoring `.gitignore`, groups files into components
+//! (co-located `.html/.css/.js`, Vue/Svelte SFCs, Angular components,
+//! JSX/TSX files and standalone stylesheets), extracts them in parallel and
+//! writes the results to the configured graph and vector stores in batches.
+//!
+//! Snippet and element IDs are derived from the source root and path, so
+//! re-running ingestion updates changed files in place, skips files whose
+//! content hash is unchanged and removes elements whose sources disappeared.
+//!
+//! Root and composite elements are fingerprinted; an LSH index over all
+//! fingerprints in the graph links near-duplicates with `SIMILAR_TO` and
+//! proposes `CAN_REPLACE` for near-identical elements of the same category.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use codegraph_core::sparse;
+use codegraph_extraction::fingerprint::DEFAULT_REPLACE_THRESHOLD;
+use codegraph_extraction::pipeline::PipelineConfig;
+use codegraph_extraction::{
+    ComponentSource, ExtractionInput, ExtractionPipeline, ExtractionResult, Fingerprint, TailwindTheme,
+};
+use codegraph_graph::{DesignSystem, DesignToken, GraphStore, SimilarityBasis, Snippet, UIElement};
+use codegraph_vector::collections::collection_name;
+use codegraph_vector::models::PointPayload;
+use codegraph_vector::{EmbeddingPoint, VectorStore};
+use codegraph_ws::handlers::segment_elements;
+
+use crate::gitignore::{to_slash, GitIgnore};
+use crate::similarity::{self, SimilarityOptions, SimilarityReport};
+
+/// Options for `codegraph ingest`
+#[derive(Debug, Clone)]
+pub struct IngestOptions {
+    pub root: PathBuf,
+    /// Extract and report without writing to the databases
+    pub dry_run: bool,
+    /// Design system recorded on every snippet instead of the detected one
+    pub design_system: Option<String>,
+    /// Maximum number of components extracted at once
+    pub concurrency: usize,
+    /// Number of extracted components written per database batch
+    pub batch_size: usize,
+    /// Minimum fingerprint similarity linked with `SIMILAR_TO`
+    pub similarity_threshold: f32,
+    /// Project Tailwind theme utilities are resolved against
+    pub tailwind_theme: Option<TailwindTheme>,
+}
+
+/// How a component's files are combined into pipeline input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ComponentKind {
+    Html,
+    Vue,
+    Svelte,
+    Angular,
+    Jsx,
+    Stylesheet,
+}
+
+impl ComponentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Vue => "vue",
+            Self::Svelte => "svelte",
+            Self::Angular => "angular",
+            Self::Jsx => "jsx",
+            Self::Stylesheet => "stylesheet",
+        }
+    }
+}
+
+/// A group of co-located files ingested as one snippet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub name: String,
+    pub kind: ComponentKind,
+    /// Markup file (`.html`, `.vue`, `.svelte`, `.jsx`/`.tsx`)
+    pub markup: Option<PathBuf>,
+    pub script: Option<PathBuf>,
+    pub style: Option<PathBuf>,
+}
+
+impl Component {
+    /// File recorded as the snippet's source path
+    pub fn primary_path(&self) -> &Path {
+        match self.kind {
+            ComponentKind::Angular => self.script.as_deref(),
+            ComponentKind::Stylesheet => self.style.as_deref(),
+            _ => self.markup.as_deref(),
+        }
+        .or(self.markup.as_deref())
+        .or(self.script.as_deref())
+        .or(self.style.as_deref())
+        .unwrap_or(Path::new(""))
+    }
+
+    /// SHA-256 over the component's file paths and contents
+    pub fn content_hash(&self, root: &Path) -> anyhow::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.kind.as_str());
+        for path in [&self.markup, &self.script, &self.style].into_iter().flatten() {
+            hasher.update([0]);
+            hasher.update(to_slash(path));
+            hasher.update([0]);
+            hasher.update(std::fs::read(root.join(path))?);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Read the component's files and build the extraction input
+    pub fn load(&self, root: &Path) -> anyhow::Result<ExtractionInput> {
+        let read = |path: &Option<PathBuf>| -> anyhow::Result<Option<String>> {
+            path.as_ref()
+                .map(|p| std::fs::read_to_string(root.join(p)))
+                .transpose()
+                .map_err(Into::into)
+        };
+        let markup = read(&self.markup)?;
+        let script = read(&self.script)?;
+        let style = read(&self.style)?;
+        let style_lang = self.style.as_deref().and_then(style_lang);
+
+        let mut input = match self.kind {
+            ComponentKind::Vue => ComponentSource::from_vue(&markup.unwrap_or_default()).into(),
+            ComponentKind::Svelte => ComponentSource::from_svelte(&markup.unwrap_or_default()).into(),
+            ComponentKind::Angular => {
+                let mut source = ComponentSource::from_angular(
+                    &script.unwrap_or_default(),
+                    markup.as_deref(),
+                    style.as_deref(),
+                );
+                if style.is_some() {
+                    source.style_lang = style_lang.map(String::from);
+                }
+                return Ok(source.into());
+            }
+            ComponentKind::Jsx => ExtractionInput::from_jsx(markup.unwrap_or_default()),
+            ComponentKind::Html | ComponentKind::Stylesheet => {
+                let mut input = ExtractionInput::new(markup.unwrap_or_default());
+                if let Some(script) = script {
+                    input = input.with_js(script);
+                }
+                input
+            }
+        };
+
+        if let Some(style) = style {
+            input = input.with_css(style);
+            if let Some(lang) = style_lang {
+                input = input.with_style_lang(lang);
+            }
+        }
+        Ok(input)
+    }
+}
+
+/// Style language for the pipeline, from a stylesheet's extension
+fn style_lang(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "scss" => Some("scss"),
+        "sass" => Some("sass"),
+        "less" => Some("less"),
+        _ => None,
+    }
+}
+
+/// Walk `root`, returning files (relative to `root`) that are not ignored
+pub fn walk(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut ignore = GitIgnore::new();
+    let mut files = Vec::new();
+    walk_dir(root, Path::new(""), &mut ignore, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn walk_dir(root: &Path, relative: &Path, ignore: &mut GitIgnore, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let dir = root.join(relative);
+    if let Ok(contents) = std::fs::read_to_string(dir.join(".gitignore")) {
+        ignore.add_file(relative, &contents);
+    }
+
+    let mut entries = std::fs::read_dir(&dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let path = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() || ignore.is_ignored(&path, file_type.is_dir()) {
+            continue;
+        }
+        if file_type.is_dir() {
+            walk_dir(root, &path, ignore, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Group files sharing a directory and stem into components
+pub fn group_components(files: &[PathBuf]) -> Vec<Component> {
+    // (directory, stem) -> extension -> path
+    let mut groups: BTreeMap<(PathBuf, String), HashMap<String, PathBuf>> = BTreeMap::new();
+
+    for path in files {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some((stem, ext)) = file_name.rsplit_once('.') else {
+            continue;
+        };
+        if stem.is_empty() || is_auxiliary(stem) {
+            continue;
+        }
+        let ext = ext.to_ascii_lowercase();
+        let is_style = matches!(ext.as_str(), "css" | "scss" | "sass" | "less");
+        // `Button.module.css` styles `Button.tsx`
+        let stem = if is_style { stem.trim_end_matches(".module") } else { stem };
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        groups
+            .entry((dir, stem.to_string()))
+            .or_default()
+            .insert(ext, path.clone());
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|((_, stem), mut files)| {
+            let style = ["scss", "sass", "less", "css"].iter().find_map(|e| files.remove(*e));
+            let script_of = |files: &mut HashMap<String, PathBuf>| {
+                ["ts", "js", "mjs"].iter().find_map(|e| files.remove(*e))
+            };
+            let name = stem.trim_end_matches(".component").to_string();
+
+            let (kind, markup, script, style) = if let Some(vue) = files.remove("vue") {
+                (ComponentKind::Vue, Some(vue), None, None)
+            } else if let Some(svelte) = files.remove("svelte") {
+                (ComponentKind::Svelte, Some(svelte), None, None)
+            } else if let Some(jsx) = files.remove("tsx").or_else(|| files.remove("jsx")) {
+                (ComponentKind::Jsx, Some(jsx), None, style)
+            } else if stem.ends_with(".component") && files.contains_key("ts") {
+                let script = script_of(&mut files);
+                (ComponentKind::Angular, files.remove("html"), script, style)
+            } else if let Some(html) = files.remove("html").or_else(|| files.remove("htm")) {
+                let script = script_of(&mut files);
+                (ComponentKind::Html, Some(html), script, style)
+            } else if style.is_some() {
+                (ComponentKind::Stylesheet, None, None, style)
+            } else {
+                return None;
+            };
+
+            Some(Component {
+                name,
+                kind,
+                markup,
+                script,
+                style,
+            })
+        })
+        .collect()
+}
+
+/// Tests, stories and type declarations are not components
+fn is_auxiliary(stem: &str) -> bool {
+    [".test", ".spec", ".stories", ".d"]
+        .iter()
+        .any(|suffix| stem.ends_with(suffix))
+}
+
+/// Commit checked out in `root`, if it is a git work tree
+pub fn git_commit(root: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+}
+
+/// Deterministic ID for `key` (a source path, optionally with an element suffix) within a source root
+pub fn stable_id(source_root: &str, key: &str) -> Uuid {
+    let digest = Sha256::digest(format!("{}\0{}", source_root, key));
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// Name recorded as the snippets' source root: the final component of the walked directory
+fn source_root_name(root: &Path) -> String {
+    root.canonicalize()
+        .ok()
+        .as_deref()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| to_slash(root))
+}
+
+/// A successfully extracted component awaiting persistence
+struct Extracted {
+    component: Component,
+    content_hash: String,
+    input: ExtractionInput,
+    result: ExtractionResult,
+}
+
+/// What a worker did with one component
+enum Outcome {
+    Extracted(Box<Extracted>),
+    /// Content hash matches the stored snippet
+    Unchanged,
+    Failed(String, String),
+}
+
+/// Summary printed at the end of an ingest run
+#[derive(Debug, Default)]
+pub struct IngestReport {
+    pub files_scanned: usize,
+    pub components: BTreeMap<&'static str, usize>,
+    pub extracted: usize,
+    pub elements: usize,
+    pub tokens: usize,
+    pub snippets_written: usize,
+    pub vectors_written: usize,
+    pub design_systems: BTreeMap<String, usize>,
+    /// Source paths ingested for the first time
+    pub added: Vec<String>,
+    /// Source paths whose content changed since the last ingest
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+    /// Source paths that no longer exist
+    pub removed: Vec<String>,
+    /// Elements deleted because their source or position disappeared
+    pub elements_removed: u64,
+    /// Near-duplicate pairs linked with `SIMILAR_TO`
+    pub similar_pairs: usize,
+    /// `CAN_REPLACE` relations proposed between near-identical elements
+    pub replacements_proposed: usize,
+    /// Embedding similarity linking of the new and changed elements
+    pub similarity: Option<SimilarityReport>,
+    pub failures: Vec<(String, String)>,
+    pub elapsed_ms: u64,
+}
+
+impl IngestReport {
+    pub fn print(&self, dry_run: bool) {
+        println!("Ingest summary{}", if dry_run { " (dry run)" } else { "" });
+        println!("  files scanned:   {}", self.files_scanned);
+        let components: usize = self.components.values().sum();
+        let kinds = self
+            .components
+            .iter()
+            .map(|(kind, count)| format!("{} {}", count, kind))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("  components:      {} ({})", components, kinds);
+        println!("  extracted:       {}", self.extracted);
+        println!("  elements:        {}", self.elements);
+        println!("  design tokens:   {}", self.tokens);
+        for (design_system, count) in &self.design_systems {
+            println!("  design system:   {} ({})", design_system, count);
+        }
+        if !dry_run {
+            println!("  snippets saved:  {}", self.snippets_written);
+            println!("  vectors saved:   {}", self.vectors_written);
+            println!(
+                "  changes:         {} added, {} updated, {} unchanged, {} removed ({} elements deleted)",
+                self.added.len(),
+                self.updated.len(),
+                self.unchanged,
+                self.removed.len(),
+                self.elements_removed
+            );
+            for path in &self.added {
+                println!("    + {}", path);
+            }
+            for path in &self.updated {
+                println!("    ~ {}", path);
+            }
+            for path in &self.removed {
+                println!("    - {}", path);
+            }
+            println!(
+                "  near-duplicates: {} pairs, {} replacements proposed",
+                self.similar_pairs, self.replacements_proposed
+            );
+            if let Some(similarity) = &self.similarity {
+                similarity.print();
+            }
+        }
+        println!("  failures:        {}", self.failures.len());
+        for (path, error) in &self.failures {
+            println!("    {}: {}", path, error);
+        }
+        println!("  elapsed:         {}ms", self.elapsed_ms);
+    }
+}
+
+/// Stores written by a non-dry run
+pub struct Stores {
+    pub graph: Arc<dyn GraphStore>,
+    /// Embeddings are not stored without one
+    pub vectors: Option<Arc<dyn VectorStore>>,
+}
+
+/// Stores written by a non-dry run, with the state of previous runs
+struct Sink {
+    graph: Arc<dyn GraphStore>,
+    vectors: Option<Arc<dyn VectorStore>>,
+    git_commit: Option<String>,
+    design_system: Option<String>,
+    source_root: String,
+    /// Snippets stored by previous runs over the same source root, by source path
+    existing: HashMap<String, Snippet>,
+    saved_design_systems: HashSet<String>,
+    similarity_threshold: f32,
+}
+
+/// Fingerprinted elements of one component, linked after the batch is saved
+struct Fingerprinted {
+    /// Every element ID of the component; never linked to each other
+    own_ids: Vec<Uuid>,
+    elements: Vec<(Uuid, Fingerprint, String)>,
+}
+
+impl Sink {
+    async fn open(options: &IngestOptions, source_root: &str, stores: Stores) -> anyhow::Result<Self> {
+        let Stores { graph, vectors } = stores;
+        let existing = graph
+            .find_snippets_by_source_root(source_root)
+            .await?
+            .into_iter()
+            .filter_map(|snippet| Some((snippet.source_path.clone()?, snippet)))
+            .collect();
+
+        Ok(Self {
+            graph,
+            vectors,
+            git_commit: git_commit(&options.root),
+            design_system: options.design_system.clone(),
+            source_root: source_root.to_string(),
+            existing,
+            saved_design_systems: HashSet::new(),
+            similarity_threshold: options.similarity_threshold,
+        })
+    }
+
+    /// Content hashes of previously ingested sources, by source path
+    fn existing_hashes(&self) -> HashMap<String, String> {
+        self.existing
+            .iter()
+            .filter_map(|(path, snippet)| Some((path.clone(), snippet.content_hash.clone()?)))
+            .collect()
+    }
+
+    /// Write one batch of extracted components
+    async fn write(&mut self, batch: Vec<Extracted>, report: &mut IngestReport) -> anyhow::Result<()> {
+        let mut elements = Vec::new();
+        let mut snippets = Vec::new();
+        let mut token_links = Vec::new();
+        let mut child_links = Vec::new();
+        let mut stale = Vec::new();
+        // Kept elements whose design system changed, by their previous one
+        let mut moved = Vec::new();
+        let mut fingerprinted = Vec::new();
+        let mut points: HashMap<String, Vec<EmbeddingPoint>> = HashMap::new();
+        let mut previous_ids = Vec::new();
+
+        for extracted in &batch {
+            let design_system = resolve_design_system(self.design_system.as_deref(), &extracted.result);
+            if self.saved_design_systems.insert(design_system.clone()) {
+                self.graph
+                    .save_design_system(&DesignSystem::new(&design_system, &design_system))
+                    .await?;
+            }
+
+            let component = &extracted.component;
+            let result = &extracted.result;
+            let source_path = to_slash(component.primary_path());
+            let previous = self.existing.get(&source_path);
+            let tags = vec![component.kind.as_str().to_string()];
+            let mut ids = Vec::new();
+            let mut fingerprints = Vec::new();
+            // Elements keep their ID (and feedback) while their category and position are unchanged
+            let mut ordinals: HashMap<&str, usize> = HashMap::new();
+
+            for (index, mapped) in result.ontology.elements.iter().enumerate() {
+                let category = mapped.category.as_str();
+                let ordinal = ordinals.entry(category).or_default();
+                let id = stable_id(&self.source_root, &format!("{}#{}:{}", source_path, category, ordinal));
+                *ordinal += 1;
+
+                let name = if index == 0 {
+                    component.name.clone()
+                } else {
+                    format!("{}-{}-{}", component.name, category, index)
+                };
+                let mut element = UIElement::new(name, category)
+                    .with_id(id)
+                    .with_element_type(&mapped.element_type)
+                    .with_design_system(&design_system)
+                    .with_css_classes(mapped.classes.clone())
+                    .with_tags(tags.clone())
+                    .with_source_path(&source_path)
+                    .with_content_hash(&extracted.content_hash)
+                    .with_a11y_score(result.a11y.score);
+                if let Some(fingerprint) = &mapped.fingerprint {
+                    element = element.with_fingerprint(&fingerprint.shape, fingerprint.signature.clone());
+                    fingerprints.push((element.id, fingerprint.clone(), category.to_string()));
+                }
+                if index == 0 {
+                    element = element.with_html_template(&extracted.input.html);
+                    if let Some(embedding) = &result.embedding {
+                        element = element
+                            .with_embedding(embedding.embedding.clone())
+                            .with_confidence(result.design_system.confidence);
+
+                        let payload = PointPayload::new(&component.name, category, &mapped.element_type, &design_system)
+                            .with_confidence(result.design_system.confidence)
+                            .with_css_classes(mapped.classes.clone())
+                            .with_tags(tags.clone());
+                        let lexical = sparse::encode_document(&payload, Some(&extracted.input.html));
+                        points.entry(collection_name(&design_system)).or_default().push(
+                            EmbeddingPoint::new(element.id, embedding.embedding.clone(), payload).with_sparse(lexical),
+                        );
+                    }
+                }
+
+                token_links.extend(mapped.tokens_used.iter().map(|token| (element.id, token.clone())));
+                ids.push(element.id);
+                elements.push(element);
+            }
+
+            // Segments become composites owning their atoms; top-level ones belong to their root element
+            let segments = segment_elements(
+                result,
+                &ids,
+                |key| stable_id(&self.source_root, &format!("{}#{}", source_path, key)),
+                |element| {
+                    element
+                        .with_design_system(&design_system)
+                        .with_tags(tags.clone())
+                        .with_source_path(&source_path)
+                        .with_content_hash(&extracted.content_hash)
+                },
+            );
+            for element in segments.elements {
+                if let (Some(shape), Some(signature)) = (&element.shape, &element.fingerprint) {
+                    let fingerprint = Fingerprint::from_signature(shape.clone(), signature.clone());
+                    fingerprints.push((element.id, fingerprint, element.category.clone()));
+                }
+                if let (Some(embedding), Some(html)) = (&element.embedding, &element.html_template) {
+                    let payload = PointPayload::new(&element.name, &element.category, &element.element_type, &design_system)
+                        .with_confidence(result.design_system.confidence)
+                        .with_css_classes(element.css_classes.clone())
+                        .with_tags(element.tags.clone());
+                    let lexical = sparse::encode_document(&payload, Some(html));
+                    points
+                        .entry(collection_name(&design_system))
+                        .or_default()
+                        .push(EmbeddingPoint::new(element.id, embedding.clone(), payload).with_sparse(lexical));
+                }
+                ids.push(element.id);
+                elements.push(element);
+            }
+            child_links.extend(segments.child_links);
+            token_links.extend(segments.token_links);
+
+            if let Some(previous) = previous {
+                previous_ids.extend(previous.element_ids.iter().copied());
+                let removed: Vec<Uuid> = previous.element_ids.iter().filter(|id| !ids.contains(id)).copied().collect();
+                let previous_design_system = previous.design_system.clone().unwrap_or_default();
+                if collection_name(&previous_design_system) != collection_name(&design_system) {
+                    let kept: Vec<Uuid> = previous.element_ids.iter().filter(|id| ids.contains(id)).copied().collect();
+                    moved.push((previous_design_system.clone(), kept));
+                }
+                stale.push((previous_design_system, removed));
+                report.updated.push(source_path.clone());
+            } else {
+                report.added.push(source_path.clone());
+            }
+
+            fingerprinted.push(Fingerprinted {
+                own_ids: ids.clone(),
+                elements: fingerprints,
+            });
+
+            let id = previous.map_or_else(|| stable_id(&self.source_root, &source_path), |p| p.id);
+            let mut snippet = Snippet::new(&extracted.input.html)
+                .with_id(id)
+                .with_name(&component.name)
+                .with_design_system(&design_system)
+                .with_tags(tags)
+                .with_element_ids(ids)
+                .with_source_root(&self.source_root)
+                .with_source_path(source_path)
+                .with_content_hash(&extracted.content_hash);
+            if let Some(css) = &extracted.input.css {
+                snippet = snippet.with_css(css);
+            }
+            if let Some(js) = extracted.input.js.as_ref().or(extracted.input.jsx.as_ref()) {
+                snippet = snippet.with_js(js);
+            }
+            if let Some(commit) = &self.git_commit {
+                snippet = snippet.with_git_commit(commit);
+            }
+            snippets.push(snippet);
+
+            self.write_tokens(result, &design_system).await?;
+        }
+
+        // Feedback adjusts the confidence of stored elements; keep it over the detector's
+        if !previous_ids.is_empty() {
+            let stored: HashMap<Uuid, f32> = self
+                .graph
+                .find_by_ids(&previous_ids)
+                .await?
+                .into_iter()
+                .filter_map(|element| Some((element.id, element.confidence?)))
+                .collect();
+            for element in &mut elements {
+                if let Some(&confidence) = stored.get(&element.id) {
+                    element.confidence = Some(confidence);
+                }
+            }
+            for point in points.values_mut().flatten() {
+                if let Some(&confidence) = stored.get(&point.id) {
+                    point.payload.confidence = confidence;
+                }
+            }
+        }
+
+        self.graph.save_batch(&elements).await?;
+        for snippet in &snippets {
+            self.graph.save_snippet(snippet).await?;
+        }
+        self.graph.link_children(&child_links).await?;
+        for (element_id, token) in &token_links {
+            self.graph.link_to_token(*element_id, token).await?;
+        }
+        report.snippets_written += snippets.len();
+
+        if let Some(vectors) = &self.vectors {
+            for (collection, points) in points {
+                match vectors.upsert_batch(&collection, points).await {
+                    Ok(count) => report.vectors_written += count,
+                    Err(e) => warn!("Failed to upsert embeddings into {}: {}", collection, e),
+                }
+            }
+            // Points of kept elements now live in their new design system's collection
+            for (design_system, ids) in moved {
+                let collection = collection_name(&design_system);
+                if let Err(e) = vectors.delete_batch(&collection, ids).await {
+                    warn!("Failed to delete embeddings from {}: {}", collection, e);
+                }
+            }
+        }
+        for (design_system, ids) in stale {
+            report.elements_removed += self.delete_elements(&design_system, ids).await?;
+        }
+        self.link_near_duplicates(fingerprinted, report).await?;
+
+        debug!("Wrote batch of {} components", batch.len());
+        Ok(())
+    }
+
+    /// Replace the similarity relations of freshly written elements
+    ///
+    /// Candidates are the stored elements sharing an LSH band with each
+    /// written fingerprint, including the rest of the batch.
+    async fn link_near_duplicates(&self, batch: Vec<Fingerprinted>, report: &mut IngestReport) -> anyhow::Result<()> {
+        let written: Vec<Uuid> = batch.iter().flat_map(|c| c.elements.iter().map(|(id, _, _)| *id)).collect();
+        self.graph
+            .clear_similarity(&written, SimilarityBasis::Structure)
+            .await?;
+
+        let mut linked = HashSet::new();
+        let mut similar = Vec::new();
+        let mut replacements = Vec::new();
+        for component in batch {
+            for (id, fingerprint, category) in &component.elements {
+                for candidate in self.graph.find_fingerprint_candidates(&fingerprint.signature).await? {
+                    let other = candidate.id;
+                    let (Some(shape), Some(signature)) = (candidate.shape, candidate.fingerprint) else {
+                        continue;
+                    };
+                    let score = fingerprint.similarity(&Fingerprint::from_signature(shape, signature));
+                    if score < self.similarity_threshold
+                        || component.own_ids.contains(&other)
+                        || !linked.insert((other.min(*id), other.max(*id)))
+                    {
+                        continue;
+                    }
+                    similar.push((*id, other, score));
+                    similar.push((other, *id, score));
+                    if score >= DEFAULT_REPLACE_THRESHOLD && candidate.category == *category {
+                        replacements.push((*id, other, score));
+                        replacements.push((other, *id, score));
+                    }
+                }
+            }
+        }
+
+        self.graph.create_similarities(&similar, SimilarityBasis::Structure).await?;
+        self.graph.propose_replacements(&replacements, SimilarityBasis::Structure).await?;
+        report.similar_pairs += similar.len() / 2;
+        report.replacements_proposed += replacements.len() / 2;
+        Ok(())
+    }
+
+    async fn write_tokens(&self, result: &ExtractionResult, design_system: &str) -> anyhow::Result<()> {
+        let tokens = &result.ontology.token_graph.tokens;
+        for token in tokens {
+            let mut node = DesignToken::new(&token.name, &token.value, token.category.as_str())
+                .with_theme(&token.theme)
+                .with_design_system(design_system);
+            if let Some(resolved) = &token.resolved_value {
+                node = node.with_resolved_value(resolved);
+            }
+            if let Some(alias_of) = &token.alias_of {
+                node = node.with_alias_of(alias_of);
+            }
+            self.graph.save_design_token(&node).await?;
+        }
+
+        // Aliases are linked once all targets exist
+        for token in tokens {
+            if let Some(alias_of) = &token.alias_of {
+                self.graph
+                    .link_token_alias(&token.name, alias_of, &token.theme)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete snippets whose source file was not seen in this walk
+    async fn remove_missing(&self, seen: &HashSet<String>, report: &mut IngestReport) -> anyhow::Result<()> {
+        for (path, snippet) in &self.existing {
+            if seen.contains(path) {
+                continue;
+            }
+            let design_system = snippet.design_system.clone().unwrap_or_default();
+            report.elements_removed += self.delete_elements(&design_system, snippet.element_ids.clone()).await?;
+            self.graph.delete_snippet(snippet.id, false).await?;
+            report.removed.push(path.clone());
+        }
+        report.removed.sort();
+        Ok(())
+    }
+
+    /// Delete elements from the graph and their embeddings from the vector store
+    async fn delete_elements(&self, design_system: &str, ids: Vec<Uuid>) -> anyhow::Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let deleted = self.graph.delete_batch(&ids).await?;
+        if let Some(vectors) = &self.vectors {
+            let collection = collection_name(design_system);
+            if let Err(e) = vectors.delete_batch(&collection, ids).await {
+                warn!("Failed to delete embeddings from {}: {}", collection, e);
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+/// Design system recorded for a component: the override, else the detected one
+fn resolve_design_system(design_system: Option<&str>, result: &ExtractionResult) -> String {
+    design_system
+        .map(String::from)
+        .unwrap_or_else(|| result.design_system.design_system.as_str().to_string())
+}
+
+/// Run `codegraph ingest`, writing to `stores` unless it is a dry run
+pub async fn ingest(options: IngestOptions, stores: Option<Stores>) -> anyhow::Result<IngestReport> {
+    let start = Instant::now();
+    let mut report = IngestReport::default();
+
+    let files = walk(&options.root)?;
+    report.files_scanned = files.len();
+    let components = group_components(&files);
+    for component in &components {
+        *report.components.entry(component.kind.as_str()).or_default() += 1;
+    }
+    let seen: HashSet<String> = components.iter().map(|c| to_slash(c.primary_path())).collect();
+    let source_root = source_root_name(&options.root);
+    info!(
+        "Found {} components in {} files under {}",
+        components.len(),
+        files.len(),
+        options.root.display()
+    );
+
+    let mut sink = match stores {
+        Some(stores) if !options.dry_run => Some(Sink::open(&options, &source_root, stores).await?),
+        _ => None,
+    };
+    let known_hashes = Arc::new(sink.as_ref().map(Sink::existing_hashes).unwrap_or_default());
+
+    // Each worker owns a pipeline, since extraction needs `&mut self`
+    let queue = Arc::new(Mutex::new(components.into_iter().collect::<VecDeque<_>>()));
+    let (tx, mut rx) = mpsc::channel(options.batch_size.max(1));
+    let config = PipelineConfig {
+        generate_embeddings: !options.dry_run,
+        warn_on_slow: false,
+        ..PipelineConfig::default()
+    };
+
+    for _ in 0..options.concurrency.max(1) {
+        let queue = queue.clone();
+        let known_hashes = known_hashes.clone();
+        let tx = tx.clone();
+        let root = options.root.clone();
+        let mut pipeline = ExtractionPipeline::with_config(config.clone());
+        if let Some(theme) = &options.tailwind_theme {
+            pipeline = pipeline.with_tailwind_theme(theme.clone());
+        }
+
+        tokio::spawn(async move {
+            loop {
+                let Some(component) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let path = to_slash(component.primary_path());
+                let outcome = match component.content_hash(&root) {
+                    Ok(hash) if known_hashes.get(&path) == Some(&hash) => Outcome::Unchanged,
+                    Ok(content_hash) => match component.load(&root) {
+                        Ok(input) => match pipeline.extract(input.clone()).await {
+                            Ok(result) => Outcome::Extracted(Box::new(Extracted {
+                                component,
+                                content_hash,
+                                input,
+                                result,
+                            })),
+                            Err(e) => Outcome::Failed(path, e.to_string()),
+                        },
+                        Err(e) => Outcome::Failed(path, e.to_string()),
+                    },
+                    Err(e) => Outcome::Failed(path, e.to_string()),
+                };
+                if tx.send(outcome).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    let mut batch = Vec::new();
+    while let Some(outcome) = rx.recv().await {
+        let extracted = match outcome {
+            Outcome::Extracted(extracted) => *extracted,
+            Outcome::Unchanged => {
+                report.unchanged += 1;
+                continue;
+            }
+            Outcome::Failed(path, error) => {
+                warn!("Failed to extract {}: {}", path, error);
+                report.failures.push((path, error));
+                continue;
+            }
+        };
+
+        let design_system = resolve_design_system(options.design_system.as_deref(), &extracted.result);
+        report.extracted += 1;
+        report.elements += extracted.result.ontology.elements.len();
+        report.tokens += extracted.result.ontology.token_graph.tokens.len();
+        *report.design_systems.entry(design_system.clone()).or_default() += 1;
+
+        if options.dry_run {
+            println!(
+                "{} [{}] {} elements, {} tokens, design system {}",
+                to_slash(extracted.component.primary_path()),
+                extracted.component.kind.as_str(),
+                extracted.result.ontology.elements.len(),
+                extracted.result.ontology.token_graph.tokens.len(),
+                design_system
+            );
+            continue;
+        }
+
+        batch.push(extracted);
+        if batch.len() >= options.batch_size
+            && let Some(sink) = sink.as_mut()
+        {
+            sink.write(std::mem::take(&mut batch), &mut report).await?;
+        }
+    }
+    if let Some(sink) = sink.as_mut() {
+        if !batch.is_empty() {
+            sink.write(batch, &mut report).await?;
+        }
+        sink.remove_missing(&seen, &mut report).await?;
+        if let Some(vectors) = &sink.vectors {
+            match similarity::link_similar(sink.graph.as_ref(), vectors.as_ref(), &SimilarityOptions::default()).await {
+                Ok(similarity) => report.similarity = Some(similarity),
+                Err(e) => warn!("Failed to link similar elements: {}", e),
+            }
+        }
+    }
+
+    report.added.sort();
+    report.updated.sort();
+    report.failures.sort();
+    report.elapsed_ms = start.elapsed().as_millis() as u64;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(paths: &[&str]) -> Vec<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_group_components() {
+        let files = paths(&[
+            "src/app/user-card.component.html",
+            "src/app/user-card.component.scss",
+            "src/app/user-card.component.spec.ts",
+            "src/app/user-card.component.ts",
+            "src/components/Button.module.css",
+            "src/components/Button.test.tsx",
+            "src/components/Button.tsx",
+            "src/components/Modal.vue",
+            "src/index.ts",
+            "src/styles/theme.less",
+            "static/card.css",
+            "static/card.html",
+            "static/card.js",
+        ]);
+
+        let components = group_components(&files);
+        let kinds: Vec<_> = components.iter().map(|c| (c.name.as_str(), c.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("user-card", ComponentKind::Angular),
+                ("Button", ComponentKind::Jsx),
+                ("Modal", ComponentKind::Vue),
+                ("theme", ComponentKind::Stylesheet),
+                ("card", ComponentKind::Html),
+            ]
+        );
+
+        let angular = &components[0];
+        assert_eq!(angular.primary_path(), Path::new("src/app/user-card.component.ts"));
+        assert_eq!(angular.markup.as_deref(), Some(Path::new("src/app/user-card.component.html")));
+        assert_eq!(angular.style.as_deref(), Some(Path::new("src/app/user-card.component.scss")));
+        assert_eq!(components[1].style.as_deref(), Some(Path::new("src/components/Button.module.css")));
+        assert_eq!(components[4].script.as_deref(), Some(Path::new("static/card.js")));
+    }
+
+    #[test]
+    fn test_stable_identity_and_content_hash() {
+        assert_eq!(stable_id("shop", "src/Card.tsx"), stable_id("shop", "src/Card.tsx"));
+        assert_ne!(stable_id("shop", "src/Card.tsx"), stable_id("admin", "src/Card.tsx"));
+
+        let root = std::env::temp_dir().join(format!("codegraph-ingest-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("card.html"), "<div class=\"card\"></div>").unwrap();
+        std::fs::write(root.join("card.css"), ".card { padding: 8px; }").unwrap();
+
+        let component = group_components(&walk(&root).unwrap()).remove(0);
+        let before = component.content_hash(&root).unwrap();
+        assert_eq!(before, component.content_hash(&root).unwrap());
+
+        std::fs::write(root.join("card.css"), ".card { padding: 16px; }").unwrap();
+        assert_ne!(before, component.content_hash(&root).unwrap());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reingest_keeps_stored_confidence() {
+        let root = std::env::temp_dir().join(format!("codegraph-ingest-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("card.html"), "<div class=\"card\"><button>Buy</button></div>").unwrap();
+        std::fs::write(root.join("card.css"), ".card { padding: 8px; }").unwrap();
+
+        let graph: Arc<dyn GraphStore> = Arc::new(codegraph_core::MemoryGraphStore::new());
+        let vectors: Arc<dyn VectorStore> = Arc::new(codegraph_core::MemoryVectorStore::new());
+        let options = IngestOptions {
+            root: root.clone(),
+            dry_run: false,
+            design_system: Some("custom".to_string()),
+            concurrency: 1,
+            batch_size: 10,
+            similarity_threshold: 0.9,
+            tailwind_theme: None,
+        };
+        let stores = || Stores {
+            graph: graph.clone(),
+            vectors: Some(vectors.clone()),
+        };
+
+        ingest(options.clone(), Some(stores())).await.unwrap();
+        let snippet = graph.find_snippets_by_source_root(&source_root_name(&root)).await.unwrap().remove(0);
+        let root_id = snippet.element_ids[0];
+
+        // Feedback raises the stored confidence, then the source changes
+        let mut element = graph.find_by_id(root_id).await.unwrap().unwrap();
+        element.confidence = Some(0.97);
+        graph.save(&element).await.unwrap();
+        std::fs::write(root.join("card.css"), ".card { padding: 16px; }").unwrap();
+
+        let report = ingest(options, Some(stores())).await.unwrap();
+        assert_eq!(report.updated, vec!["card.html".to_string()]);
+        let element = graph.find_by_id(root_id).await.unwrap().unwrap();
+        assert_eq!(element.confidence, Some(0.97));
+        let (points, _) = vectors.scroll_points(&collection_name("custom"), None, 10).await.unwrap();
+        let point = points.iter().find(|point| point.id == root_id).unwrap();
+        assert_eq!(point.payload.confidence, 0.97);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ingest_links_near_duplicates_through_store_candidates() {
+        let root = std::env::temp_dir().join(format!("codegraph-ingest-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let card = "<div class=\"card\"><img src=\"a.png\"><h3>Title</h3><button class=\"btn\">Buy</button></div>";
+        std::fs::write(root.join("card.html"), card).unwrap();
+        std::fs::write(root.join("offer.html"), card.replace("Title", "Offer")).unwrap();
+        std::fs::write(root.join("alert.html"), "<p class=\"alert\">Saved</p>").unwrap();
+
+        let graph: Arc<dyn GraphStore> = Arc::new(codegraph_core::MemoryGraphStore::new());
+        let options = IngestOptions {
+            root: root.clone(),
+            dry_run: false,
+            design_system: Some("custom".to_string()),
+            concurrency: 1,
+            batch_size: 1,
+            similarity_threshold: 0.9,
+            tailwind_theme: None,
+        };
+        let report = ingest(options, Some(Stores { graph: graph.clone(), vectors: None })).await.unwrap();
+
+        // The two cards match each other once, across batches; the alert matches nothing
+        assert_eq!(report.similar_pairs, 1);
+        assert_eq!(report.replacements_proposed, 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reingest_moves_points_of_changed_design_system() {
+        let root = std::env::temp_dir().join(format!("codegraph-ingest-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("card.html"), "<div class=\"card\"><button>Buy</button></div>").unwrap();
+
+        let graph: Arc<dyn GraphStore> = Arc::new(codegraph_core::MemoryGraphStore::new());
+        let vectors: Arc<dyn VectorStore> = Arc::new(codegraph_core::MemoryVectorStore::new());
+        let mut options = IngestOptions {
+            root: root.clone(),
+            dry_run: false,
+            design_system: Some("custom".to_string()),
+            concurrency: 1,
+            batch_size: 10,
+            similarity_threshold: 0.9,
+            tailwind_theme: None,
+        };
+        let stores = || Stores {
+            graph: graph.clone(),
+            vectors: Some(vectors.clone()),
+        };
+
+        ingest(options.clone(), Some(stores())).await.unwrap();
+        let snippet = graph.find_snippets_by_source_root(&source_root_name(&root)).await.unwrap().remove(0);
+        let root_id = snippet.element_ids[0];
+
+        std::fs::write(root.join("card.html"), "<div class=\"card shadow\"><button>Buy</button></div>").unwrap();
+        options.design_system = Some("material".to_string());
+        ingest(options, Some(stores())).await.unwrap();
+
+        let (old, _) = vectors.scroll_points(&collection_name("custom"), None, 10).await.unwrap();
+        assert!(old.iter().all(|point| point.id != root_id));
+        let (new, _) = vectors.scroll_points(&collection_name("material"), None, 10).await.unwrap();
+        assert!(new.iter().any(|point| point.id == root_id));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
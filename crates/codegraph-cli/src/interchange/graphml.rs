@@ -0,0 +1,217 @@
+//! GraphML
+//!
+//! One `<key>` per property, typed by its values; list-valued properties are
+//! `string` keys holding JSON, marked with `<desc>json</desc>`. Node IDs are
+//! the neighbourhood node IDs, and each node has a `kind` (its Neo4j label).
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use codegraph_core::{GraphDump, GraphNodeRef, GraphRelation};
+use serde_json::{Map, Number, Value};
+
+use super::{relation_properties, DumpBuilder, Node, NodeKind};
+
+const NAMESPACE: &str = "http://graphml.graphdrawing.org/xmlns";
+const JSON: &str = "json";
+
+/// `<key>` of a property
+struct Key {
+    id: String,
+    name: String,
+    /// GraphML `attr.type`
+    attr_type: &'static str,
+    json: bool,
+}
+
+fn attr_type(value: &Value) -> (&'static str, bool) {
+    match value {
+        Value::Bool(_) => ("boolean", false),
+        Value::Number(n) if n.is_f64() => ("double", false),
+        Value::Number(_) => ("long", false),
+        Value::String(_) => ("string", false),
+        _ => ("string", true),
+    }
+}
+
+/// Keys of the properties in `maps`, typed by their first value
+fn keys<'a>(prefix: &str, maps: impl Iterator<Item = &'a Map<String, Value>>) -> Vec<Key> {
+    let mut keys: Vec<Key> = Vec::new();
+    for properties in maps {
+        for (name, value) in properties {
+            if !keys.iter().any(|k| &k.name == name) {
+                let (attr_type, json) = attr_type(value);
+                keys.push(Key { id: format!("{}{}", prefix, name), name: name.clone(), attr_type, json });
+            }
+        }
+    }
+    keys.sort_by(|a, b| a.name.cmp(&b.name));
+    keys
+}
+
+pub(super) fn write(nodes: &[Node], relations: &[GraphRelation]) -> Result<String> {
+    let relation_properties: Vec<_> = relations.iter().map(relation_properties).collect::<Result<_>>()?;
+    let node_keys = keys("n_", nodes.iter().map(|n| &n.properties));
+    let edge_keys = keys("e_", relation_properties.iter());
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!("<graphml xmlns=\"{}\">\n", NAMESPACE));
+    out.push_str("  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"rel_type\" for=\"edge\" attr.name=\"rel_type\" attr.type=\"string\"/>\n");
+    for (domain, keys) in [("node", &node_keys), ("edge", &edge_keys)] {
+        for key in keys {
+            out.push_str(&format!(
+                "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"",
+                escape(&key.id),
+                domain,
+                escape(&key.name),
+                key.attr_type
+            ));
+            if key.json {
+                out.push_str(&format!("><desc>{}</desc></key>\n", JSON));
+            } else {
+                out.push_str("/>\n");
+            }
+        }
+    }
+
+    out.push_str("  <graph id=\"codegraph\" edgedefault=\"directed\">\n");
+    for node in nodes {
+        out.push_str(&format!("    <node id=\"{}\">\n", escape(&node.node_ref.key())));
+        out.push_str(&format!("      <data key=\"kind\">{}</data>\n", node.kind.label()));
+        data(&mut out, &node_keys, &node.properties);
+        out.push_str("    </node>\n");
+    }
+    for (i, (relation, properties)) in relations.iter().zip(&relation_properties).enumerate() {
+        out.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            i,
+            escape(&relation.from.key()),
+            escape(&relation.to.key())
+        ));
+        out.push_str(&format!("      <data key=\"rel_type\">{}</data>\n", escape(&relation.rel_type)));
+        data(&mut out, &edge_keys, properties);
+        out.push_str("    </edge>\n");
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    Ok(out)
+}
+
+fn data(out: &mut String, keys: &[Key], properties: &Map<String, Value>) {
+    for key in keys {
+        let text = match properties.get(&key.name) {
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        out.push_str(&format!("      <data key=\"{}\">{}</data>\n", escape(&key.id), escape(&text)));
+    }
+}
+
+/// Escape for attribute values and text, keeping whitespace XML would normalize
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub(super) fn read(text: &str) -> Result<GraphDump> {
+    let document = roxmltree::Document::parse(text).context("invalid GraphML")?;
+    let root = document.root_element();
+    if root.tag_name().name() != "graphml" {
+        bail!("expected a <graphml> document, found <{}>", root.tag_name().name());
+    }
+    let mut keys: HashMap<&str, Key> = HashMap::new();
+    // Key ID, domain (`node`, `edge`, `all`, ...) and default
+    let mut defaults: Vec<(&str, &str, &str)> = Vec::new();
+    for key in children(root, "key") {
+        let id = key.attribute("id").ok_or_else(|| anyhow!("<key> without id"))?;
+        let name = key.attribute("attr.name").unwrap_or(id);
+        let attr_type = match key.attribute("attr.type").unwrap_or("string") {
+            "boolean" => "boolean",
+            "int" | "long" => "long",
+            "float" | "double" => "double",
+            _ => "string",
+        };
+        let json = children(key, "desc").any(|d| d.text() == Some(JSON));
+        if let Some(default) = children(key, "default").next().and_then(|d| d.text()) {
+            defaults.push((id, key.attribute("for").unwrap_or("all"), default));
+        }
+        keys.insert(id, Key { id: id.to_string(), name: name.to_string(), attr_type, json });
+    }
+    let properties = |element: roxmltree::Node<'_, '_>| -> Result<Map<String, Value>> {
+        let mut properties = Map::new();
+        let domain = element.tag_name().name();
+        for &(id, _, text) in defaults.iter().filter(|(_, d, _)| *d == domain || *d == "all") {
+            let key = &keys[id];
+            properties.insert(key.name.clone(), value(key, text)?);
+        }
+        for data in children(element, "data") {
+            let id = data.attribute("key").ok_or_else(|| anyhow!("<data> without key"))?;
+            let key = keys.get(id).ok_or_else(|| anyhow!("<data> of undeclared key {:?}", id))?;
+            properties.insert(key.name.clone(), value(key, data.text().unwrap_or(""))?);
+        }
+        Ok(properties)
+    };
+
+    let mut builder = DumpBuilder::default();
+    for graph in children(root, "graph") {
+        let mut nodes: HashMap<&str, GraphNodeRef> = HashMap::new();
+        for node in children(graph, "node") {
+            let id = node.attribute("id").ok_or_else(|| anyhow!("<node> without id"))?;
+            let mut properties = properties(node)?;
+            let kind = match properties.remove("kind") {
+                Some(Value::String(kind)) => NodeKind::from_label(&kind)
+                    .ok_or_else(|| anyhow!("node {:?} has unknown kind {:?}", id, kind))?,
+                _ => bail!("node {:?} has no kind", id),
+            };
+            let node_ref = builder.add_node(kind, properties).with_context(|| format!("node {:?}", id))?;
+            nodes.insert(id, node_ref);
+        }
+        for edge in children(graph, "edge") {
+            let endpoint = |attribute: &str| -> Result<GraphNodeRef> {
+                let id = edge.attribute(attribute).ok_or_else(|| anyhow!("<edge> without {}", attribute))?;
+                nodes.get(id).cloned().ok_or_else(|| anyhow!("edge {} {:?} is not a node of the graph", attribute, id))
+            };
+            let (from, to) = (endpoint("source")?, endpoint("target")?);
+            let mut properties = properties(edge)?;
+            let rel_type = match properties.remove("rel_type") {
+                Some(Value::String(rel_type)) => rel_type,
+                _ => bail!("edge without rel_type"),
+            };
+            builder.add_relation(from, to, &rel_type, properties)?;
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Child elements of `node` named `name`, in any namespace
+fn children<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children().filter(move |c| c.is_element() && c.tag_name().name() == name)
+}
+
+fn value(key: &Key, text: &str) -> Result<Value> {
+    let invalid = || anyhow!("invalid {} value {:?} of {}", key.attr_type, text, key.name);
+    Ok(match key.attr_type {
+        "boolean" => Value::Bool(text.trim().parse().map_err(|_| invalid())?),
+        "long" => Value::Number(text.trim().parse::<i64>().map_err(|_| invalid())?.into()),
+        "double" => Value::Number(text.trim().parse().ok().and_then(Number::from_f64).ok_or_else(invalid)?),
+        _ if key.json => serde_json::from_str(text).map_err(|_| invalid())?,
+        _ => Value::String(text.to_string()),
+    })
+}
@@ -0,0 +1,310 @@
+//! JSON-LD
+//!
+//! Written as one `@graph` of node objects in compact IRI form. The reader
+//! handles that shape, with prefixes and simple term definitions from a
+//! local `@context`; remote contexts and `@list` are not supported.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde_json::{json, Map, Value};
+
+use super::rdf::{Term, Triple, PREFIXES, RDF, XSD};
+
+pub(super) fn write(triples: &[Triple]) -> Result<String> {
+    let context: Map<String, Value> =
+        PREFIXES.iter().map(|(prefix, namespace)| (prefix.to_string(), Value::from(*namespace))).collect();
+
+    let mut graph: Vec<Value> = Vec::new();
+    let mut current: Option<(&Term, Map<String, Value>)> = None;
+    for triple in triples {
+        if current.as_ref().is_none_or(|(subject, _)| *subject != &triple.subject) {
+            graph.extend(current.take().map(|(_, object)| Value::Object(object)));
+            let mut object = Map::new();
+            object.insert("@id".into(), id(&triple.subject).into());
+            current = Some((&triple.subject, object));
+        }
+        let Some((_, object)) = current.as_mut() else { unreachable!() };
+        let (key, value) = if triple.predicate == format!("{}type", RDF) {
+            ("@type".to_string(), Value::from(id(&triple.object)))
+        } else {
+            (compact(&triple.predicate), value(&triple.object)?)
+        };
+        match object.get_mut(&key) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+            None => {
+                object.insert(key, value);
+            }
+        }
+    }
+    graph.extend(current.map(|(_, object)| Value::Object(object)));
+
+    Ok(serde_json::to_string_pretty(&json!({ "@context": context, "@graph": graph }))?)
+}
+
+fn compact(iri: &str) -> String {
+    PREFIXES
+        .iter()
+        .find_map(|(prefix, namespace)| {
+            iri.strip_prefix(namespace)
+                .filter(|local| !local.is_empty() && !local.starts_with("//"))
+                .map(|local| format!("{}:{}", prefix, local))
+        })
+        .unwrap_or_else(|| iri.to_string())
+}
+
+fn id(term: &Term) -> String {
+    match term {
+        Term::Iri(iri) => compact(iri),
+        Term::Blank(label) => format!("_:{}", label),
+        Term::Literal { value, .. } => value.clone(),
+    }
+}
+
+fn value(term: &Term) -> Result<Value> {
+    let Term::Literal { value, datatype } = term else {
+        return Ok(json!({ "@id": id(term) }));
+    };
+    Ok(match datatype.strip_prefix(XSD) {
+        Some("string") => Value::from(value.as_str()),
+        Some("boolean") => Value::Bool(value == "true"),
+        Some("integer") | Some("double") => serde_json::from_str(value)?,
+        _ if *datatype == format!("{}JSON", RDF) => json!({ "@value": serde_json::from_str::<Value>(value)?, "@type": "@json" }),
+        _ => json!({ "@value": value, "@type": compact(datatype) }),
+    })
+}
+
+pub(super) fn read(text: &str) -> Result<Vec<Triple>> {
+    let document: Value = serde_json::from_str(text).context("invalid JSON")?;
+    let mut reader = Reader { context: LocalContext::default(), triples: Vec::new(), generated: 0 };
+    match &document {
+        Value::Object(top) => {
+            if let Some(context) = top.get("@context") {
+                reader.context.extend(context)?;
+            }
+            match top.get("@graph") {
+                Some(Value::Array(nodes)) => {
+                    for node in nodes {
+                        reader.node(node)?;
+                    }
+                }
+                Some(_) => bail!("@graph must be an array"),
+                None => {
+                    let mut node = top.clone();
+                    node.remove("@context");
+                    reader.node(&Value::Object(node))?;
+                }
+            }
+        }
+        Value::Array(nodes) => {
+            for node in nodes {
+                reader.node(node)?;
+            }
+        }
+        _ => bail!("expected a JSON-LD object or array"),
+    }
+    Ok(reader.triples)
+}
+
+/// Term definition of a local `@context`
+struct Definition {
+    iri: String,
+    /// `@type` coercion: `@id`, `@json` or a datatype IRI
+    coercion: Option<String>,
+}
+
+#[derive(Default)]
+struct LocalContext {
+    terms: HashMap<String, Definition>,
+}
+
+impl LocalContext {
+    fn extend(&mut self, context: &Value) -> Result<()> {
+        match context {
+            Value::Array(contexts) => contexts.iter().try_for_each(|c| self.extend(c)),
+            Value::Object(definitions) => {
+                // Prefixes first, so term definitions can use them
+                for (term, definition) in definitions {
+                    if let Value::String(iri) = definition {
+                        let iri = self.expand(iri);
+                        self.terms.insert(term.clone(), Definition { iri, coercion: None });
+                    }
+                }
+                for (term, definition) in definitions {
+                    if let Value::Object(definition) = definition {
+                        let iri = match definition.get("@id").and_then(Value::as_str) {
+                            Some(iri) => self.expand(iri),
+                            None => self.expand(term),
+                        };
+                        let coercion = definition.get("@type").and_then(Value::as_str).map(|t| self.expand(t));
+                        self.terms.insert(term.clone(), Definition { iri, coercion });
+                    }
+                }
+                Ok(())
+            }
+            Value::Null => Ok(()),
+            _ => bail!("remote @context is not supported"),
+        }
+    }
+
+    /// Absolute IRI of a term, compact IRI or IRI
+    fn expand(&self, value: &str) -> String {
+        if value.starts_with('@') {
+            return value.to_string();
+        }
+        if let Some(definition) = self.terms.get(value) {
+            return definition.iri.clone();
+        }
+        if let Some((prefix, local)) = value.split_once(':')
+            && !local.starts_with("//")
+            && let Some(definition) = self.terms.get(prefix)
+        {
+            return format!("{}{}", definition.iri, local);
+        }
+        value.to_string()
+    }
+}
+
+struct Reader {
+    context: LocalContext,
+    triples: Vec<Triple>,
+    /// Blank nodes created for objects without `@id`
+    generated: usize,
+}
+
+impl Reader {
+    /// Triples of a node object, returning its subject
+    fn node(&mut self, node: &Value) -> Result<Term> {
+        let Value::Object(node) = node else {
+            bail!("expected a node object, got {}", node);
+        };
+        if node.contains_key("@context") {
+            bail!("nested @context is not supported");
+        }
+        let subject = match node.get("@id").and_then(Value::as_str) {
+            Some(id) => self.reference(id),
+            None => {
+                self.generated += 1;
+                // `#` cannot appear in a document's blank node labels
+                Term::Blank(format!("#{}", self.generated))
+            }
+        };
+
+        for (key, values) in node {
+            if key == "@type" {
+                let types = match values {
+                    Value::Array(types) => types.iter().collect(),
+                    single => vec![single],
+                };
+                for class in types {
+                    let class = class.as_str().ok_or_else(|| anyhow!("@type must be a string"))?;
+                    let object = self.reference(class);
+                    self.push(&subject, format!("{}type", RDF), object);
+                }
+                continue;
+            }
+            if key.starts_with('@') {
+                continue;
+            }
+            let (predicate, coercion) = match self.context.terms.get(key.as_str()) {
+                Some(definition) => (definition.iri.clone(), definition.coercion.clone()),
+                None => (self.context.expand(key), None),
+            };
+            // Undefined terms are dropped, as JSON-LD expansion does
+            if !predicate.contains(':') {
+                continue;
+            }
+            if coercion.as_deref() == Some("@json") {
+                let object = Term::Literal { value: values.to_string(), datatype: format!("{}JSON", RDF) };
+                self.push(&subject, predicate, object);
+                continue;
+            }
+            let values = match values {
+                Value::Array(values) => values.iter().collect(),
+                single => vec![single],
+            };
+            for value in values {
+                let object = self.object(value, coercion.as_deref())?;
+                self.push(&subject, predicate.clone(), object);
+            }
+        }
+        Ok(subject)
+    }
+
+    fn object(&mut self, value: &Value, coercion: Option<&str>) -> Result<Term> {
+        let literal = |value: String, local: &str| Term::Literal { value, datatype: format!("{}{}", XSD, local) };
+        Ok(match value {
+            Value::String(s) => match coercion {
+                Some("@id") => self.reference(s),
+                Some(datatype) => Term::Literal { value: s.clone(), datatype: datatype.to_string() },
+                None => Term::string(s.as_str()),
+            },
+            Value::Bool(b) => literal(b.to_string(), "boolean"),
+            Value::Number(n) if n.is_f64() => literal(n.to_string(), "double"),
+            Value::Number(n) => literal(n.to_string(), "integer"),
+            Value::Object(object) if object.contains_key("@value") => {
+                let datatype = object.get("@type").and_then(Value::as_str).map(|t| self.context.expand(t));
+                match (&object["@value"], datatype.as_deref()) {
+                    (value, Some("@json")) => Term::Literal { value: value.to_string(), datatype: format!("{}JSON", RDF) },
+                    (Value::String(s), Some(datatype)) => Term::Literal { value: s.clone(), datatype: datatype.into() },
+                    (other, Some(datatype)) => Term::Literal { value: other.to_string(), datatype: datatype.into() },
+                    (other, None) => self.object(other, None)?,
+                }
+            }
+            Value::Object(object) if object.contains_key("@list") => bail!("@list is not supported"),
+            Value::Object(object) if object.len() == 1 && object.contains_key("@id") => {
+                let id = object["@id"].as_str().ok_or_else(|| anyhow!("@id must be a string"))?;
+                self.reference(id)
+            }
+            Value::Object(_) => self.node(value)?,
+            Value::Array(_) | Value::Null => bail!("unexpected value {}", value),
+        })
+    }
+
+    fn reference(&self, id: &str) -> Term {
+        match id.strip_prefix("_:") {
+            Some(label) => Term::Blank(label.to_string()),
+            None => Term::Iri(self.context.expand(id)),
+        }
+    }
+
+    fn push(&mut self, subject: &Term, predicate: String, object: Term) {
+        self.triples.push(Triple { subject: subject.clone(), predicate, object });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_term_definitions_and_nested_nodes() {
+        let text = r#"{
+            "@context": {
+                "cg": "urn:codegraph:ontology#",
+                "name": "cg:name",
+                "tags": { "@id": "cg:tags", "@type": "@json" },
+                "child": { "@id": "cg:hasChild", "@type": "@id" }
+            },
+            "@id": "urn:codegraph:element:a",
+            "@type": "cg:UIElement",
+            "name": "Card",
+            "tags": ["x", "y"],
+            "child": "urn:codegraph:element:b",
+            "cg:hasStyle": { "@type": "cg:Style", "name": "s" },
+            "ignored": 1
+        }"#;
+        let triples = read(text).unwrap();
+        let card = Term::Iri("urn:codegraph:element:a".into());
+        let object = |predicate: &str| {
+            let predicate = format!("urn:codegraph:ontology#{}", predicate);
+            &triples.iter().find(|t| t.subject == card && t.predicate == predicate).unwrap().object
+        };
+        assert_eq!(object("name"), &Term::string("Card"));
+        assert_eq!(object("tags"), &Term::Literal { value: r#"["x","y"]"#.into(), datatype: format!("{}JSON", RDF) });
+        assert_eq!(object("hasChild"), &Term::Iri("urn:codegraph:element:b".into()));
+        assert!(matches!(object("hasStyle"), Term::Blank(_)));
+        assert_eq!(triples.len(), 7);
+    }
+}
@@ -0,0 +1,307 @@
+//! Graph interchange formats for `codegraph export` / `codegraph import`
+//!
+//! Every format carries the same [`GraphDump`]: nodes keep all their
+//! properties (list-valued ones as JSON text) and relationships keep their
+//! score, basis, review state and creation time. Node identities are part of
+//! the node IDs / IRIs, so importing a file twice merges instead of
+//! duplicating, and relationships may point at nodes that only exist in the
+//! target store.
+
+mod graphml;
+mod jsonld;
+mod rdf;
+mod turtle;
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use codegraph_core::{DesignSystem, DesignToken, GraphDump, GraphNodeRef, GraphRelation, Snippet, UIElement};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// GraphML, for Gephi, yEd, NetworkX, ...
+    Graphml,
+    /// JSON-LD, with the OWL ontology in the same graph
+    Jsonld,
+    /// RDF Turtle, with the OWL ontology in the same graph
+    Turtle,
+}
+
+impl Format {
+    /// Format implied by a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "graphml" | "xml" => Some(Self::Graphml),
+            "jsonld" | "json" => Some(Self::Jsonld),
+            "ttl" => Some(Self::Turtle),
+            _ => None,
+        }
+    }
+}
+
+/// Serialize a dump in `format`
+pub fn write(dump: &GraphDump, format: Format) -> Result<String> {
+    let nodes = nodes(dump)?;
+    Ok(match format {
+        Format::Graphml => graphml::write(&nodes, &dump.relations)?,
+        Format::Jsonld => jsonld::write(&rdf::triples(&nodes, &dump.relations)?)?,
+        Format::Turtle => turtle::write(&rdf::triples(&nodes, &dump.relations)?),
+    })
+}
+
+/// Parse a file written by [`write`] (or by another tool, in the same shape)
+pub fn read(text: &str, format: Format) -> Result<GraphDump> {
+    match format {
+        Format::Graphml => graphml::read(text),
+        Format::Jsonld => rdf::to_dump(&jsonld::read(text)?),
+        Format::Turtle => rdf::to_dump(&turtle::read(text)?),
+    }
+}
+
+/// Kind of a dumped node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    Element,
+    Snippet,
+    DesignSystem,
+    DesignToken,
+}
+
+impl NodeKind {
+    const ALL: [NodeKind; 4] = [Self::Element, Self::Snippet, Self::DesignSystem, Self::DesignToken];
+
+    /// Neo4j label, also used as GraphML `kind` and RDF class name
+    fn label(self) -> &'static str {
+        match self {
+            Self::Element => "UIElement",
+            Self::Snippet => "Snippet",
+            Self::DesignSystem => "DesignSystem",
+            Self::DesignToken => "DesignToken",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.label() == label)
+    }
+}
+
+/// Node of a dump with its properties flattened to JSON values
+struct Node {
+    node_ref: GraphNodeRef,
+    kind: NodeKind,
+    properties: Map<String, Value>,
+}
+
+fn nodes(dump: &GraphDump) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    for element in &dump.elements {
+        nodes.push(node(GraphNodeRef::Element { id: element.id }, NodeKind::Element, element)?);
+    }
+    for snippet in &dump.snippets {
+        nodes.push(node(GraphNodeRef::Snippet { id: snippet.id }, NodeKind::Snippet, snippet)?);
+    }
+    for design_system in &dump.design_systems {
+        let node_ref = GraphNodeRef::DesignSystem { name: design_system.name.clone() };
+        nodes.push(node(node_ref, NodeKind::DesignSystem, design_system)?);
+    }
+    for token in &dump.tokens {
+        let node_ref = GraphNodeRef::DesignToken { name: token.name.clone(), theme: token.theme.clone() };
+        nodes.push(node(node_ref, NodeKind::DesignToken, token)?);
+    }
+    Ok(nodes)
+}
+
+fn node(node_ref: GraphNodeRef, kind: NodeKind, entity: &impl Serialize) -> Result<Node> {
+    Ok(Node { node_ref, kind, properties: properties(entity)? })
+}
+
+/// Non-null fields of an entity
+///
+/// Goes through JSON text so `f32` values keep their short form (`0.9`, not
+/// `0.8999999761581421`).
+fn properties(entity: &impl Serialize) -> Result<Map<String, Value>> {
+    let mut properties: Map<String, Value> = serde_json::from_str(&serde_json::to_string(entity)?)?;
+    properties.retain(|_, value| !value.is_null());
+    Ok(properties)
+}
+
+/// Properties of a relationship besides its endpoints and type
+fn relation_properties(relation: &GraphRelation) -> Result<Map<String, Value>> {
+    let mut properties = properties(relation)?;
+    for key in ["from", "to", "rel_type"] {
+        properties.remove(key);
+    }
+    Ok(properties)
+}
+
+/// Collects the nodes and relationships read back from a file
+#[derive(Default)]
+struct DumpBuilder {
+    dump: GraphDump,
+}
+
+impl DumpBuilder {
+    /// Add a node from its properties, returning its identity
+    fn add_node(&mut self, kind: NodeKind, properties: Map<String, Value>) -> Result<GraphNodeRef> {
+        let value = Value::Object(properties);
+        let invalid = || format!("invalid {} node", kind.label());
+        Ok(match kind {
+            NodeKind::Element => {
+                let element: UIElement = serde_json::from_value(value).with_context(invalid)?;
+                let node_ref = GraphNodeRef::Element { id: element.id };
+                self.dump.elements.push(element);
+                node_ref
+            }
+            NodeKind::Snippet => {
+                let snippet: Snippet = serde_json::from_value(value).with_context(invalid)?;
+                let node_ref = GraphNodeRef::Snippet { id: snippet.id };
+                self.dump.snippets.push(snippet);
+                node_ref
+            }
+            NodeKind::DesignSystem => {
+                let design_system: DesignSystem = serde_json::from_value(value).with_context(invalid)?;
+                let node_ref = GraphNodeRef::DesignSystem { name: design_system.name.clone() };
+                self.dump.design_systems.push(design_system);
+                node_ref
+            }
+            NodeKind::DesignToken => {
+                let token: DesignToken = serde_json::from_value(value).with_context(invalid)?;
+                let node_ref = GraphNodeRef::DesignToken { name: token.name.clone(), theme: token.theme.clone() };
+                self.dump.tokens.push(token);
+                node_ref
+            }
+        })
+    }
+
+    /// Add a relationship from the properties [`relation_properties`] keeps
+    fn add_relation(
+        &mut self,
+        from: GraphNodeRef,
+        to: GraphNodeRef,
+        rel_type: &str,
+        mut properties: Map<String, Value>,
+    ) -> Result<()> {
+        properties.insert("from".into(), serde_json::to_value(&from)?);
+        properties.insert("to".into(), serde_json::to_value(&to)?);
+        properties.insert("rel_type".into(), rel_type.into());
+        let relation = serde_json::from_value(Value::Object(properties))
+            .with_context(|| format!("invalid {} relationship", rel_type))?;
+        self.dump.relations.push(relation);
+        Ok(())
+    }
+
+    fn finish(self) -> GraphDump {
+        self.dump
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use codegraph_core::SimilarityBasis;
+
+    fn sample() -> GraphDump {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let mut button = UIElement::new("Primary \"CTA\"", "icon-button");
+        button.design_system = Some("tailwind".into());
+        button.html_template = Some("<button class=\"btn\">\n  Go\n</button>".into());
+        button.css_classes = vec!["btn".into(), "btn-primary".into()];
+        button.embedding = Some(vec![0.25, -0.5, 0.9]);
+        button.a11y_score = Some(0.9);
+        button.created_at = now;
+        button.updated_at = now;
+        let mut card = UIElement::new("Card", "made-up-category");
+        card.element_type = "composite".into();
+        card.created_at = now;
+        card.updated_at = now;
+        let mut snippet = Snippet::new("<div></div>");
+        snippet.element_ids = vec![button.id, card.id];
+        snippet.element_count = 2;
+        snippet.created_at = now;
+        snippet.updated_at = now;
+        let mut design_system = DesignSystem::new("tailwind", "Tailwind CSS");
+        design_system.created_at = now;
+        let mut token = DesignToken::new("--color:primary", "#00f", "color").with_design_system("tailwind");
+        token.created_at = now;
+        let token_ref = GraphNodeRef::DesignToken { name: token.name.clone(), theme: token.theme.clone() };
+
+        let relation = |from: &UIElement, to: GraphNodeRef, rel_type: &str| GraphRelation {
+            from: GraphNodeRef::Element { id: from.id },
+            to,
+            rel_type: rel_type.into(),
+            score: None,
+            basis: None,
+            proposed: false,
+            created_at: None,
+        };
+        let relations = vec![
+            relation(&card, GraphNodeRef::Element { id: button.id }, "HAS_CHILD"),
+            GraphRelation {
+                score: Some(0.87),
+                basis: Some(SimilarityBasis::Embedding),
+                proposed: true,
+                created_at: Some(now),
+                ..relation(&button, GraphNodeRef::Element { id: card.id }, "CAN_REPLACE")
+            },
+            relation(&button, token_ref.clone(), "USES_TOKEN"),
+            GraphRelation {
+                from: GraphNodeRef::Snippet { id: snippet.id },
+                ..relation(&button, GraphNodeRef::Element { id: button.id }, "HAS_ELEMENT")
+            },
+            GraphRelation {
+                from: token_ref,
+                ..relation(&button, GraphNodeRef::DesignSystem { name: "tailwind".into() }, "BELONGS_TO_DESIGN_SYSTEM")
+            },
+        ];
+
+        GraphDump {
+            elements: vec![button, card],
+            snippets: vec![snippet],
+            design_systems: vec![design_system],
+            tokens: vec![token],
+            relations,
+        }
+    }
+
+    /// Dumps compared through their JSON form, which every entity has
+    fn assert_same(actual: &GraphDump, expected: &GraphDump) {
+        assert_eq!(serde_json::to_value(actual).unwrap(), serde_json::to_value(expected).unwrap());
+    }
+
+    #[test]
+    fn test_every_format_round_trips() {
+        let dump = sample();
+        for format in [Format::Graphml, Format::Jsonld, Format::Turtle] {
+            let text = write(&dump, format).unwrap();
+            let read_back = read(&text, format).unwrap_or_else(|e| panic!("{:?}: {:#}", format, e));
+            assert_same(&read_back, &dump);
+            // Writing what was read gives the same file
+            assert_eq!(write(&read_back, format).unwrap(), text, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_rdf_exports_category_taxonomy() {
+        let turtle = write(&sample(), Format::Turtle).unwrap();
+        assert!(turtle.contains("cg:IconButton a owl:Class ;\n    rdfs:subClassOf cg:ActionElement"));
+        assert!(turtle.contains("cg:ActionElement a owl:Class ;\n    rdfs:subClassOf cg:UIElement"));
+        assert!(turtle.contains("a cg:UIElement, cg:IconButton ;"));
+
+        let jsonld: Value = serde_json::from_str(&write(&sample(), Format::Jsonld).unwrap()).unwrap();
+        let graph = jsonld["@graph"].as_array().unwrap();
+        let icon_button = graph.iter().find(|n| n["@id"] == "cg:IconButton").unwrap();
+        assert_eq!(icon_button["rdfs:subClassOf"]["@id"], "cg:ActionElement");
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("out/graph.TTL")), Some(Format::Turtle));
+        assert_eq!(Format::from_path(Path::new("graph.jsonld")), Some(Format::Jsonld));
+        assert_eq!(Format::from_path(Path::new("graph.graphml")), Some(Format::Graphml));
+        assert_eq!(Format::from_path(Path::new("graph")), None);
+    }
+}
@@ -0,0 +1,406 @@
+//! RDF model shared by the Turtle and JSON-LD formats
+//!
+//! Nodes are typed by their kind class (`cg:UIElement`, ...) and, for
+//! elements, by the class of their category in the OWL taxonomy
+//! (`cg:IconButton` ⊑ `cg:ActionElement` ⊑ `cg:UIElement`). Relationships
+//! are written both as plain triples (`el:a cg:hasChild el:b`) and as
+//! `rdf:Statement`s carrying score, basis, review state and creation time;
+//! import reads the statements and falls back to plain triples.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, bail, Result};
+use codegraph_core::{GraphDump, GraphNodeRef, GraphRelation};
+use codegraph_extraction::ontology::UICategory;
+use codegraph_graph::relations::RelationType;
+use serde_json::{Map, Number, Value};
+
+use super::{relation_properties, DumpBuilder, Node, NodeKind};
+
+pub const CG: &str = "urn:codegraph:ontology#";
+pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+pub const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+pub const OWL: &str = "http://www.w3.org/2002/07/owl#";
+pub const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Prefixes the writers compact IRIs with
+pub const PREFIXES: [(&str, &str); 5] = [("cg", CG), ("owl", OWL), ("rdf", RDF), ("rdfs", RDFS), ("xsd", XSD)];
+
+const NODE_IRI: &str = "urn:codegraph:";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term {
+    Iri(String),
+    Blank(String),
+    /// Lexical form and datatype IRI
+    Literal { value: String, datatype: String },
+}
+
+impl Term {
+    fn iri(iri: impl Into<String>) -> Self {
+        Self::Iri(iri.into())
+    }
+
+    fn literal(value: impl Into<String>, datatype: &str) -> Self {
+        Self::Literal { value: value.into(), datatype: datatype.into() }
+    }
+
+    pub fn string(value: impl Into<String>) -> Self {
+        Self::literal(value, &format!("{}string", XSD))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triple {
+    pub subject: Term,
+    pub predicate: String,
+    pub object: Term,
+}
+
+impl Triple {
+    fn new(subject: &Term, predicate: impl Into<String>, object: Term) -> Self {
+        Self { subject: subject.clone(), predicate: predicate.into(), object }
+    }
+}
+
+fn rdf(local: &str) -> String {
+    format!("{}{}", RDF, local)
+}
+
+fn cg(local: &str) -> String {
+    format!("{}{}", CG, local)
+}
+
+/// IRI of a node, encoding its identity
+pub fn node_iri(node: &GraphNodeRef) -> String {
+    match node {
+        GraphNodeRef::Element { id } => format!("{}element:{}", NODE_IRI, id),
+        GraphNodeRef::Snippet { id } => format!("{}snippet:{}", NODE_IRI, id),
+        GraphNodeRef::DesignSystem { name } => format!("{}design-system:{}", NODE_IRI, encode(name)),
+        GraphNodeRef::DesignToken { name, theme } => {
+            format!("{}design-token:{}:{}", NODE_IRI, encode(theme), encode(name))
+        }
+    }
+}
+
+/// Node identity of an IRI written by [`node_iri`]
+pub fn node_from_iri(iri: &str) -> Option<GraphNodeRef> {
+    let (kind, rest) = iri.strip_prefix(NODE_IRI)?.split_once(':')?;
+    Some(match kind {
+        "element" => GraphNodeRef::Element { id: rest.parse().ok()? },
+        "snippet" => GraphNodeRef::Snippet { id: rest.parse().ok()? },
+        "design-system" => GraphNodeRef::DesignSystem { name: decode(rest)? },
+        "design-token" => {
+            let (theme, name) = rest.split_once(':')?;
+            GraphNodeRef::DesignToken { name: decode(name)?, theme: decode(theme)? }
+        }
+        _ => return None,
+    })
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+fn encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// `icon-button` → `IconButton`
+fn pascal_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Class of an element category in the taxonomy
+fn category_class(category: UICategory) -> String {
+    cg(&pascal_case(category.as_str()))
+}
+
+/// Class of a category group (`action` → `ActionElement`)
+fn group_class(group: &str) -> String {
+    cg(&format!("{}Element", pascal_case(group)))
+}
+
+/// Relationship types of the graph, including `HAS_ELEMENT`
+fn relation_types() -> impl Iterator<Item = &'static str> {
+    RelationType::ALL.iter().map(RelationType::as_str).chain(["HAS_ELEMENT"])
+}
+
+/// Predicate of a relationship type (`HAS_CHILD` → `cg:hasChild`)
+fn relation_predicate(rel_type: &str) -> String {
+    let pascal = pascal_case(rel_type);
+    let mut chars = pascal.chars();
+    let camel: String = chars.next().map(|c| c.to_ascii_lowercase()).into_iter().chain(chars).collect();
+    cg(&camel)
+}
+
+/// Relationship type of a predicate written by [`relation_predicate`]
+fn relation_type(predicate: &str) -> Option<&'static str> {
+    relation_types().find(|rel_type| relation_predicate(rel_type) == predicate)
+}
+
+/// OWL declarations: node classes, the category taxonomy and properties
+fn ontology(datatype_properties: &[&str]) -> Vec<Triple> {
+    let a = rdf("type");
+    let label = format!("{}label", RDFS);
+    let sub_class_of = format!("{}subClassOf", RDFS);
+    let class = Term::iri(format!("{}Class", OWL));
+    let mut triples = Vec::new();
+
+    for kind in NodeKind::ALL {
+        let subject = Term::iri(cg(kind.label()));
+        triples.push(Triple::new(&subject, &a, class.clone()));
+        triples.push(Triple::new(&subject, &label, Term::string(kind.label())));
+    }
+
+    let mut groups = Vec::new();
+    for category in UICategory::ALL {
+        if !groups.contains(&category.group()) {
+            groups.push(category.group());
+        }
+    }
+    for group in groups {
+        let subject = Term::iri(group_class(group));
+        triples.push(Triple::new(&subject, &a, class.clone()));
+        triples.push(Triple::new(&subject, &sub_class_of, Term::iri(cg(NodeKind::Element.label()))));
+        triples.push(Triple::new(&subject, &label, Term::string(group)));
+    }
+    for category in UICategory::ALL {
+        let subject = Term::iri(category_class(category));
+        triples.push(Triple::new(&subject, &a, class.clone()));
+        triples.push(Triple::new(&subject, &sub_class_of, Term::iri(group_class(category.group()))));
+        triples.push(Triple::new(&subject, &label, Term::string(category.as_str())));
+    }
+
+    for rel_type in relation_types() {
+        let subject = Term::iri(relation_predicate(rel_type));
+        triples.push(Triple::new(&subject, &a, Term::iri(format!("{}ObjectProperty", OWL))));
+        triples.push(Triple::new(&subject, &label, Term::string(rel_type)));
+    }
+    for property in datatype_properties {
+        let subject = Term::iri(cg(property));
+        triples.push(Triple::new(&subject, &a, Term::iri(format!("{}DatatypeProperty", OWL))));
+    }
+    triples
+}
+
+/// Literal of a property value; lists and objects become `rdf:JSON`
+fn literal(key: &str, value: &Value) -> Term {
+    match value {
+        Value::String(s) if key.ends_with("_at") => Term::literal(s, &format!("{}dateTime", XSD)),
+        Value::String(s) => Term::string(s),
+        Value::Bool(b) => Term::literal(b.to_string(), &format!("{}boolean", XSD)),
+        Value::Number(n) if n.is_f64() => Term::literal(n.to_string(), &format!("{}double", XSD)),
+        Value::Number(n) => Term::literal(n.to_string(), &format!("{}integer", XSD)),
+        other => Term::literal(other.to_string(), &rdf("JSON")),
+    }
+}
+
+/// Property value of a literal, the inverse of [`literal`]
+fn value(lexical: &str, datatype: &str) -> Result<Value> {
+    let invalid = || anyhow!("invalid <{}> literal {:?}", datatype, lexical);
+    let Some(local) = datatype.strip_prefix(XSD) else {
+        return Ok(if datatype == rdf("JSON") {
+            serde_json::from_str(lexical).map_err(|_| invalid())?
+        } else {
+            Value::String(lexical.to_string())
+        });
+    };
+    Ok(match local {
+        "boolean" => Value::Bool(match lexical {
+            "true" | "1" => true,
+            "false" | "0" => false,
+            _ => return Err(invalid()),
+        }),
+        "integer" | "int" | "long" | "short" | "nonNegativeInteger" | "unsignedInt" | "unsignedLong" => {
+            Value::Number(lexical.parse::<i64>().map_err(|_| invalid())?.into())
+        }
+        "double" | "float" | "decimal" => {
+            let n: f64 = lexical.parse().map_err(|_| invalid())?;
+            Value::Number(Number::from_f64(n).ok_or_else(invalid)?)
+        }
+        _ => Value::String(lexical.to_string()),
+    })
+}
+
+/// Triples of a dump, ontology first
+pub(super) fn triples(nodes: &[Node], relations: &[GraphRelation]) -> Result<Vec<Triple>> {
+    let a = rdf("type");
+    let mut keys: Vec<&str> = nodes.iter().flat_map(|n| n.properties.keys().map(String::as_str)).collect();
+    keys.extend(["score", "basis", "proposed", "created_at"]);
+    keys.sort_unstable();
+    keys.dedup();
+
+    let mut triples = ontology(&keys);
+    for node in nodes {
+        let subject = Term::iri(node_iri(&node.node_ref));
+        triples.push(Triple::new(&subject, &a, Term::iri(cg(node.kind.label()))));
+        if node.kind == NodeKind::Element
+            && let Some(category) = node.properties.get("category").and_then(Value::as_str).and_then(UICategory::from_name)
+        {
+            triples.push(Triple::new(&subject, &a, Term::iri(category_class(category))));
+        }
+        for (key, value) in &node.properties {
+            triples.push(Triple::new(&subject, cg(key), literal(key, value)));
+        }
+        for relation in relations.iter().filter(|r| r.from == node.node_ref) {
+            let object = Term::iri(node_iri(&relation.to));
+            triples.push(Triple::new(&subject, relation_predicate(&relation.rel_type), object));
+        }
+    }
+
+    for (i, relation) in relations.iter().enumerate() {
+        let statement = Term::Blank(format!("r{}", i));
+        triples.push(Triple::new(&statement, &a, Term::iri(rdf("Statement"))));
+        triples.push(Triple::new(&statement, rdf("subject"), Term::iri(node_iri(&relation.from))));
+        triples.push(Triple::new(&statement, rdf("predicate"), Term::iri(relation_predicate(&relation.rel_type))));
+        triples.push(Triple::new(&statement, rdf("object"), Term::iri(node_iri(&relation.to))));
+        for (key, value) in relation_properties(relation)? {
+            triples.push(Triple::new(&statement, cg(&key), literal(&key, &value)));
+        }
+    }
+    Ok(triples)
+}
+
+/// Dump of the nodes and relationships in `triples`
+pub(super) fn to_dump(triples: &[Triple]) -> Result<GraphDump> {
+    let a = rdf("type");
+    let mut subjects: Vec<&Term> = Vec::new();
+    let mut by_subject: HashMap<&Term, Vec<&Triple>> = HashMap::new();
+    for triple in triples {
+        let entry = by_subject.entry(&triple.subject).or_default();
+        if entry.is_empty() {
+            subjects.push(&triple.subject);
+        }
+        entry.push(triple);
+    }
+    let types = |subject: &Term| -> Vec<&str> {
+        by_subject[subject]
+            .iter()
+            .filter(|t| t.predicate == a)
+            .filter_map(|t| match &t.object {
+                Term::Iri(iri) => Some(iri.as_str()),
+                _ => None,
+            })
+            .collect()
+    };
+    // Literal-valued `cg:` properties of a subject
+    let properties = |subject: &Term| -> Result<Map<String, Value>> {
+        let mut properties = Map::new();
+        for triple in &by_subject[subject] {
+            if let (Some(key), Term::Literal { value: lexical, datatype }) =
+                (triple.predicate.strip_prefix(CG), &triple.object)
+            {
+                properties.insert(key.to_string(), value(lexical, datatype)?);
+            }
+        }
+        Ok(properties)
+    };
+
+    let mut builder = DumpBuilder::default();
+    let mut nodes: HashMap<&Term, GraphNodeRef> = HashMap::new();
+    for &subject in &subjects {
+        let kind = types(subject)
+            .into_iter()
+            .find_map(|class| class.strip_prefix(CG).and_then(NodeKind::from_label));
+        if let Some(kind) = kind {
+            let node_ref = builder.add_node(kind, properties(subject)?)?;
+            nodes.insert(subject, node_ref);
+        }
+    }
+    let endpoint = |term: &Term| -> Result<GraphNodeRef> {
+        if let Some(node_ref) = nodes.get(term) {
+            return Ok(node_ref.clone());
+        }
+        match term {
+            Term::Iri(iri) => node_from_iri(iri).ok_or_else(|| anyhow!("<{}> is not a node of the graph", iri)),
+            other => bail!("{:?} is not a node of the graph", other),
+        }
+    };
+
+    let mut stated = HashSet::new();
+    for &subject in &subjects {
+        if !types(subject).contains(&rdf("Statement").as_str()) {
+            continue;
+        }
+        let object_of = |predicate: String| {
+            by_subject[subject]
+                .iter()
+                .find(|t| t.predicate == predicate)
+                .map(|t| &t.object)
+                .ok_or_else(|| anyhow!("statement without {}", predicate))
+        };
+        let rel_type = match object_of(rdf("predicate"))? {
+            Term::Iri(iri) => relation_type(iri).ok_or_else(|| anyhow!("unknown relationship <{}>", iri))?,
+            other => bail!("statement predicate {:?} is not an IRI", other),
+        };
+        let from = endpoint(object_of(rdf("subject"))?)?;
+        let to = endpoint(object_of(rdf("object"))?)?;
+        stated.insert((from.clone(), to.clone(), rel_type));
+        builder.add_relation(from, to, rel_type, properties(subject)?)?;
+    }
+
+    // Plain triples nothing was stated about, e.g. from hand-written files
+    for triple in triples {
+        let Some(rel_type) = relation_type(&triple.predicate) else { continue };
+        if matches!(triple.object, Term::Literal { .. }) {
+            continue;
+        }
+        let from = endpoint(&triple.subject)?;
+        let to = endpoint(&triple.object)?;
+        if stated.insert((from.clone(), to.clone(), rel_type)) {
+            builder.add_relation(from, to, rel_type, Map::new())?;
+        }
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_iris_round_trip() {
+        let nodes = [
+            GraphNodeRef::Element { id: uuid::Uuid::new_v4() },
+            GraphNodeRef::DesignSystem { name: "my system/β".into() },
+            GraphNodeRef::DesignToken { name: "--space:1".into(), theme: "dark".into() },
+        ];
+        for node in nodes {
+            assert_eq!(node_from_iri(&node_iri(&node)), Some(node));
+        }
+        assert_eq!(relation_predicate("BELONGS_TO_DESIGN_SYSTEM"), cg("belongsToDesignSystem"));
+        assert_eq!(relation_type(&cg("hasElement")), Some("HAS_ELEMENT"));
+        assert_eq!(relation_type(&cg("name")), None);
+    }
+}
@@ -0,0 +1,495 @@
+//! RDF Turtle
+//!
+//! The reader covers the Turtle the writer produces plus the usual
+//! hand-written forms (`@prefix` / `PREFIX`, `[ ... ]` blank nodes, bare
+//! numbers and booleans, long strings); collections and `@base` are not
+//! supported.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use super::rdf::{Term, Triple, PREFIXES, RDF, XSD};
+
+pub(super) fn write(triples: &[Triple]) -> String {
+    let mut out = String::new();
+    for (prefix, namespace) in PREFIXES {
+        out.push_str(&format!("@prefix {}: <{}> .\n", prefix, namespace));
+    }
+
+    let mut previous: Option<&Triple> = None;
+    for triple in triples {
+        match previous {
+            Some(p) if p.subject == triple.subject && p.predicate == triple.predicate => out.push_str(", "),
+            Some(p) if p.subject == triple.subject => {
+                out.push_str(" ;\n    ");
+                out.push_str(&predicate(&triple.predicate));
+                out.push(' ');
+            }
+            _ => {
+                if previous.is_some() {
+                    out.push_str(" .\n");
+                }
+                out.push('\n');
+                out.push_str(&term(&triple.subject));
+                out.push(' ');
+                out.push_str(&predicate(&triple.predicate));
+                out.push(' ');
+            }
+        }
+        out.push_str(&term(&triple.object));
+        previous = Some(triple);
+    }
+    if previous.is_some() {
+        out.push_str(" .\n");
+    }
+    out
+}
+
+fn predicate(iri: &str) -> String {
+    if iri == format!("{}type", RDF) {
+        "a".to_string()
+    } else {
+        compact(iri)
+    }
+}
+
+/// `prefix:local` when the local part is a plain name, `<iri>` otherwise
+fn compact(iri: &str) -> String {
+    for (prefix, namespace) in PREFIXES {
+        if let Some(local) = iri.strip_prefix(namespace)
+            && local.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && local.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return format!("{}:{}", prefix, local);
+        }
+    }
+    format!("<{}>", iri)
+}
+
+fn term(term: &Term) -> String {
+    match term {
+        Term::Iri(iri) => compact(iri),
+        Term::Blank(label) => format!("_:{}", label),
+        Term::Literal { value, datatype } => match datatype.strip_prefix(XSD) {
+            Some("string") => quote(value),
+            Some("boolean") | Some("integer") => value.clone(),
+            _ => format!("{}^^{}", quote(value), compact(datatype)),
+        },
+    }
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+pub(super) fn read(text: &str) -> Result<Vec<Triple>> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+        prefixes: HashMap::new(),
+        triples: Vec::new(),
+        generated: 0,
+    };
+    parser.document()?;
+    Ok(parser.triples)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    prefixes: HashMap<String, String>,
+    triples: Vec<Triple>,
+    /// Blank nodes created for `[ ... ]`
+    generated: usize,
+}
+
+impl Parser {
+    fn document(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none() {
+                return Ok(());
+            }
+            if self.eat_keyword("@prefix") {
+                self.prefix()?;
+                self.expect('.')?;
+            } else if self.eat_keyword("PREFIX") {
+                self.prefix()?;
+            } else if self.eat_keyword("@base") || self.eat_keyword("BASE") {
+                bail!("{}: @base is not supported", self.location());
+            } else {
+                let (subject, described) = self.subject()?;
+                self.skip_whitespace();
+                // `[ ... ] .` is a complete statement
+                if !(described && self.peek() == Some('.')) {
+                    self.predicate_objects(&subject)?;
+                }
+                self.expect('.')?;
+            }
+        }
+    }
+
+    fn prefix(&mut self) -> Result<()> {
+        self.skip_whitespace();
+        let mut prefix = String::new();
+        while let Some(c) = self.peek().filter(|&c| c != ':') {
+            if !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')) {
+                bail!("{}: invalid prefix", self.location());
+            }
+            prefix.push(c);
+            self.pos += 1;
+        }
+        self.expect(':')?;
+        self.skip_whitespace();
+        let namespace = self.iri_ref()?;
+        self.prefixes.insert(prefix, namespace);
+        Ok(())
+    }
+
+    /// Subject, and whether it was a `[ ... ]` with its own properties
+    fn subject(&mut self) -> Result<(Term, bool)> {
+        match self.peek() {
+            Some('[') => {
+                let described = self.chars.get(self.pos + 1..).is_some_and(|rest| {
+                    rest.iter().find(|c| !c.is_whitespace()).is_some_and(|&c| c != ']')
+                });
+                Ok((self.blank_node_list()?, described))
+            }
+            _ => match self.object()? {
+                Term::Literal { .. } => bail!("{}: a literal cannot be a subject", self.location()),
+                term => Ok((term, false)),
+            },
+        }
+    }
+
+    fn predicate_objects(&mut self, subject: &Term) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+            let predicate = self.verb()?;
+            loop {
+                self.skip_whitespace();
+                let object = self.object()?;
+                self.triples.push(Triple { subject: subject.clone(), predicate: predicate.clone(), object });
+                self.skip_whitespace();
+                if !self.eat(',') {
+                    break;
+                }
+            }
+            if !self.eat(';') {
+                return Ok(());
+            }
+            loop {
+                self.skip_whitespace();
+                if !self.eat(';') {
+                    break;
+                }
+            }
+            if matches!(self.peek(), Some('.') | Some(']')) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<String> {
+        if self.peek() == Some('a')
+            && self.chars.get(self.pos + 1).is_none_or(|c| c.is_whitespace() || matches!(c, '<' | '[' | '"'))
+        {
+            self.pos += 1;
+            return Ok(format!("{}type", RDF));
+        }
+        match self.object()? {
+            Term::Iri(iri) => Ok(iri),
+            _ => bail!("{}: predicates must be IRIs", self.location()),
+        }
+    }
+
+    fn object(&mut self) -> Result<Term> {
+        match self.peek() {
+            Some('<') => Ok(Term::Iri(self.iri_ref()?)),
+            Some('[') => self.blank_node_list(),
+            Some('_') if self.chars.get(self.pos + 1) == Some(&':') => {
+                self.pos += 2;
+                Ok(Term::Blank(self.name()))
+            }
+            Some('"') | Some('\'') => self.literal(),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | '.') => self.number(),
+            Some('(') => bail!("{}: collections are not supported", self.location()),
+            Some(_) => {
+                for keyword in ["true", "false"] {
+                    if self.eat_keyword(keyword) {
+                        return Ok(Term::Literal { value: keyword.into(), datatype: format!("{}boolean", XSD) });
+                    }
+                }
+                Ok(Term::Iri(self.prefixed_name()?))
+            }
+            None => bail!("unexpected end of document"),
+        }
+    }
+
+    fn blank_node_list(&mut self) -> Result<Term> {
+        self.expect('[')?;
+        self.generated += 1;
+        // `#` cannot appear in a document's blank node labels
+        let node = Term::Blank(format!("#{}", self.generated));
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            self.predicate_objects(&node)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn iri_ref(&mut self) -> Result<String> {
+        self.expect('<')?;
+        let mut iri = String::new();
+        loop {
+            match self.next() {
+                Some('>') => return Ok(iri),
+                Some('\\') => iri.push(self.unicode_escape()?),
+                Some(c) if !c.is_whitespace() => iri.push(c),
+                _ => bail!("{}: unterminated IRI", self.location()),
+            }
+        }
+    }
+
+    fn prefixed_name(&mut self) -> Result<String> {
+        let start = self.location();
+        let mut prefix = String::new();
+        while let Some(c) = self.peek().filter(|&c| c != ':') {
+            if !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')) {
+                bail!("{}: unexpected {:?}", start, c);
+            }
+            prefix.push(c);
+            self.pos += 1;
+        }
+        self.expect(':')?;
+        let Some(namespace) = self.prefixes.get(&prefix).cloned() else {
+            bail!("{}: undeclared prefix {:?}", start, prefix);
+        };
+        Ok(namespace + &self.name())
+    }
+
+    /// Local name or blank node label; a trailing `.` ends the statement
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\\' && self.chars.get(self.pos + 1).is_some() {
+                name.push(self.chars[self.pos + 1]);
+                self.pos += 2;
+            } else if c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '%') {
+                name.push(c);
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        while name.ends_with('.') {
+            name.pop();
+            self.pos -= 1;
+        }
+        name
+    }
+
+    fn literal(&mut self) -> Result<Term> {
+        let quote = self.next().unwrap_or('"');
+        let long = self.chars.get(self.pos..self.pos + 2) == Some(&[quote, quote][..]);
+        if long {
+            self.pos += 2;
+        }
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote && !long => break,
+                Some(c) if c == quote && self.chars.get(self.pos..self.pos + 2) == Some(&[quote, quote][..]) => {
+                    self.pos += 2;
+                    break;
+                }
+                Some('\\') => value.push(match self.peek() {
+                    Some('u') | Some('U') => self.unicode_escape()?,
+                    _ => match self.next() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some(c @ ('"' | '\'' | '\\')) => c,
+                        _ => bail!("{}: invalid escape", self.location()),
+                    },
+                }),
+                Some('\n') | Some('\r') if !long => bail!("{}: newline in string", self.location()),
+                Some(c) => value.push(c),
+                None => bail!("unterminated string"),
+            }
+        }
+
+        let datatype = if self.eat('^') {
+            self.expect('^')?;
+            match self.object()? {
+                Term::Iri(iri) => iri,
+                _ => bail!("{}: datatypes must be IRIs", self.location()),
+            }
+        } else {
+            if self.eat('@') {
+                // Language tags carry nothing the graph keeps
+                while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '-') {
+                    self.pos += 1;
+                }
+            }
+            format!("{}string", XSD)
+        };
+        Ok(Term::Literal { value, datatype })
+    }
+
+    /// `\uXXXX` / `\UXXXXXXXX`, after the backslash
+    fn unicode_escape(&mut self) -> Result<char> {
+        let digits = match self.next() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => bail!("{}: invalid escape", self.location()),
+        };
+        let hex: String = self.chars.iter().skip(self.pos).take(digits).collect();
+        self.pos += digits;
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| anyhow::anyhow!("{}: invalid escape \\u{}", self.location(), hex))
+    }
+
+    fn number(&mut self) -> Result<Term> {
+        let mut lexical = String::new();
+        let mut datatype = "integer";
+        if let Some(sign) = self.peek().filter(|c| matches!(c, '+' | '-')) {
+            lexical.push(sign);
+            self.pos += 1;
+        }
+        while let Some(c) = self.peek() {
+            let next_is_digit = self.chars.get(self.pos + 1).is_some_and(|c| c.is_ascii_digit());
+            match c {
+                c if c.is_ascii_digit() => {}
+                // A `.` not followed by a digit ends the statement
+                '.' if datatype == "integer" && next_is_digit => datatype = "decimal",
+                'e' | 'E' if datatype != "double" => datatype = "double",
+                '+' | '-' if lexical.ends_with(['e', 'E']) => {}
+                _ => break,
+            }
+            lexical.push(c);
+            self.pos += 1;
+        }
+        if !lexical.contains(|c: char| c.is_ascii_digit()) {
+            bail!("{}: invalid number", self.location());
+        }
+        Ok(Term::Literal { value: lexical, datatype: format!("{}{}", XSD, datatype) })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.next().is_some_and(|c| c != '\n') {}
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Consume `keyword` if it is followed by whitespace or a delimiter
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let end = self.pos + keyword.chars().count();
+        let matches = self.chars.get(self.pos..end).is_some_and(|s| s.iter().copied().eq(keyword.chars()))
+            && self.chars.get(end).is_none_or(|c| !(c.is_alphanumeric() || matches!(c, '_' | ':')));
+        if matches {
+            self.pos = end;
+        }
+        matches
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        self.skip_whitespace();
+        if !self.eat(c) {
+            bail!("{}: expected {:?}", self.location(), c);
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += c.is_some() as usize;
+        c
+    }
+
+    fn location(&self) -> String {
+        let consumed = &self.chars[..self.pos.min(self.chars.len())];
+        let line = consumed.iter().filter(|&&c| c == '\n').count() + 1;
+        let column = consumed.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+        format!("line {}, column {}", line, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_hand_written_turtle() {
+        let text = r#"
+            PREFIX cg: <urn:codegraph:ontology#>
+            @prefix x: <http://example.com/> .
+            # A comment
+            x:a a cg:UIElement ; cg:n 3 , -1.5, 2e3 ; cg:ok true ;
+                cg:label """two
+            lines"""@en ; cg:esc 'it\'s é' .
+            [ cg:p x:b ] .
+        "#;
+        let triples = read(text).unwrap();
+        let objects: Vec<_> = triples.iter().map(|t| &t.object).collect();
+        assert_eq!(objects[0], &Term::Iri("urn:codegraph:ontology#UIElement".into()));
+        let datatype = |t: &Term| match t {
+            Term::Literal { datatype, .. } => datatype.strip_prefix(XSD).unwrap().to_string(),
+            _ => panic!(),
+        };
+        assert_eq!(
+            objects[1..5].iter().map(|t| datatype(t)).collect::<Vec<_>>(),
+            ["integer", "decimal", "double", "boolean"]
+        );
+        assert_eq!(objects[5], &Term::string("two\n            lines"));
+        assert_eq!(objects[6], &Term::string("it's é"));
+        assert_eq!(triples[7].object, Term::Iri("http://example.com/b".into()));
+        assert!(matches!(triples[7].subject, Term::Blank(_)));
+    }
+
+    #[test]
+    fn test_reports_line_of_syntax_errors() {
+        let err = read("@prefix cg: <urn:x#> .\ncg:a cg:b .").unwrap_err();
+        assert!(err.to_string().starts_with("line 2"), "{}", err);
+        assert!(read("cg:a cg:b cg:c .").unwrap_err().to_string().contains("undeclared prefix"));
+    }
+}
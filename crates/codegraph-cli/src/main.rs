@@ -3,17 +3,33 @@
 //! This is the main entry point for the CodeGraph system.
 //! It initializes all components and starts the requested service.
 
+mod backup;
+mod duplicates;
+mod fsck;
+mod gitignore;
+mod ingest;
+mod interchange;
+mod similarity;
+
 use std::sync::Arc;
 
+use anyhow::Context;
 use clap::Parser;
 use tracing::{error, info, warn};
 
 use codegraph_extraction::embedding::EmbeddingGenerator;
-use codegraph_extraction::ExtractionPipeline;
+use codegraph_extraction::fingerprint::DEFAULT_SIMILARITY_THRESHOLD;
+use codegraph_extraction::{ExtractionPipeline, NarseseGenerator, TailwindTheme, TokenImporter};
+use codegraph_core::{
+    Config, EmbeddedFeedbackStore, EmbeddedGraphStore, EmbeddedVectorStore, FeedbackStore, MemoryGraphStore,
+    MemoryVectorStore, StorageBackend,
+};
+use codegraph_feedback::FeedbackRepository;
 use codegraph_generation::VanillaCodeGenerator;
-use codegraph_graph::Neo4jRepository;
-use codegraph_retrieval::HybridRetriever;
-use codegraph_vector::{QdrantConfig, QdrantRepository};
+use codegraph_graph::{DesignSystem, DesignToken, GraphStore, Neo4jRepository};
+use codegraph_reasoning::ReasoningPipeline;
+use codegraph_retrieval::{FulltextSource, HybridRetriever};
+use codegraph_vector::{QdrantConfig, QdrantRepository, VectorStore};
 use codegraph_ws::SharedState;
 
 #[derive(Parser)]
@@ -30,45 +46,308 @@ enum Commands {
     Serve {
         #[arg(short, long, default_value = "3000")]
         port: u16,
+        /// Storage backend: `neo4j` (Neo4j + Qdrant), `memory` (nothing persisted)
+        /// or `embedded` (files under the data directory).
+        /// Defaults to `CODEGRAPH_STORAGE`, then `neo4j`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend; implies `--storage embedded`.
+        /// Defaults to `CODEGRAPH_DATA_DIR`, then `./codegraph-data`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+        /// Tailwind config as JSON (e.g. the output of `resolveConfig`) whose
+        /// theme resolves utility classes instead of the default theme
+        #[arg(long)]
+        tailwind_config: Option<std::path::PathBuf>,
     },
     /// Run MCP server over stdio
     Mcp,
     /// Run benchmark suite
     Benchmark,
+    /// Import design tokens from W3C Design Tokens or Style Dictionary JSON into
+    /// the graph and the reasoner
+    ImportTokens {
+        /// Path to the token JSON file
+        path: std::path::PathBuf,
+        /// Design system the tokens belong to
+        #[arg(long)]
+        design_system: String,
+    },
+    /// Ingest every component in a repository or directory
+    Ingest {
+        /// Root directory to walk (`.gitignore` rules are honored)
+        path: std::path::PathBuf,
+        /// Extract and report without writing to the stores
+        #[arg(long)]
+        dry_run: bool,
+        /// Record this design system instead of the detected one
+        #[arg(long)]
+        design_system: Option<String>,
+        /// Maximum number of components extracted in parallel
+        #[arg(long)]
+        concurrency: Option<usize>,
+        /// Number of components written per database batch
+        #[arg(long, default_value = "50")]
+        batch_size: usize,
+        /// Minimum structural similarity (0.0-1.0) linked as a near-duplicate
+        #[arg(long, default_value_t = DEFAULT_SIMILARITY_THRESHOLD)]
+        similarity_threshold: f32,
+        /// Tailwind config as JSON, as for `serve`
+        #[arg(long)]
+        tailwind_config: Option<std::path::PathBuf>,
+        /// Storage backend to write to, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Link similar elements via embedding k-NN and propose replacements
+    LinkSimilar {
+        /// Relink every element, not only those changed since the last run
+        #[arg(long)]
+        all: bool,
+        /// Neighbours kept per element, within and across design systems each
+        #[arg(long, default_value_t = similarity::DEFAULT_NEIGHBOURS)]
+        neighbours: usize,
+        /// Minimum cosine similarity linked with SIMILAR_TO
+        #[arg(long, default_value_t = similarity::DEFAULT_MIN_SCORE)]
+        min_score: f32,
+        /// Minimum cosine similarity for proposing CAN_REPLACE
+        #[arg(long, default_value_t = similarity::DEFAULT_REPLACE_MIN_SCORE)]
+        replace_min_score: f32,
+        /// Storage backend to link in, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// List clusters of near-duplicate elements
+    Duplicates {
+        /// Minimum similarity of the edges forming a cluster
+        #[arg(long, default_value_t = DEFAULT_SIMILARITY_THRESHOLD)]
+        min_score: f32,
+        /// Storage backend to read, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Export the graph as GraphML, JSON-LD or Turtle (with the OWL category taxonomy)
+    Export {
+        /// File to write
+        path: std::path::PathBuf,
+        /// Defaults to the one implied by the extension (`.graphml`, `.jsonld`, `.ttl`)
+        #[arg(long, value_enum)]
+        format: Option<interchange::Format>,
+        /// Storage backend to export from, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Import a graph written by `export`, merging nodes and relationships by ID
+    Import {
+        /// File to read
+        path: std::path::PathBuf,
+        /// Defaults to the one implied by the extension (`.graphml`, `.jsonld`, `.ttl`)
+        #[arg(long, value_enum)]
+        format: Option<interchange::Format>,
+        /// Storage backend to import into, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Check that the graph and vector stores agree, optionally repairing them
+    Fsck {
+        /// Write missing points, rewrite mismatched payloads, and delete points
+        /// without an element and dangling relationships
+        #[arg(long)]
+        repair: bool,
+        /// Storage backend to check, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Run pending schema migrations of Neo4j, Qdrant and PostgreSQL
+    Migrate {
+        /// Storage backend to migrate, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Back up the graph, vectors and feedback into one archive (`.tar.gz`)
+    Backup {
+        /// Archive to write
+        path: std::path::PathBuf,
+        /// Storage backend to back up, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+    /// Restore a backup archive, merging it into the stores
+    Restore {
+        /// Archive to read
+        path: std::path::PathBuf,
+        /// Verify the archive and report orphans without restoring it
+        #[arg(long)]
+        check: bool,
+        /// Storage backend to restore into, as for `serve`
+        #[arg(long)]
+        storage: Option<StorageBackend>,
+        /// Directory of the embedded backend, as for `serve`
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+    },
+}
+
+/// Read the Tailwind theme given by `--tailwind-config`, if any
+fn load_tailwind_theme(path: Option<&std::path::Path>) -> anyhow::Result<Option<TailwindTheme>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let json = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let theme = TailwindTheme::from_config_json(&json)
+        .with_context(|| format!("invalid Tailwind config {}", path.display()))?;
+    info!("Resolving Tailwind utilities against {}", path.display());
+    Ok(Some(theme))
+}
+
+/// Configuration from the environment, overridden by `--storage` / `--data-dir`
+fn storage_config(storage: Option<StorageBackend>, data_dir: Option<std::path::PathBuf>) -> anyhow::Result<Config> {
+    let mut config = Config::from_env();
+    config.storage = match (storage, data_dir.is_some()) {
+        (Some(storage), true) if storage != StorageBackend::Embedded => {
+            anyhow::bail!("--data-dir only applies to --storage embedded")
+        }
+        (Some(storage), _) => storage,
+        (None, true) => StorageBackend::Embedded,
+        (None, false) => config.storage,
+    };
+    if let Some(data_dir) = data_dir {
+        config.data_dir = data_dir;
+    }
+    Ok(config)
+}
+
+/// Open the persistent graph store of `config` for a one-off command
+async fn open_graph_store(config: &Config) -> anyhow::Result<Arc<dyn GraphStore>> {
+    match config.storage {
+        StorageBackend::Neo4j => {
+            let repository = Neo4jRepository::new().await?;
+            repository.initialize_schema().await?;
+            Ok(Arc::new(repository))
+        }
+        StorageBackend::Memory => anyhow::bail!("in-memory storage holds no graph outside `serve`"),
+        StorageBackend::Embedded => Ok(Arc::new(EmbeddedGraphStore::open(&config.data_dir).await?)),
+    }
+}
+
+/// Open the persistent vector store of `config` for a one-off command
+async fn open_vector_store(config: &Config) -> anyhow::Result<Arc<dyn VectorStore>> {
+    match config.storage {
+        StorageBackend::Neo4j => Ok(Arc::new(QdrantRepository::new(QdrantConfig::from_env()).await?)),
+        StorageBackend::Memory => anyhow::bail!("in-memory storage holds no vectors outside `serve`"),
+        StorageBackend::Embedded => Ok(Arc::new(EmbeddedVectorStore::open(&config.data_dir).await?)),
+    }
+}
+
+/// Open the stores of `config` written by `ingest`, running pending migrations
+async fn open_ingest_stores(config: &Config) -> anyhow::Result<ingest::Stores> {
+    match config.storage {
+        StorageBackend::Neo4j => {
+            let (graph, vectors) = connect_external_stores().await?;
+            Ok(ingest::Stores { graph, vectors })
+        }
+        StorageBackend::Memory => anyhow::bail!("in-memory storage holds nothing outside `serve`; use --dry-run"),
+        StorageBackend::Embedded => Ok(ingest::Stores {
+            graph: open_graph_store(config).await?,
+            vectors: Some(open_vector_store(config).await?),
+        }),
+    }
+}
+
+/// Open every persistent store of `config` for `backup` / `restore`
+async fn open_backup_stores(config: &Config) -> anyhow::Result<backup::Stores> {
+    match config.storage {
+        StorageBackend::Neo4j => {
+            let graph = open_graph_store(config).await?;
+            let vectors = open_vector_store(config).await?;
+            let feedback: Option<Arc<dyn FeedbackStore>> = match &config.postgres.url {
+                Some(url) => {
+                    let repository = FeedbackRepository::connect(url).await?;
+                    repository.init_schema().await?;
+                    Some(Arc::new(repository))
+                }
+                None => {
+                    warn!("DATABASE_URL not set: feedback is left out");
+                    None
+                }
+            };
+            Ok(backup::Stores { graph, vectors, feedback })
+        }
+        StorageBackend::Memory => anyhow::bail!("in-memory storage holds nothing outside `serve`"),
+        StorageBackend::Embedded => Ok(backup::Stores {
+            graph: open_graph_store(config).await?,
+            vectors: open_vector_store(config).await?,
+            feedback: Some(Arc::new(EmbeddedFeedbackStore::open(&config.data_dir).await?)),
+        }),
+    }
+}
+
+/// `--format`, or the format implied by the file extension
+fn interchange_format(
+    path: &std::path::Path,
+    format: Option<interchange::Format>,
+) -> anyhow::Result<interchange::Format> {
+    format.or_else(|| interchange::Format::from_path(path)).ok_or_else(|| {
+        anyhow::anyhow!("cannot tell the format of {} from its extension; pass --format", path.display())
+    })
 }
 
 /// Validate that all services are reachable before serving requests
 async fn validate_connections(
-    repository: &Neo4jRepository,
-    qdrant: Option<&Arc<QdrantRepository>>,
+    repository: &dyn GraphStore,
+    vectors: Option<&Arc<dyn VectorStore>>,
 ) -> anyhow::Result<()> {
-    // Validate Neo4j by running a simple count query
+    // Validate the graph store by running a simple count query
     match repository.count().await {
-        Ok(count) => info!("Neo4j validated: {} UI elements in database", count),
+        Ok(count) => info!("Graph store validated: {} UI elements in database", count),
         Err(e) => {
-            error!("Neo4j validation failed: {}", e);
-            return Err(anyhow::anyhow!("Neo4j connection validation failed: {}", e));
+            error!("Graph store validation failed: {}", e);
+            return Err(anyhow::anyhow!("Graph store connection validation failed: {}", e));
         }
     }
 
-    // Validate Qdrant if available
-    if let Some(qdrant_repo) = qdrant {
-        match qdrant_repo.all_collections_info().await {
+    // Validate the vector store if available
+    if let Some(vectors) = vectors {
+        match vectors.all_collections_info().await {
             Ok(infos) => {
                 let total_points: u64 = infos.iter().map(|i| i.points_count).sum();
                 info!(
-                    "Qdrant validated: {} collections, {} total points",
+                    "Vector store validated: {} collections, {} total points",
                     infos.len(),
                     total_points
                 );
             }
             Err(e) => {
-                warn!("Qdrant validation warning: {}. Vector search may not work.", e);
-                // Don't fail - Qdrant is optional
+                warn!("Vector store validation warning: {}. Vector search may not work.", e);
+                // Don't fail - vector search is optional
             }
         }
     } else {
-        info!("Qdrant not configured - skipping validation");
+        info!("Vector store not configured - skipping validation");
     }
 
     // Validate OpenAI API key if set
@@ -82,24 +361,13 @@ async fn validate_connections(
     Ok(())
 }
 
-/// Initialize all application components
-async fn init_shared_state() -> anyhow::Result<SharedState> {
-    info!("Initializing CodeGraph application state...");
-
+/// Connect to Neo4j and Qdrant, running their pending migrations
+async fn connect_external_stores() -> anyhow::Result<(Arc<dyn GraphStore>, Option<Arc<dyn VectorStore>>)> {
     // 1. Initialize Neo4j repository
     info!("Connecting to Neo4j...");
     let neo4j_repository = match Neo4jRepository::new().await {
         Ok(repo) => {
             info!("Neo4j connected successfully");
-
-            // Initialize schema (constraints + indexes)
-            info!("Initializing Neo4j schema...");
-            if let Err(e) = repo.initialize_schema().await {
-                warn!("Failed to initialize Neo4j schema: {}. Continuing anyway.", e);
-            } else {
-                info!("Neo4j schema initialized successfully");
-            }
-
             repo
         }
         Err(e) => {
@@ -107,22 +375,15 @@ async fn init_shared_state() -> anyhow::Result<SharedState> {
             return Err(e);
         }
     };
+    neo4j_repository.migrate().await.context("Neo4j schema migration failed")?;
 
     // 2. Initialize Qdrant repository and collections
     info!("Connecting to Qdrant...");
-    let qdrant_repository = match QdrantRepository::new(QdrantConfig::default()).await {
+    let qdrant_repository = match QdrantRepository::new(QdrantConfig::from_env()).await {
         Ok(repo) => {
             info!("Qdrant connected successfully");
-
-            // Initialize collections on startup
-            info!("Initializing Qdrant collections...");
-            if let Err(e) = repo.init_collections().await {
-                warn!("Failed to initialize some Qdrant collections: {}. Continuing anyway.", e);
-            } else {
-                info!("Qdrant collections initialized successfully");
-            }
-
-            Some(Arc::new(repo))
+            repo.migrate().await.context("Qdrant migration failed")?;
+            Some(Arc::new(repo) as Arc<dyn VectorStore>)
         }
         Err(e) => {
             warn!("Failed to connect to Qdrant: {}. Vector search will be disabled.", e);
@@ -130,6 +391,30 @@ async fn init_shared_state() -> anyhow::Result<SharedState> {
         }
     };
 
+    Ok((Arc::new(neo4j_repository), qdrant_repository))
+}
+
+/// Initialize all application components
+async fn init_shared_state(config: &Config, tailwind_theme: Option<TailwindTheme>) -> anyhow::Result<SharedState> {
+    info!("Initializing CodeGraph application state ({} storage)...", config.storage.as_str());
+
+    // 1-2. Initialize graph and vector stores
+    let (graph_store, vector_store) = match config.storage {
+        StorageBackend::Neo4j => connect_external_stores().await?,
+        StorageBackend::Memory => {
+            warn!("Using in-memory storage: data is lost when the server stops");
+            let graph_store: Arc<dyn GraphStore> = Arc::new(MemoryGraphStore::new());
+            let vector_store: Arc<dyn VectorStore> = Arc::new(MemoryVectorStore::new());
+            (graph_store, Some(vector_store))
+        }
+        StorageBackend::Embedded => {
+            info!("Opening embedded storage in {}", config.data_dir.display());
+            let graph_store: Arc<dyn GraphStore> = Arc::new(EmbeddedGraphStore::open(&config.data_dir).await?);
+            let vector_store: Arc<dyn VectorStore> = Arc::new(EmbeddedVectorStore::open(&config.data_dir).await?);
+            (graph_store, Some(vector_store))
+        }
+    };
+
     // 3. Initialize embedding generator
     let embedding_generator = Arc::new(EmbeddingGenerator::new());
     if std::env::var("OPENAI_API_KEY").is_err() {
@@ -143,31 +428,119 @@ async fn init_shared_state() -> anyhow::Result<SharedState> {
     }
 
     // 5. Initialize extraction pipeline
-    let extraction = ExtractionPipeline::new();
+    let extraction = match tailwind_theme {
+        Some(theme) => ExtractionPipeline::new().with_tailwind_theme(theme),
+        None => ExtractionPipeline::new(),
+    };
 
     // 6. Create retriever with all components
     info!("Creating retriever...");
-    let retriever = HybridRetriever::new().with_embedding_generator(embedding_generator);
+    let retriever = HybridRetriever::new()
+        .with_embedding_generator(embedding_generator)
+        .with_graph_store(graph_store.clone());
 
-    // If Qdrant is available, configure it in the retriever
-    let retriever = if let Some(ref qdrant) = qdrant_repository {
-        retriever.with_qdrant(qdrant.clone())
+    // If a vector store is available, configure it in the retriever
+    let retriever = if let Some(ref vectors) = vector_store {
+        let fulltext_source = match std::env::var("RETRIEVAL_FULLTEXT_SOURCE") {
+            Ok(source) => source.parse().map_err(anyhow::Error::msg)?,
+            Err(_) => FulltextSource::default(),
+        };
+        info!("Fulltext channel searches {:?}", fulltext_source);
+        retriever
+            .with_vector_store(vectors.clone())
+            .with_fulltext_source(fulltext_source)
     } else {
         retriever
     };
 
     // 7. Validate connections before serving requests
     info!("Validating service connections...");
-    validate_connections(&neo4j_repository, qdrant_repository.as_ref()).await?;
+    validate_connections(graph_store.as_ref(), vector_store.as_ref()).await?;
 
     // 8. Create SharedState with all components
     info!("Creating shared state...");
-    let state = SharedState::new(neo4j_repository, retriever, generator, extraction);
+    let state = SharedState::new(graph_store, retriever, generator, extraction);
+
+    // 9. Link newly stored elements in the background
+    let interval = std::env::var("SIMILARITY_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(300);
+    match vector_store {
+        Some(vectors) if interval > 0 => {
+            info!("Similarity linking every {}s", interval);
+            similarity::spawn_periodic(
+                state.repository.clone(),
+                vectors,
+                std::time::Duration::from_secs(interval),
+            );
+        }
+        _ => info!("Background similarity linking disabled"),
+    }
 
     info!("Application state initialized successfully");
     Ok(state)
 }
 
+/// Import a token file and attach the tokens to a DesignSystem node
+async fn import_tokens(path: &std::path::Path, design_system: &str) -> anyhow::Result<()> {
+    let json = std::fs::read_to_string(path)?;
+    let set = TokenImporter::new().import_str(&json, design_system)?;
+    info!(
+        "Parsed {} tokens ({:?}) from {}",
+        set.tokens.len(),
+        set.format,
+        path.display()
+    );
+
+    let repository = Neo4jRepository::new().await?;
+    repository
+        .save_design_system(&DesignSystem::new(design_system, design_system))
+        .await?;
+
+    let mut nodes = Vec::new();
+    for token in &set.tokens {
+        let mut node = DesignToken::new(&token.name, &token.value, token.category.as_str())
+            .with_design_system(design_system);
+        if let Some(resolved) = &token.resolved_value {
+            node = node.with_resolved_value(resolved);
+        }
+        if let Some(alias_of) = &token.alias_of {
+            node = node.with_alias_of(alias_of);
+        }
+        repository.save_design_token(&node).await?;
+        nodes.push(node);
+    }
+
+    // Aliases are linked once all targets exist
+    for node in &nodes {
+        if let Some(alias_of) = &node.alias_of {
+            repository
+                .relations()
+                .link_token_alias(&node.name, alias_of, &node.theme)
+                .await?;
+        }
+    }
+
+    let kb = NarseseGenerator::new().generate_token_set(&set);
+    info!(
+        "Imported {} tokens into design system '{}' ({} Narsese statements)",
+        set.tokens.len(),
+        design_system,
+        kb.statements.len()
+    );
+
+    // The palette becomes beliefs of the reasoner
+    let statements: Vec<String> = kb.statements.into_iter().map(|s| s.statement).collect();
+    match tokio::task::spawn_blocking(move || ReasoningPipeline::new().add_beliefs(&statements)).await? {
+        Ok(0) => warn!("ONA disabled: token beliefs were not sent to the reasoner"),
+        Ok(sent) => info!("Sent {} token beliefs to ONA", sent),
+        Err(e) => warn!("Failed to send token beliefs to ONA: {}. Re-run import-tokens once ONA is up", e),
+    }
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt()
@@ -180,9 +553,17 @@ async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Serve { port } => {
+        Commands::Serve {
+            port,
+            storage,
+            data_dir,
+            tailwind_config,
+        } => {
+            let config = storage_config(storage, data_dir)?;
+            let tailwind_theme = load_tailwind_theme(tailwind_config.as_deref())?;
+
             // Initialize all components
-            let state = init_shared_state().await?;
+            let state = init_shared_state(&config, tailwind_theme).await?;
 
             // Start WebSocket server
             codegraph_ws::serve(state, port).await?;
@@ -194,6 +575,165 @@ async fn main() -> anyhow::Result<()> {
             info!("Running benchmark suite...");
             codegraph_benchmark::run().await?;
         }
+        Commands::ImportTokens { path, design_system } => {
+            import_tokens(&path, &design_system).await?;
+        }
+        Commands::Ingest {
+            path,
+            dry_run,
+            design_system,
+            concurrency,
+            batch_size,
+            similarity_threshold,
+            tailwind_config,
+            storage,
+            data_dir,
+        } => {
+            let concurrency = concurrency.unwrap_or_else(|| {
+                std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+            });
+            let tailwind_theme = load_tailwind_theme(tailwind_config.as_deref())?;
+            let config = storage_config(storage, data_dir)?;
+            let stores = if dry_run {
+                None
+            } else {
+                Some(open_ingest_stores(&config).await?)
+            };
+            let options = ingest::IngestOptions {
+                root: path,
+                dry_run,
+                design_system,
+                concurrency,
+                batch_size,
+                similarity_threshold,
+                tailwind_theme,
+            };
+            let report = ingest::ingest(options, stores).await?;
+            report.print(dry_run);
+        }
+        Commands::LinkSimilar {
+            all,
+            neighbours,
+            min_score,
+            replace_min_score,
+            storage,
+            data_dir,
+        } => {
+            let config = storage_config(storage, data_dir)?;
+            let graph_store = open_graph_store(&config).await?;
+            let vector_store = open_vector_store(&config).await?;
+            let options = similarity::SimilarityOptions {
+                neighbours,
+                min_score,
+                replace_min_score,
+                all,
+            };
+            let report = similarity::link_similar(graph_store.as_ref(), vector_store.as_ref(), &options).await?;
+            println!("Similarity linking");
+            report.print();
+        }
+        Commands::Duplicates {
+            min_score,
+            storage,
+            data_dir,
+        } => {
+            let graph_store = open_graph_store(&storage_config(storage, data_dir)?).await?;
+            let clusters = duplicates::find_clusters(graph_store.as_ref(), min_score).await?;
+            duplicates::print(&clusters);
+        }
+        Commands::Export { path, format, storage, data_dir } => {
+            let format = interchange_format(&path, format)?;
+            let graph_store = open_graph_store(&storage_config(storage, data_dir)?).await?;
+            let dump = graph_store.export_graph().await?;
+            std::fs::write(&path, interchange::write(&dump, format)?)?;
+            info!(
+                "Exported {} elements, {} snippets, {} design systems, {} tokens and {} relationships to {}",
+                dump.elements.len(),
+                dump.snippets.len(),
+                dump.design_systems.len(),
+                dump.tokens.len(),
+                dump.relations.len(),
+                path.display()
+            );
+        }
+        Commands::Import { path, format, storage, data_dir } => {
+            let format = interchange_format(&path, format)?;
+            let text = std::fs::read_to_string(&path)?;
+            let dump = interchange::read(&text, format)
+                .map_err(|e| anyhow::anyhow!("failed to read {}: {:#}", path.display(), e))?;
+            let graph_store = open_graph_store(&storage_config(storage, data_dir)?).await?;
+            let written = graph_store.import_graph(&dump).await?;
+            info!("Imported {} nodes and relationships from {}", written, path.display());
+        }
+        Commands::Fsck { repair, storage, data_dir } => {
+            let config = storage_config(storage, data_dir)?;
+            let stores = backup::Stores {
+                graph: open_graph_store(&config).await?,
+                vectors: open_vector_store(&config).await?,
+                feedback: None,
+            };
+            let snapshot = backup::Snapshot::take(&stores).await?;
+            let report = fsck::check(&snapshot);
+            fsck::print(&report);
+            if repair {
+                fsck::repair(&stores, &snapshot, &report, &mut ExtractionPipeline::new()).await?;
+            } else if report.problems() > 0 {
+                anyhow::bail!("{} problems found; run `codegraph fsck --repair` to fix them", report.problems());
+            }
+        }
+        Commands::Migrate { storage, data_dir } => {
+            let config = storage_config(storage, data_dir)?;
+            if config.storage != StorageBackend::Neo4j {
+                println!("{} storage has no schema to migrate", config.storage.as_str());
+            } else {
+                let mut migrated = vec![
+                    ("Neo4j", Neo4jRepository::new().await?.migrate().await?),
+                    ("Qdrant", QdrantRepository::new(QdrantConfig::from_env()).await?.migrate().await?),
+                ];
+                match &config.postgres.url {
+                    Some(url) => migrated.push(("PostgreSQL", FeedbackRepository::connect(url).await?.migrate().await?)),
+                    None => warn!("DATABASE_URL not set: PostgreSQL is not migrated"),
+                }
+                for (store, applied) in migrated {
+                    if applied.is_empty() {
+                        println!("{}: up to date", store);
+                    }
+                    for migration in applied {
+                        println!("{}: applied {} ({})", store, migration.version, migration.description);
+                    }
+                }
+            }
+        }
+        Commands::Backup { path, storage, data_dir } => {
+            let config = storage_config(storage, data_dir)?;
+            let stores = open_backup_stores(&config).await?;
+            let snapshot = backup::Snapshot::take(&stores).await?;
+            let file = std::fs::File::create(&path)?;
+            let manifest = backup::write(&snapshot, config.storage.as_str(), std::io::BufWriter::new(file))?;
+            info!(
+                "Backed up {} graph records, {} points in {} collections and {} feedback records to {}",
+                manifest.entries[0].count,
+                snapshot.point_count(),
+                snapshot.vectors.len(),
+                snapshot.feedback.as_ref().map_or(0, Vec::len),
+                path.display()
+            );
+        }
+        Commands::Restore { path, check, storage, data_dir } => {
+            let file = std::fs::File::open(&path)?;
+            let (manifest, snapshot) = backup::read(std::io::BufReader::new(file))
+                .map_err(|e| anyhow::anyhow!("failed to read {}: {:#}", path.display(), e))?;
+            info!(
+                "Read backup of {} storage taken {} (format {}, codegraph {})",
+                manifest.storage, manifest.created_at, manifest.format_version, manifest.codegraph_version
+            );
+            backup::print_orphans(&backup::orphans(&snapshot));
+            if !check {
+                let stores = open_backup_stores(&storage_config(storage, data_dir)?).await?;
+                backup::restore(&stores, &snapshot).await?;
+                info!("Restored {}", path.display());
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,242 @@
+//! Embedding similarity linking
+//!
+//! For every embedded element changed since it was last linked, queries its
+//! nearest neighbours in each vector collection and keeps the best `k` within
+//! its own design system and the best `k` in other design systems. Neighbours
+//! above a threshold are linked with `SIMILAR_TO {score}`; neighbours in
+//! another design system with the same category and interaction signature are
+//! proposed as `CAN_REPLACE`.
+//!
+//! Runs after `codegraph ingest`, on demand via `codegraph link-similar` and
+//! periodically while serving.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use codegraph_extraction::html::HtmlParser;
+use codegraph_graph::{GraphStore, SimilarityBasis, UIElement};
+use codegraph_vector::collections::COLLECTIONS;
+use codegraph_vector::{SearchResult, VectorStore};
+
+/// Neighbours kept per element, within and across design systems each
+pub const DEFAULT_NEIGHBOURS: usize = 10;
+
+/// Minimum cosine similarity linked with `SIMILAR_TO`
+pub const DEFAULT_MIN_SCORE: f32 = 0.85;
+
+/// Minimum cosine similarity for proposing `CAN_REPLACE`
+pub const DEFAULT_REPLACE_MIN_SCORE: f32 = 0.9;
+
+/// Pending elements processed per round trip
+const PAGE_SIZE: usize = 100;
+
+/// Options for a similarity linking run
+#[derive(Debug, Clone)]
+pub struct SimilarityOptions {
+    pub neighbours: usize,
+    pub min_score: f32,
+    pub replace_min_score: f32,
+    /// Relink every element instead of only those changed since the last run
+    pub all: bool,
+}
+
+impl Default for SimilarityOptions {
+    fn default() -> Self {
+        Self {
+            neighbours: DEFAULT_NEIGHBOURS,
+            min_score: DEFAULT_MIN_SCORE,
+            replace_min_score: DEFAULT_REPLACE_MIN_SCORE,
+            all: false,
+        }
+    }
+}
+
+/// Summary of a linking run
+#[derive(Debug, Default)]
+pub struct SimilarityReport {
+    pub elements: usize,
+    /// `SIMILAR_TO` pairs inside one design system
+    pub within: usize,
+    /// `SIMILAR_TO` pairs spanning two design systems
+    pub across: usize,
+    pub replacements: usize,
+}
+
+impl SimilarityReport {
+    pub fn print(&self) {
+        println!("  similarity:      {} elements linked", self.elements);
+        println!(
+            "  similar pairs:   {} within, {} across design systems",
+            self.within, self.across
+        );
+        println!("  replacements:    {} proposed", self.replacements);
+    }
+}
+
+/// Link elements pending since their last update (all elements with `options.all`)
+pub async fn link_similar(
+    repository: &dyn GraphStore,
+    vectors: &dyn VectorStore,
+    options: &SimilarityOptions,
+) -> anyhow::Result<SimilarityReport> {
+    if options.all {
+        repository.reset_similarity_linked().await?;
+    }
+
+    let mut report = SimilarityReport::default();
+    let mut parser = HtmlParser::new();
+    let mut signatures: HashMap<Uuid, Option<Vec<String>>> = HashMap::new();
+    let collections = collections(vectors).await;
+
+    loop {
+        let pending = repository.find_similarity_pending(PAGE_SIZE).await?;
+        if pending.is_empty() {
+            break;
+        }
+        let ids: Vec<Uuid> = pending.iter().map(|e| e.id).collect();
+        repository
+            .clear_similarity(&ids, SimilarityBasis::Embedding)
+            .await?;
+
+        let mut linked: HashSet<(Uuid, Uuid)> = HashSet::new();
+        let mut similar = Vec::new();
+        let mut candidates = Vec::new();
+        for element in &pending {
+            let Some(embedding) = &element.embedding else {
+                continue;
+            };
+            for (neighbour, across) in nearest(vectors, &collections, element, embedding, options).await {
+                let pair = (element.id.min(neighbour.id), element.id.max(neighbour.id));
+                if !linked.insert(pair) {
+                    continue;
+                }
+                similar.push((element.id, neighbour.id, neighbour.score));
+                similar.push((neighbour.id, element.id, neighbour.score));
+                if across {
+                    report.across += 1;
+                } else {
+                    report.within += 1;
+                }
+
+                if across && neighbour.score >= options.replace_min_score && neighbour.payload.category == element.category {
+                    candidates.push((element.id, neighbour.id, neighbour.score));
+                }
+            }
+            signatures
+                .entry(element.id)
+                .or_insert_with(|| interaction_signature(&mut parser, element));
+        }
+
+        // Replacement needs equal interaction signatures on both sides
+        let unknown: Vec<Uuid> = candidates
+            .iter()
+            .map(|(_, neighbour, _)| *neighbour)
+            .filter(|id| !signatures.contains_key(id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        for element in repository.find_by_ids(&unknown).await? {
+            let signature = interaction_signature(&mut parser, &element);
+            signatures.insert(element.id, signature);
+        }
+        let mut replacements = Vec::new();
+        for (a, b, score) in candidates {
+            if let (Some(Some(sig_a)), Some(Some(sig_b))) = (signatures.get(&a), signatures.get(&b))
+                && sig_a == sig_b
+            {
+                replacements.push((a, b, score));
+                replacements.push((b, a, score));
+            }
+        }
+
+        repository.create_similarities(&similar, SimilarityBasis::Embedding).await?;
+        repository
+            .propose_replacements(&replacements, SimilarityBasis::Embedding)
+            .await?;
+        repository.mark_similarity_linked(&ids).await?;
+
+        report.replacements += replacements.len() / 2;
+        report.elements += pending.len();
+        debug!("Linked {} elements ({} similar pairs)", pending.len(), similar.len() / 2);
+    }
+
+    Ok(report)
+}
+
+/// Predefined collections plus any the vector store has created since
+async fn collections(vectors: &dyn VectorStore) -> Vec<String> {
+    let mut names: Vec<String> = COLLECTIONS.iter().map(|c| c.to_string()).collect();
+    match vectors.all_collections_info().await {
+        Ok(infos) => names.extend(infos.into_iter().map(|info| info.name)),
+        Err(e) => debug!("Listing vector collections failed: {}", e),
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Best neighbours above `min_score`, flagged `true` when in another design system
+async fn nearest(
+    vectors: &dyn VectorStore,
+    collections: &[String],
+    element: &UIElement,
+    embedding: &[f32],
+    options: &SimilarityOptions,
+) -> Vec<(SearchResult, bool)> {
+    let mut hits = Vec::new();
+    for collection in collections {
+        match vectors
+            .search(collection, embedding.to_vec(), options.neighbours as u64 + 1, None)
+            .await
+        {
+            Ok(results) => hits.extend(results),
+            Err(e) => debug!("Similarity search in {} failed for {}: {}", collection, element.id, e),
+        }
+    }
+    hits.retain(|hit| hit.id != element.id && hit.score >= options.min_score);
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let (mut within, mut across) = (0, 0);
+    hits.into_iter()
+        .filter_map(|hit| {
+            let is_across = element.design_system.as_deref() != Some(hit.payload.design_system.as_str());
+            let count = if is_across { &mut across } else { &mut within };
+            *count += 1;
+            (*count <= options.neighbours).then_some((hit, is_across))
+        })
+        .collect()
+}
+
+/// Interaction signature of an element's HTML template
+fn interaction_signature(parser: &mut HtmlParser, element: &UIElement) -> Option<Vec<String>> {
+    let html = parser.parse(element.html_template.as_deref()?).ok()?;
+    let signature: BTreeSet<String> = html
+        .elements
+        .iter()
+        .flat_map(|root| root.interaction_signature())
+        .collect();
+    Some(signature.into_iter().collect())
+}
+
+/// Relink pending elements every `interval` until the process exits
+pub fn spawn_periodic(repository: Arc<dyn GraphStore>, vectors: Arc<dyn VectorStore>, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            match link_similar(repository.as_ref(), vectors.as_ref(), &SimilarityOptions::default()).await {
+                Ok(report) if report.elements > 0 => info!(
+                    "Similarity job linked {} elements ({} within, {} across, {} replacements)",
+                    report.elements, report.within, report.across, report.replacements
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Similarity job failed: {}", e),
+            }
+        }
+    });
+}
@@ -4,6 +4,7 @@
 //! See the `Config` struct for all available options.
 
 use std::env;
+use std::path::PathBuf;
 
 /// Application configuration loaded from environment variables
 #[derive(Debug, Clone)]
@@ -11,9 +12,13 @@ pub struct Config {
     // Server
     pub server: ServerConfig,
     // Database
+    pub storage: StorageBackend,
+    /// Directory of the embedded backend's files
+    pub data_dir: PathBuf,
     pub neo4j: Neo4jConfig,
     pub qdrant: QdrantConfig,
     pub redis: RedisConfig,
+    pub postgres: PostgresConfig,
     // External APIs
     pub openai: OpenAIConfig,
     // ONA/NARS
@@ -33,6 +38,51 @@ pub struct ServerConfig {
     pub request_timeout_secs: u64,
 }
 
+/// Storage backend behind the graph, vector and feedback stores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    /// Neo4j + Qdrant + PostgreSQL
+    #[default]
+    Neo4j,
+    /// In-memory stores; nothing is persisted
+    Memory,
+    /// In-memory stores persisted under `Config::data_dir`
+    Embedded,
+}
+
+impl StorageBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Neo4j => "neo4j",
+            Self::Memory => "memory",
+            Self::Embedded => "embedded",
+        }
+    }
+
+    pub fn from_env() -> Self {
+        env::var("CODEGRAPH_STORAGE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "neo4j" | "external" => Ok(Self::Neo4j),
+            "memory" | "in-memory" => Ok(Self::Memory),
+            "embedded" | "local" => Ok(Self::Embedded),
+            other => Err(format!(
+                "unknown storage backend '{}' (expected neo4j, memory or embedded)",
+                other
+            )),
+        }
+    }
+}
+
 /// Neo4j database configuration
 #[derive(Debug, Clone)]
 pub struct Neo4jConfig {
@@ -56,12 +106,20 @@ pub struct RedisConfig {
     pub url: Option<String>,
 }
 
+/// PostgreSQL configuration (feedback store of the `neo4j` backend)
+#[derive(Debug, Clone)]
+pub struct PostgresConfig {
+    pub url: Option<String>,
+}
+
 /// OpenAI API configuration
 #[derive(Debug, Clone)]
 pub struct OpenAIConfig {
     pub api_key: Option<String>,
     pub model: String,
     pub embedding_model: String,
+    /// Dimensions requested from the embedding model; vector indexes are created with this size
+    pub embedding_dimensions: u64,
     pub max_tokens: u32,
     pub temperature: f32,
 }
@@ -87,9 +145,14 @@ impl Config {
     pub fn from_env() -> Self {
         Self {
             server: ServerConfig::from_env(),
+            storage: StorageBackend::from_env(),
+            data_dir: env::var("CODEGRAPH_DATA_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("codegraph-data")),
             neo4j: Neo4jConfig::from_env(),
             qdrant: QdrantConfig::from_env(),
             redis: RedisConfig::from_env(),
+            postgres: PostgresConfig::from_env(),
             openai: OpenAIConfig::from_env(),
             ona: OnaConfig::from_env(),
             rate_limit: RateLimitConfig::from_env(),
@@ -106,12 +169,18 @@ impl Config {
             errors.push("SERVER_PORT must be > 0".to_string());
         }
 
-        if self.neo4j.uri.is_empty() {
-            errors.push("NEO4J_URI is required".to_string());
+        if self.storage == StorageBackend::Neo4j {
+            if self.neo4j.uri.is_empty() {
+                errors.push("NEO4J_URI is required".to_string());
+            }
+
+            if self.qdrant.url.is_empty() {
+                errors.push("QDRANT_URL is required".to_string());
+            }
         }
 
-        if self.qdrant.url.is_empty() {
-            errors.push("QDRANT_URL is required".to_string());
+        if self.storage == StorageBackend::Embedded && self.data_dir.as_os_str().is_empty() {
+            errors.push("CODEGRAPH_DATA_DIR is required".to_string());
         }
 
         if errors.is_empty() {
@@ -161,11 +230,19 @@ impl QdrantConfig {
             vector_size: env::var("QDRANT_VECTOR_SIZE")
                 .ok()
                 .and_then(|s| s.parse().ok())
-                .unwrap_or(1536),
+                .unwrap_or_else(embedding_dimensions_from_env),
         }
     }
 }
 
+/// `OPENAI_EMBEDDING_DIMENSIONS`, defaulting to 1536
+fn embedding_dimensions_from_env() -> u64 {
+    env::var("OPENAI_EMBEDDING_DIMENSIONS")
+        .ok()
+        .and_then(|d| d.parse().ok())
+        .unwrap_or(1536)
+}
+
 impl RedisConfig {
     pub fn from_env() -> Self {
         Self {
@@ -174,6 +251,14 @@ impl RedisConfig {
     }
 }
 
+impl PostgresConfig {
+    pub fn from_env() -> Self {
+        Self {
+            url: env::var("DATABASE_URL").ok(),
+        }
+    }
+}
+
 impl OpenAIConfig {
     pub fn from_env() -> Self {
         Self {
@@ -181,6 +266,7 @@ impl OpenAIConfig {
             model: env::var("OPENAI_MODEL").unwrap_or_else(|_| "gpt-4o".to_string()),
             embedding_model: env::var("OPENAI_EMBEDDING_MODEL")
                 .unwrap_or_else(|_| "text-embedding-3-small".to_string()),
+            embedding_dimensions: embedding_dimensions_from_env(),
             max_tokens: env::var("OPENAI_MAX_TOKENS")
                 .ok()
                 .and_then(|t| t.parse().ok())
@@ -244,4 +330,12 @@ mod tests {
         assert_eq!(config.neo4j.max_connections, 50);
         assert_eq!(config.rate_limit.requests_per_minute, 100);
     }
+
+    #[test]
+    fn test_storage_backend_parse() {
+        assert_eq!("memory".parse::<StorageBackend>(), Ok(StorageBackend::Memory));
+        assert_eq!("Neo4j".parse::<StorageBackend>(), Ok(StorageBackend::Neo4j));
+        assert_eq!("embedded".parse::<StorageBackend>(), Ok(StorageBackend::Embedded));
+        assert!("sqlite".parse::<StorageBackend>().is_err());
+    }
 }
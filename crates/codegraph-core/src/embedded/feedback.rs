@@ -0,0 +1,221 @@
+//! Feedback store persisted as an append-only JSON Lines file
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::entities::{CreateFeedback, Feedback, FeedbackMetrics, FeedbackSummary};
+use crate::memory::MemoryFeedbackStore;
+use crate::traits::FeedbackStore;
+
+use super::{blocking, read_optional, write_at, write_atomic};
+
+const FEEDBACK_FILE: &str = "feedback.jsonl";
+
+/// [`MemoryFeedbackStore`] that appends each record to `feedback.jsonl`
+pub struct EmbeddedFeedbackStore {
+    inner: MemoryFeedbackStore,
+    path: PathBuf,
+    /// Bytes of acknowledged records; held across a write so the file
+    /// matches memory
+    len: Mutex<u64>,
+}
+
+impl EmbeddedFeedbackStore {
+    /// Open (or create) the feedback stored under `data_dir`
+    pub async fn open(data_dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let data_dir = data_dir.as_ref().to_path_buf();
+        let path = data_dir.join(FEEDBACK_FILE);
+        let (records, len) = blocking({
+            let path = path.clone();
+            move || {
+                fs::create_dir_all(&data_dir)?;
+                load(&path)
+            }
+        })
+        .await?;
+
+        Ok(Self {
+            inner: MemoryFeedbackStore::restore(records),
+            path,
+            len: Mutex::new(len),
+        })
+    }
+
+    /// Rewrite the file after a change made by `change`, undoing it when that fails
+    async fn rewritten(&self, change: impl std::future::Future<Output = anyhow::Result<u64>>) -> anyhow::Result<u64> {
+        let mut len = self.len.lock().await;
+        let before = self.inner.records();
+        let changed = change.await?;
+        if changed == 0 {
+            return Ok(0);
+        }
+
+        let mut bytes = Vec::new();
+        for feedback in self.inner.records() {
+            bytes.extend(serde_json::to_vec(&feedback)?);
+            bytes.push(b'\n');
+        }
+        let path = self.path.clone();
+        let written = bytes.len() as u64;
+        match blocking(move || write_atomic(&path, &bytes)).await {
+            Ok(()) => {
+                *len = written;
+                Ok(changed)
+            }
+            Err(e) => {
+                self.inner.replace(before);
+                Err(e).with_context(|| format!("Failed to rewrite {}", self.path.display()))
+            }
+        }
+    }
+}
+
+/// Records of `path` and the length they span
+fn load(path: &Path) -> anyhow::Result<(Vec<Feedback>, u64)> {
+    let bytes = read_optional(path)?.unwrap_or_default();
+    let mut records = Vec::new();
+    let mut len = 0;
+    // A final line without a newline is an append torn by a crash, never acknowledged
+    for (n, line) in bytes.split_inclusive(|b| *b == b'\n').enumerate() {
+        if !line.ends_with(b"\n") {
+            warn!("Dropping {} bytes of an unfinished record from {}", line.len(), path.display());
+            break;
+        }
+        len += line.len() as u64;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let record = serde_json::from_slice(line).with_context(|| format!("Corrupt line {} of {}", n + 1, path.display()))?;
+        records.push(record);
+    }
+    Ok((records, len))
+}
+
+#[async_trait]
+impl FeedbackStore for EmbeddedFeedbackStore {
+    async fn init_schema(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn save(&self, feedback: CreateFeedback) -> anyhow::Result<Feedback> {
+        let mut len = self.len.lock().await;
+        let feedback = self.inner.save(feedback).await?;
+        let mut line = serde_json::to_vec(&feedback)?;
+        line.push(b'\n');
+
+        let (path, offset, written) = (self.path.clone(), *len, line.len() as u64);
+        if let Err(e) = blocking(move || write_at(&path, offset, &line)).await {
+            self.inner.remove(feedback.id);
+            return Err(e).with_context(|| format!("Failed to append to {}", self.path.display()));
+        }
+        *len += written;
+        Ok(feedback)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<Feedback>> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_generation(&self, generation_id: Uuid) -> anyhow::Result<Vec<Feedback>> {
+        self.inner.find_by_generation(generation_id).await
+    }
+
+    async fn find_by_element(&self, element_id: Uuid) -> anyhow::Result<Vec<Feedback>> {
+        self.inner.find_by_element(element_id).await
+    }
+
+    async fn get_element_summary(&self, element_id: Uuid) -> anyhow::Result<FeedbackSummary> {
+        self.inner.get_element_summary(element_id).await
+    }
+
+    async fn list_recent(&self, limit: i64, offset: i64) -> anyhow::Result<Vec<Feedback>> {
+        self.inner.list_recent(limit, offset).await
+    }
+
+    async fn list_until(
+        &self,
+        until: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Feedback>> {
+        self.inner.list_until(until, after, limit).await
+    }
+
+    async fn get_metrics(&self) -> anyhow::Result<FeedbackMetrics> {
+        self.inner.get_metrics().await
+    }
+
+    async fn delete_older_than(&self, days: i64) -> anyhow::Result<u64> {
+        self.rewritten(self.inner.delete_older_than(days)).await
+    }
+
+    async fn import_feedback(&self, records: &[Feedback]) -> anyhow::Result<u64> {
+        self.rewritten(self.inner.import_feedback(records)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::FeedbackType;
+
+    #[tokio::test]
+    async fn test_feedback_survives_reopen() {
+        let dir = std::env::temp_dir().join(format!("codegraph-feedback-{}", Uuid::new_v4()));
+        let element = Uuid::new_v4();
+
+        {
+            let store = EmbeddedFeedbackStore::open(&dir).await.unwrap();
+            for feedback_type in [FeedbackType::ThumbsUp, FeedbackType::ThumbsDown] {
+                store
+                    .save(CreateFeedback {
+                        generation_id: Uuid::new_v4(),
+                        element_ids: vec![element],
+                        feedback_type,
+                        query_context: None,
+                        comment: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let store = EmbeddedFeedbackStore::open(&dir).await.unwrap();
+        let summary = store.get_element_summary(element).await.unwrap();
+        assert_eq!(summary.positive_count, 1);
+        assert_eq!(summary.negative_count, 1);
+        assert_eq!(store.find_by_element(element).await.unwrap()[0].feedback_type, FeedbackType::ThumbsDown);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_failed_append_leaves_memory_unchanged() {
+        let dir = std::env::temp_dir().join(format!("codegraph-feedback-{}", Uuid::new_v4()));
+        let store = EmbeddedFeedbackStore::open(&dir).await.unwrap();
+        // A directory in place of the file makes every append fail
+        fs::create_dir(dir.join(FEEDBACK_FILE)).unwrap();
+
+        let generation_id = Uuid::new_v4();
+        let saved = store
+            .save(CreateFeedback {
+                generation_id,
+                element_ids: vec![Uuid::new_v4()],
+                feedback_type: FeedbackType::ThumbsUp,
+                query_context: None,
+                comment: None,
+            })
+            .await;
+        assert!(saved.is_err());
+        assert!(store.find_by_generation(generation_id).await.unwrap().is_empty());
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,605 @@
+//! Graph store kept as a JSON snapshot plus a JSON Lines journal of writes
+//!
+//! `fulltext.json` holds the inverted index of the elements in `graph.json`,
+//! tagged with the same generation. It is written on compaction, so opening
+//! the graph only indexes the elements saved by its journal; an index that is
+//! missing or from another generation is rebuilt instead.
+
+use std::borrow::Cow;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::entities::{
+    Counterpart, DesignSystem, DesignToken, ElementPage, ElementQuery, GraphDump, GraphRelation, Neighborhood, NeighborhoodQuery, SimilarElement,
+    SimilarityBasis, Snippet, SnippetSummary, UIElement,
+};
+use crate::memory::{FulltextSnapshot, GraphSnapshot, MemoryGraphStore};
+use crate::traits::GraphStore;
+
+use super::{blocking, journal_path, read_optional, write_atomic, Journal};
+
+const GRAPH_FILE: &str = "graph.json";
+const FULLTEXT_FILE: &str = "fulltext.json";
+
+/// `graph.json`; `generation` numbers the journal extending it
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    #[serde(default)]
+    generation: u64,
+    #[serde(flatten)]
+    graph: GraphSnapshot,
+}
+
+/// `fulltext.json`; `generation` is that of the `graph.json` it indexes
+#[derive(Serialize, Deserialize)]
+struct FulltextFile {
+    generation: u64,
+    #[serde(flatten)]
+    index: FulltextSnapshot,
+}
+
+/// One journaled write, replayed through the [`MemoryGraphStore`] method of the same name
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum GraphWrite<'a> {
+    SaveBatch { elements: Cow<'a, [UIElement]> },
+    DeleteBatch { ids: Cow<'a, [Uuid]> },
+    MarkSimilarityLinked { ids: Cow<'a, [Uuid]> },
+    ResetSimilarityLinked,
+    SaveDesignSystem { design_system: Cow<'a, DesignSystem> },
+    SaveDesignToken { token: Cow<'a, DesignToken> },
+    SaveSnippet { snippet: Cow<'a, Snippet> },
+    DeleteSnippet { id: Uuid, delete_orphans: bool },
+    LinkChildren { links: Cow<'a, [(Uuid, Uuid)]> },
+    LinkToToken { element_id: Uuid, token_name: Cow<'a, str> },
+    LinkTokenAlias { name: Cow<'a, str>, alias_of: Cow<'a, str>, theme: Cow<'a, str> },
+    CreateSimilarities { pairs: Cow<'a, [(Uuid, Uuid, f32)]>, basis: SimilarityBasis },
+    ProposeReplacements { pairs: Cow<'a, [(Uuid, Uuid, f32)]>, basis: SimilarityBasis },
+    ClearSimilarity { element_ids: Cow<'a, [Uuid]>, basis: SimilarityBasis },
+    DeleteRelations { relations: Cow<'a, [GraphRelation]> },
+    ImportGraph { dump: Cow<'a, GraphDump> },
+}
+
+impl GraphWrite<'_> {
+    async fn replay(self, store: &MemoryGraphStore) -> anyhow::Result<()> {
+        match self {
+            Self::SaveBatch { elements } => store.save_batch(&elements).await.map(drop),
+            Self::DeleteBatch { ids } => store.delete_batch(&ids).await.map(drop),
+            Self::MarkSimilarityLinked { ids } => store.mark_similarity_linked(&ids).await,
+            Self::ResetSimilarityLinked => store.reset_similarity_linked().await,
+            Self::SaveDesignSystem { design_system } => store.save_design_system(&design_system).await,
+            Self::SaveDesignToken { token } => store.save_design_token(&token).await,
+            Self::SaveSnippet { snippet } => store.save_snippet(&snippet).await,
+            Self::DeleteSnippet { id, delete_orphans } => store.delete_snippet(id, delete_orphans).await.map(drop),
+            Self::LinkChildren { links } => store.link_children(&links).await,
+            Self::LinkToToken { element_id, token_name } => store.link_to_token(element_id, &token_name).await,
+            Self::LinkTokenAlias { name, alias_of, theme } => store.link_token_alias(&name, &alias_of, &theme).await,
+            Self::CreateSimilarities { pairs, basis } => store.create_similarities(&pairs, basis).await,
+            Self::ProposeReplacements { pairs, basis } => store.propose_replacements(&pairs, basis).await,
+            Self::ClearSimilarity { element_ids, basis } => store.clear_similarity(&element_ids, basis).await.map(drop),
+            Self::DeleteRelations { relations } => store.delete_relations(&relations).await.map(drop),
+            Self::ImportGraph { dump } => store.import_graph(&dump).await.map(drop),
+        }
+    }
+}
+
+/// [`MemoryGraphStore`] that journals every write to the data directory
+pub struct EmbeddedGraphStore {
+    inner: Arc<MemoryGraphStore>,
+    /// Held across a write so the journal records writes in the order they are applied
+    journal: Mutex<Journal>,
+}
+
+impl EmbeddedGraphStore {
+    /// Open (or create) the graph stored under `data_dir`
+    pub async fn open(data_dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let data_dir = data_dir.as_ref().to_path_buf();
+        let (snapshot, fulltext, writes, journal) = blocking(move || load(&data_dir)).await?;
+
+        let inner = MemoryGraphStore::restore(snapshot, fulltext)?;
+        for write in writes {
+            // A write that failed when it was made fails again without effect
+            if let Err(e) = write.replay(&inner).await {
+                debug!("Skipped journaled graph write that failed: {:#}", e);
+            }
+        }
+        Ok(Self {
+            inner: Arc::new(inner),
+            journal: Mutex::new(journal),
+        })
+    }
+
+    /// Journal `write`, then apply it in memory through `apply`
+    async fn journaled<T>(&self, write: GraphWrite<'_>, apply: impl Future<Output = anyhow::Result<T>>) -> anyhow::Result<T> {
+        let mut record = serde_json::to_vec(&write)?;
+        record.push(b'\n');
+
+        let mut journal = self.journal.lock().await;
+        journal.append(record).await?;
+        let result = apply.await;
+        if journal.needs_compaction() {
+            let inner = self.inner.clone();
+            let fulltext = journal.snapshot.with_file_name(FULLTEXT_FILE);
+            let compacted = journal
+                .compact(move |generation| {
+                    // Written first: a crash before the snapshot leaves an index of a newer generation, which is ignored
+                    let index = FulltextFile {
+                        generation,
+                        index: inner.fulltext_snapshot(),
+                    };
+                    write_atomic(&fulltext, &serde_json::to_vec(&index)?)
+                        .with_context(|| format!("Failed to write {}", fulltext.display()))?;
+                    let file = SnapshotFile {
+                        generation,
+                        graph: inner.snapshot(),
+                    };
+                    Ok(serde_json::to_vec(&file)?)
+                })
+                .await;
+            // Every write is still journaled, so compaction is retried on the next one
+            if let Err(e) = compacted {
+                warn!("Failed to compact the graph journal: {:#}", e);
+            }
+        }
+        result
+    }
+}
+
+/// Read the snapshot, its fulltext index and the journal under `data_dir`
+#[allow(clippy::type_complexity)]
+fn load(data_dir: &Path) -> anyhow::Result<(GraphSnapshot, Option<FulltextSnapshot>, Vec<GraphWrite<'static>>, Journal)> {
+    fs::create_dir_all(data_dir)?;
+    let path = data_dir.join(GRAPH_FILE);
+    let file = match read_optional(&path)? {
+        Some(bytes) => serde_json::from_slice(&bytes).with_context(|| format!("Corrupt graph file {}", path.display()))?,
+        None => SnapshotFile {
+            generation: 0,
+            graph: GraphSnapshot::default(),
+        },
+    };
+
+    let journal_file = journal_path(&path, file.generation);
+    let bytes = read_optional(&journal_file)?.unwrap_or_default();
+    let mut writes = Vec::new();
+    let mut len = 0;
+    // A final line without a newline is an append torn by a crash, never acknowledged
+    for line in bytes.split_inclusive(|b| *b == b'\n').filter(|line| line.ends_with(b"\n")) {
+        let write = serde_json::from_slice(line)
+            .with_context(|| format!("Corrupt record at byte {} of {}", len, journal_file.display()))?;
+        writes.push(write);
+        len += line.len() as u64;
+    }
+    if len < bytes.len() as u64 {
+        warn!(
+            "Dropping {} bytes of an unfinished write from {}",
+            bytes.len() as u64 - len,
+            journal_file.display()
+        );
+    }
+
+    let fulltext_path = data_dir.join(FULLTEXT_FILE);
+    let fulltext = match read_optional(&fulltext_path)?.map(|bytes| serde_json::from_slice::<FulltextFile>(&bytes)) {
+        Some(Ok(index)) if index.generation == file.generation => Some(index.index),
+        Some(Ok(index)) => {
+            debug!("Ignoring {} of generation {}", fulltext_path.display(), index.generation);
+            None
+        }
+        Some(Err(e)) => {
+            debug!("Ignoring corrupt {}: {}", fulltext_path.display(), e);
+            None
+        }
+        None => None,
+    };
+
+    let journal = Journal::open(&path, file.generation, len)?;
+    Ok((file.graph, fulltext, writes, journal))
+}
+
+#[async_trait]
+impl GraphStore for EmbeddedGraphStore {
+    async fn initialize_schema(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn save(&self, element: &UIElement) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::SaveBatch {
+                elements: Cow::Borrowed(std::slice::from_ref(element)),
+            },
+            self.inner.save(element),
+        )
+        .await
+    }
+
+    async fn save_batch(&self, elements: &[UIElement]) -> anyhow::Result<usize> {
+        self.journaled(
+            GraphWrite::SaveBatch {
+                elements: Cow::Borrowed(elements),
+            },
+            self.inner.save_batch(elements),
+        )
+        .await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<UIElement>> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> anyhow::Result<Vec<UIElement>> {
+        self.inner.find_by_ids(ids).await
+    }
+
+    async fn list_all_elements(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        self.inner.list_all_elements(limit).await
+    }
+
+    async fn list_elements(&self, query: &ElementQuery) -> anyhow::Result<ElementPage> {
+        self.inner.list_elements(query).await
+    }
+
+    async fn find_by_category(&self, category: &str) -> anyhow::Result<Vec<UIElement>> {
+        self.inner.find_by_category(category).await
+    }
+
+    async fn fulltext_search(&self, search_term: &str, limit: usize) -> anyhow::Result<Vec<SimilarElement>> {
+        self.inner.fulltext_search(search_term, limit).await
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        self.journaled(
+            GraphWrite::DeleteBatch {
+                ids: Cow::Owned(vec![id]),
+            },
+            self.inner.delete(id),
+        )
+        .await
+    }
+
+    async fn delete_batch(&self, ids: &[Uuid]) -> anyhow::Result<u64> {
+        self.journaled(GraphWrite::DeleteBatch { ids: Cow::Borrowed(ids) }, self.inner.delete_batch(ids))
+            .await
+    }
+
+    async fn count(&self) -> anyhow::Result<u64> {
+        self.inner.count().await
+    }
+
+    async fn find_fingerprint_candidates(&self, signature: &[u32]) -> anyhow::Result<Vec<UIElement>> {
+        self.inner.find_fingerprint_candidates(signature).await
+    }
+
+    async fn find_similarity_pending(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        self.inner.find_similarity_pending(limit).await
+    }
+
+    async fn mark_similarity_linked(&self, ids: &[Uuid]) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::MarkSimilarityLinked { ids: Cow::Borrowed(ids) },
+            self.inner.mark_similarity_linked(ids),
+        )
+        .await
+    }
+
+    async fn reset_similarity_linked(&self) -> anyhow::Result<()> {
+        self.journaled(GraphWrite::ResetSimilarityLinked, self.inner.reset_similarity_linked())
+            .await
+    }
+
+    async fn find_counterparts(&self, id: Uuid, design_system: &str, limit: usize) -> anyhow::Result<Vec<Counterpart>> {
+        self.inner.find_counterparts(id, design_system, limit).await
+    }
+
+    async fn save_design_system(&self, ds: &DesignSystem) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::SaveDesignSystem {
+                design_system: Cow::Borrowed(ds),
+            },
+            self.inner.save_design_system(ds),
+        )
+        .await
+    }
+
+    async fn save_design_token(&self, token: &DesignToken) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::SaveDesignToken {
+                token: Cow::Borrowed(token),
+            },
+            self.inner.save_design_token(token),
+        )
+        .await
+    }
+
+    async fn find_design_tokens(&self, theme: Option<&str>) -> anyhow::Result<Vec<DesignToken>> {
+        self.inner.find_design_tokens(theme).await
+    }
+
+    async fn find_design_tokens_by_design_system(&self, design_system: &str) -> anyhow::Result<Vec<DesignToken>> {
+        self.inner.find_design_tokens_by_design_system(design_system).await
+    }
+
+    async fn find_elements_using_token(&self, token_name: &str) -> anyhow::Result<Vec<UIElement>> {
+        self.inner.find_elements_using_token(token_name).await
+    }
+
+    async fn find_tokens_used_by(&self, id: Uuid) -> anyhow::Result<Vec<DesignToken>> {
+        self.inner.find_tokens_used_by(id).await
+    }
+
+    async fn save_snippet(&self, snippet: &Snippet) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::SaveSnippet {
+                snippet: Cow::Borrowed(snippet),
+            },
+            self.inner.save_snippet(snippet),
+        )
+        .await
+    }
+
+    async fn find_snippet_by_id(&self, id: Uuid) -> anyhow::Result<Option<Snippet>> {
+        self.inner.find_snippet_by_id(id).await
+    }
+
+    async fn list_snippets(
+        &self,
+        page: u32,
+        per_page: u32,
+        design_system: Option<&str>,
+        category: Option<&str>,
+    ) -> anyhow::Result<(Vec<SnippetSummary>, u64)> {
+        self.inner.list_snippets(page, per_page, design_system, category).await
+    }
+
+    async fn delete_snippet(&self, id: Uuid, delete_orphans: bool) -> anyhow::Result<bool> {
+        self.journaled(
+            GraphWrite::DeleteSnippet { id, delete_orphans },
+            self.inner.delete_snippet(id, delete_orphans),
+        )
+        .await
+    }
+
+    async fn find_snippets_by_source_root(&self, source_root: &str) -> anyhow::Result<Vec<Snippet>> {
+        self.inner.find_snippets_by_source_root(source_root).await
+    }
+
+    async fn count_snippets(&self) -> anyhow::Result<u64> {
+        self.inner.count_snippets().await
+    }
+
+    async fn link_children(&self, links: &[(Uuid, Uuid)]) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::LinkChildren {
+                links: Cow::Borrowed(links),
+            },
+            self.inner.link_children(links),
+        )
+        .await
+    }
+
+    async fn link_to_token(&self, element_id: Uuid, token_name: &str) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::LinkToToken {
+                element_id,
+                token_name: Cow::Borrowed(token_name),
+            },
+            self.inner.link_to_token(element_id, token_name),
+        )
+        .await
+    }
+
+    async fn link_token_alias(&self, name: &str, alias_of: &str, theme: &str) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::LinkTokenAlias {
+                name: Cow::Borrowed(name),
+                alias_of: Cow::Borrowed(alias_of),
+                theme: Cow::Borrowed(theme),
+            },
+            self.inner.link_token_alias(name, alias_of, theme),
+        )
+        .await
+    }
+
+    async fn create_similarities(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::CreateSimilarities {
+                pairs: Cow::Borrowed(pairs),
+                basis,
+            },
+            self.inner.create_similarities(pairs, basis),
+        )
+        .await
+    }
+
+    async fn propose_replacements(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.journaled(
+            GraphWrite::ProposeReplacements {
+                pairs: Cow::Borrowed(pairs),
+                basis,
+            },
+            self.inner.propose_replacements(pairs, basis),
+        )
+        .await
+    }
+
+    async fn clear_similarity(&self, element_ids: &[Uuid], basis: SimilarityBasis) -> anyhow::Result<u64> {
+        self.journaled(
+            GraphWrite::ClearSimilarity {
+                element_ids: Cow::Borrowed(element_ids),
+                basis,
+            },
+            self.inner.clear_similarity(element_ids, basis),
+        )
+        .await
+    }
+
+    async fn delete_relations(&self, relations: &[GraphRelation]) -> anyhow::Result<u64> {
+        self.journaled(
+            GraphWrite::DeleteRelations {
+                relations: Cow::Borrowed(relations),
+            },
+            self.inner.delete_relations(relations),
+        )
+        .await
+    }
+
+    async fn similarity_edges(&self, min_score: f32, basis: SimilarityBasis) -> anyhow::Result<Vec<(Uuid, Uuid, f32)>> {
+        self.inner.similarity_edges(min_score, basis).await
+    }
+
+    async fn get_degree(&self, element_id: Uuid) -> anyhow::Result<u32> {
+        self.inner.get_degree(element_id).await
+    }
+
+    async fn neighborhood(&self, query: &NeighborhoodQuery) -> anyhow::Result<Option<Neighborhood>> {
+        self.inner.neighborhood(query).await
+    }
+
+    async fn count_by_category(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        self.inner.count_by_category().await
+    }
+
+    async fn count_by_design_system(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        self.inner.count_by_design_system().await
+    }
+
+    async fn list_css_classes(&self, design_system: &str) -> anyhow::Result<Vec<String>> {
+        self.inner.list_css_classes(design_system).await
+    }
+
+    async fn count_relationships(&self) -> anyhow::Result<u64> {
+        self.inner.count_relationships().await
+    }
+
+    async fn count_relationships_by_type(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        self.inner.count_relationships_by_type().await
+    }
+
+    async fn count_by_label(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        self.inner.count_by_label().await
+    }
+
+    async fn export_graph(&self) -> anyhow::Result<GraphDump> {
+        self.inner.export_graph().await
+    }
+
+    async fn import_graph(&self, dump: &GraphDump) -> anyhow::Result<u64> {
+        self.journaled(
+            GraphWrite::ImportGraph {
+                dump: Cow::Borrowed(dump),
+            },
+            self.inner.import_graph(dump),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Fresh directory under the system temp dir
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codegraph-{}-{}", name, Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_graph_survives_reopen() {
+        let dir = temp_dir("graph");
+        let button = UIElement::new("Primary Button", "button").with_design_system("custom");
+        let card = UIElement::new("Card", "card").with_design_system("custom");
+
+        {
+            let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+            store.save_batch(&[button.clone(), card.clone()]).await.unwrap();
+            store
+                .create_similarities(&[(button.id, card.id, 0.8)], SimilarityBasis::Structure)
+                .await
+                .unwrap();
+            store.delete(card.id).await.unwrap();
+            store.save(&card).await.unwrap();
+        }
+
+        let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 2);
+        // Deleting the card dropped its relation before it was re-saved
+        assert_eq!(store.count_relationships().await.unwrap(), 0);
+        let hits = store.fulltext_search("primary", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].element.id, button.id);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_journal_compacts_and_survives_torn_write() {
+        let dir = temp_dir("graph-journal");
+        let elements: Vec<UIElement> = (0..200)
+            .map(|i| UIElement::new(format!("Button {}", i), "button").with_embedding(vec![0.123_456_7; 1536]))
+            .collect();
+
+        {
+            let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+            for element in &elements {
+                store.save(element).await.unwrap();
+            }
+        }
+        // The journal outgrew the snapshot, which took over its writes
+        assert!(!dir.join("graph.0.log").exists());
+        let generation = serde_json::from_slice::<SnapshotFile>(&fs::read(dir.join(GRAPH_FILE)).unwrap())
+            .unwrap()
+            .generation;
+        assert!(generation > 0);
+        let fulltext = serde_json::from_slice::<FulltextFile>(&fs::read(dir.join(FULLTEXT_FILE)).unwrap()).unwrap();
+        assert_eq!(fulltext.generation, generation);
+
+        // A crash mid-append leaves an unfinished record behind
+        let journal = journal_path(&dir.join(GRAPH_FILE), generation);
+        let mut bytes = fs::read(&journal).unwrap_or_default();
+        bytes.extend_from_slice(b"{\"op\":\"save_batch\",\"elem");
+        fs::write(&journal, bytes).unwrap();
+
+        {
+            let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+            assert_eq!(store.count().await.unwrap(), 200);
+            store.delete(elements[0].id).await.unwrap();
+        }
+        let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 199);
+        let hits = store.fulltext_search("199", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].element.id, elements[199].id);
+        assert!(store.fulltext_search("0", 10).await.unwrap().is_empty());
+
+        // An index of another generation is rebuilt rather than trusted
+        fs::write(dir.join(FULLTEXT_FILE), br#"{"generation":0,"documents":[],"postings":{}}"#).unwrap();
+        let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+        assert_eq!(store.fulltext_search("199", 10).await.unwrap().len(), 1);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_unjournaled_write_is_not_applied() {
+        let dir = temp_dir("graph-readonly");
+        let store = EmbeddedGraphStore::open(&dir).await.unwrap();
+        // A directory in place of the journal makes every append fail
+        fs::create_dir(dir.join("graph.0.log")).unwrap();
+
+        let button = UIElement::new("Button", "button");
+        assert!(store.save(&button).await.is_err());
+        assert!(store.find_by_id(button.id).await.unwrap().is_none());
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,164 @@
+//! Embedded storage backend
+//!
+//! Runs the [`memory`](crate::memory) stores on top of a local data directory,
+//! so a single binary serves a persistent knowledge graph without Neo4j,
+//! Qdrant or PostgreSQL. Every write is flushed before it returns:
+//!
+//! ```text
+//! <data-dir>/
+//!   graph.json                   snapshot of elements, snippets, tokens and relations
+//!   graph.<gen>.log              writes made since that snapshot
+//!   fulltext.json                inverted index of the elements in graph.json
+//!   vectors/<collection>.bin     snapshot of a collection's embedding points
+//!   vectors/<collection>.hnsw    HNSW graph over the points in that snapshot
+//!   vectors/<collection>.<gen>.log
+//!   feedback.jsonl               feedback records, appended in order
+//! ```
+//!
+//! A write appends one record to the journal (`.log`) before it is applied in
+//! memory, so a write that cannot be persisted changes nothing. Once a journal
+//! outgrows its snapshot, the snapshot is rewritten and a new journal started;
+//! each write therefore costs its own size plus an amortized constant, not a
+//! rewrite of the whole store. Snapshots are replaced atomically (write to a
+//! temporary file, then rename) and journals are numbered by the snapshot
+//! generation they extend, so a crash at any point recovers the last
+//! acknowledged write. File I/O runs on the blocking thread pool.
+//!
+//! Indexes are written alongside each snapshot and loaded with it, so opening
+//! a store only indexes the writes of its journal. Everything is held in
+//! memory once open; the backend targets small teams indexing up to a few
+//! thousand components.
+
+mod feedback;
+mod graph;
+mod vector;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+pub use feedback::EmbeddedFeedbackStore;
+pub use graph::EmbeddedGraphStore;
+pub use vector::EmbeddedVectorStore;
+
+/// Journals smaller than this are never compacted
+const MIN_COMPACTION_BYTES: u64 = 1 << 20;
+
+/// Replace `path` with `bytes` via a temporary sibling file
+fn write_atomic(path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Contents of `path`, or `None` when it does not exist yet
+fn read_optional(path: &Path) -> anyhow::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write `bytes` at `offset` and cut the file there, restoring its length on failure
+///
+/// Anything past `offset` is the tail of an append that failed or was torn by
+/// a crash, and is overwritten.
+fn write_at(path: &Path, offset: u64, bytes: &[u8]) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new().create(true).write(true).truncate(false).open(path)?;
+    let result = (|| {
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(bytes)?;
+        file.set_len(offset + bytes.len() as u64)?;
+        file.sync_data()
+    })();
+    if result.is_err() {
+        let _ = file.set_len(offset);
+    }
+    Ok(result?)
+}
+
+/// Run file I/O on the blocking thread pool
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> anyhow::Result<T> + Send + 'static) -> anyhow::Result<T> {
+    tokio::task::spawn_blocking(f).await?
+}
+
+/// Journal extending the snapshot at `snapshot` with generation `generation`
+fn journal_path(snapshot: &Path, generation: u64) -> PathBuf {
+    snapshot.with_extension(format!("{}.log", generation))
+}
+
+/// Append-only log of the writes made since a snapshot
+struct Journal {
+    snapshot: PathBuf,
+    generation: u64,
+    /// Bytes of acknowledged records
+    len: u64,
+    snapshot_len: u64,
+}
+
+impl Journal {
+    /// Journal of `generation`, keeping its first `len` bytes
+    ///
+    /// The previous generation's journal is only left behind by a crash
+    /// between writing a snapshot and deleting it; the snapshot holds its
+    /// writes, so it is removed.
+    fn open(snapshot: &Path, generation: u64, len: u64) -> anyhow::Result<Self> {
+        if let Some(previous) = generation.checked_sub(1) {
+            match fs::remove_file(journal_path(snapshot, previous)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        let snapshot_len = fs::metadata(snapshot).map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            snapshot: snapshot.to_path_buf(),
+            generation,
+            len,
+            snapshot_len,
+        })
+    }
+
+    fn path(&self) -> PathBuf {
+        journal_path(&self.snapshot, self.generation)
+    }
+
+    /// Durably append `record`; on failure the journal is left as it was
+    async fn append(&mut self, record: Vec<u8>) -> anyhow::Result<()> {
+        let path = self.path();
+        let (offset, len) = (self.len, record.len() as u64);
+        blocking(move || write_at(&path, offset, &record).with_context(|| format!("Failed to write {}", path.display())))
+            .await?;
+        self.len += len;
+        Ok(())
+    }
+
+    /// Whether rewriting the snapshot would shrink the files on disk
+    fn needs_compaction(&self) -> bool {
+        self.len > self.snapshot_len.max(MIN_COMPACTION_BYTES)
+    }
+
+    /// Replace the snapshot with `encode(next generation)` and start an empty journal
+    ///
+    /// `encode` must capture everything journaled so far.
+    async fn compact(&mut self, encode: impl FnOnce(u64) -> anyhow::Result<Vec<u8>> + Send + 'static) -> anyhow::Result<()> {
+        let generation = self.generation + 1;
+        let snapshot = self.snapshot.clone();
+        let previous = self.path();
+        self.snapshot_len = blocking(move || {
+            let bytes = encode(generation)?;
+            write_atomic(&snapshot, &bytes).with_context(|| format!("Failed to write {}", snapshot.display()))?;
+            fs::remove_file(&previous).ok();
+            Ok(bytes.len() as u64)
+        })
+        .await?;
+        self.generation = generation;
+        self.len = 0;
+        Ok(())
+    }
+}
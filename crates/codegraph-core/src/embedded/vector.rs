@@ -0,0 +1,531 @@
+//! Vector store kept as one binary snapshot and journal per collection
+//!
+//! Each snapshot starts with [`MAGIC`] and the `u64` journal generation,
+//! followed by one record per point: 16-byte ID, `u32` dimension, the `f32`
+//! components, a `u32`-length JSON payload, then the sparse vector as a `u32`
+//! term count, the `u32` indices and the `f32` weights. Journal records are a
+//! tag byte and a `u32` count, followed by that many points ([`UPSERT`]) or
+//! 16-byte IDs ([`DELETE`]). Integers and floats are little-endian.
+//!
+//! Snapshots written before journals ([`MAGIC_V2`]) or sparse vectors
+//! ([`MAGIC_V1`]) existed are still read; the latter derive their sparse
+//! vectors from the payload.
+//!
+//! Next to each snapshot, `<collection>.hnsw` holds the HNSW graph searched by
+//! the collection: [`INDEX_MAGIC`], the generation of the snapshot it was
+//! built over, then the encoded graph. It is written on compaction, so opening
+//! a collection only links in the points of its journal; a graph that is
+//! missing or from another generation is rebuilt from the points instead.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::entities::{CollectionInfo, EmbeddingPoint, SearchFilter, SearchResult, SparseVector};
+use crate::memory::MemoryVectorStore;
+use crate::traits::VectorStore;
+
+use super::{blocking, journal_path, read_optional, write_atomic, Journal};
+
+const VECTORS_DIR: &str = "vectors";
+const EXTENSION: &str = "bin";
+const JOURNAL_EXTENSION: &str = "log";
+const INDEX_EXTENSION: &str = "hnsw";
+const INDEX_MAGIC: &[u8; 4] = b"CGH1";
+const MAGIC: &[u8; 4] = b"CGV3";
+const MAGIC_V2: &[u8; 4] = b"CGV2";
+const MAGIC_V1: &[u8; 4] = b"CGV1";
+const UPSERT: u8 = b'U';
+const DELETE: u8 = b'D';
+
+/// One journaled write of a collection
+enum VectorWrite {
+    Upsert(Vec<EmbeddingPoint>),
+    Delete(Vec<Uuid>),
+}
+
+/// A collection as found on disk
+struct StoredCollection {
+    points: Vec<EmbeddingPoint>,
+    /// Encoded HNSW graph over `points`, if one was written for this snapshot
+    graph: Option<Vec<u8>>,
+    /// Writes made since `points` were snapshotted
+    writes: Vec<VectorWrite>,
+}
+
+/// [`MemoryVectorStore`] that journals every write to the data directory
+pub struct EmbeddedVectorStore {
+    inner: Arc<MemoryVectorStore>,
+    dir: PathBuf,
+    /// Journal of each collection written to; held across a write so the
+    /// journals record writes in the order they are applied
+    journals: Mutex<HashMap<String, Journal>>,
+}
+
+impl EmbeddedVectorStore {
+    /// Open (or create) the collections stored under `data_dir`
+    pub async fn open(data_dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = data_dir.as_ref().join(VECTORS_DIR);
+        let (collections, journals) = blocking({
+            let dir = dir.clone();
+            move || load(&dir)
+        })
+        .await?;
+
+        let inner = MemoryVectorStore::new();
+        for (collection, stored) in collections {
+            inner.restore(&collection, stored.points, stored.graph.as_deref())?;
+            for write in stored.writes {
+                // A write that failed when it was made fails again without effect
+                if let Err(e) = apply(&inner, &collection, write).await {
+                    debug!("Skipped journaled write to {} that failed: {:#}", collection, e);
+                }
+            }
+        }
+
+        Ok(Self {
+            inner: Arc::new(inner),
+            dir,
+            journals: Mutex::new(journals),
+        })
+    }
+
+    /// Journal `write` to `collection`, then apply it in memory
+    async fn journaled(&self, collection: &str, write: VectorWrite) -> anyhow::Result<usize> {
+        let record = encode_write(&write);
+        let mut journals = self.journals.lock().await;
+        let journal = match journals.entry(collection.to_string()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let snapshot = self.dir.join(collection).with_extension(EXTENSION);
+                entry.insert(Journal::open(&snapshot, 0, 0)?)
+            }
+        };
+        journal.append(record).await?;
+        let result = apply(&self.inner, collection, write).await;
+        if journal.needs_compaction() {
+            // Every write is still journaled, so compaction is retried on the next one
+            if let Err(e) = self.compact(collection, journal).await {
+                warn!("Failed to compact the journal of {}: {:#}", collection, e);
+            }
+        }
+        result
+    }
+
+    /// Snapshot `collection` and the graph over it, starting a new journal
+    async fn compact(&self, collection: &str, journal: &mut Journal) -> anyhow::Result<()> {
+        let inner = self.inner.clone();
+        let name = collection.to_string();
+        let index = self.dir.join(collection).with_extension(INDEX_EXTENSION);
+        journal
+            .compact(move |generation| {
+                // Written first: a crash before the snapshot leaves a graph of a newer generation, which is ignored
+                let graph = encode_index(generation, &inner.index_graph(&name));
+                write_atomic(&index, &graph).with_context(|| format!("Failed to write {}", index.display()))?;
+                Ok(encode(generation, &inner.points(&name)))
+            })
+            .await
+    }
+}
+
+async fn apply(store: &MemoryVectorStore, collection: &str, write: VectorWrite) -> anyhow::Result<usize> {
+    match write {
+        VectorWrite::Upsert(points) => store.upsert_batch(collection, points).await,
+        VectorWrite::Delete(ids) => store.delete_batch(collection, ids).await,
+    }
+}
+
+/// Every collection stored under `dir`, with its journal
+fn load(dir: &Path) -> anyhow::Result<(BTreeMap<String, StoredCollection>, HashMap<String, Journal>)> {
+    fs::create_dir_all(dir)?;
+
+    // A collection never compacted has a journal but no snapshot yet
+    let mut names = BTreeSet::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else { continue };
+        let name = match path.extension().and_then(|e| e.to_str()) {
+            Some(EXTENSION) => file_name.strip_suffix(".bin"),
+            Some(JOURNAL_EXTENSION) => file_name
+                .strip_suffix(".log")
+                .and_then(|stem| stem.rsplit_once('.'))
+                .map(|(name, _generation)| name),
+            _ => None,
+        };
+        names.extend(name.map(String::from));
+    }
+
+    let mut collections = BTreeMap::new();
+    let mut journals = HashMap::new();
+    for name in names {
+        let snapshot = dir.join(&name).with_extension(EXTENSION);
+        let (generation, points) = match read_optional(&snapshot)? {
+            Some(bytes) => decode(&bytes).with_context(|| format!("Corrupt vector file {}", snapshot.display()))?,
+            None => (0, Vec::new()),
+        };
+        let journal_file = journal_path(&snapshot, generation);
+        let bytes = read_optional(&journal_file)?.unwrap_or_default();
+        let (writes, len) = decode_journal(&bytes);
+        if len < bytes.len() {
+            warn!(
+                "Dropping {} bytes of an unfinished write from {}",
+                bytes.len() - len,
+                journal_file.display()
+            );
+        }
+        let index = snapshot.with_extension(INDEX_EXTENSION);
+        let graph = match read_optional(&index)?.map(|bytes| decode_index(&bytes, generation).map(<[u8]>::to_vec)) {
+            Some(Ok(graph)) => Some(graph),
+            Some(Err(e)) => {
+                debug!("Ignoring vector index {}: {:#}", index.display(), e);
+                None
+            }
+            None => None,
+        };
+
+        journals.insert(name.clone(), Journal::open(&snapshot, generation, len as u64)?);
+        collections.insert(name, StoredCollection { points, graph, writes });
+    }
+    Ok((collections, journals))
+}
+
+#[async_trait]
+impl VectorStore for EmbeddedVectorStore {
+    async fn init_collections(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn upsert_point(&self, collection: &str, point: EmbeddingPoint) -> anyhow::Result<()> {
+        self.journaled(collection, VectorWrite::Upsert(vec![point])).await.map(drop)
+    }
+
+    async fn upsert_batch(&self, collection: &str, points: Vec<EmbeddingPoint>) -> anyhow::Result<usize> {
+        self.journaled(collection, VectorWrite::Upsert(points)).await
+    }
+
+    async fn search(
+        &self,
+        collection: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        self.inner.search(collection, vector, limit, filter).await
+    }
+
+    async fn search_all(&self, vector: Vec<f32>, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>> {
+        self.inner.search_all(vector, limit, filter).await
+    }
+
+    async fn search_sparse(&self, query: &str, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>> {
+        self.inner.search_sparse(query, limit, filter).await
+    }
+
+    async fn search_hybrid(
+        &self,
+        vector: Vec<f32>,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        self.inner.search_hybrid(vector, query, limit, filter).await
+    }
+
+    async fn delete_point(&self, collection: &str, id: Uuid) -> anyhow::Result<()> {
+        self.journaled(collection, VectorWrite::Delete(vec![id])).await.map(drop)
+    }
+
+    async fn delete_batch(&self, collection: &str, ids: Vec<Uuid>) -> anyhow::Result<usize> {
+        self.journaled(collection, VectorWrite::Delete(ids)).await
+    }
+
+    async fn all_collections_info(&self) -> anyhow::Result<Vec<CollectionInfo>> {
+        self.inner.all_collections_info().await
+    }
+
+    async fn scroll_points(
+        &self,
+        collection: &str,
+        offset: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<EmbeddingPoint>, Option<Uuid>)> {
+        self.inner.scroll_points(collection, offset, limit).await
+    }
+}
+
+fn encode(generation: u64, points: &[EmbeddingPoint]) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&generation.to_le_bytes());
+    for point in points {
+        encode_point(&mut bytes, point);
+    }
+    bytes
+}
+
+fn encode_point(bytes: &mut Vec<u8>, point: &EmbeddingPoint) {
+    let payload = serde_json::to_vec(&point.payload).expect("payload serializes to JSON");
+    bytes.extend_from_slice(point.id.as_bytes());
+    bytes.extend_from_slice(&(point.vector.len() as u32).to_le_bytes());
+    for component in &point.vector {
+        bytes.extend_from_slice(&component.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+
+    let sparse = point.sparse.clone().unwrap_or_default();
+    bytes.extend_from_slice(&(sparse.indices.len() as u32).to_le_bytes());
+    for index in &sparse.indices {
+        bytes.extend_from_slice(&index.to_le_bytes());
+    }
+    for value in &sparse.values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// Journal generation and points of a snapshot
+fn decode(bytes: &[u8]) -> anyhow::Result<(u64, Vec<EmbeddingPoint>)> {
+    let mut reader = Reader { bytes };
+    let magic = reader.take(MAGIC.len())?;
+    anyhow::ensure!(magic == MAGIC || magic == MAGIC_V2 || magic == MAGIC_V1, "not a vector file");
+    let generation = if magic == MAGIC { reader.u64()? } else { 0 };
+    let has_sparse = magic != MAGIC_V1;
+
+    let mut points = Vec::new();
+    while !reader.bytes.is_empty() {
+        points.push(decode_point(&mut reader, has_sparse)?);
+    }
+    Ok((generation, points))
+}
+
+fn decode_point(reader: &mut Reader<'_>, has_sparse: bool) -> anyhow::Result<EmbeddingPoint> {
+    let id = Uuid::from_slice(reader.take(16)?)?;
+    let dimensions = reader.u32()? as usize;
+    let vector = reader.f32s(dimensions)?;
+    let payload_len = reader.u32()? as usize;
+    let payload = serde_json::from_slice(reader.take(payload_len)?)?;
+    let mut point = EmbeddingPoint::new(id, vector, payload);
+    if has_sparse {
+        let terms = reader.u32()? as usize;
+        let indices = reader
+            .take(terms * 4)?
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        let values = reader.f32s(terms)?;
+        // Journaled points may not have had theirs derived yet; an empty one is derived again
+        if terms > 0 {
+            point = point.with_sparse(SparseVector { indices, values });
+        }
+    }
+    Ok(point)
+}
+
+fn encode_index(generation: u64, graph: &[u8]) -> Vec<u8> {
+    let mut bytes = INDEX_MAGIC.to_vec();
+    bytes.extend_from_slice(&generation.to_le_bytes());
+    bytes.extend_from_slice(graph);
+    bytes
+}
+
+/// Encoded graph of an index file, if it was built over the snapshot of `generation`
+fn decode_index(bytes: &[u8], generation: u64) -> anyhow::Result<&[u8]> {
+    let mut reader = Reader { bytes };
+    anyhow::ensure!(reader.take(INDEX_MAGIC.len())? == INDEX_MAGIC, "not a vector index file");
+    let built = reader.u64()?;
+    anyhow::ensure!(built == generation, "built over generation {}, not {}", built, generation);
+    Ok(reader.bytes)
+}
+
+fn encode_write(write: &VectorWrite) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    match write {
+        VectorWrite::Upsert(points) => {
+            bytes.push(UPSERT);
+            bytes.extend_from_slice(&(points.len() as u32).to_le_bytes());
+            for point in points {
+                encode_point(&mut bytes, point);
+            }
+        }
+        VectorWrite::Delete(ids) => {
+            bytes.push(DELETE);
+            bytes.extend_from_slice(&(ids.len() as u32).to_le_bytes());
+            for id in ids {
+                bytes.extend_from_slice(id.as_bytes());
+            }
+        }
+    }
+    bytes
+}
+
+/// Writes of a journal and the length they span
+///
+/// Reading stops at the first record that does not decode: appends that fail
+/// are cut off, so only a crash mid-append leaves one, at the end.
+fn decode_journal(bytes: &[u8]) -> (Vec<VectorWrite>, usize) {
+    let mut reader = Reader { bytes };
+    let mut writes = Vec::new();
+    let mut len = 0;
+    while !reader.bytes.is_empty() {
+        match decode_write(&mut reader) {
+            Ok(write) => {
+                writes.push(write);
+                len = bytes.len() - reader.bytes.len();
+            }
+            Err(_) => break,
+        }
+    }
+    (writes, len)
+}
+
+fn decode_write(reader: &mut Reader<'_>) -> anyhow::Result<VectorWrite> {
+    let tag = reader.take(1)?[0];
+    let count = reader.u32()? as usize;
+    match tag {
+        UPSERT => Ok(VectorWrite::Upsert(
+            (0..count).map(|_| decode_point(reader, true)).collect::<anyhow::Result<_>>()?,
+        )),
+        DELETE => Ok(VectorWrite::Delete(
+            (0..count).map(|_| Ok(Uuid::from_slice(reader.take(16)?)?)).collect::<anyhow::Result<_>>()?,
+        )),
+        _ => anyhow::bail!("unknown journal record {}", tag),
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        anyhow::ensure!(self.bytes.len() >= len, "unexpected end of file");
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> anyhow::Result<u64> {
+        let mut b = [0; 8];
+        b.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(b))
+    }
+
+    fn f32s(&mut self, count: usize) -> anyhow::Result<Vec<f32>> {
+        Ok(self
+            .take(count * 4)?
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PointPayload;
+
+    #[tokio::test]
+    async fn test_collections_survive_reopen() {
+        let dir = std::env::temp_dir().join(format!("codegraph-vectors-{}", Uuid::new_v4()));
+        let kept = EmbeddingPoint::new(
+            Uuid::new_v4(),
+            vec![0.5, -1.25, 3.0],
+            PointPayload::new("Button", "button", "atomic", "custom").with_tags(vec!["primary".to_string()]),
+        );
+        let dropped = EmbeddingPoint::new(
+            Uuid::new_v4(),
+            vec![1.0, 0.0, 0.0],
+            PointPayload::new("Card", "card", "molecule", "custom"),
+        );
+
+        {
+            let store = EmbeddedVectorStore::open(&dir).await.unwrap();
+            store.upsert_batch("ui_custom", vec![kept.clone(), dropped.clone()]).await.unwrap();
+            store.delete_point("ui_custom", dropped.id).await.unwrap();
+        }
+
+        let store = EmbeddedVectorStore::open(&dir).await.unwrap();
+        assert_eq!(store.search_sparse("primary", 10, None).await.unwrap().len(), 1);
+        let results = store.search("ui_custom", vec![0.5, -1.25, 3.0], 10, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, kept.id);
+        assert_eq!(results[0].payload.tags, vec!["primary".to_string()]);
+        assert!((results[0].score - 1.0).abs() < 1e-6);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_index_is_persisted_on_compaction() {
+        let dir = std::env::temp_dir().join(format!("codegraph-vectors-{}", Uuid::new_v4()));
+        let points: Vec<EmbeddingPoint> = (0..50)
+            .map(|i| {
+                let angle = i as f32 / 10.0;
+                EmbeddingPoint::new(Uuid::new_v4(), vec![angle.cos(), angle.sin()], PointPayload::new("a", "b", "c", "d"))
+            })
+            .collect();
+
+        {
+            let store = EmbeddedVectorStore::open(&dir).await.unwrap();
+            store.upsert_batch("ui_custom", points.clone()).await.unwrap();
+            store.delete_point("ui_custom", points[0].id).await.unwrap();
+            let mut journals = store.journals.lock().await;
+            store.compact("ui_custom", journals.get_mut("ui_custom").unwrap()).await.unwrap();
+        }
+
+        let index = dir.join(VECTORS_DIR).join("ui_custom.hnsw");
+        let bytes = fs::read(&index).unwrap();
+        let graph = decode_index(&bytes, 1).unwrap();
+        assert!(decode_index(&bytes, 0).is_err());
+
+        let store = EmbeddedVectorStore::open(&dir).await.unwrap();
+        let info = store.all_collections_info().await.unwrap();
+        assert_eq!((info[0].points_count, info[0].indexed_vectors_count), (49, 49));
+        assert_eq!(store.inner.index_graph("ui_custom"), graph);
+        let results = store.search("ui_custom", points[1].vector.clone(), 3, None).await.unwrap();
+        assert_eq!(results[0].id, points[1].id);
+
+        // A graph from another snapshot is rebuilt rather than trusted
+        fs::write(&index, encode_index(7, graph)).unwrap();
+        let store = EmbeddedVectorStore::open(&dir).await.unwrap();
+        let results = store.search("ui_custom", points[1].vector.clone(), 3, None).await.unwrap();
+        assert_eq!(results[0].id, points[1].id);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_truncated_file_is_rejected() {
+        let point = EmbeddingPoint::new(Uuid::new_v4(), vec![1.0; 4], PointPayload::new("a", "b", "c", "d"));
+        let bytes = encode(7, &[point]);
+        let (generation, points) = decode(&bytes).unwrap();
+        assert_eq!((generation, points.len()), (7, 1));
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_journal_stops_at_torn_record() {
+        let point = EmbeddingPoint::new(Uuid::new_v4(), vec![1.0; 4], PointPayload::new("a", "b", "c", "d"));
+        let mut bytes = encode_write(&VectorWrite::Upsert(vec![point.clone()]));
+        bytes.extend(encode_write(&VectorWrite::Delete(vec![point.id])));
+        let complete = bytes.len();
+        bytes.extend(&encode_write(&VectorWrite::Upsert(vec![point]))[..10]);
+
+        let (writes, len) = decode_journal(&bytes);
+        assert_eq!(writes.len(), 2);
+        assert_eq!(len, complete);
+        assert!(matches!(&writes[1], VectorWrite::Delete(ids) if ids.len() == 1));
+    }
+}
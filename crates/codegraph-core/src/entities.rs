@@ -1 +1,1086 @@
 //! Domain entities for CodeGraph
+//!
+//! Shared by every storage backend: graph nodes (elements, snippets, design
+//! systems and tokens), vector points and feedback records.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ============================================================================
+// Graph Entities
+// ============================================================================
+
+/// A UI element node in the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UIElement {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Element name (e.g., "PrimaryButton", "LoginForm")
+    pub name: String,
+    /// Category (e.g., "button", "form", "card")
+    pub category: String,
+    /// Element type (e.g., "atomic", "composite")
+    pub element_type: String,
+    /// Design system this belongs to
+    pub design_system: Option<String>,
+    /// HTML template
+    pub html_template: Option<String>,
+    /// CSS classes used
+    pub css_classes: Vec<String>,
+    /// Tags/attributes
+    pub tags: Vec<String>,
+    /// Embedding vector (1536 dims for OpenAI)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+    /// Accessibility audit score (0.0-1.0, `None` if not audited)
+    #[serde(default)]
+    pub a11y_score: Option<f32>,
+    /// Confidence (0.0-1.0) mirrored in the element's vector payload, `None` if not recorded
+    #[serde(default)]
+    pub confidence: Option<f32>,
+    /// Path of the source file the element was ingested from
+    #[serde(default)]
+    pub source_path: Option<String>,
+    /// SHA-256 of the source content the element was extracted from
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Normalized DOM shape of the element subtree (e.g. `button(svg,span)`)
+    #[serde(default)]
+    pub shape: Option<String>,
+    /// MinHash signature of the structural fingerprint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<Vec<u32>>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UIElement {
+    pub fn new(name: impl Into<String>, category: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            category: category.into(),
+            element_type: "atomic".to_string(),
+            design_system: None,
+            html_template: None,
+            css_classes: vec![],
+            tags: vec![],
+            embedding: None,
+            a11y_score: None,
+            confidence: None,
+            source_path: None,
+            content_hash: None,
+            shape: None,
+            fingerprint: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_element_type(mut self, element_type: impl Into<String>) -> Self {
+        self.element_type = element_type.into();
+        self
+    }
+
+    pub fn with_design_system(mut self, design_system: impl Into<String>) -> Self {
+        self.design_system = Some(design_system.into());
+        self
+    }
+
+    pub fn with_html_template(mut self, template: impl Into<String>) -> Self {
+        self.html_template = Some(template.into());
+        self
+    }
+
+    pub fn with_css_classes(mut self, classes: Vec<String>) -> Self {
+        self.css_classes = classes;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_embedding(mut self, embedding: Vec<f32>) -> Self {
+        self.embedding = Some(embedding);
+        self
+    }
+
+    pub fn with_source_path(mut self, path: impl Into<String>) -> Self {
+        self.source_path = Some(path.into());
+        self
+    }
+
+    pub fn with_content_hash(mut self, hash: impl Into<String>) -> Self {
+        self.content_hash = Some(hash.into());
+        self
+    }
+
+    pub fn with_a11y_score(mut self, score: f32) -> Self {
+        self.a11y_score = Some(score);
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = Some(confidence.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn with_fingerprint(mut self, shape: impl Into<String>, signature: Vec<u32>) -> Self {
+        self.shape = Some(shape.into());
+        self.fingerprint = Some(signature);
+        self
+    }
+}
+
+/// A design system node in the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesignSystem {
+    /// Unique name (e.g., "tailwind", "bootstrap", "material")
+    pub name: String,
+    /// Display name
+    pub display_name: String,
+    /// Version
+    pub version: Option<String>,
+    /// Description
+    pub description: Option<String>,
+    /// Base URL for documentation
+    pub docs_url: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl DesignSystem {
+    pub fn new(name: impl Into<String>, display_name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            display_name: display_name.into(),
+            version: None,
+            description: None,
+            docs_url: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// A design token node in the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesignToken {
+    /// Custom property name (e.g., "--color-primary")
+    pub name: String,
+    /// Declared value (may contain `var()` references)
+    pub value: String,
+    /// Value after resolving `var()` chains
+    pub resolved_value: Option<String>,
+    /// Token category (e.g., "color", "spacing")
+    pub category: String,
+    /// Theme the value belongs to ("default", "dark", ...)
+    pub theme: String,
+    /// Token this one aliases, if its value is a single `var()` reference
+    pub alias_of: Option<String>,
+    /// Design system that defines the token
+    pub design_system: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl DesignToken {
+    pub fn new(name: impl Into<String>, value: impl Into<String>, category: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            resolved_value: None,
+            category: category.into(),
+            theme: "default".to_string(),
+            alias_of: None,
+            design_system: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: impl Into<String>) -> Self {
+        self.theme = theme.into();
+        self
+    }
+
+    pub fn with_resolved_value(mut self, resolved_value: impl Into<String>) -> Self {
+        self.resolved_value = Some(resolved_value.into());
+        self
+    }
+
+    pub fn with_alias_of(mut self, alias_of: impl Into<String>) -> Self {
+        self.alias_of = Some(alias_of.into());
+        self
+    }
+
+    pub fn with_design_system(mut self, design_system: impl Into<String>) -> Self {
+        self.design_system = Some(design_system.into());
+        self
+    }
+}
+
+/// Search result with similarity score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarElement {
+    pub element: UIElement,
+    pub similarity: f32,
+}
+
+/// Element linked to another through `CAN_REPLACE` or `SIMILAR_TO`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Counterpart {
+    pub element: UIElement,
+    /// Relationship type (`CAN_REPLACE` or `SIMILAR_TO`)
+    pub relation: String,
+    pub score: f32,
+}
+
+/// A code snippet containing one or more UI elements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Optional name for the snippet
+    pub name: Option<String>,
+    /// Original HTML content
+    pub html: String,
+    /// Original CSS content (if provided)
+    pub css: Option<String>,
+    /// Original JavaScript content (if provided)
+    pub js: Option<String>,
+    /// Detected design system
+    pub design_system: Option<String>,
+    /// User-provided tags
+    pub tags: Vec<String>,
+    /// IDs of UI elements extracted from this snippet
+    pub element_ids: Vec<Uuid>,
+    /// Number of elements in this snippet
+    pub element_count: u32,
+    /// Repository-relative path of the file the snippet was ingested from
+    #[serde(default)]
+    pub source_path: Option<String>,
+    /// Git commit the source was read at
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    /// Name of the repository or directory the snippet was ingested from
+    #[serde(default)]
+    pub source_root: Option<String>,
+    /// SHA-256 of the source files, used to skip unchanged files on re-ingestion
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Snippet {
+    pub fn new(html: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name: None,
+            html: html.into(),
+            css: None,
+            js: None,
+            design_system: None,
+            tags: vec![],
+            element_ids: vec![],
+            element_count: 0,
+            source_path: None,
+            git_commit: None,
+            source_root: None,
+            content_hash: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_css(mut self, css: impl Into<String>) -> Self {
+        self.css = Some(css.into());
+        self
+    }
+
+    pub fn with_js(mut self, js: impl Into<String>) -> Self {
+        self.js = Some(js.into());
+        self
+    }
+
+    pub fn with_design_system(mut self, design_system: impl Into<String>) -> Self {
+        self.design_system = Some(design_system.into());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_element_ids(mut self, element_ids: Vec<Uuid>) -> Self {
+        self.element_count = element_ids.len() as u32;
+        self.element_ids = element_ids;
+        self
+    }
+
+    pub fn with_source_path(mut self, path: impl Into<String>) -> Self {
+        self.source_path = Some(path.into());
+        self
+    }
+
+    pub fn with_git_commit(mut self, commit: impl Into<String>) -> Self {
+        self.git_commit = Some(commit.into());
+        self
+    }
+
+    pub fn with_source_root(mut self, root: impl Into<String>) -> Self {
+        self.source_root = Some(root.into());
+        self
+    }
+
+    pub fn with_content_hash(mut self, hash: impl Into<String>) -> Self {
+        self.content_hash = Some(hash.into());
+        self
+    }
+}
+
+/// Snippet summary for list views
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetSummary {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub design_system: Option<String>,
+    pub element_count: u32,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Sort key of element listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElementSort {
+    #[default]
+    Name,
+    /// Creation time
+    Created,
+    /// NARS confidence recorded on the element (0.5 when none is recorded)
+    Confidence,
+    /// Number of relationships
+    Degree,
+}
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Elements an element listing returns per page at most
+pub const MAX_ELEMENTS_PER_PAGE: u32 = 200;
+
+/// Filters, order and page of an element listing
+///
+/// Filters combine with AND; every listed tag must be present. Pages are
+/// 1-based, and ties in the sort key are broken by name, then ID, so pages
+/// are stable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementQuery {
+    pub category: Option<String>,
+    pub design_system: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Case-insensitive prefix of the element name
+    pub name_prefix: Option<String>,
+    #[serde(default)]
+    pub sort: ElementSort,
+    #[serde(default)]
+    pub direction: SortDirection,
+    pub page: u32,
+    /// Page size, capped at [`MAX_ELEMENTS_PER_PAGE`]
+    pub per_page: u32,
+}
+
+impl Default for ElementQuery {
+    fn default() -> Self {
+        Self {
+            category: None,
+            design_system: None,
+            tags: Vec::new(),
+            name_prefix: None,
+            sort: ElementSort::default(),
+            direction: SortDirection::default(),
+            page: 1,
+            per_page: 50,
+        }
+    }
+}
+
+impl ElementQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only elements of a category
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Only elements of a design system
+    pub fn with_design_system(mut self, design_system: impl Into<String>) -> Self {
+        self.design_system = Some(design_system.into());
+        self
+    }
+
+    /// Only elements carrying every tag
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Only elements whose name starts with `prefix` (case-insensitive)
+    pub fn with_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.name_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sort key and direction
+    pub fn sorted_by(mut self, sort: ElementSort, direction: SortDirection) -> Self {
+        self.sort = sort;
+        self.direction = direction;
+        self
+    }
+
+    /// Page to return (1-based) and its size
+    pub fn with_page(mut self, page: u32, per_page: u32) -> Self {
+        self.page = page;
+        self.per_page = per_page;
+        self
+    }
+
+    /// Elements returned at most: the page size within the global limit
+    pub fn limit(&self) -> u32 {
+        self.per_page.clamp(1, MAX_ELEMENTS_PER_PAGE)
+    }
+
+    /// Elements skipped before the requested page
+    pub fn offset(&self) -> u64 {
+        self.page.saturating_sub(1) as u64 * self.limit() as u64
+    }
+
+    /// Whether an element passes every filter
+    pub fn matches(&self, element: &UIElement) -> bool {
+        self.category.as_ref().is_none_or(|c| element.category == *c)
+            && self
+                .design_system
+                .as_ref()
+                .is_none_or(|ds| element.design_system.as_ref() == Some(ds))
+            && self.tags.iter().all(|tag| element.tags.contains(tag))
+            && self
+                .name_prefix
+                .as_ref()
+                .is_none_or(|prefix| element.name.to_lowercase().starts_with(&prefix.to_lowercase()))
+    }
+}
+
+/// Element with its relationship count, as returned by listings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListedElement {
+    pub element: UIElement,
+    pub degree: u32,
+}
+
+/// One page of an element listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementPage {
+    pub elements: Vec<ListedElement>,
+    /// Elements matching the filters across all pages
+    pub total: u64,
+}
+
+/// Hops a neighbourhood traversal may take at most
+pub const MAX_NEIGHBORHOOD_DEPTH: u32 = 5;
+
+/// Nodes a neighbourhood may contain at most
+pub const MAX_NEIGHBORHOOD_NODES: usize = 1000;
+
+/// Node a neighbourhood traversal starts from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NeighborhoodRoot {
+    Element(Uuid),
+    DesignSystem(String),
+}
+
+/// Root, reach and relationship filter of a neighbourhood
+///
+/// Relationships are followed in both directions. An empty
+/// `relation_types` follows every type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborhoodQuery {
+    pub root: NeighborhoodRoot,
+    /// Hops from the root, capped at [`MAX_NEIGHBORHOOD_DEPTH`]
+    pub depth: u32,
+    #[serde(default)]
+    pub relation_types: Vec<String>,
+    /// Node cap including the root, capped at [`MAX_NEIGHBORHOOD_NODES`]
+    pub max_nodes: usize,
+}
+
+impl NeighborhoodQuery {
+    pub fn new(root: NeighborhoodRoot) -> Self {
+        Self {
+            root,
+            depth: 1,
+            relation_types: Vec::new(),
+            max_nodes: 200,
+        }
+    }
+
+    /// Hops to take from the root
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Only follow relationships of these types
+    pub fn with_relation_types(mut self, relation_types: Vec<String>) -> Self {
+        self.relation_types = relation_types;
+        self
+    }
+
+    /// Stop adding nodes once this many are collected
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Depth and node cap within the global limits (at least the root is returned)
+    pub fn bounds(&self) -> (u32, usize) {
+        (
+            self.depth.min(MAX_NEIGHBORHOOD_DEPTH),
+            self.max_nodes.clamp(1, MAX_NEIGHBORHOOD_NODES),
+        )
+    }
+
+    /// Whether a relationship type is followed
+    pub fn follows(&self, rel_type: &str) -> bool {
+        self.relation_types.is_empty() || self.relation_types.iter().any(|t| t == rel_type)
+    }
+}
+
+/// Label of a neighbourhood node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NeighborhoodNodeKind {
+    Element,
+    Snippet,
+    DesignToken,
+    DesignSystem,
+}
+
+/// Node reached by a neighbourhood traversal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborhoodNode {
+    /// Element and snippet UUIDs; `design_system:<name>` and
+    /// `design_token:<name>:<theme>` for the other kinds
+    pub id: String,
+    pub kind: NeighborhoodNodeKind,
+    pub name: String,
+    pub category: Option<String>,
+    pub design_system: Option<String>,
+    /// Hops from the root
+    pub depth: u32,
+    /// Relationships of any type touching the node
+    pub degree: u32,
+    /// Followed relationships lead to nodes left out by the depth or node cap
+    pub truncated: bool,
+}
+
+/// Relationship between two nodes of a neighbourhood, in its stored direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborhoodEdge {
+    pub source: String,
+    pub target: String,
+    pub rel_type: String,
+    /// Similarity score of `SIMILAR_TO` / `CAN_REPLACE`
+    pub score: Option<f32>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Nodes within N hops of a root and the relationships between them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Neighborhood {
+    /// Root first, then by depth
+    pub nodes: Vec<NeighborhoodNode>,
+    pub edges: Vec<NeighborhoodEdge>,
+    /// The node cap stopped the traversal before the requested depth
+    pub truncated: bool,
+}
+
+/// Node a relationship of a [`GraphDump`] starts or ends at, by its identity
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GraphNodeRef {
+    Element { id: Uuid },
+    Snippet { id: Uuid },
+    DesignToken { name: String, theme: String },
+    DesignSystem { name: String },
+}
+
+impl GraphNodeRef {
+    /// Stable key, as used for neighbourhood node IDs
+    pub fn key(&self) -> String {
+        match self {
+            Self::Element { id } | Self::Snippet { id } => id.to_string(),
+            Self::DesignToken { name, theme } => format!("design_token:{}:{}", name, theme),
+            Self::DesignSystem { name } => format!("design_system:{}", name),
+        }
+    }
+}
+
+/// Relationship of a [`GraphDump`] with the properties stores keep on it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphRelation {
+    pub from: GraphNodeRef,
+    pub to: GraphNodeRef,
+    pub rel_type: String,
+    /// Similarity score of `SIMILAR_TO` / `CAN_REPLACE`
+    pub score: Option<f32>,
+    pub basis: Option<SimilarityBasis>,
+    /// `CAN_REPLACE` not yet reviewed
+    #[serde(default)]
+    pub proposed: bool,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Every node and relationship of a graph store, for export and import
+///
+/// `relations` includes the `HAS_ELEMENT` and token
+/// `BELONGS_TO_DESIGN_SYSTEM` relationships that snippets and tokens also
+/// describe through `element_ids` and `design_system`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphDump {
+    pub elements: Vec<UIElement>,
+    pub snippets: Vec<Snippet>,
+    pub design_systems: Vec<DesignSystem>,
+    pub tokens: Vec<DesignToken>,
+    pub relations: Vec<GraphRelation>,
+}
+
+/// What a `SIMILAR_TO` / `CAN_REPLACE` relation was derived from.
+///
+/// Each basis keeps its own relation between a pair of elements, so
+/// re-deriving one kind never overwrites or deletes the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SimilarityBasis {
+    /// Structural fingerprint (DOM shape, classes, tokens)
+    Structure,
+    /// Embedding k-NN
+    Embedding,
+}
+
+impl SimilarityBasis {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Structure => "structure",
+            Self::Embedding => "embedding",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "structure" => Some(Self::Structure),
+            "embedding" => Some(Self::Embedding),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Vector Entities
+// ============================================================================
+
+/// An embedding point stored in Qdrant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingPoint {
+    /// Unique identifier (element ID)
+    pub id: Uuid,
+    /// Embedding vector (1536 dimensions for OpenAI)
+    pub vector: Vec<f32>,
+    /// Payload metadata
+    pub payload: PointPayload,
+    /// Sparse lexical vector (derived from the payload when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sparse: Option<SparseVector>,
+}
+
+impl EmbeddingPoint {
+    /// Create a new embedding point
+    pub fn new(id: Uuid, vector: Vec<f32>, payload: PointPayload) -> Self {
+        Self {
+            id,
+            vector,
+            payload,
+            sparse: None,
+        }
+    }
+
+    /// Set the sparse lexical vector
+    pub fn with_sparse(mut self, sparse: SparseVector) -> Self {
+        self.sparse = Some(sparse);
+        self
+    }
+
+    /// Validate vector dimensions
+    pub fn validate(&self, expected_size: usize) -> bool {
+        self.vector.len() == expected_size
+    }
+}
+
+/// Sparse lexical vector: hashed term indices and their weights, sorted by index
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SparseVector {
+    pub indices: Vec<u32>,
+    pub values: Vec<f32>,
+}
+
+impl SparseVector {
+    /// Whether the vector has no terms
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Weight of a term index (0.0 when absent)
+    pub fn weight(&self, index: u32) -> f32 {
+        self.indices
+            .binary_search(&index)
+            .map(|i| self.values[i])
+            .unwrap_or_default()
+    }
+}
+
+/// Payload metadata for an embedding point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointPayload {
+    /// Element name
+    pub name: String,
+    /// Category (button, card, form, etc.)
+    pub category: String,
+    /// Element type (component, pattern, layout)
+    pub element_type: String,
+    /// Design system name
+    pub design_system: String,
+    /// NARS confidence value (0.0 - 1.0)
+    pub confidence: f32,
+    /// CSS classes used
+    #[serde(default)]
+    pub css_classes: Vec<String>,
+    /// Additional tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl PointPayload {
+    /// Create a new payload
+    pub fn new(
+        name: impl Into<String>,
+        category: impl Into<String>,
+        element_type: impl Into<String>,
+        design_system: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            category: category.into(),
+            element_type: element_type.into(),
+            design_system: design_system.into(),
+            confidence: 0.5,
+            css_classes: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Set confidence
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set CSS classes
+    pub fn with_css_classes(mut self, classes: Vec<String>) -> Self {
+        self.css_classes = classes;
+        self
+    }
+
+    /// Set tags
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+/// Search filter for vector queries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFilter {
+    /// Filter by category
+    pub category: Option<String>,
+    /// Filter by element type
+    pub element_type: Option<String>,
+    /// Filter by design system
+    pub design_system: Option<String>,
+    /// Minimum confidence threshold
+    pub min_confidence: Option<f32>,
+    /// Filter by tags (any match)
+    pub tags: Option<Vec<String>>,
+}
+
+impl SearchFilter {
+    /// Create a new empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filter by category
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Filter by element type
+    pub fn with_element_type(mut self, element_type: impl Into<String>) -> Self {
+        self.element_type = Some(element_type.into());
+        self
+    }
+
+    /// Filter by design system
+    pub fn with_design_system(mut self, design_system: impl Into<String>) -> Self {
+        self.design_system = Some(design_system.into());
+        self
+    }
+
+    /// Filter by minimum confidence
+    pub fn with_min_confidence(mut self, confidence: f32) -> Self {
+        self.min_confidence = Some(confidence);
+        self
+    }
+
+    /// Filter by tags
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Check if any filter is active
+    pub fn is_active(&self) -> bool {
+        self.category.is_some()
+            || self.element_type.is_some()
+            || self.design_system.is_some()
+            || self.min_confidence.is_some()
+            || self.tags.is_some()
+    }
+
+    /// Check whether a payload passes every active condition (all tags must match)
+    pub fn matches(&self, payload: &PointPayload) -> bool {
+        self.category.as_ref().is_none_or(|c| *c == payload.category)
+            && self.element_type.as_ref().is_none_or(|t| *t == payload.element_type)
+            && self.design_system.as_ref().is_none_or(|d| *d == payload.design_system)
+            && self.min_confidence.is_none_or(|min| payload.confidence >= min)
+            && self
+                .tags
+                .as_ref()
+                .is_none_or(|tags| tags.iter().all(|tag| payload.tags.contains(tag)))
+    }
+}
+
+/// Result from a similarity search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    /// Element ID
+    pub id: Uuid,
+    /// Similarity score (0.0 - 1.0 for cosine)
+    pub score: f32,
+    /// Payload metadata
+    pub payload: PointPayload,
+}
+
+impl SearchResult {
+    /// Create a new search result
+    pub fn new(id: Uuid, score: f32, payload: PointPayload) -> Self {
+        Self { id, score, payload }
+    }
+}
+
+/// Collection statistics
+#[derive(Debug, Clone)]
+pub struct CollectionInfo {
+    /// Collection name
+    pub name: String,
+    /// Number of points
+    pub points_count: u64,
+    /// Number of vectors
+    pub vectors_count: u64,
+    /// Number of indexed vectors
+    pub indexed_vectors_count: u64,
+}
+
+// ============================================================================
+// Feedback Entities
+// ============================================================================
+
+/// Feedback type - positive or negative user signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackType {
+    /// User liked the generation
+    ThumbsUp,
+    /// User disliked the generation
+    ThumbsDown,
+}
+
+impl FeedbackType {
+    /// Returns the confidence delta for this feedback type
+    ///
+    /// - ThumbsUp: +0.1
+    /// - ThumbsDown: -0.15
+    pub fn confidence_delta(&self) -> f32 {
+        match self {
+            FeedbackType::ThumbsUp => 0.1,
+            FeedbackType::ThumbsDown => -0.15,
+        }
+    }
+
+    /// Returns true if this is positive feedback
+    pub fn is_positive(&self) -> bool {
+        matches!(self, FeedbackType::ThumbsUp)
+    }
+}
+
+impl std::fmt::Display for FeedbackType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedbackType::ThumbsUp => write!(f, "thumbs_up"),
+            FeedbackType::ThumbsDown => write!(f, "thumbs_down"),
+        }
+    }
+}
+
+/// Feedback record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Feedback {
+    /// Unique feedback ID
+    pub id: Uuid,
+
+    /// ID of the generation that received feedback
+    pub generation_id: Uuid,
+
+    /// IDs of elements referenced in the generation (stored as JSON array)
+    pub element_ids: Vec<Uuid>,
+
+    /// Type of feedback (positive/negative)
+    pub feedback_type: FeedbackType,
+
+    /// Optional query context for the generation
+    pub query_context: Option<String>,
+
+    /// Optional user comment
+    pub comment: Option<String>,
+
+    /// Confidence delta applied to elements
+    pub confidence_delta: f32,
+
+    /// When the feedback was submitted
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to create new feedback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateFeedback {
+    /// ID of the generation that received feedback
+    pub generation_id: Uuid,
+
+    /// IDs of elements referenced in the generation
+    pub element_ids: Vec<Uuid>,
+
+    /// Type of feedback (positive/negative)
+    pub feedback_type: FeedbackType,
+
+    /// Optional query context for the generation
+    pub query_context: Option<String>,
+
+    /// Optional user comment
+    pub comment: Option<String>,
+}
+
+/// Summary of feedback for an element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackSummary {
+    /// Element ID
+    pub element_id: Uuid,
+
+    /// Total positive feedback count
+    pub positive_count: i64,
+
+    /// Total negative feedback count
+    pub negative_count: i64,
+
+    /// Net confidence delta applied
+    pub net_confidence_delta: f32,
+
+    /// Last feedback timestamp
+    pub last_feedback_at: Option<DateTime<Utc>>,
+}
+
+/// Aggregated feedback metrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedbackMetrics {
+    /// Total feedback count
+    pub total_feedback: i64,
+
+    /// Positive feedback count
+    pub positive_count: i64,
+
+    /// Negative feedback count
+    pub negative_count: i64,
+
+    /// Positive ratio (0.0 - 1.0)
+    pub positive_ratio: f64,
+
+    /// Negative ratio (0.0 - 1.0)
+    pub negative_ratio: f64,
+
+    /// Average confidence delta
+    pub avg_confidence_delta: f64,
+}
@@ -0,0 +1,53 @@
+//! LSH band keys of structural fingerprints
+//!
+//! Graph stores index fingerprinted elements under one key per band of their
+//! MinHash signature, so near-duplicate candidates (elements sharing at least
+//! one band) are looked up instead of compared against every element. Keys
+//! are FNV-1a hashes, stable across builds so stored keys stay comparable.
+
+/// Bands a signature is split into; each gets its own key
+pub const FINGERPRINT_BANDS: usize = 16;
+
+/// Key of each band of `signature`, in band order
+///
+/// A 64-value signature gives 16 bands of 4 rows. Shorter signatures give
+/// fewer bands, and an empty one gives none.
+pub fn band_keys(signature: &[u32]) -> Vec<i64> {
+    if signature.is_empty() {
+        return Vec::new();
+    }
+    let rows = (signature.len() / FINGERPRINT_BANDS).max(1);
+    signature
+        .chunks(rows)
+        .take(FINGERPRINT_BANDS)
+        .map(|band| {
+            let hash = band
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                    (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+                });
+            hash as i64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_keys_match_on_shared_bands() {
+        let signature: Vec<u32> = (0..64).collect();
+        let keys = band_keys(&signature);
+        assert_eq!(keys.len(), FINGERPRINT_BANDS);
+        assert_eq!(keys, band_keys(&signature));
+
+        let mut changed = signature.clone();
+        changed[0] = 1000;
+        let other = band_keys(&changed);
+        assert_ne!(keys[0], other[0]);
+        assert_eq!(keys[1..], other[1..]);
+        assert!(band_keys(&[]).is_empty());
+    }
+}
@@ -1,23 +1,31 @@
 //! CodeGraph Core - Domain entities and traits
 pub mod config;
 pub mod degradation;
+pub mod embedded;
 pub mod entities;
 pub mod error;
 pub mod error_tracking;
+pub mod fingerprint;
+pub mod memory;
+pub mod migrations;
 pub mod retry;
+pub mod sparse;
 pub mod traits;
 
-pub use config::Config;
+pub use config::{Config, StorageBackend};
 pub use degradation::{
     DegradationManager, DegradationStatus, DegradedResponse, HealthState, OperatingMode,
     ResponseCache, Service, ServiceHealth,
 };
+pub use embedded::{EmbeddedFeedbackStore, EmbeddedGraphStore, EmbeddedVectorStore};
 pub use entities::*;
 pub use error::*;
 pub use error_tracking::{
     capture_error, capture_error_with_context, capture_message, install_panic_hook,
     ErrorContext, ErrorTracker, ErrorTrackerConfig, MessageLevel,
 };
+pub use memory::{MemoryFeedbackStore, MemoryGraphStore, MemoryVectorStore};
+pub use migrations::{AppliedMigration, Migration};
 pub use retry::{
     with_retry, with_retry_and_circuit_breaker, CircuitBreaker, CircuitOpenError, CircuitState,
     RetryError, RetryMetrics, RetryPolicy, RetryResult, ServiceType,
@@ -0,0 +1,53 @@
+//! LSH band index backing near-duplicate candidate lookup
+
+use std::collections::{BTreeSet, HashMap};
+
+use uuid::Uuid;
+
+use crate::entities::UIElement;
+use crate::fingerprint::band_keys;
+
+/// (band, key) → elements whose fingerprint has that key in that band
+#[derive(Default)]
+pub(super) struct BandIndex {
+    buckets: HashMap<(usize, i64), BTreeSet<Uuid>>,
+    /// Keys each element is bucketed under, so re-indexing can retract them
+    indexed: HashMap<Uuid, Vec<i64>>,
+}
+
+impl BandIndex {
+    /// Index an element's fingerprint, replacing any previous one
+    pub(super) fn insert(&mut self, element: &UIElement) {
+        self.remove(element.id);
+        let Some(signature) = &element.fingerprint else {
+            return;
+        };
+        let keys = band_keys(signature);
+        for (band, key) in keys.iter().enumerate() {
+            self.buckets.entry((band, *key)).or_default().insert(element.id);
+        }
+        self.indexed.insert(element.id, keys);
+    }
+
+    pub(super) fn remove(&mut self, id: Uuid) {
+        for (band, key) in self.indexed.remove(&id).unwrap_or_default().into_iter().enumerate() {
+            if let Some(bucket) = self.buckets.get_mut(&(band, key)) {
+                bucket.remove(&id);
+                if bucket.is_empty() {
+                    self.buckets.remove(&(band, key));
+                }
+            }
+        }
+    }
+
+    /// Elements sharing at least one band with `signature`
+    pub(super) fn candidates(&self, signature: &[u32]) -> BTreeSet<Uuid> {
+        band_keys(signature)
+            .into_iter()
+            .enumerate()
+            .filter_map(|bucket| self.buckets.get(&bucket))
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
@@ -0,0 +1,246 @@
+//! In-memory feedback store
+
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::entities::{CreateFeedback, Feedback, FeedbackMetrics, FeedbackSummary};
+use crate::traits::FeedbackStore;
+
+/// Feedback store keeping records in insertion order
+#[derive(Default)]
+pub struct MemoryFeedbackStore {
+    records: RwLock<Vec<Feedback>>,
+}
+
+impl MemoryFeedbackStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store holding previously saved records, oldest first
+    pub(crate) fn restore(records: Vec<Feedback>) -> Self {
+        Self {
+            records: RwLock::new(records),
+        }
+    }
+
+    /// Every record, oldest first
+    pub(crate) fn records(&self) -> Vec<Feedback> {
+        self.records.read().expect("feedback store lock poisoned").clone()
+    }
+
+    /// Replace every record, oldest first
+    pub(crate) fn replace(&self, records: Vec<Feedback>) {
+        *self.records.write().expect("feedback store lock poisoned") = records;
+    }
+
+    /// Remove the record with `id`
+    pub(crate) fn remove(&self, id: Uuid) {
+        self.records.write().expect("feedback store lock poisoned").retain(|f| f.id != id);
+    }
+
+    /// Records matching `predicate`, newest first
+    fn newest_first(&self, predicate: impl Fn(&Feedback) -> bool) -> Vec<Feedback> {
+        let records = self.records.read().expect("feedback store lock poisoned");
+        records.iter().rev().filter(|f| predicate(f)).cloned().collect()
+    }
+}
+
+#[async_trait]
+impl FeedbackStore for MemoryFeedbackStore {
+    async fn init_schema(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn save(&self, feedback: CreateFeedback) -> anyhow::Result<Feedback> {
+        let record = Feedback {
+            id: Uuid::new_v4(),
+            generation_id: feedback.generation_id,
+            element_ids: feedback.element_ids,
+            feedback_type: feedback.feedback_type,
+            query_context: feedback.query_context,
+            comment: feedback.comment,
+            confidence_delta: feedback.feedback_type.confidence_delta(),
+            created_at: Utc::now(),
+        };
+        self.records.write().expect("feedback store lock poisoned").push(record.clone());
+        Ok(record)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<Feedback>> {
+        Ok(self.newest_first(|f| f.id == id).into_iter().next())
+    }
+
+    async fn find_by_generation(&self, generation_id: Uuid) -> anyhow::Result<Vec<Feedback>> {
+        Ok(self.newest_first(|f| f.generation_id == generation_id))
+    }
+
+    async fn find_by_element(&self, element_id: Uuid) -> anyhow::Result<Vec<Feedback>> {
+        Ok(self.newest_first(|f| f.element_ids.contains(&element_id)))
+    }
+
+    async fn get_element_summary(&self, element_id: Uuid) -> anyhow::Result<FeedbackSummary> {
+        let records = self.newest_first(|f| f.element_ids.contains(&element_id));
+        let positive_count = records.iter().filter(|f| f.feedback_type.is_positive()).count() as i64;
+
+        Ok(FeedbackSummary {
+            element_id,
+            positive_count,
+            negative_count: records.len() as i64 - positive_count,
+            net_confidence_delta: records.iter().map(|f| f.confidence_delta).sum(),
+            last_feedback_at: records.first().map(|f| f.created_at),
+        })
+    }
+
+    async fn list_recent(&self, limit: i64, offset: i64) -> anyhow::Result<Vec<Feedback>> {
+        Ok(self
+            .newest_first(|_| true)
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn list_until(
+        &self,
+        until: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Feedback>> {
+        let records = self.records.read().expect("feedback store lock poisoned");
+        let mut page: Vec<Feedback> = records
+            .iter()
+            .filter(|f| f.created_at <= until && after.is_none_or(|after| (f.created_at, f.id) > after))
+            .cloned()
+            .collect();
+        page.sort_by_key(|f| (f.created_at, f.id));
+        page.truncate(limit.max(0) as usize);
+        Ok(page)
+    }
+
+    async fn get_metrics(&self) -> anyhow::Result<FeedbackMetrics> {
+        let records = self.records.read().expect("feedback store lock poisoned");
+        let total = records.len() as i64;
+        if total == 0 {
+            return Ok(FeedbackMetrics::default());
+        }
+        let positive = records.iter().filter(|f| f.feedback_type.is_positive()).count() as i64;
+        let delta_sum: f64 = records.iter().map(|f| f.confidence_delta as f64).sum();
+
+        Ok(FeedbackMetrics {
+            total_feedback: total,
+            positive_count: positive,
+            negative_count: total - positive,
+            positive_ratio: positive as f64 / total as f64,
+            negative_ratio: (total - positive) as f64 / total as f64,
+            avg_confidence_delta: delta_sum / total as f64,
+        })
+    }
+
+    async fn delete_older_than(&self, days: i64) -> anyhow::Result<u64> {
+        let cutoff = Utc::now() - Duration::days(days);
+        let mut records = self.records.write().expect("feedback store lock poisoned");
+        let before = records.len();
+        records.retain(|f| f.created_at >= cutoff);
+        Ok((before - records.len()) as u64)
+    }
+
+    async fn import_feedback(&self, records: &[Feedback]) -> anyhow::Result<u64> {
+        let mut stored = self.records.write().expect("feedback store lock poisoned");
+        let mut ids: HashSet<Uuid> = stored.iter().map(|f| f.id).collect();
+        let before = stored.len();
+        stored.extend(records.iter().filter(|f| ids.insert(f.id)).cloned());
+        // Keep records oldest first, as `save` appends them
+        stored.sort_by_key(|f| f.created_at);
+        Ok((stored.len() - before) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::FeedbackType;
+
+    fn feedback(element_id: Uuid, feedback_type: FeedbackType) -> CreateFeedback {
+        CreateFeedback {
+            generation_id: Uuid::new_v4(),
+            element_ids: vec![element_id],
+            feedback_type,
+            query_context: None,
+            comment: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_and_metrics() {
+        let store = MemoryFeedbackStore::new();
+        let element = Uuid::new_v4();
+        store.save(feedback(element, FeedbackType::ThumbsUp)).await.unwrap();
+        store.save(feedback(element, FeedbackType::ThumbsDown)).await.unwrap();
+        store.save(feedback(Uuid::new_v4(), FeedbackType::ThumbsUp)).await.unwrap();
+
+        let summary = store.get_element_summary(element).await.unwrap();
+        assert_eq!(summary.positive_count, 1);
+        assert_eq!(summary.negative_count, 1);
+        assert!((summary.net_confidence_delta + 0.05).abs() < 1e-6);
+
+        let metrics = store.get_metrics().await.unwrap();
+        assert_eq!(metrics.total_feedback, 3);
+        assert_eq!(metrics.positive_count, 2);
+
+        let recent = store.list_recent(2, 0).await.unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_ne!(recent[0].element_ids, vec![element]);
+    }
+
+    #[tokio::test]
+    async fn test_list_until_pages_by_key_up_to_cutoff() {
+        let store = MemoryFeedbackStore::new();
+        let cutoff = Utc::now();
+        for _ in 0..5 {
+            store.save(feedback(Uuid::new_v4(), FeedbackType::ThumbsUp)).await.unwrap();
+        }
+        // Records sharing a timestamp are ordered by ID
+        let mut records = store.records();
+        for record in &mut records {
+            record.created_at = cutoff;
+        }
+        store.replace(records);
+
+        let first = store.list_until(cutoff, None, 3).await.unwrap();
+        assert_eq!(first.len(), 3);
+        // Saved between pages, after the cutoff: neither shifts the next page nor appears in it
+        store.save(feedback(Uuid::new_v4(), FeedbackType::ThumbsDown)).await.unwrap();
+        let last = first.last().map(|f| (f.created_at, f.id));
+        let second = store.list_until(cutoff, last, 3).await.unwrap();
+        assert_eq!(second.len(), 2);
+
+        let ids: Vec<Uuid> = first.iter().chain(&second).map(|f| f.id).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(ids, sorted);
+    }
+
+    #[tokio::test]
+    async fn test_import_keeps_ids_and_skips_known_records() {
+        let source = MemoryFeedbackStore::new();
+        source.save(feedback(Uuid::new_v4(), FeedbackType::ThumbsUp)).await.unwrap();
+        source.save(feedback(Uuid::new_v4(), FeedbackType::ThumbsDown)).await.unwrap();
+        let records = source.records();
+
+        let store = MemoryFeedbackStore::new();
+        let newer = store.save(feedback(Uuid::new_v4(), FeedbackType::ThumbsUp)).await.unwrap();
+        assert_eq!(store.import_feedback(&records).await.unwrap(), 2);
+        assert_eq!(store.import_feedback(&records).await.unwrap(), 0);
+
+        let stored = store.records();
+        assert_eq!(stored.len(), 3);
+        assert_eq!(stored.last().unwrap().id, newer.id);
+        assert_eq!(store.find_by_id(records[0].id).await.unwrap().unwrap().created_at, records[0].created_at);
+    }
+}
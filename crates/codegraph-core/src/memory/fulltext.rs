@@ -0,0 +1,130 @@
+//! Inverted index backing element fulltext search
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::UIElement;
+
+/// Weight of a term found in the element name, relative to template and class hits
+const NAME_BOOST: f32 = 2.0;
+
+/// Serializable copy of a [`FulltextIndex`]: the indexed elements and each term's postings
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct FulltextSnapshot {
+    documents: Vec<Uuid>,
+    postings: BTreeMap<String, Vec<(Uuid, f32)>>,
+}
+
+/// Term → element postings over names, HTML templates and CSS classes
+#[derive(Default)]
+pub(super) struct FulltextIndex {
+    postings: HashMap<String, HashMap<Uuid, f32>>,
+    /// Terms each element is posted under, so re-indexing can retract them
+    indexed: HashMap<Uuid, Vec<String>>,
+}
+
+impl FulltextIndex {
+    /// Index an element, replacing any previous postings for it
+    pub(super) fn insert(&mut self, element: &UIElement) {
+        self.remove(element.id);
+
+        let mut weights: HashMap<String, f32> = HashMap::new();
+        for term in tokenize(&element.name) {
+            *weights.entry(term).or_default() += NAME_BOOST;
+        }
+        let body = tokenize(element.html_template.as_deref().unwrap_or_default())
+            .into_iter()
+            .chain(element.css_classes.iter().flat_map(|c| tokenize(c)));
+        for term in body {
+            *weights.entry(term).or_default() += 1.0;
+        }
+
+        let terms = weights.keys().cloned().collect();
+        for (term, weight) in weights {
+            self.postings.entry(term).or_default().insert(element.id, weight);
+        }
+        self.indexed.insert(element.id, terms);
+    }
+
+    pub(super) fn remove(&mut self, id: Uuid) {
+        for term in self.indexed.remove(&id).unwrap_or_default() {
+            if let Some(postings) = self.postings.get_mut(&term) {
+                postings.remove(&id);
+                if postings.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    /// Serializable copy of the postings
+    pub(super) fn snapshot(&self) -> FulltextSnapshot {
+        let mut documents: Vec<Uuid> = self.indexed.keys().copied().collect();
+        documents.sort_unstable();
+        let postings = self
+            .postings
+            .iter()
+            .map(|(term, postings)| {
+                let mut postings: Vec<(Uuid, f32)> = postings.iter().map(|(id, weight)| (*id, *weight)).collect();
+                postings.sort_unstable_by_key(|(id, _)| *id);
+                (term.clone(), postings)
+            })
+            .collect();
+        FulltextSnapshot { documents, postings }
+    }
+
+    /// Index restored from a snapshot of exactly the elements `ids`
+    pub(super) fn restore(snapshot: FulltextSnapshot, ids: &HashSet<Uuid>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            snapshot.documents.len() == ids.len() && snapshot.documents.iter().all(|id| ids.contains(id)),
+            "index covers {} elements, the graph has {}",
+            snapshot.documents.len(),
+            ids.len()
+        );
+        let mut index = Self {
+            indexed: snapshot.documents.into_iter().map(|id| (id, Vec::new())).collect(),
+            ..Self::default()
+        };
+        for (term, postings) in snapshot.postings {
+            for (id, _) in &postings {
+                index
+                    .indexed
+                    .get_mut(id)
+                    .ok_or_else(|| anyhow::anyhow!("term {:?} is posted for unknown element {}", term, id))?
+                    .push(term.clone());
+            }
+            index.postings.insert(term, postings.into_iter().collect());
+        }
+        Ok(index)
+    }
+
+    /// Elements matching any query term with their summed weights, unordered
+    pub(super) fn search(&self, query: &str) -> HashMap<Uuid, f32> {
+        let mut scores: HashMap<Uuid, f32> = HashMap::new();
+        for term in query_terms(query) {
+            for (id, weight) in self.postings.get(&term).into_iter().flatten() {
+                *scores.entry(*id).or_default() += weight;
+            }
+        }
+        scores
+    }
+}
+
+/// Lowercased alphanumeric words of a text
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Search terms of a Lucene-style query (boolean operators are dropped)
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "OR" | "AND" | "NOT"))
+        .flat_map(tokenize)
+        .collect()
+}
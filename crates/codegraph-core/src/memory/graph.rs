@@ -0,0 +1,1459 @@
+//! In-memory graph store backed by adjacency lists
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::entities::{
+    Counterpart, DesignSystem, DesignToken, ElementPage, ElementQuery, ElementSort, GraphDump, GraphNodeRef,
+    GraphRelation, ListedElement, Neighborhood,
+    NeighborhoodEdge, NeighborhoodNode, NeighborhoodNodeKind, NeighborhoodQuery, NeighborhoodRoot, SimilarElement,
+    SimilarityBasis, Snippet, SnippetSummary, SortDirection, UIElement,
+};
+use crate::traits::GraphStore;
+
+use super::bands::BandIndex;
+use super::fulltext::{FulltextIndex, FulltextSnapshot};
+
+/// Maximum `ALIAS_OF` hops followed when resolving token usage
+const MAX_ALIAS_HOPS: usize = 5;
+
+/// Relationship types the store holds; the last three only arrive by import
+const RELATION_TYPES: [&str; 10] = [
+    "HAS_ELEMENT",
+    "HAS_CHILD",
+    "USES_TOKEN",
+    "ALIAS_OF",
+    "BELONGS_TO_DESIGN_SYSTEM",
+    "SIMILAR_TO",
+    "CAN_REPLACE",
+    "HAS_STYLE",
+    "HAS_STATE",
+    "TRIGGERS_EVENT",
+];
+
+/// Static name of a known relationship type
+fn relation_type(name: &str) -> anyhow::Result<&'static str> {
+    RELATION_TYPES
+        .into_iter()
+        .find(|t| *t == name)
+        .ok_or_else(|| anyhow::anyhow!("unknown relationship type '{}'", name))
+}
+
+/// Graph node identity
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Node {
+    Element(Uuid),
+    Snippet(Uuid),
+    /// Design token keyed by name + theme
+    Token(String, String),
+    DesignSystem(String),
+}
+
+impl From<&Node> for GraphNodeRef {
+    fn from(node: &Node) -> Self {
+        match node.clone() {
+            Node::Element(id) => Self::Element { id },
+            Node::Snippet(id) => Self::Snippet { id },
+            Node::Token(name, theme) => Self::DesignToken { name, theme },
+            Node::DesignSystem(name) => Self::DesignSystem { name },
+        }
+    }
+}
+
+impl From<&GraphNodeRef> for Node {
+    fn from(node: &GraphNodeRef) -> Self {
+        match node.clone() {
+            GraphNodeRef::Element { id } => Self::Element(id),
+            GraphNodeRef::Snippet { id } => Self::Snippet(id),
+            GraphNodeRef::DesignToken { name, theme } => Self::Token(name, theme),
+            GraphNodeRef::DesignSystem { name } => Self::DesignSystem(name),
+        }
+    }
+}
+
+/// Outgoing relationship
+#[derive(Debug, Clone)]
+struct Edge {
+    to: Node,
+    rel_type: &'static str,
+    score: f32,
+    basis: Option<SimilarityBasis>,
+    proposed: bool,
+    /// Unknown for edges restored from snapshots that predate it
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl Edge {
+    fn new(to: Node, rel_type: &'static str) -> Self {
+        Self {
+            to,
+            rel_type,
+            score: 0.0,
+            basis: None,
+            proposed: false,
+            created_at: Some(Utc::now()),
+        }
+    }
+}
+
+/// Element plus the bookkeeping Neo4j keeps as node properties
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredElement {
+    element: UIElement,
+    /// Write sequence of the last save
+    updated: u64,
+    /// Write sequence at which the similarity job last linked the element
+    similarity_linked: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredSnippet {
+    snippet: Snippet,
+    created: u64,
+}
+
+#[derive(Default)]
+struct GraphData {
+    elements: HashMap<Uuid, StoredElement>,
+    snippets: HashMap<Uuid, StoredSnippet>,
+    design_systems: BTreeMap<String, DesignSystem>,
+    tokens: BTreeMap<(String, String), DesignToken>,
+    adjacency: HashMap<Node, Vec<Edge>>,
+    fulltext: FulltextIndex,
+    bands: BandIndex,
+    /// Monotonic write counter used for ordering and staleness checks
+    sequence: u64,
+}
+
+/// Serializable copy of a [`MemoryGraphStore`] without its indexes
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct GraphSnapshot {
+    sequence: u64,
+    elements: Vec<StoredElement>,
+    snippets: Vec<StoredSnippet>,
+    design_systems: Vec<DesignSystem>,
+    tokens: Vec<DesignToken>,
+    edges: Vec<EdgeRecord>,
+}
+
+/// Relationship as written to a snapshot
+#[derive(Serialize, Deserialize)]
+struct EdgeRecord {
+    from: Node,
+    to: Node,
+    rel_type: String,
+    score: f32,
+    basis: Option<SimilarityBasis>,
+    proposed: bool,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl GraphData {
+    fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    fn contains(&self, node: &Node) -> bool {
+        match node {
+            Node::Element(id) => self.elements.contains_key(id),
+            Node::Snippet(id) => self.snippets.contains_key(id),
+            Node::Token(name, theme) => self.tokens.contains_key(&(name.clone(), theme.clone())),
+            Node::DesignSystem(name) => self.design_systems.contains_key(name),
+        }
+    }
+
+    fn edges(&self, from: &Node) -> impl Iterator<Item = &Edge> {
+        self.adjacency.get(from).into_iter().flatten()
+    }
+
+    /// MERGE an unscored edge between existing nodes
+    fn merge_edge(&mut self, from: Node, to: Node, rel_type: &'static str) {
+        if !self.contains(&from) || !self.contains(&to) {
+            return;
+        }
+        let edges = self.adjacency.entry(from).or_default();
+        if !edges.iter().any(|e| e.rel_type == rel_type && e.to == to) {
+            edges.push(Edge::new(to, rel_type));
+        }
+    }
+
+    /// MERGE a scored similarity edge; curated (non-proposed) edges keep their flag
+    fn merge_scored(&mut self, pairs: &[(Uuid, Uuid, f32)], rel_type: &'static str, basis: SimilarityBasis, proposed: bool) {
+        for &(from, to, score) in pairs {
+            if !self.elements.contains_key(&from) || !self.elements.contains_key(&to) {
+                continue;
+            }
+            let to = Node::Element(to);
+            let edges = self.adjacency.entry(Node::Element(from)).or_default();
+            match edges
+                .iter_mut()
+                .find(|e| e.rel_type == rel_type && e.to == to && e.basis == Some(basis))
+            {
+                Some(edge) => edge.score = score,
+                None => edges.push(Edge {
+                    score,
+                    basis: Some(basis),
+                    proposed,
+                    ..Edge::new(to, rel_type)
+                }),
+            }
+        }
+    }
+
+    /// Remove a node together with every relationship touching it
+    fn detach(&mut self, node: &Node) {
+        self.adjacency.remove(node);
+        for edges in self.adjacency.values_mut() {
+            edges.retain(|e| e.to != *node);
+        }
+    }
+
+    fn delete_element(&mut self, id: Uuid) -> bool {
+        let existed = self.elements.remove(&id).is_some();
+        if existed {
+            self.fulltext.remove(id);
+            self.bands.remove(id);
+            self.detach(&Node::Element(id));
+        }
+        existed
+    }
+
+    fn snippet_element_ids(&self, id: Uuid) -> Vec<Uuid> {
+        self.edges(&Node::Snippet(id))
+            .filter(|e| e.rel_type == "HAS_ELEMENT")
+            .filter_map(|e| match e.to {
+                Node::Element(element_id) => Some(element_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn snippet_with_elements(&self, stored: &StoredSnippet) -> Snippet {
+        let element_ids = self.snippet_element_ids(stored.snippet.id);
+        Snippet {
+            element_count: element_ids.len() as u32,
+            element_ids,
+            ..stored.snippet.clone()
+        }
+    }
+
+    fn degree(&self, node: &Node) -> usize {
+        self.adjacency.values().flatten().filter(|e| e.to == *node).count() + self.edges(node).count()
+    }
+
+    /// ID of a node in neighbourhood results
+    fn public_id(node: &Node) -> String {
+        GraphNodeRef::from(node).key()
+    }
+
+    /// Neighbourhood entry of an existing node
+    fn neighborhood_node(&self, node: &Node, depth: u32, truncated: bool) -> NeighborhoodNode {
+        let (kind, name, category, design_system) = match node {
+            Node::Element(id) => {
+                let element = &self.elements[id].element;
+                (
+                    NeighborhoodNodeKind::Element,
+                    element.name.clone(),
+                    Some(element.category.clone()),
+                    element.design_system.clone(),
+                )
+            }
+            Node::Snippet(id) => {
+                let snippet = &self.snippets[id].snippet;
+                let name = snippet.name.clone().unwrap_or_else(|| id.to_string());
+                (NeighborhoodNodeKind::Snippet, name, None, snippet.design_system.clone())
+            }
+            Node::Token(name, theme) => {
+                let token = &self.tokens[&(name.clone(), theme.clone())];
+                (
+                    NeighborhoodNodeKind::DesignToken,
+                    token.name.clone(),
+                    Some(token.category.clone()),
+                    token.design_system.clone(),
+                )
+            }
+            Node::DesignSystem(name) => (NeighborhoodNodeKind::DesignSystem, name.clone(), None, Some(name.clone())),
+        };
+        NeighborhoodNode {
+            id: Self::public_id(node),
+            kind,
+            name,
+            category,
+            design_system,
+            depth,
+            degree: self.degree(node) as u32,
+            truncated,
+        }
+    }
+
+    fn elements_where(&self, predicate: impl Fn(&UIElement) -> bool) -> Vec<UIElement> {
+        self.elements
+            .values()
+            .map(|stored| &stored.element)
+            .filter(|e| predicate(e))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Graph store keeping every node and relationship in memory
+#[derive(Default)]
+pub struct MemoryGraphStore {
+    data: RwLock<GraphData>,
+}
+
+impl MemoryGraphStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, GraphData> {
+        self.data.read().expect("graph store lock poisoned")
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, GraphData> {
+        self.data.write().expect("graph store lock poisoned")
+    }
+
+    pub(crate) fn snapshot(&self) -> GraphSnapshot {
+        let data = self.read();
+        GraphSnapshot {
+            sequence: data.sequence,
+            elements: data.elements.values().cloned().collect(),
+            snippets: data.snippets.values().cloned().collect(),
+            design_systems: data.design_systems.values().cloned().collect(),
+            tokens: data.tokens.values().cloned().collect(),
+            edges: data
+                .adjacency
+                .iter()
+                .flat_map(|(from, edges)| {
+                    edges.iter().map(move |edge| EdgeRecord {
+                        from: from.clone(),
+                        to: edge.to.clone(),
+                        rel_type: edge.rel_type.to_string(),
+                        score: edge.score,
+                        basis: edge.basis,
+                        proposed: edge.proposed,
+                        created_at: edge.created_at,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Serializable copy of the fulltext index
+    pub(crate) fn fulltext_snapshot(&self) -> FulltextSnapshot {
+        self.read().fulltext.snapshot()
+    }
+
+    /// Store holding `snapshot`, searched through `fulltext` if it indexes exactly its elements
+    ///
+    /// A missing or mismatched fulltext index is rebuilt from the elements.
+    pub(crate) fn restore(snapshot: GraphSnapshot, fulltext: Option<FulltextSnapshot>) -> anyhow::Result<Self> {
+        let mut data = GraphData {
+            sequence: snapshot.sequence,
+            ..GraphData::default()
+        };
+        for stored in snapshot.elements {
+            data.bands.insert(&stored.element);
+            data.elements.insert(stored.element.id, stored);
+        }
+        let ids: HashSet<Uuid> = data.elements.keys().copied().collect();
+        data.fulltext = match fulltext.map(|fulltext| FulltextIndex::restore(fulltext, &ids)) {
+            Some(Ok(fulltext)) => fulltext,
+            restored => {
+                if let Some(Err(e)) = restored {
+                    debug!("Rebuilding the fulltext index: {:#}", e);
+                }
+                let mut fulltext = FulltextIndex::default();
+                for stored in data.elements.values() {
+                    fulltext.insert(&stored.element);
+                }
+                fulltext
+            }
+        };
+        for stored in snapshot.snippets {
+            data.snippets.insert(stored.snippet.id, stored);
+        }
+        for ds in snapshot.design_systems {
+            data.design_systems.insert(ds.name.clone(), ds);
+        }
+        for token in snapshot.tokens {
+            data.tokens.insert((token.name.clone(), token.theme.clone()), token);
+        }
+        for record in snapshot.edges {
+            let rel_type = relation_type(&record.rel_type)?;
+            data.adjacency.entry(record.from).or_default().push(Edge {
+                score: record.score,
+                basis: record.basis,
+                proposed: record.proposed,
+                created_at: record.created_at,
+                ..Edge::new(record.to, rel_type)
+            });
+        }
+        Ok(Self { data: RwLock::new(data) })
+    }
+}
+
+#[async_trait]
+impl GraphStore for MemoryGraphStore {
+    async fn initialize_schema(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn save(&self, element: &UIElement) -> anyhow::Result<()> {
+        self.save_batch(std::slice::from_ref(element)).await.map(|_| ())
+    }
+
+    async fn save_batch(&self, elements: &[UIElement]) -> anyhow::Result<usize> {
+        let mut data = self.write();
+        for element in elements {
+            let updated = data.next_sequence();
+            let mut element = element.clone();
+            element.updated_at = Utc::now();
+            data.fulltext.insert(&element);
+            data.bands.insert(&element);
+            match data.elements.get_mut(&element.id) {
+                Some(stored) => {
+                    element.created_at = stored.element.created_at;
+                    stored.element = element;
+                    stored.updated = updated;
+                }
+                None => {
+                    data.elements.insert(
+                        element.id,
+                        StoredElement {
+                            element,
+                            updated,
+                            similarity_linked: None,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(elements.len())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<UIElement>> {
+        Ok(self.read().elements.get(&id).map(|stored| stored.element.clone()))
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> anyhow::Result<Vec<UIElement>> {
+        let data = self.read();
+        Ok(ids
+            .iter()
+            .filter_map(|id| data.elements.get(id))
+            .map(|stored| stored.element.clone())
+            .collect())
+    }
+
+    async fn list_all_elements(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        let data = self.read();
+        let mut stored: Vec<&StoredElement> = data.elements.values().collect();
+        stored.sort_by_key(|s| std::cmp::Reverse(s.updated));
+        Ok(stored.into_iter().take(limit).map(|s| s.element.clone()).collect())
+    }
+
+    async fn list_elements(&self, query: &ElementQuery) -> anyhow::Result<ElementPage> {
+        let data = self.read();
+        let mut matching: Vec<ListedElement> = data
+            .elements
+            .values()
+            .filter(|s| query.matches(&s.element))
+            .map(|s| ListedElement {
+                element: s.element.clone(),
+                degree: data.degree(&Node::Element(s.element.id)) as u32,
+            })
+            .collect();
+
+        matching.sort_by(|a, b| {
+            let key = match query.sort {
+                ElementSort::Name => Ordering::Equal,
+                ElementSort::Created => a.element.created_at.cmp(&b.element.created_at),
+                // Unrecorded confidence sorts as the default 0.5, as in Neo4j
+                ElementSort::Confidence => {
+                    let confidence = |e: &UIElement| e.confidence.unwrap_or(0.5);
+                    confidence(&a.element).total_cmp(&confidence(&b.element))
+                }
+                ElementSort::Degree => a.degree.cmp(&b.degree),
+            };
+            let key = match query.direction {
+                SortDirection::Asc => key,
+                SortDirection::Desc => key.reverse(),
+            };
+            let name = match (query.sort, query.direction) {
+                (ElementSort::Name, SortDirection::Desc) => b.element.name.cmp(&a.element.name),
+                _ => a.element.name.cmp(&b.element.name),
+            };
+            key.then(name).then(a.element.id.cmp(&b.element.id))
+        });
+
+        let total = matching.len() as u64;
+        let elements = matching
+            .into_iter()
+            .skip(query.offset() as usize)
+            .take(query.limit() as usize)
+            .collect();
+        Ok(ElementPage { elements, total })
+    }
+
+    async fn find_by_category(&self, category: &str) -> anyhow::Result<Vec<UIElement>> {
+        let mut elements = self.read().elements_where(|e| e.category == category);
+        elements.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(elements)
+    }
+
+    async fn fulltext_search(&self, search_term: &str, limit: usize) -> anyhow::Result<Vec<SimilarElement>> {
+        let data = self.read();
+        let mut results: Vec<SimilarElement> = data
+            .fulltext
+            .search(search_term)
+            .into_iter()
+            .filter_map(|(id, score)| {
+                data.elements.get(&id).map(|stored| SimilarElement {
+                    element: stored.element.clone(),
+                    similarity: score,
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        Ok(self.write().delete_element(id))
+    }
+
+    async fn delete_batch(&self, ids: &[Uuid]) -> anyhow::Result<u64> {
+        let mut data = self.write();
+        Ok(ids.iter().filter(|id| data.delete_element(**id)).count() as u64)
+    }
+
+    async fn count(&self) -> anyhow::Result<u64> {
+        Ok(self.read().elements.len() as u64)
+    }
+
+    async fn find_fingerprint_candidates(&self, signature: &[u32]) -> anyhow::Result<Vec<UIElement>> {
+        let data = self.read();
+        Ok(data
+            .bands
+            .candidates(signature)
+            .into_iter()
+            .filter_map(|id| data.elements.get(&id))
+            .map(|stored| stored.element.clone())
+            .collect())
+    }
+
+    async fn find_similarity_pending(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        let data = self.read();
+        Ok(data
+            .elements
+            .values()
+            .filter(|s| s.element.embedding.is_some() && s.similarity_linked.is_none_or(|linked| linked < s.updated))
+            .take(limit)
+            .map(|s| s.element.clone())
+            .collect())
+    }
+
+    async fn mark_similarity_linked(&self, ids: &[Uuid]) -> anyhow::Result<()> {
+        let mut data = self.write();
+        let linked = data.next_sequence();
+        for id in ids {
+            if let Some(stored) = data.elements.get_mut(id) {
+                stored.similarity_linked = Some(linked);
+            }
+        }
+        Ok(())
+    }
+
+    async fn reset_similarity_linked(&self) -> anyhow::Result<()> {
+        for stored in self.write().elements.values_mut() {
+            stored.similarity_linked = None;
+        }
+        Ok(())
+    }
+
+    async fn find_counterparts(&self, id: Uuid, design_system: &str, limit: usize) -> anyhow::Result<Vec<Counterpart>> {
+        let data = self.read();
+
+        // Best score per (element, relation)
+        let mut best: HashMap<(Uuid, &'static str), f32> = HashMap::new();
+        for edge in data.edges(&Node::Element(id)) {
+            let Node::Element(to) = edge.to else { continue };
+            if !matches!(edge.rel_type, "CAN_REPLACE" | "SIMILAR_TO") {
+                continue;
+            }
+            let in_system = data
+                .elements
+                .get(&to)
+                .is_some_and(|s| s.element.design_system.as_deref() == Some(design_system));
+            if in_system {
+                let score = best.entry((to, edge.rel_type)).or_insert(f32::MIN);
+                *score = score.max(edge.score);
+            }
+        }
+
+        let mut ranked: Vec<_> = best.into_iter().collect();
+        ranked.sort_by(|((_, ra), sa), ((_, rb), sb)| {
+            (*ra != "CAN_REPLACE")
+                .cmp(&(*rb != "CAN_REPLACE"))
+                .then_with(|| sb.total_cmp(sa))
+        });
+
+        // An element linked by both relationships is listed once, as a replacement
+        let mut counterparts: Vec<Counterpart> = Vec::new();
+        for ((to, relation), score) in ranked {
+            if counterparts.iter().any(|c| c.element.id == to) {
+                continue;
+            }
+            counterparts.push(Counterpart {
+                element: data.elements[&to].element.clone(),
+                relation: relation.to_string(),
+                score,
+            });
+            if counterparts.len() == limit {
+                break;
+            }
+        }
+        Ok(counterparts)
+    }
+
+    async fn save_design_system(&self, ds: &DesignSystem) -> anyhow::Result<()> {
+        let mut data = self.write();
+        let mut ds = ds.clone();
+        if let Some(existing) = data.design_systems.get(&ds.name) {
+            ds.created_at = existing.created_at;
+        }
+        data.design_systems.insert(ds.name.clone(), ds);
+        Ok(())
+    }
+
+    async fn save_design_token(&self, token: &DesignToken) -> anyhow::Result<()> {
+        let mut data = self.write();
+        let key = (token.name.clone(), token.theme.clone());
+        let mut token = token.clone();
+        if let Some(existing) = data.tokens.get(&key) {
+            token.created_at = existing.created_at;
+        }
+        let design_system = token.design_system.clone();
+        data.tokens.insert(key.clone(), token);
+
+        if let Some(ds) = design_system {
+            data.merge_edge(Node::Token(key.0, key.1), Node::DesignSystem(ds), "BELONGS_TO_DESIGN_SYSTEM");
+        }
+        Ok(())
+    }
+
+    async fn find_design_tokens(&self, theme: Option<&str>) -> anyhow::Result<Vec<DesignToken>> {
+        let data = self.read();
+        let mut tokens: Vec<DesignToken> = data
+            .tokens
+            .values()
+            .filter(|t| theme.is_none_or(|theme| t.theme == theme))
+            .cloned()
+            .collect();
+        tokens.sort_by(|a, b| (&a.theme, &a.name).cmp(&(&b.theme, &b.name)));
+        Ok(tokens)
+    }
+
+    async fn find_design_tokens_by_design_system(&self, design_system: &str) -> anyhow::Result<Vec<DesignToken>> {
+        let data = self.read();
+        let mut tokens: Vec<DesignToken> = data
+            .tokens
+            .values()
+            .filter(|t| t.design_system.as_deref() == Some(design_system))
+            .cloned()
+            .collect();
+        tokens.sort_by(|a, b| (&a.theme, &a.name).cmp(&(&b.theme, &b.name)));
+        Ok(tokens)
+    }
+
+    async fn find_elements_using_token(&self, token_name: &str) -> anyhow::Result<Vec<UIElement>> {
+        let data = self.read();
+
+        // Does this token, or a token it aliases within MAX_ALIAS_HOPS, carry the name?
+        let resolves_to_target = |start: &Node| {
+            let mut frontier = vec![start.clone()];
+            for _ in 0..=MAX_ALIAS_HOPS {
+                if frontier.iter().any(|n| matches!(n, Node::Token(name, _) if name == token_name)) {
+                    return true;
+                }
+                frontier = frontier
+                    .iter()
+                    .flat_map(|n| data.edges(n).filter(|e| e.rel_type == "ALIAS_OF").map(|e| e.to.clone()))
+                    .collect();
+            }
+            false
+        };
+
+        let mut elements = data.elements_where(|element| {
+            data.edges(&Node::Element(element.id))
+                .any(|e| e.rel_type == "USES_TOKEN" && resolves_to_target(&e.to))
+        });
+        elements.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(elements)
+    }
+
+    async fn find_tokens_used_by(&self, id: Uuid) -> anyhow::Result<Vec<DesignToken>> {
+        let data = self.read();
+        let mut tokens: Vec<DesignToken> = data
+            .edges(&Node::Element(id))
+            .filter(|e| e.rel_type == "USES_TOKEN")
+            .filter_map(|e| match &e.to {
+                Node::Token(name, theme) => data.tokens.get(&(name.clone(), theme.clone())).cloned(),
+                _ => None,
+            })
+            .collect();
+        tokens.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tokens)
+    }
+
+    async fn save_snippet(&self, snippet: &Snippet) -> anyhow::Result<()> {
+        let mut data = self.write();
+        let created = match data.snippets.get(&snippet.id) {
+            Some(existing) => existing.created,
+            None => data.next_sequence(),
+        };
+        let mut stored = snippet.clone();
+        stored.updated_at = Utc::now();
+        if let Some(existing) = data.snippets.get(&snippet.id) {
+            stored.created_at = existing.snippet.created_at;
+        }
+        data.snippets.insert(snippet.id, StoredSnippet { snippet: stored, created });
+
+        for element_id in &snippet.element_ids {
+            data.merge_edge(Node::Snippet(snippet.id), Node::Element(*element_id), "HAS_ELEMENT");
+        }
+        Ok(())
+    }
+
+    async fn find_snippet_by_id(&self, id: Uuid) -> anyhow::Result<Option<Snippet>> {
+        let data = self.read();
+        Ok(data.snippets.get(&id).map(|stored| data.snippet_with_elements(stored)))
+    }
+
+    async fn list_snippets(
+        &self,
+        page: u32,
+        per_page: u32,
+        design_system: Option<&str>,
+        category: Option<&str>,
+    ) -> anyhow::Result<(Vec<SnippetSummary>, u64)> {
+        let data = self.read();
+        let mut matching: Vec<&StoredSnippet> = data
+            .snippets
+            .values()
+            .filter(|s| design_system.is_none_or(|ds| s.snippet.design_system.as_deref() == Some(ds)))
+            .filter(|s| {
+                category.is_none_or(|category| {
+                    data.snippet_element_ids(s.snippet.id)
+                        .iter()
+                        .any(|id| data.elements.get(id).is_some_and(|e| e.element.category == category))
+                })
+            })
+            .collect();
+        matching.sort_by_key(|s| std::cmp::Reverse(s.created));
+
+        let total = matching.len() as u64;
+        let offset = (page.saturating_sub(1) * per_page) as usize;
+        let summaries = matching
+            .into_iter()
+            .skip(offset)
+            .take(per_page as usize)
+            .map(|s| SnippetSummary {
+                id: s.snippet.id,
+                name: s.snippet.name.clone(),
+                design_system: s.snippet.design_system.clone(),
+                element_count: data.snippet_element_ids(s.snippet.id).len() as u32,
+                tags: s.snippet.tags.clone(),
+                created_at: s.snippet.created_at,
+            })
+            .collect();
+        Ok((summaries, total))
+    }
+
+    async fn delete_snippet(&self, id: Uuid, delete_orphans: bool) -> anyhow::Result<bool> {
+        let mut data = self.write();
+
+        if delete_orphans {
+            let shared: HashSet<Uuid> = data
+                .snippets
+                .keys()
+                .filter(|other| **other != id)
+                .flat_map(|other| data.snippet_element_ids(*other))
+                .collect();
+            for element_id in data.snippet_element_ids(id) {
+                if !shared.contains(&element_id) {
+                    data.delete_element(element_id);
+                }
+            }
+        }
+
+        let existed = data.snippets.remove(&id).is_some();
+        if existed {
+            data.detach(&Node::Snippet(id));
+        }
+        Ok(existed)
+    }
+
+    async fn find_snippets_by_source_root(&self, source_root: &str) -> anyhow::Result<Vec<Snippet>> {
+        let data = self.read();
+        Ok(data
+            .snippets
+            .values()
+            .filter(|s| s.snippet.source_root.as_deref() == Some(source_root))
+            .map(|s| data.snippet_with_elements(s))
+            .collect())
+    }
+
+    async fn count_snippets(&self) -> anyhow::Result<u64> {
+        Ok(self.read().snippets.len() as u64)
+    }
+
+    async fn link_children(&self, links: &[(Uuid, Uuid)]) -> anyhow::Result<()> {
+        let mut data = self.write();
+        for (parent, _) in links {
+            if let Some(edges) = data.adjacency.get_mut(&Node::Element(*parent)) {
+                edges.retain(|e| e.rel_type != "HAS_CHILD");
+            }
+        }
+        for (parent, child) in links {
+            data.merge_edge(Node::Element(*parent), Node::Element(*child), "HAS_CHILD");
+        }
+        Ok(())
+    }
+
+    async fn link_to_token(&self, element_id: Uuid, token_name: &str) -> anyhow::Result<()> {
+        let mut data = self.write();
+        let themes: Vec<String> = data
+            .tokens
+            .keys()
+            .filter(|(name, _)| name == token_name)
+            .map(|(_, theme)| theme.clone())
+            .collect();
+        for theme in themes {
+            data.merge_edge(
+                Node::Element(element_id),
+                Node::Token(token_name.to_string(), theme),
+                "USES_TOKEN",
+            );
+        }
+        Ok(())
+    }
+
+    async fn link_token_alias(&self, name: &str, alias_of: &str, theme: &str) -> anyhow::Result<()> {
+        let mut data = self.write();
+        let target_theme = [theme, "default"]
+            .into_iter()
+            .find(|t| data.tokens.contains_key(&(alias_of.to_string(), t.to_string())));
+        if let Some(target_theme) = target_theme {
+            data.merge_edge(
+                Node::Token(name.to_string(), theme.to_string()),
+                Node::Token(alias_of.to_string(), target_theme.to_string()),
+                "ALIAS_OF",
+            );
+        }
+        Ok(())
+    }
+
+    async fn create_similarities(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.write().merge_scored(pairs, "SIMILAR_TO", basis, false);
+        Ok(())
+    }
+
+    async fn propose_replacements(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.write().merge_scored(pairs, "CAN_REPLACE", basis, true);
+        Ok(())
+    }
+
+    async fn clear_similarity(&self, element_ids: &[Uuid], basis: SimilarityBasis) -> anyhow::Result<u64> {
+        let ids: HashSet<Uuid> = element_ids.iter().copied().collect();
+        let mut data = self.write();
+        let mut removed = 0;
+        for (from, edges) in data.adjacency.iter_mut() {
+            let Node::Element(from) = from else { continue };
+            edges.retain(|e| {
+                let touches = ids.contains(from) || matches!(e.to, Node::Element(to) if ids.contains(&to));
+                let clearable = e.basis == Some(basis)
+                    && (e.rel_type == "SIMILAR_TO" || (e.rel_type == "CAN_REPLACE" && e.proposed));
+                let remove = touches && clearable && matches!(e.to, Node::Element(_));
+                removed += remove as u64;
+                !remove
+            });
+        }
+        Ok(removed)
+    }
+
+    async fn delete_relations(&self, relations: &[GraphRelation]) -> anyhow::Result<u64> {
+        let mut data = self.write();
+        let mut removed = 0;
+        for relation in relations {
+            let (from, to) = (Node::from(&relation.from), Node::from(&relation.to));
+            let Some(edges) = data.adjacency.get_mut(&from) else { continue };
+            let before = edges.len();
+            edges.retain(|e| {
+                !(e.to == to
+                    && e.rel_type == relation.rel_type
+                    && (relation.basis.is_none() || e.basis == relation.basis))
+            });
+            removed += (before - edges.len()) as u64;
+        }
+        Ok(removed)
+    }
+
+    async fn similarity_edges(&self, min_score: f32, basis: SimilarityBasis) -> anyhow::Result<Vec<(Uuid, Uuid, f32)>> {
+        let data = self.read();
+        Ok(data
+            .adjacency
+            .iter()
+            .filter_map(|(from, edges)| match from {
+                Node::Element(from) => Some((*from, edges)),
+                _ => None,
+            })
+            .flat_map(|(from, edges)| {
+                edges.iter().filter_map(move |e| match e.to {
+                    Node::Element(to)
+                        if e.rel_type == "SIMILAR_TO" && e.basis == Some(basis) && e.score >= min_score =>
+                    {
+                        Some((from, to, e.score))
+                    }
+                    _ => None,
+                })
+            })
+            .collect())
+    }
+
+    async fn get_degree(&self, element_id: Uuid) -> anyhow::Result<u32> {
+        Ok(self.read().degree(&Node::Element(element_id)) as u32)
+    }
+
+    async fn neighborhood(&self, query: &NeighborhoodQuery) -> anyhow::Result<Option<Neighborhood>> {
+        let data = self.read();
+        let root = match &query.root {
+            NeighborhoodRoot::Element(id) => Node::Element(*id),
+            NeighborhoodRoot::DesignSystem(name) => Node::DesignSystem(name.clone()),
+        };
+        if !data.contains(&root) {
+            return Ok(None);
+        }
+        let (max_depth, max_nodes) = query.bounds();
+
+        // Followed relationships; elements belong to their design system
+        // through a property, reported as a BELONGS_TO_DESIGN_SYSTEM edge
+        let mut links: Vec<(Node, &Edge)> = data
+            .adjacency
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(move |edge| (from.clone(), edge)))
+            .filter(|(_, edge)| query.follows(edge.rel_type))
+            .collect();
+        let memberships: Vec<(Node, Edge)> = if query.follows("BELONGS_TO_DESIGN_SYSTEM") {
+            data.elements
+                .values()
+                .filter_map(|s| s.element.design_system.as_ref().map(|ds| (s.element.id, ds)))
+                .filter(|(_, ds)| data.design_systems.contains_key(*ds))
+                .map(|(id, ds)| {
+                    let edge = Edge {
+                        created_at: None,
+                        ..Edge::new(Node::DesignSystem(ds.clone()), "BELONGS_TO_DESIGN_SYSTEM")
+                    };
+                    (Node::Element(id), edge)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        links.extend(memberships.iter().map(|(from, edge)| (from.clone(), edge)));
+
+        // Traversable in both directions
+        let mut neighbours: HashMap<&Node, Vec<&Node>> = HashMap::new();
+        for (from, edge) in &links {
+            neighbours.entry(from).or_default().push(&edge.to);
+            neighbours.entry(&edge.to).or_default().push(from);
+        }
+
+        // Breadth-first; a node is visited once, so cycles end the walk
+        let mut depths: HashMap<&Node, u32> = HashMap::from([(&root, 0)]);
+        let mut order = vec![&root];
+        let mut level_start = 0;
+        let mut truncated = false;
+        for depth in 1..=max_depth {
+            let mut next: Vec<&Node> = order[level_start..]
+                .iter()
+                .flat_map(|node| neighbours.get(node).into_iter().flatten().copied())
+                .filter(|node| !depths.contains_key(node))
+                .collect();
+            next.sort_by_cached_key(|node| GraphData::public_id(node));
+            next.dedup();
+
+            level_start = order.len();
+            for node in next {
+                if order.len() == max_nodes {
+                    truncated = true;
+                    break;
+                }
+                depths.insert(node, depth);
+                order.push(node);
+            }
+            if truncated || level_start == order.len() {
+                break;
+            }
+        }
+
+        let nodes = order
+            .iter()
+            .map(|node| {
+                let cut = neighbours
+                    .get(node)
+                    .is_some_and(|linked| linked.iter().any(|n| !depths.contains_key(n)));
+                data.neighborhood_node(node, depths[node], cut)
+            })
+            .collect();
+
+        let mut edges: Vec<NeighborhoodEdge> = links
+            .iter()
+            .filter(|(from, edge)| depths.contains_key(from) && depths.contains_key(&edge.to))
+            .map(|(from, edge)| NeighborhoodEdge {
+                source: GraphData::public_id(from),
+                target: GraphData::public_id(&edge.to),
+                rel_type: edge.rel_type.to_string(),
+                score: edge.basis.map(|_| edge.score),
+                created_at: edge.created_at,
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.source, &a.target, &a.rel_type).cmp(&(&b.source, &b.target, &b.rel_type)));
+
+        Ok(Some(Neighborhood { nodes, edges, truncated }))
+    }
+
+    async fn count_by_category(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        let data = self.read();
+        Ok(group_counts(data.elements.values().map(|s| s.element.category.clone())))
+    }
+
+    async fn count_by_design_system(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        let data = self.read();
+        Ok(group_counts(data.elements.values().filter_map(|s| s.element.design_system.clone())))
+    }
+
+    async fn list_css_classes(&self, design_system: &str) -> anyhow::Result<Vec<String>> {
+        let data = self.read();
+        let mut seen = HashSet::new();
+        Ok(data
+            .elements
+            .values()
+            .filter(|s| s.element.design_system.as_deref() == Some(design_system))
+            .flat_map(|s| s.element.css_classes.iter())
+            .filter(|class| seen.insert(class.as_str()))
+            .cloned()
+            .collect())
+    }
+
+    async fn count_relationships(&self) -> anyhow::Result<u64> {
+        Ok(self.read().adjacency.values().map(Vec::len).sum::<usize>() as u64)
+    }
+
+    async fn count_relationships_by_type(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        let data = self.read();
+        Ok(group_counts(data.adjacency.values().flatten().map(|e| e.rel_type.to_string())))
+    }
+
+    async fn count_by_label(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        let data = self.read();
+        Ok([
+            ("UIElement", data.elements.len()),
+            ("Snippet", data.snippets.len()),
+            ("DesignSystem", data.design_systems.len()),
+            ("DesignToken", data.tokens.len()),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(label, count)| (label.to_string(), count as u64))
+        .collect())
+    }
+
+    async fn export_graph(&self) -> anyhow::Result<GraphDump> {
+        let data = self.read();
+        let mut elements: Vec<UIElement> = data.elements.values().map(|s| s.element.clone()).collect();
+        elements.sort_by_key(|e| e.id);
+        let mut snippets: Vec<Snippet> = data.snippets.values().map(|s| data.snippet_with_elements(s)).collect();
+        snippets.sort_by_key(|s| s.id);
+
+        let mut relations: Vec<GraphRelation> = data
+            .adjacency
+            .iter()
+            .flat_map(|(from, edges)| {
+                edges.iter().map(move |edge| GraphRelation {
+                    from: from.into(),
+                    to: (&edge.to).into(),
+                    rel_type: edge.rel_type.to_string(),
+                    score: edge.basis.map(|_| edge.score),
+                    basis: edge.basis,
+                    proposed: edge.proposed,
+                    created_at: edge.created_at,
+                })
+            })
+            .collect();
+        relations.sort_by(|a, b| (&a.from, &a.to, &a.rel_type).cmp(&(&b.from, &b.to, &b.rel_type)));
+
+        Ok(GraphDump {
+            elements,
+            snippets,
+            design_systems: data.design_systems.values().cloned().collect(),
+            tokens: data.tokens.values().cloned().collect(),
+            relations,
+        })
+    }
+
+    async fn import_graph(&self, dump: &GraphDump) -> anyhow::Result<u64> {
+        let rel_types = dump
+            .relations
+            .iter()
+            .map(|r| relation_type(&r.rel_type))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut data = self.write();
+        for ds in &dump.design_systems {
+            data.design_systems.insert(ds.name.clone(), ds.clone());
+        }
+        for token in &dump.tokens {
+            let key = (token.name.clone(), token.theme.clone());
+            data.tokens.insert(key.clone(), token.clone());
+            if let Some(ds) = &token.design_system {
+                data.merge_edge(Node::Token(key.0, key.1), Node::DesignSystem(ds.clone()), "BELONGS_TO_DESIGN_SYSTEM");
+            }
+        }
+        for element in &dump.elements {
+            let updated = data.next_sequence();
+            let similarity_linked = data.elements.get(&element.id).and_then(|s| s.similarity_linked);
+            data.fulltext.insert(element);
+            data.bands.insert(element);
+            data.elements.insert(
+                element.id,
+                StoredElement {
+                    element: element.clone(),
+                    updated,
+                    similarity_linked,
+                },
+            );
+        }
+        for snippet in &dump.snippets {
+            let created = match data.snippets.get(&snippet.id) {
+                Some(existing) => existing.created,
+                None => data.next_sequence(),
+            };
+            data.snippets.insert(
+                snippet.id,
+                StoredSnippet {
+                    snippet: snippet.clone(),
+                    created,
+                },
+            );
+            for element_id in &snippet.element_ids {
+                data.merge_edge(Node::Snippet(snippet.id), Node::Element(*element_id), "HAS_ELEMENT");
+            }
+        }
+
+        let mut written = 0;
+        for (relation, rel_type) in dump.relations.iter().zip(rel_types) {
+            let (from, to) = (Node::from(&relation.from), Node::from(&relation.to));
+            if !data.contains(&from) || !data.contains(&to) {
+                continue;
+            }
+            let edge = Edge {
+                score: relation.score.unwrap_or(0.0),
+                basis: relation.basis,
+                proposed: relation.proposed,
+                created_at: relation.created_at,
+                ..Edge::new(to.clone(), rel_type)
+            };
+            let edges = data.adjacency.entry(from).or_default();
+            match edges
+                .iter_mut()
+                .find(|e| e.rel_type == rel_type && e.to == to && e.basis == relation.basis)
+            {
+                Some(existing) => {
+                    let created_at = edge.created_at.or(existing.created_at);
+                    *existing = Edge { created_at, ..edge };
+                }
+                None => edges.push(Edge {
+                    created_at: edge.created_at.or(Some(Utc::now())),
+                    ..edge
+                }),
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+/// Count occurrences of non-empty keys, largest group first
+fn group_counts(keys: impl Iterator<Item = String>) -> Vec<(String, u64)> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    for key in keys.filter(|k| !k.is_empty()) {
+        *counts.entry(key).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(ka, a), (kb, b)| b.cmp(a).then_with(|| ka.cmp(kb)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::MAX_ELEMENTS_PER_PAGE;
+
+    fn element(name: &str, category: &str, design_system: &str) -> UIElement {
+        UIElement::new(name, category).with_design_system(design_system)
+    }
+
+    #[tokio::test]
+    async fn test_list_elements_filters_before_paging() {
+        let store = MemoryGraphStore::new();
+        let mut elements: Vec<UIElement> = (0..5)
+            .map(|i| element(&format!("Button {}", i), "button", "tailwind"))
+            .collect();
+        elements.push(element("Card", "card", "tailwind"));
+        elements.push(element("Badge", "badge", "material").with_tags(vec!["status".to_string()]));
+        store.save_batch(&elements).await.unwrap();
+        store.link_children(&[(elements[0].id, elements[5].id), (elements[0].id, elements[6].id)]).await.unwrap();
+
+        let page = store
+            .list_elements(&ElementQuery::new().with_category("button").with_page(2, 2))
+            .await
+            .unwrap();
+        assert_eq!(page.total, 5);
+        let names: Vec<&str> = page.elements.iter().map(|e| e.element.name.as_str()).collect();
+        assert_eq!(names, vec!["Button 2", "Button 3"]);
+
+        let by_degree = store
+            .list_elements(&ElementQuery::new().sorted_by(ElementSort::Degree, SortDirection::Desc))
+            .await
+            .unwrap();
+        assert_eq!(by_degree.elements[0].element.id, elements[0].id);
+        assert_eq!(by_degree.elements[0].degree, 2);
+
+        let tagged = store
+            .list_elements(&ElementQuery::new().with_tags(vec!["status".to_string()]).with_name_prefix("bad"))
+            .await
+            .unwrap();
+        assert_eq!(tagged.total, 1);
+        assert_eq!(tagged.elements[0].element.name, "Badge");
+    }
+
+    #[tokio::test]
+    async fn test_list_elements_sorts_by_confidence_and_caps_page_size() {
+        let store = MemoryGraphStore::new();
+        let mut elements: Vec<UIElement> = (0..MAX_ELEMENTS_PER_PAGE + 10)
+            .map(|i| element(&format!("Button {:03}", i), "button", "tailwind"))
+            .collect();
+        elements[3] = elements[3].clone().with_confidence(0.9);
+        elements[7] = elements[7].clone().with_confidence(0.2);
+        store.save_batch(&elements).await.unwrap();
+
+        let query = ElementQuery::new().sorted_by(ElementSort::Confidence, SortDirection::Desc);
+        let first = store.list_elements(&query.clone().with_page(1, u32::MAX)).await.unwrap();
+        assert_eq!(first.elements.len(), MAX_ELEMENTS_PER_PAGE as usize);
+        assert_eq!(first.elements[0].element.id, elements[3].id);
+
+        let last = store.list_elements(&query.with_page(2, u32::MAX)).await.unwrap();
+        assert_eq!(last.elements.len(), 10);
+        assert_eq!(last.elements.last().unwrap().element.id, elements[7].id);
+    }
+
+    #[tokio::test]
+    async fn test_snippet_orphans_are_deleted_with_their_snippet() {
+        let store = MemoryGraphStore::new();
+        let shared = element("Shared", "button", "custom");
+        let orphan = element("Orphan", "card", "custom");
+        store.save_batch(&[shared.clone(), orphan.clone()]).await.unwrap();
+
+        let mut first = Snippet::new("<div></div>");
+        first.element_ids = vec![shared.id, orphan.id];
+        let mut second = Snippet::new("<button></button>");
+        second.element_ids = vec![shared.id];
+        store.save_snippet(&first).await.unwrap();
+        store.save_snippet(&second).await.unwrap();
+
+        let (page, total) = store.list_snippets(1, 10, None, Some("card")).await.unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(page[0].element_count, 2);
+
+        assert!(store.delete_snippet(first.id, true).await.unwrap());
+        assert!(store.find_by_id(orphan.id).await.unwrap().is_none());
+        assert!(store.find_by_id(shared.id).await.unwrap().is_some());
+        assert_eq!(store.count_relationships().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_counterparts_prefer_replacements() {
+        let store = MemoryGraphStore::new();
+        let source = element("BsButton", "button", "bootstrap");
+        let similar = element("TwButton", "button", "tailwind");
+        let replacement = element("TwPrimary", "button", "tailwind");
+        store
+            .save_batch(&[source.clone(), similar.clone(), replacement.clone()])
+            .await
+            .unwrap();
+
+        let basis = SimilarityBasis::Embedding;
+        store
+            .create_similarities(&[(source.id, similar.id, 0.95), (source.id, replacement.id, 0.9)], basis)
+            .await
+            .unwrap();
+        store.propose_replacements(&[(source.id, replacement.id, 0.9)], basis).await.unwrap();
+
+        let counterparts = store.find_counterparts(source.id, "tailwind", 5).await.unwrap();
+        assert_eq!(counterparts.len(), 2);
+        assert_eq!(counterparts[0].element.id, replacement.id);
+        assert_eq!(counterparts[0].relation, "CAN_REPLACE");
+
+        assert_eq!(store.clear_similarity(&[source.id], basis).await.unwrap(), 3);
+        assert!(store.similarity_edges(0.0, basis).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_token_usage_follows_aliases() {
+        let store = MemoryGraphStore::new();
+        let button = element("Button", "button", "custom");
+        store.save(&button).await.unwrap();
+        store.save_design_token(&DesignToken::new("--blue", "#00f", "color")).await.unwrap();
+        store
+            .save_design_token(&DesignToken::new("--primary", "var(--blue)", "color").with_alias_of("--blue"))
+            .await
+            .unwrap();
+        store.link_token_alias("--primary", "--blue", "default").await.unwrap();
+        store.link_to_token(button.id, "--primary").await.unwrap();
+
+        let users = store.find_elements_using_token("--blue").await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(store.find_tokens_used_by(button.id).await.unwrap()[0].name, "--primary");
+    }
+
+    #[tokio::test]
+    async fn test_neighborhood_survives_cycles_and_marks_truncation() {
+        let store = MemoryGraphStore::new();
+        let [a, b, c, d, e] = ["A", "B", "C", "D", "E"].map(|name| element(name, "button", "tailwind"));
+        store.save_batch(&[a.clone(), b.clone(), c.clone(), d.clone(), e.clone()]).await.unwrap();
+        store.save_design_system(&DesignSystem::new("tailwind", "Tailwind")).await.unwrap();
+        // A - B - C - A cycle of similarities, plus A -> D -> E children
+        let cycle = [(a.id, b.id, 0.9), (b.id, c.id, 0.8), (c.id, a.id, 0.7)];
+        store.create_similarities(&cycle, SimilarityBasis::Embedding).await.unwrap();
+        store.link_children(&[(a.id, d.id), (d.id, e.id)]).await.unwrap();
+
+        let similar = NeighborhoodQuery::new(NeighborhoodRoot::Element(a.id))
+            .with_depth(5)
+            .with_relation_types(vec!["SIMILAR_TO".to_string()]);
+        let hood = store.neighborhood(&similar).await.unwrap().unwrap();
+        assert_eq!(hood.nodes.len(), 3);
+        assert_eq!(hood.edges.len(), 3);
+        assert!(!hood.truncated && hood.nodes.iter().all(|n| !n.truncated));
+        assert_eq!(hood.nodes[0].id, a.id.to_string());
+        let ab = hood.edges.iter().find(|edge| edge.target == b.id.to_string()).unwrap();
+        assert_eq!(ab.score, Some(0.9));
+        assert!(ab.created_at.is_some());
+
+        let children = NeighborhoodQuery::new(NeighborhoodRoot::Element(a.id))
+            .with_relation_types(vec!["HAS_CHILD".to_string()]);
+        let hood = store.neighborhood(&children).await.unwrap().unwrap();
+        let child = hood.nodes.iter().find(|n| n.id == d.id.to_string()).unwrap();
+        assert_eq!((child.depth, child.truncated), (1, true));
+        assert_eq!(hood.edges[0].score, None);
+
+        let capped = NeighborhoodQuery::new(NeighborhoodRoot::Element(a.id)).with_depth(2).with_max_nodes(2);
+        let hood = store.neighborhood(&capped).await.unwrap().unwrap();
+        assert_eq!(hood.nodes.len(), 2);
+        assert!(hood.truncated);
+
+        // Elements belong to their design system through a property
+        let system = NeighborhoodQuery::new(NeighborhoodRoot::DesignSystem("tailwind".to_string()));
+        let hood = store.neighborhood(&system).await.unwrap().unwrap();
+        assert_eq!(hood.nodes[0].kind, NeighborhoodNodeKind::DesignSystem);
+        assert_eq!(hood.nodes.len(), 6);
+        let memberships = hood.edges.iter().filter(|edge| edge.rel_type == "BELONGS_TO_DESIGN_SYSTEM");
+        assert!(memberships.map(|edge| edge.target.as_str()).all(|t| t == "design_system:tailwind"));
+        // Relationships between the members are returned too
+        assert_eq!(hood.edges.len(), 5 + 3 + 2);
+
+        let missing = NeighborhoodQuery::new(NeighborhoodRoot::DesignSystem("bootstrap".to_string()));
+        assert!(store.neighborhood(&missing).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_import_of_export_is_idempotent() {
+        let source = MemoryGraphStore::new();
+        let (card, button) = (element("Card", "card", "tailwind"), element("Button", "button", "tailwind"));
+        source.save_batch(&[card.clone(), button.clone()]).await.unwrap();
+        source.save_design_system(&DesignSystem::new("tailwind", "Tailwind")).await.unwrap();
+        source
+            .save_design_token(&DesignToken::new("--blue", "#00f", "color").with_design_system("tailwind"))
+            .await
+            .unwrap();
+        source.link_children(&[(card.id, button.id)]).await.unwrap();
+        source.link_to_token(button.id, "--blue").await.unwrap();
+        source.propose_replacements(&[(button.id, card.id, 0.6)], SimilarityBasis::Embedding).await.unwrap();
+        let dump = source.export_graph().await.unwrap();
+        assert_eq!(dump.relations.len(), 4);
+
+        let target = MemoryGraphStore::new();
+        assert_eq!(target.import_graph(&dump).await.unwrap(), 4);
+        assert_eq!(target.import_graph(&dump).await.unwrap(), 4);
+        let again = target.export_graph().await.unwrap();
+        assert_eq!(again.relations, dump.relations);
+        assert_eq!(target.count().await.unwrap(), 2);
+        assert_eq!(target.find_counterparts(button.id, "tailwind", 5).await.unwrap()[0].score, 0.6);
+
+        let mut dangling = dump.clone();
+        dangling.elements.clear();
+        assert_eq!(MemoryGraphStore::new().import_graph(&dangling).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_relations_matches_basis_when_given() {
+        let store = MemoryGraphStore::new();
+        let (a, b) = (element("A", "button", "tailwind"), element("B", "button", "tailwind"));
+        store.save_batch(&[a.clone(), b.clone()]).await.unwrap();
+        store.create_similarities(&[(a.id, b.id, 0.9)], SimilarityBasis::Embedding).await.unwrap();
+        store.create_similarities(&[(a.id, b.id, 0.8)], SimilarityBasis::Structure).await.unwrap();
+        store.link_children(&[(a.id, b.id)]).await.unwrap();
+
+        let relation = |rel_type: &str, basis| GraphRelation {
+            from: GraphNodeRef::Element { id: a.id },
+            to: GraphNodeRef::Element { id: b.id },
+            rel_type: rel_type.to_string(),
+            score: None,
+            basis,
+            proposed: false,
+            created_at: None,
+        };
+        let removed = store.delete_relations(&[relation("SIMILAR_TO", Some(SimilarityBasis::Structure))]).await.unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(store.similarity_edges(0.0, SimilarityBasis::Embedding).await.unwrap().len(), 1);
+
+        assert_eq!(store.delete_relations(&[relation("SIMILAR_TO", None), relation("HAS_CHILD", None)]).await.unwrap(), 2);
+        assert_eq!(store.count_relationships().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fulltext_ranks_name_matches_first() {
+        let store = MemoryGraphStore::new();
+        let mut card = element("Card", "card", "custom");
+        card.css_classes = vec!["btn-primary".to_string()];
+        let button = element("Primary Button", "button", "custom");
+        store.save_batch(&[card, button.clone()]).await.unwrap();
+
+        let results = store.fulltext_search("primary OR button", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].element.id, button.id);
+    }
+}
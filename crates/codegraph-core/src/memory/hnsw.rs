@@ -0,0 +1,424 @@
+//! Hierarchical navigable small world graph for approximate cosine search
+//!
+//! Follows Malkov & Yashunin (2016): every point is a node on layers
+//! `0..=level`, where the level is drawn from an exponential distribution.
+//! Upper layers are sparse express lanes; searches descend greedily to layer
+//! 0 and then run a best-first search bounded by `ef`. The graph stores only
+//! IDs and links; vectors are read from the collection's points, except for
+//! removed nodes, which keep their vector until the graph is rebuilt.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::entities::EmbeddingPoint;
+
+use super::vector::cosine_similarity;
+
+/// Links per node on upper layers
+const M: usize = 16;
+/// Links per node on layer 0
+const M0: usize = 2 * M;
+/// Candidates considered when linking a new node
+const EF_CONSTRUCTION: usize = 100;
+/// Minimum candidates considered by a query
+const EF_SEARCH: usize = 64;
+const MAX_LEVEL: usize = 16;
+const NO_ENTRY: u32 = u32::MAX;
+
+struct Node {
+    id: Uuid,
+    /// Neighbors on each layer the node is part of
+    links: Vec<Vec<u32>>,
+    /// Vector of a node whose point was removed or replaced
+    removed: Option<Vec<f32>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Scored {
+    similarity: f32,
+    slot: u32,
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.similarity.total_cmp(&other.similarity).then(self.slot.cmp(&other.slot))
+    }
+}
+
+/// HNSW graph over the points of one collection
+#[derive(Default)]
+pub(super) struct Hnsw {
+    nodes: Vec<Node>,
+    /// Live node of each point
+    slots: HashMap<Uuid, u32>,
+    entry: Option<u32>,
+}
+
+impl Hnsw {
+    /// Graph of `points`, inserted in ID order so a rebuild is deterministic
+    pub(super) fn build(points: &HashMap<Uuid, EmbeddingPoint>) -> Self {
+        let mut ids: Vec<Uuid> = points.keys().copied().collect();
+        ids.sort_unstable();
+        let mut graph = Self::default();
+        for id in ids {
+            graph.insert(id, points);
+        }
+        graph
+    }
+
+    /// Live points in the graph
+    pub(super) fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Nodes kept only because their point was removed
+    pub(super) fn removed(&self) -> usize {
+        self.nodes.len() - self.slots.len()
+    }
+
+    /// Link the point `id` of `points` into the graph
+    ///
+    /// A point already in the graph must be [removed](Self::remove) first.
+    pub(super) fn insert(&mut self, id: Uuid, points: &HashMap<Uuid, EmbeddingPoint>) {
+        let slot = self.nodes.len() as u32;
+        let level = level_of(id);
+        self.nodes.push(Node {
+            id,
+            links: vec![Vec::new(); level + 1],
+            removed: None,
+        });
+        self.slots.insert(id, slot);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(slot);
+            return;
+        };
+        let query = &points[&id].vector;
+        let top = self.nodes[entry as usize].links.len() - 1;
+        let mut nearest = vec![self.score(points, query, entry)];
+        for layer in (level + 1..=top).rev() {
+            nearest = self.search_layer(points, query, &nearest, 1, layer, |_| true);
+        }
+        for layer in (0..=level.min(top)).rev() {
+            nearest = self.search_layer(points, query, &nearest, EF_CONSTRUCTION, layer, |_| true);
+            let max_links = if layer == 0 { M0 } else { M };
+            let neighbors = self.select_neighbors(points, &nearest, M);
+            for &neighbor in &neighbors {
+                let links = &mut self.nodes[neighbor as usize].links[layer];
+                links.push(slot);
+                if links.len() > max_links {
+                    self.shrink(points, neighbor, layer, max_links);
+                }
+            }
+            self.nodes[slot as usize].links[layer] = neighbors;
+        }
+        if level > top {
+            self.entry = Some(slot);
+        }
+    }
+
+    /// Drop the point `id` from search results, keeping its node for navigation
+    pub(super) fn remove(&mut self, id: Uuid, vector: Vec<f32>) {
+        if let Some(slot) = self.slots.remove(&id) {
+            self.nodes[slot as usize].removed = Some(vector);
+        }
+    }
+
+    /// Up to `limit` live points most similar to `query` among those `accept`ed, best first
+    pub(super) fn search(
+        &self,
+        points: &HashMap<Uuid, EmbeddingPoint>,
+        query: &[f32],
+        limit: usize,
+        accept: impl Fn(&EmbeddingPoint) -> bool,
+    ) -> Vec<(Uuid, f32)> {
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+        let top = self.nodes[entry as usize].links.len() - 1;
+        let mut nearest = vec![self.score(points, query, entry)];
+        for layer in (1..=top).rev() {
+            nearest = self.search_layer(points, query, &nearest, 1, layer, |_| true);
+        }
+        let keep = |slot: u32| {
+            let node = &self.nodes[slot as usize];
+            node.removed.is_none() && accept(&points[&node.id])
+        };
+        self.search_layer(points, query, &nearest, limit.max(EF_SEARCH), 0, keep)
+            .into_iter()
+            .take(limit)
+            .map(|scored| (self.nodes[scored.slot as usize].id, scored.similarity))
+            .collect()
+    }
+
+    fn vector<'a>(&'a self, points: &'a HashMap<Uuid, EmbeddingPoint>, slot: u32) -> &'a [f32] {
+        let node = &self.nodes[slot as usize];
+        node.removed.as_deref().unwrap_or_else(|| &points[&node.id].vector)
+    }
+
+    fn score(&self, points: &HashMap<Uuid, EmbeddingPoint>, query: &[f32], slot: u32) -> Scored {
+        Scored {
+            similarity: cosine_similarity(query, self.vector(points, slot)),
+            slot,
+        }
+    }
+
+    /// Best-first search of one layer, returning up to `ef` `keep`-ing nodes, best first
+    ///
+    /// Nodes that are not kept are still traversed, so a selective `keep`
+    /// widens the search instead of cutting it short.
+    fn search_layer(
+        &self,
+        points: &HashMap<Uuid, EmbeddingPoint>,
+        query: &[f32],
+        entry: &[Scored],
+        ef: usize,
+        layer: usize,
+        keep: impl Fn(u32) -> bool,
+    ) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entry.iter().map(|s| s.slot).collect();
+        let mut candidates: BinaryHeap<Scored> = entry.iter().copied().collect();
+        let mut found: BinaryHeap<Reverse<Scored>> = entry.iter().filter(|s| keep(s.slot)).map(|s| Reverse(*s)).collect();
+
+        while let Some(candidate) = candidates.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|worst| candidate < worst.0) {
+                break;
+            }
+            for &neighbor in &self.nodes[candidate.slot as usize].links[layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let scored = self.score(points, query, neighbor);
+                if found.len() < ef || found.peek().is_some_and(|worst| scored > worst.0) {
+                    candidates.push(scored);
+                    if keep(neighbor) {
+                        found.push(Reverse(scored));
+                        if found.len() > ef {
+                            found.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut found: Vec<Scored> = found.into_iter().map(|s| s.0).collect();
+        found.sort_unstable_by(|a, b| b.cmp(a));
+        found
+    }
+
+    /// Up to `m` of `candidates` (best first), skipping those closer to a chosen neighbor than to the base
+    ///
+    /// Skipped candidates fill any remaining room, so sparse regions keep `m` links.
+    fn select_neighbors(&self, points: &HashMap<Uuid, EmbeddingPoint>, candidates: &[Scored], m: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(m);
+        let mut skipped = Vec::new();
+        for candidate in candidates {
+            if selected.len() == m {
+                break;
+            }
+            let vector = self.vector(points, candidate.slot);
+            let diverse = selected
+                .iter()
+                .all(|&chosen| cosine_similarity(vector, self.vector(points, chosen)) < candidate.similarity);
+            if diverse {
+                selected.push(candidate.slot);
+            } else {
+                skipped.push(candidate.slot);
+            }
+        }
+        let room = m - selected.len();
+        selected.extend(skipped.into_iter().take(room));
+        selected
+    }
+
+    /// Cut the links of `slot` on `layer` down to `max_links`
+    fn shrink(&mut self, points: &HashMap<Uuid, EmbeddingPoint>, slot: u32, layer: usize, max_links: usize) {
+        let base = self.vector(points, slot);
+        let mut candidates: Vec<Scored> = self.nodes[slot as usize].links[layer]
+            .iter()
+            .map(|&neighbor| self.score(points, base, neighbor))
+            .collect();
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+        let kept = self.select_neighbors(points, &candidates, max_links);
+        self.nodes[slot as usize].links[layer] = kept;
+    }
+
+    /// Node count, entry node, then each node's ID, layer count and links per layer
+    ///
+    /// Only a graph without removed nodes can be encoded, since their vectors are not stored.
+    pub(super) fn encode(&self) -> Vec<u8> {
+        debug_assert_eq!(self.removed(), 0, "encoded graph has removed nodes");
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(self.nodes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.entry.unwrap_or(NO_ENTRY).to_le_bytes());
+        for node in &self.nodes {
+            bytes.extend_from_slice(node.id.as_bytes());
+            bytes.push(node.links.len() as u8);
+            for links in &node.links {
+                bytes.extend_from_slice(&(links.len() as u32).to_le_bytes());
+                for link in links {
+                    bytes.extend_from_slice(&link.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Graph encoded by [`encode`](Self::encode), which must cover exactly `points`
+    pub(super) fn decode(bytes: &[u8], points: &HashMap<Uuid, EmbeddingPoint>) -> anyhow::Result<Self> {
+        let mut reader = bytes;
+        let count = read_u32(&mut reader)? as usize;
+        anyhow::ensure!(count == points.len(), "graph has {} nodes for {} points", count, points.len());
+        let entry = read_u32(&mut reader)?;
+
+        let mut graph = Self::default();
+        for slot in 0..count as u32 {
+            let id = Uuid::from_slice(take(&mut reader, 16)?)?;
+            anyhow::ensure!(points.contains_key(&id), "graph node {} has no point", id);
+            anyhow::ensure!(graph.slots.insert(id, slot).is_none(), "graph node {} is duplicated", id);
+            let layers = take(&mut reader, 1)?[0] as usize;
+            anyhow::ensure!((1..=MAX_LEVEL + 1).contains(&layers), "graph node {} has {} layers", id, layers);
+            let mut links = Vec::with_capacity(layers);
+            for _ in 0..layers {
+                let len = read_u32(&mut reader)? as usize;
+                let layer = (0..len).map(|_| read_u32(&mut reader)).collect::<anyhow::Result<Vec<_>>>()?;
+                anyhow::ensure!(layer.iter().all(|&link| (link as usize) < count), "graph node {} links past the end", id);
+                links.push(layer);
+            }
+            graph.nodes.push(Node { id, links, removed: None });
+        }
+        anyhow::ensure!(reader.is_empty(), "trailing bytes after graph");
+
+        graph.entry = match entry {
+            NO_ENTRY => None,
+            slot => Some(slot),
+        };
+        anyhow::ensure!(graph.entry.is_some() == (count > 0), "graph entry does not match its nodes");
+        if let Some(entry) = graph.entry {
+            let top = graph.nodes.get(entry as usize).map(|n| n.links.len());
+            anyhow::ensure!(top == graph.nodes.iter().map(|n| n.links.len()).max(), "graph entry is not on the top layer");
+        }
+        // Links to layers a neighbor is not on would panic while searching
+        for node in &graph.nodes {
+            for (layer, links) in node.links.iter().enumerate() {
+                anyhow::ensure!(
+                    links.iter().all(|&link| graph.nodes[link as usize].links.len() > layer),
+                    "graph node {} links past its neighbor's top layer",
+                    node.id
+                );
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// Layer of a point, drawn from an exponential distribution seeded by its ID
+fn level_of(id: Uuid) -> usize {
+    // SplitMix64 finalizer over the ID, mapped to (0, 1]
+    let (high, low) = id.as_u64_pair();
+    let mut z = high ^ low.rotate_left(32);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    let uniform = ((z >> 11) + 1) as f64 / (1u64 << 53) as f64;
+    let level = -uniform.ln() / (M as f64).ln();
+    (level as usize).min(MAX_LEVEL)
+}
+
+fn take<'a>(reader: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    anyhow::ensure!(reader.len() >= len, "unexpected end of graph");
+    let (head, rest) = reader.split_at(len);
+    *reader = rest;
+    Ok(head)
+}
+
+fn read_u32(reader: &mut &[u8]) -> anyhow::Result<u32> {
+    let b = take(reader, 4)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PointPayload;
+
+    /// Deterministic pseudo-random unit-ish vectors
+    fn points(count: usize, dimensions: usize) -> HashMap<Uuid, EmbeddingPoint> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..count)
+            .map(|_| {
+                let vector = (0..dimensions)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 2000) as f32 / 1000.0 - 1.0
+                    })
+                    .collect();
+                let point = EmbeddingPoint::new(Uuid::new_v4(), vector, PointPayload::new("el", "button", "atomic", "custom"));
+                (point.id, point)
+            })
+            .collect()
+    }
+
+    fn exact(points: &HashMap<Uuid, EmbeddingPoint>, query: &[f32], limit: usize) -> Vec<Uuid> {
+        let mut scored: Vec<_> = points.values().map(|p| (p.id, cosine_similarity(query, &p.vector))).collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(limit).map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn test_search_recalls_exact_neighbors() {
+        let points = points(1500, 16);
+        let graph = Hnsw::build(&points);
+        let queries: Vec<&EmbeddingPoint> = points.values().take(50).collect();
+
+        let mut hits = 0;
+        for query in &queries {
+            let found: HashSet<Uuid> = graph.search(&points, &query.vector, 10, |_| true).into_iter().map(|(id, _)| id).collect();
+            hits += exact(&points, &query.vector, 10).iter().filter(|id| found.contains(id)).count();
+        }
+        let recall = hits as f32 / (queries.len() * 10) as f32;
+        assert!(recall > 0.95, "recall {}", recall);
+    }
+
+    #[test]
+    fn test_removed_points_are_not_returned() {
+        let mut points = points(200, 8);
+        let mut graph = Hnsw::build(&points);
+        let query = points.values().next().unwrap().clone();
+
+        let removed = points.remove(&query.id).unwrap();
+        graph.remove(removed.id, removed.vector);
+        assert_eq!(graph.removed(), 1);
+        let found = graph.search(&points, &query.vector, 5, |_| true);
+        assert_eq!(found.len(), 5);
+        assert!(found.iter().all(|(id, _)| *id != query.id));
+    }
+
+    #[test]
+    fn test_graph_round_trips_and_rejects_other_points() {
+        let points = points(300, 8);
+        let graph = Hnsw::build(&points);
+        let bytes = graph.encode();
+
+        let decoded = Hnsw::decode(&bytes, &points).unwrap();
+        let query = &points.values().next().unwrap().vector;
+        assert_eq!(decoded.search(&points, query, 10, |_| true), graph.search(&points, query, 10, |_| true));
+
+        let mut fewer = points.clone();
+        fewer.remove(&graph.nodes[0].id);
+        assert!(Hnsw::decode(&bytes, &fewer).is_err());
+        assert!(Hnsw::decode(&bytes[..bytes.len() - 1], &points).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+//! In-memory storage backend
+//!
+//! Implements [`GraphStore`](crate::GraphStore), [`VectorStore`](crate::VectorStore)
+//! and [`FeedbackStore`](crate::FeedbackStore) without any external service:
+//! the graph is kept as adjacency lists with an inverted index for fulltext
+//! search and an LSH band index for near-duplicate candidates, and vectors are
+//! searched through an HNSW graph per collection. Nothing survives a restart,
+//! which makes it suitable for demos and hermetic tests.
+
+mod bands;
+mod feedback;
+mod fulltext;
+mod graph;
+mod hnsw;
+mod vector;
+
+pub(crate) use fulltext::FulltextSnapshot;
+pub(crate) use graph::GraphSnapshot;
+
+pub use feedback::MemoryFeedbackStore;
+pub use graph::MemoryGraphStore;
+pub use vector::MemoryVectorStore;
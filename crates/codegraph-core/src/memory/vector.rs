@@ -0,0 +1,424 @@
+//! In-memory vector store with HNSW cosine search and brute-force sparse search
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::entities::{CollectionInfo, EmbeddingPoint, SearchFilter, SearchResult, SparseVector};
+use crate::sparse;
+use crate::traits::VectorStore;
+
+use super::hnsw::Hnsw;
+
+/// Rank offset of reciprocal rank fusion (the customary 60, as in Qdrant)
+const RRF_K: f32 = 60.0;
+
+/// Points of one collection and the HNSW graph over their vectors
+#[derive(Default)]
+struct Collection {
+    points: HashMap<Uuid, EmbeddingPoint>,
+    index: Hnsw,
+}
+
+impl Collection {
+    fn upsert(&mut self, point: EmbeddingPoint) {
+        let id = point.id;
+        if let Some(previous) = self.points.insert(id, point) {
+            self.index.remove(id, previous.vector);
+        }
+        self.index.insert(id, &self.points);
+    }
+
+    fn remove(&mut self, id: Uuid) {
+        if let Some(previous) = self.points.remove(&id) {
+            self.index.remove(id, previous.vector);
+        }
+        // Removed nodes keep their vectors, so they are dropped once they outnumber live ones
+        if self.index.removed() > self.points.len() {
+            self.index = Hnsw::build(&self.points);
+        }
+    }
+
+    /// Best `limit` cosine hits matching `filter`
+    ///
+    /// The graph search is approximate; when it finds fewer than `limit`
+    /// hits (a filter matching few points), the collection is scanned instead.
+    fn search(&self, vector: &[f32], limit: usize, filter: Option<&SearchFilter>) -> Vec<SearchResult> {
+        let accept = |point: &EmbeddingPoint| filter.is_none_or(|f| f.matches(&point.payload));
+        let hits = self.index.search(&self.points, vector, limit, accept);
+        if hits.len() == limit || hits.len() == self.points.len() {
+            return hits
+                .into_iter()
+                .map(|(id, score)| SearchResult::new(id, score, self.points[&id].payload.clone()))
+                .collect();
+        }
+
+        let mut results: Vec<SearchResult> = self
+            .points
+            .values()
+            .filter(|point| accept(point))
+            .map(|point| SearchResult::new(point.id, cosine_similarity(vector, &point.vector), point.payload.clone()))
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit);
+        results
+    }
+}
+
+/// Vector store keeping every collection in memory
+///
+/// Collections are created on first upsert; searching a missing collection
+/// returns no results.
+#[derive(Default)]
+pub struct MemoryVectorStore {
+    collections: RwLock<BTreeMap<String, Collection>>,
+}
+
+impl MemoryVectorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Points of one collection (empty when missing)
+    pub(crate) fn points(&self, collection: &str) -> Vec<EmbeddingPoint> {
+        let collections = self.collections.read().expect("vector store lock poisoned");
+        collections
+            .get(collection)
+            .map(|stored| stored.points.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Restore a collection from `points` and the [encoded](Self::index_graph) graph over them
+    ///
+    /// A missing graph, or one not built over exactly these points, is rebuilt.
+    pub(crate) fn restore(&self, collection: &str, points: Vec<EmbeddingPoint>, graph: Option<&[u8]>) -> anyhow::Result<()> {
+        let points: HashMap<Uuid, EmbeddingPoint> = points
+            .into_iter()
+            .map(|mut point| {
+                if point.sparse.is_none() {
+                    point.sparse = Some(sparse::encode_document(&point.payload, None));
+                }
+                (point.id, point)
+            })
+            .collect();
+        let dimensions: BTreeSet<usize> = points.values().map(|p| p.vector.len()).collect();
+        anyhow::ensure!(dimensions.len() <= 1, "Collection {} mixes vector dimensions {:?}", collection, dimensions);
+
+        let index = match graph.map(|bytes| Hnsw::decode(bytes, &points)) {
+            Some(Ok(index)) => index,
+            Some(Err(e)) => {
+                debug!("Rebuilding the vector index of {}: {:#}", collection, e);
+                Hnsw::build(&points)
+            }
+            None => Hnsw::build(&points),
+        };
+        let mut collections = self.collections.write().expect("vector store lock poisoned");
+        collections.insert(collection.to_string(), Collection { points, index });
+        Ok(())
+    }
+
+    /// Encoded HNSW graph of a collection, rebuilt first if it still holds removed points
+    pub(crate) fn index_graph(&self, collection: &str) -> Vec<u8> {
+        let mut collections = self.collections.write().expect("vector store lock poisoned");
+        let Some(stored) = collections.get_mut(collection) else {
+            return Hnsw::default().encode();
+        };
+        if stored.index.removed() > 0 {
+            stored.index = Hnsw::build(&stored.points);
+        }
+        stored.index.encode()
+    }
+
+    /// Insert or replace points, creating the collection if missing
+    pub(crate) fn insert(&self, collection: &str, points: Vec<EmbeddingPoint>) -> anyhow::Result<usize> {
+        let mut collections = self.collections.write().expect("vector store lock poisoned");
+        let stored = collections.entry(collection.to_string()).or_default();
+
+        // Every point of a collection shares the dimension of the first one
+        let expected = stored.points.values().next().map(|p| p.vector.len()).or(points.first().map(|p| p.vector.len()));
+        if let Some(point) = points.iter().find(|p| Some(p.vector.len()) != expected) {
+            anyhow::bail!(
+                "Invalid vector dimension: expected {}, got {}",
+                expected.unwrap_or_default(),
+                point.vector.len()
+            );
+        }
+
+        let count = points.len();
+        for mut point in points {
+            if point.sparse.is_none() {
+                point.sparse = Some(sparse::encode_document(&point.payload, None));
+            }
+            stored.upsert(point);
+        }
+        Ok(count)
+    }
+
+    /// BM25-style scores of the points matching any query term
+    fn search_collection_sparse(
+        points: &HashMap<Uuid, EmbeddingPoint>,
+        query: &SparseVector,
+        filter: Option<&SearchFilter>,
+    ) -> Vec<SearchResult> {
+        let weights: Vec<f32> = query
+            .indices
+            .iter()
+            .zip(&query.values)
+            .map(|(index, weight)| {
+                let doc_freq = points.values().filter(|p| Self::sparse_weight(p, *index) > 0.0).count();
+                weight * sparse::idf(points.len(), doc_freq)
+            })
+            .collect();
+
+        points
+            .values()
+            .filter(|point| filter.is_none_or(|f| f.matches(&point.payload)))
+            .filter_map(|point| {
+                let score: f32 = query
+                    .indices
+                    .iter()
+                    .zip(&weights)
+                    .map(|(index, weight)| weight * Self::sparse_weight(point, *index))
+                    .sum();
+                (score > 0.0).then(|| SearchResult::new(point.id, score, point.payload.clone()))
+            })
+            .collect()
+    }
+
+    fn sparse_weight(point: &EmbeddingPoint, index: u32) -> f32 {
+        point.sparse.as_ref().map(|s| s.weight(index)).unwrap_or_default()
+    }
+
+    /// Best `limit` sparse hits across every collection
+    fn sparse_ranking(&self, query: &str, limit: u64, filter: Option<&SearchFilter>) -> Vec<SearchResult> {
+        let query = sparse::encode_query(query);
+        let collections = self.collections.read().expect("vector store lock poisoned");
+        let mut results: Vec<SearchResult> = collections
+            .values()
+            .flat_map(|stored| Self::search_collection_sparse(&stored.points, &query, filter))
+            .collect();
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit as usize);
+        results
+    }
+}
+
+#[async_trait]
+impl VectorStore for MemoryVectorStore {
+    async fn init_collections(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn upsert_point(&self, collection: &str, point: EmbeddingPoint) -> anyhow::Result<()> {
+        self.upsert_batch(collection, vec![point]).await.map(|_| ())
+    }
+
+    async fn upsert_batch(&self, collection: &str, points: Vec<EmbeddingPoint>) -> anyhow::Result<usize> {
+        self.insert(collection, points)
+    }
+
+    async fn search(
+        &self,
+        collection: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let collections = self.collections.read().expect("vector store lock poisoned");
+        Ok(collections
+            .get(collection)
+            .map(|stored| stored.search(&vector, limit as usize, filter.as_ref()))
+            .unwrap_or_default())
+    }
+
+    async fn search_all(&self, vector: Vec<f32>, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>> {
+        let collections = self.collections.read().expect("vector store lock poisoned");
+        let mut results: Vec<SearchResult> = collections
+            .values()
+            .flat_map(|stored| stored.search(&vector, limit as usize, filter.as_ref()))
+            .collect();
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit as usize);
+        Ok(results)
+    }
+
+    async fn search_sparse(&self, query: &str, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>> {
+        Ok(self.sparse_ranking(query, limit, filter.as_ref()))
+    }
+
+    async fn search_hybrid(
+        &self,
+        vector: Vec<f32>,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let dense = self.search_all(vector, limit, filter.clone()).await?;
+        let sparse = self.sparse_ranking(query, limit, filter.as_ref());
+        Ok(reciprocal_rank_fusion([dense, sparse], limit as usize))
+    }
+
+    async fn delete_point(&self, collection: &str, id: Uuid) -> anyhow::Result<()> {
+        self.delete_batch(collection, vec![id]).await.map(|_| ())
+    }
+
+    async fn delete_batch(&self, collection: &str, ids: Vec<Uuid>) -> anyhow::Result<usize> {
+        let mut collections = self.collections.write().expect("vector store lock poisoned");
+        if let Some(stored) = collections.get_mut(collection) {
+            for id in &ids {
+                stored.remove(*id);
+            }
+        }
+        Ok(ids.len())
+    }
+
+    async fn all_collections_info(&self) -> anyhow::Result<Vec<CollectionInfo>> {
+        let collections = self.collections.read().expect("vector store lock poisoned");
+        Ok(collections
+            .iter()
+            .map(|(name, stored)| CollectionInfo {
+                name: name.clone(),
+                points_count: stored.points.len() as u64,
+                vectors_count: stored.points.len() as u64,
+                indexed_vectors_count: stored.index.len() as u64,
+            })
+            .collect())
+    }
+
+    async fn scroll_points(
+        &self,
+        collection: &str,
+        offset: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<EmbeddingPoint>, Option<Uuid>)> {
+        let collections = self.collections.read().expect("vector store lock poisoned");
+        let Some(points) = collections.get(collection).map(|stored| &stored.points) else {
+            return Ok((Vec::new(), None));
+        };
+        let mut ids: Vec<Uuid> = points.keys().filter(|id| offset.is_none_or(|offset| **id >= offset)).copied().collect();
+        ids.sort_unstable();
+        let next = ids.get(limit).copied();
+        Ok((ids.iter().take(limit).map(|id| points[id].clone()).collect(), next))
+    }
+}
+
+/// Fuse rankings by summing `1 / (RRF_K + rank)` over the lists each hit appears in
+fn reciprocal_rank_fusion<const N: usize>(rankings: [Vec<SearchResult>; N], limit: usize) -> Vec<SearchResult> {
+    let mut fused: HashMap<Uuid, SearchResult> = HashMap::new();
+    for ranking in rankings {
+        for (rank, mut hit) in ranking.into_iter().enumerate() {
+            let score = 1.0 / (RRF_K + rank as f32 + 1.0);
+            fused
+                .entry(hit.id)
+                .and_modify(|existing| existing.score += score)
+                .or_insert_with(|| {
+                    hit.score = score;
+                    hit
+                });
+        }
+    }
+
+    let mut results: Vec<SearchResult> = fused.into_values().collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(limit);
+    results
+}
+
+/// Cosine similarity of two vectors (0.0 when either is zero or lengths differ)
+pub(super) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PointPayload;
+
+    fn point(vector: Vec<f32>, category: &str) -> EmbeddingPoint {
+        EmbeddingPoint::new(Uuid::new_v4(), vector, PointPayload::new("el", category, "atomic", "custom"))
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_by_cosine_and_filters() {
+        let store = MemoryVectorStore::new();
+        let near = point(vec![1.0, 0.1], "button");
+        let far = point(vec![0.0, 1.0], "button");
+        let card = point(vec![1.0, 0.0], "card");
+        store.upsert_batch("ui_custom", vec![near.clone(), far, card]).await.unwrap();
+
+        let filter = SearchFilter::new().with_category("button");
+        let results = store.search("ui_custom", vec![1.0, 0.0], 10, Some(filter)).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, near.id);
+        assert!(results[0].score > results[1].score);
+
+        assert!(store.search("ui_missing", vec![1.0, 0.0], 10, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sparse_and_hybrid_search() {
+        let store = MemoryVectorStore::new();
+        let outline = EmbeddingPoint::new(
+            Uuid::new_v4(),
+            vec![0.0, 1.0],
+            PointPayload::new("Outline Button", "button", "atomic", "custom")
+                .with_css_classes(vec!["btn-outline".to_string()]),
+        );
+        let filled = point(vec![1.0, 0.0], "button");
+        store.upsert_batch("ui_custom", vec![outline.clone(), filled.clone()]).await.unwrap();
+
+        let hits = store.search_sparse("btn-outline", 10, None).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, outline.id);
+
+        // Dense ranks `filled` first, but only `outline` scores in both rankings
+        let fused = store.search_hybrid(vec![1.0, 0.0], "outline", 10, None).await.unwrap();
+        assert_eq!(fused.len(), 2);
+        assert_eq!(fused[0].id, outline.id);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_rejects_mismatched_dimensions() {
+        let store = MemoryVectorStore::new();
+        store.upsert_point("ui_custom", point(vec![1.0, 0.0], "button")).await.unwrap();
+        assert!(store.upsert_point("ui_custom", point(vec![1.0], "button")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scroll_pages_through_collection_in_id_order() {
+        let store = MemoryVectorStore::new();
+        let points: Vec<_> = (0..5).map(|_| point(vec![1.0, 0.0], "button")).collect();
+        store.upsert_batch("ui_custom", points.clone()).await.unwrap();
+
+        let mut seen = Vec::new();
+        let mut offset = None;
+        loop {
+            let (page, next) = store.scroll_points("ui_custom", offset, 2).await.unwrap();
+            assert!(page.len() <= 2);
+            seen.extend(page.into_iter().map(|p| p.id));
+            match next {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+        let mut expected: Vec<_> = points.iter().map(|p| p.id).collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+        assert_eq!(store.scroll_points("ui_missing", None, 2).await.unwrap().0.len(), 0);
+    }
+}
@@ -0,0 +1,83 @@
+//! Versioned schema migrations
+//!
+//! Each external store (Neo4j, Qdrant, PostgreSQL) has an ordered list of
+//! up-migrations and records the versions it has applied in the store itself.
+//! Pending migrations run in version order at startup or via
+//! `codegraph migrate`; a store recording a version this binary does not
+//! know was migrated by a newer release and is refused.
+
+use serde::Serialize;
+
+/// One schema change of a store; `steps` are store-specific
+#[derive(Debug, Clone)]
+pub struct Migration<S> {
+    pub version: u32,
+    pub description: &'static str,
+    pub steps: S,
+}
+
+/// A migration applied to a store
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub description: String,
+}
+
+/// Migrations of `store` not yet applied, in version order
+///
+/// Fails if the list is not strictly ordered by version, or if the store
+/// records a version missing from it.
+pub fn pending<'a, S>(
+    store: &str,
+    migrations: &'a [Migration<S>],
+    applied: &[u32],
+) -> anyhow::Result<Vec<&'a Migration<S>>> {
+    if let Some(pair) = migrations.windows(2).find(|pair| pair[0].version >= pair[1].version) {
+        anyhow::bail!(
+            "{} migrations are out of order: version {} follows {}",
+            store,
+            pair[1].version,
+            pair[0].version
+        );
+    }
+    if let Some(unknown) = applied.iter().find(|v| !migrations.iter().any(|m| m.version == **v)) {
+        anyhow::bail!(
+            "{} is at schema version {}, unknown to this build (latest {}); upgrade codegraph",
+            store,
+            unknown,
+            migrations.last().map_or(0, |m| m.version)
+        );
+    }
+    Ok(migrations.iter().filter(|m| !applied.contains(&m.version)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrations(versions: &[u32]) -> Vec<Migration<()>> {
+        versions
+            .iter()
+            .map(|&version| Migration { version, description: "test", steps: () })
+            .collect()
+    }
+
+    #[test]
+    fn test_pending_skips_applied_versions_in_order() {
+        let all = migrations(&[1, 2, 3]);
+        let versions = |pending: Vec<&Migration<()>>| pending.iter().map(|m| m.version).collect::<Vec<_>>();
+        assert_eq!(versions(pending("neo4j", &all, &[]).unwrap()), vec![1, 2, 3]);
+        assert_eq!(versions(pending("neo4j", &all, &[1, 3]).unwrap()), vec![2]);
+        assert!(pending("neo4j", &all, &[1, 2, 3]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pending_refuses_unknown_versions_and_disorder() {
+        let error = pending("postgres", &migrations(&[1, 2]), &[1, 4]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "postgres is at schema version 4, unknown to this build (latest 2); upgrade codegraph"
+        );
+        assert!(pending("qdrant", &migrations(&[2, 1]), &[]).is_err());
+    }
+}
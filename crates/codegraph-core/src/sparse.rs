@@ -0,0 +1,142 @@
+//! Sparse lexical vectors for keyword search alongside dense embeddings
+//!
+//! Terms are hashed into a `u32` index space (FNV-1a), so indexing and querying
+//! processes need no shared vocabulary. Document weights use BM25 term
+//! frequency saturation; inverse document frequency is applied at query time
+//! by the vector store (Qdrant's `idf` modifier, or [`idf`] in memory).
+//!
+//! CSS classes and hyphenated query words are indexed both whole and split,
+//! so `btn-primary` matches exactly but `primary` still finds it.
+
+use std::collections::BTreeMap;
+
+use crate::entities::{PointPayload, SparseVector};
+
+/// BM25 term frequency saturation
+const K1: f32 = 1.2;
+/// BM25 length normalization strength
+const B: f32 = 0.75;
+/// Typical number of terms in an indexed component
+const AVG_DOC_LEN: f32 = 24.0;
+
+/// Sparse vector of a stored point from its payload and optional HTML template
+pub fn encode_document(payload: &PointPayload, template: Option<&str>) -> SparseVector {
+    let mut terms = Vec::new();
+    terms.extend(words(&payload.name));
+    terms.extend(words(&payload.category));
+    for tag in &payload.tags {
+        terms.extend(compound(tag));
+    }
+    for class in &payload.css_classes {
+        terms.extend(compound(class));
+    }
+    if let Some(template) = template {
+        terms.extend(words(&visible_text(template)));
+    }
+
+    let doc_len = terms.len() as f32;
+    let mut frequencies: BTreeMap<u32, f32> = BTreeMap::new();
+    for term in &terms {
+        *frequencies.entry(term_index(term)).or_default() += 1.0;
+    }
+
+    let norm = K1 * (1.0 - B + B * doc_len / AVG_DOC_LEN);
+    let (indices, values) = frequencies
+        .into_iter()
+        .map(|(index, tf)| (index, tf * (K1 + 1.0) / (tf + norm)))
+        .unzip();
+    SparseVector { indices, values }
+}
+
+/// Sparse vector of a query: every distinct term weighted 1.0
+///
+/// Boolean operators of Lucene-style queries are dropped.
+pub fn encode_query(query: &str) -> SparseVector {
+    let mut indices: Vec<u32> = query
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "OR" | "AND" | "NOT"))
+        .flat_map(compound)
+        .map(|term| term_index(&term))
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    let values = vec![1.0; indices.len()];
+    SparseVector { indices, values }
+}
+
+/// Inverse document frequency of a term found in `doc_freq` of `doc_count` points
+pub fn idf(doc_count: usize, doc_freq: usize) -> f32 {
+    let (n, df) = (doc_count as f32, doc_freq as f32);
+    (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+}
+
+/// Lowercased alphanumeric words of a text
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// A class-like token as a whole, followed by its words when it has several
+fn compound(token: &str) -> Vec<String> {
+    let parts = words(token);
+    if parts.len() > 1 {
+        std::iter::once(token.to_lowercase()).chain(parts).collect()
+    } else {
+        parts
+    }
+}
+
+/// Text of an HTML fragment with its tags removed
+fn visible_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// FNV-1a hash of a term, stable across processes and releases
+fn term_index(term: &str) -> u32 {
+    term.bytes()
+        .fold(0x811c_9dc5_u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_and_query_share_terms() {
+        let payload = PointPayload::new("Primary Button", "button", "atomic", "tailwind")
+            .with_css_classes(vec!["bg-blue-500".to_string()]);
+        let doc = encode_document(&payload, Some("<button class=\"x\">Submit order</button>"));
+
+        for query in ["bg-blue-500", "primary", "submit"] {
+            let q = encode_query(query);
+            assert!(q.indices.iter().all(|i| doc.weight(*i) > 0.0), "{} not indexed", query);
+        }
+        // Markup is not text
+        assert_eq!(doc.weight(term_index("class")), 0.0);
+    }
+
+    #[test]
+    fn test_repeated_terms_saturate() {
+        let once = encode_document(&PointPayload::new("card", "x", "atomic", "custom"), None);
+        let twice = encode_document(&PointPayload::new("card card", "x", "atomic", "custom"), None);
+        let card = term_index("card");
+        assert!(twice.weight(card) > once.weight(card));
+        assert!(twice.weight(card) < 2.0 * once.weight(card));
+        assert!(idf(100, 1) > idf(100, 50));
+    }
+}
@@ -1 +1,288 @@
 //! Domain traits for CodeGraph
+//!
+//! Storage is split into three stores — graph, vector and feedback — so the
+//! server and CLI can run against Neo4j/Qdrant/Postgres, an in-memory
+//! backend ([`crate::memory`]) or an on-disk one ([`crate::embedded`])
+//! without changing call sites.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::entities::{
+    CollectionInfo, Counterpart, CreateFeedback, DesignSystem, DesignToken, ElementPage, ElementQuery,
+    EmbeddingPoint, Feedback, FeedbackMetrics, FeedbackSummary, GraphDump, GraphRelation, Neighborhood, NeighborhoodQuery,
+    SearchFilter, SearchResult, SimilarElement, SimilarityBasis, Snippet, SnippetSummary, UIElement,
+};
+
+/// Graph storage: elements, snippets, design systems, tokens and their relations
+#[async_trait]
+pub trait GraphStore: Send + Sync {
+    /// Create constraints and indexes (no-op for schemaless backends)
+    async fn initialize_schema(&self) -> anyhow::Result<()>;
+
+    // ==================== Elements ====================
+
+    /// Save a UIElement (create or update)
+    async fn save(&self, element: &UIElement) -> anyhow::Result<()>;
+
+    /// Save many UIElements at once
+    async fn save_batch(&self, elements: &[UIElement]) -> anyhow::Result<usize>;
+
+    /// Find element by ID
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<UIElement>>;
+
+    /// Find elements by ID (missing IDs are skipped)
+    async fn find_by_ids(&self, ids: &[Uuid]) -> anyhow::Result<Vec<UIElement>>;
+
+    /// List elements, most recently updated first
+    async fn list_all_elements(&self, limit: usize) -> anyhow::Result<Vec<UIElement>>;
+
+    /// One filtered, sorted page of elements with their degree
+    async fn list_elements(&self, query: &ElementQuery) -> anyhow::Result<ElementPage>;
+
+    /// Find elements by category, ordered by name
+    async fn find_by_category(&self, category: &str) -> anyhow::Result<Vec<UIElement>>;
+
+    /// Fulltext search across name, html_template and css_classes
+    async fn fulltext_search(&self, search_term: &str, limit: usize) -> anyhow::Result<Vec<SimilarElement>>;
+
+    /// Delete element by ID (with all its relationships)
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool>;
+
+    /// Delete elements by ID (with all their relationships)
+    async fn delete_batch(&self, ids: &[Uuid]) -> anyhow::Result<u64>;
+
+    /// Total element count
+    async fn count(&self) -> anyhow::Result<u64>;
+
+    // ==================== Similarity ====================
+
+    /// Fingerprinted elements sharing at least one LSH band with `signature`
+    /// (see [`crate::fingerprint`]), the near-duplicate candidates of an element
+    async fn find_fingerprint_candidates(&self, signature: &[u32]) -> anyhow::Result<Vec<UIElement>>;
+
+    /// Embedded elements not yet linked by the similarity job since their last update
+    async fn find_similarity_pending(&self, limit: usize) -> anyhow::Result<Vec<UIElement>>;
+
+    /// Record that the similarity job has linked these elements
+    async fn mark_similarity_linked(&self, ids: &[Uuid]) -> anyhow::Result<()>;
+
+    /// Mark every element as pending for the similarity job
+    async fn reset_similarity_linked(&self) -> anyhow::Result<()>;
+
+    /// Elements of a design system linked to `id` by `CAN_REPLACE` or
+    /// `SIMILAR_TO`, replacements first, then by descending score
+    async fn find_counterparts(&self, id: Uuid, design_system: &str, limit: usize) -> anyhow::Result<Vec<Counterpart>>;
+
+    // ==================== Design systems & tokens ====================
+
+    /// Save a design system
+    async fn save_design_system(&self, ds: &DesignSystem) -> anyhow::Result<()>;
+
+    /// Save a design token (keyed by name + theme)
+    async fn save_design_token(&self, token: &DesignToken) -> anyhow::Result<()>;
+
+    /// List design tokens, optionally restricted to one theme
+    async fn find_design_tokens(&self, theme: Option<&str>) -> anyhow::Result<Vec<DesignToken>>;
+
+    /// List design tokens defined by a design system (all themes)
+    async fn find_design_tokens_by_design_system(&self, design_system: &str) -> anyhow::Result<Vec<DesignToken>>;
+
+    /// Elements that use a token directly or through an alias chain
+    async fn find_elements_using_token(&self, token_name: &str) -> anyhow::Result<Vec<UIElement>>;
+
+    /// Tokens an element uses directly
+    async fn find_tokens_used_by(&self, id: Uuid) -> anyhow::Result<Vec<DesignToken>>;
+
+    // ==================== Snippets ====================
+
+    /// Save a snippet and link it to its elements
+    async fn save_snippet(&self, snippet: &Snippet) -> anyhow::Result<()>;
+
+    /// Find snippet by ID with its elements
+    async fn find_snippet_by_id(&self, id: Uuid) -> anyhow::Result<Option<Snippet>>;
+
+    /// List snippets with pagination, returning the page and the total count
+    async fn list_snippets(
+        &self,
+        page: u32,
+        per_page: u32,
+        design_system: Option<&str>,
+        category: Option<&str>,
+    ) -> anyhow::Result<(Vec<SnippetSummary>, u64)>;
+
+    /// Delete snippet and optionally the elements no other snippet references
+    async fn delete_snippet(&self, id: Uuid, delete_orphans: bool) -> anyhow::Result<bool>;
+
+    /// All snippets ingested from a source root, with their element IDs
+    async fn find_snippets_by_source_root(&self, source_root: &str) -> anyhow::Result<Vec<Snippet>>;
+
+    /// Snippet count
+    async fn count_snippets(&self) -> anyhow::Result<u64>;
+
+    // ==================== Relations ====================
+
+    /// Set the HAS_CHILD edges of composite elements, replacing existing ones
+    async fn link_children(&self, links: &[(Uuid, Uuid)]) -> anyhow::Result<()>;
+
+    /// Link an element to the design tokens named `token_name` (all themes)
+    async fn link_to_token(&self, element_id: Uuid, token_name: &str) -> anyhow::Result<()>;
+
+    /// Link an alias token to its target, preferring the same theme
+    async fn link_token_alias(&self, name: &str, alias_of: &str, theme: &str) -> anyhow::Result<()>;
+
+    /// Create scored `SIMILAR_TO` relations
+    async fn create_similarities(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()>;
+
+    /// Propose `CAN_REPLACE` relations (marked `proposed` until reviewed)
+    async fn propose_replacements(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()>;
+
+    /// Remove `SIMILAR_TO` and still-proposed `CAN_REPLACE` relations of one basis touching the given elements
+    async fn clear_similarity(&self, element_ids: &[Uuid], basis: SimilarityBasis) -> anyhow::Result<u64>;
+
+    /// Remove relationships matching type, endpoints and basis (any basis when `None`)
+    async fn delete_relations(&self, relations: &[GraphRelation]) -> anyhow::Result<u64>;
+
+    /// All `SIMILAR_TO` relations of one basis scoring at least `min_score`
+    async fn similarity_edges(&self, min_score: f32, basis: SimilarityBasis) -> anyhow::Result<Vec<(Uuid, Uuid, f32)>>;
+
+    /// Number of relationships touching an element
+    async fn get_degree(&self, element_id: Uuid) -> anyhow::Result<u32>;
+
+    /// Nodes within `depth` hops of an element or design system and the
+    /// followed relationships between them; `None` if the root does not exist
+    async fn neighborhood(&self, query: &NeighborhoodQuery) -> anyhow::Result<Option<Neighborhood>>;
+
+    // ==================== Statistics ====================
+
+    /// Element counts grouped by category, largest first
+    async fn count_by_category(&self) -> anyhow::Result<Vec<(String, u64)>>;
+
+    /// Element counts grouped by design system, largest first
+    async fn count_by_design_system(&self) -> anyhow::Result<Vec<(String, u64)>>;
+
+    /// Distinct CSS classes used by elements of a design system
+    async fn list_css_classes(&self, design_system: &str) -> anyhow::Result<Vec<String>>;
+
+    /// Total relationship count
+    async fn count_relationships(&self) -> anyhow::Result<u64>;
+
+    /// Relationship counts grouped by type, largest first
+    async fn count_relationships_by_type(&self) -> anyhow::Result<Vec<(String, u64)>>;
+
+    /// Node counts grouped by label (labels without nodes are omitted)
+    async fn count_by_label(&self) -> anyhow::Result<Vec<(String, u64)>>;
+
+    // ==================== Export ====================
+
+    /// Every node and relationship of the store
+    async fn export_graph(&self) -> anyhow::Result<GraphDump>;
+
+    /// Merge a dump into the store by node identity, so importing it twice
+    /// changes nothing; relationships with a missing endpoint are skipped.
+    /// Returns the number of relationships written.
+    async fn import_graph(&self, dump: &GraphDump) -> anyhow::Result<u64>;
+}
+
+/// Vector storage: embedding points grouped in per-design-system collections
+#[async_trait]
+pub trait VectorStore: Send + Sync {
+    /// Create every known collection if missing
+    async fn init_collections(&self) -> anyhow::Result<()>;
+
+    /// Insert or update a single point
+    async fn upsert_point(&self, collection: &str, point: EmbeddingPoint) -> anyhow::Result<()>;
+
+    /// Insert or update many points
+    async fn upsert_batch(&self, collection: &str, points: Vec<EmbeddingPoint>) -> anyhow::Result<usize>;
+
+    /// Search one collection for the nearest points
+    async fn search(
+        &self,
+        collection: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>>;
+
+    /// Search every collection and keep the best `limit` results
+    async fn search_all(&self, vector: Vec<f32>, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>>;
+
+    /// Keyword search of every collection through the points' sparse vectors
+    async fn search_sparse(&self, query: &str, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>>;
+
+    /// Dense and sparse search of every collection fused by reciprocal rank
+    async fn search_hybrid(
+        &self,
+        vector: Vec<f32>,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>>;
+
+    /// Delete a point by ID
+    async fn delete_point(&self, collection: &str, id: Uuid) -> anyhow::Result<()>;
+
+    /// Delete many points by ID
+    async fn delete_batch(&self, collection: &str, ids: Vec<Uuid>) -> anyhow::Result<usize>;
+
+    /// Statistics of every collection
+    async fn all_collections_info(&self) -> anyhow::Result<Vec<CollectionInfo>>;
+
+    /// Up to `limit` points of a collection in ID order, starting at `offset`,
+    /// with the offset of the next page (`None` after the last one)
+    async fn scroll_points(
+        &self,
+        collection: &str,
+        offset: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<EmbeddingPoint>, Option<Uuid>)>;
+}
+
+/// Feedback storage: user signals on generations
+#[async_trait]
+pub trait FeedbackStore: Send + Sync {
+    /// Create tables and indexes (no-op for schemaless backends)
+    async fn init_schema(&self) -> anyhow::Result<()>;
+
+    /// Save new feedback
+    async fn save(&self, feedback: CreateFeedback) -> anyhow::Result<Feedback>;
+
+    /// Find feedback by ID
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<Feedback>>;
+
+    /// All feedback for a generation, newest first
+    async fn find_by_generation(&self, generation_id: Uuid) -> anyhow::Result<Vec<Feedback>>;
+
+    /// All feedback that includes an element, newest first
+    async fn find_by_element(&self, element_id: Uuid) -> anyhow::Result<Vec<Feedback>>;
+
+    /// Feedback summary for an element
+    async fn get_element_summary(&self, element_id: Uuid) -> anyhow::Result<FeedbackSummary>;
+
+    /// Recent feedback with pagination, newest first
+    async fn list_recent(&self, limit: i64, offset: i64) -> anyhow::Result<Vec<Feedback>>;
+
+    /// Up to `limit` records created at or before `until`, oldest first by
+    /// `(created_at, id)`, starting after the record keyed `after`
+    ///
+    /// Unlike offset pages, these neither repeat nor skip records when
+    /// feedback is saved between requests.
+    async fn list_until(
+        &self,
+        until: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Feedback>>;
+
+    /// Aggregated feedback metrics
+    async fn get_metrics(&self) -> anyhow::Result<FeedbackMetrics>;
+
+    /// Delete feedback older than `days`
+    async fn delete_older_than(&self, days: i64) -> anyhow::Result<u64>;
+
+    /// Insert records as they are, IDs included, skipping IDs already stored;
+    /// returns the number inserted
+    async fn import_feedback(&self, records: &[Feedback]) -> anyhow::Result<u64>;
+}
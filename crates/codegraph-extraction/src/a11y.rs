@@ -0,0 +1,724 @@
+//! Accessibility audit - rule engine over extracted HTML/CSS structure
+//!
+//! Checks the common WCAG failures LLM output and scraped snippets tend to
+//! have: missing alt text, unnamed buttons, unlabeled form controls, heading
+//! level jumps, invalid ARIA roles/attributes, positive tabindex and low
+//! foreground/background contrast (with colors resolved through design tokens).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::css::{CssParser, CssRule, CssStructure};
+use crate::html::{HtmlElement, HtmlParser, HtmlStructure};
+use crate::tokens::{selector_matches, TokenGraph};
+
+/// Minimum contrast ratio for normal text (WCAG AA)
+const MIN_CONTRAST: f64 = 4.5;
+
+/// Minimum contrast ratio for large text (WCAG AA)
+const MIN_CONTRAST_LARGE: f64 = 3.0;
+
+const ARIA_ROLES: &[&str] = &[
+    "alert", "alertdialog", "application", "article", "banner", "blockquote", "button", "caption",
+    "cell", "checkbox", "code", "columnheader", "combobox", "complementary", "contentinfo",
+    "definition", "deletion", "dialog", "directory", "document", "emphasis", "feed", "figure",
+    "form", "generic", "grid", "gridcell", "group", "heading", "img", "insertion", "link", "list",
+    "listbox", "listitem", "log", "main", "marquee", "math", "menu", "menubar", "menuitem",
+    "menuitemcheckbox", "menuitemradio", "meter", "navigation", "none", "note", "option",
+    "paragraph", "presentation", "progressbar", "radio", "radiogroup", "region", "row",
+    "rowgroup", "rowheader", "scrollbar", "search", "searchbox", "separator", "slider",
+    "spinbutton", "status", "strong", "subscript", "superscript", "switch", "tab", "table",
+    "tablist", "tabpanel", "term", "textbox", "time", "timer", "toolbar", "tooltip", "tree",
+    "treegrid", "treeitem",
+];
+
+const ARIA_ATTRIBUTES: &[&str] = &[
+    "activedescendant", "atomic", "autocomplete", "braillelabel", "brailleroledescription",
+    "busy", "checked", "colcount", "colindex", "colindextext", "colspan", "controls", "current",
+    "describedby", "description", "details", "disabled", "dropeffect", "errormessage", "expanded",
+    "flowto", "grabbed", "haspopup", "hidden", "invalid", "keyshortcuts", "label", "labelledby",
+    "level", "live", "modal", "multiline", "multiselectable", "orientation", "owns",
+    "placeholder", "posinset", "pressed", "readonly", "relevant", "required", "roledescription",
+    "rowcount", "rowindex", "rowindextext", "rowspan", "selected", "setsize", "sort", "valuemax",
+    "valuemin", "valuenow", "valuetext",
+];
+
+/// Input types that are labeled by their own value or need no label
+const UNLABELED_INPUT_TYPES: &[&str] = &["hidden", "submit", "reset", "button", "image"];
+
+/// Accessibility rule identifiers (axe-core naming)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum A11yRule {
+    ImageAlt,
+    ButtonName,
+    Label,
+    HeadingOrder,
+    AriaValidRole,
+    AriaValidAttr,
+    Tabindex,
+    ColorContrast,
+}
+
+impl A11yRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ImageAlt => "image-alt",
+            Self::ButtonName => "button-name",
+            Self::Label => "label",
+            Self::HeadingOrder => "heading-order",
+            Self::AriaValidRole => "aria-valid-role",
+            Self::AriaValidAttr => "aria-valid-attr",
+            Self::Tabindex => "tabindex",
+            Self::ColorContrast => "color-contrast",
+        }
+    }
+
+    fn impact(&self) -> A11yImpact {
+        match self {
+            Self::ImageAlt | Self::ButtonName | Self::Label => A11yImpact::Critical,
+            Self::AriaValidRole | Self::AriaValidAttr | Self::ColorContrast => A11yImpact::Serious,
+            Self::HeadingOrder | Self::Tabindex => A11yImpact::Moderate,
+        }
+    }
+}
+
+/// How badly an issue affects assistive technology users
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum A11yImpact {
+    Moderate,
+    Serious,
+    Critical,
+}
+
+impl A11yImpact {
+    /// Penalty applied to the score per issue
+    fn weight(&self) -> f32 {
+        match self {
+            Self::Moderate => 0.4,
+            Self::Serious => 0.7,
+            Self::Critical => 1.0,
+        }
+    }
+}
+
+/// A single failed accessibility check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A11yIssue {
+    pub rule: A11yRule,
+    pub impact: A11yImpact,
+    pub message: String,
+    /// Short element descriptor (e.g., `button.btn.btn-primary`) or selector
+    pub element: String,
+}
+
+impl A11yIssue {
+    fn new(rule: A11yRule, element: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            impact: rule.impact(),
+            message: message.into(),
+            element: element.into(),
+        }
+    }
+
+    /// Human-readable one-line description
+    pub fn describe(&self) -> String {
+        format!("[{}] {} ({})", self.rule.as_str(), self.message, self.element)
+    }
+}
+
+/// Result of an accessibility audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A11yReport {
+    pub issues: Vec<A11yIssue>,
+    /// 1.0 = no issues; decreases with issue count and impact relative to size
+    pub score: f32,
+    pub elements_checked: usize,
+}
+
+impl Default for A11yReport {
+    fn default() -> Self {
+        Self {
+            issues: vec![],
+            score: 1.0,
+            elements_checked: 0,
+        }
+    }
+}
+
+impl A11yReport {
+    fn new(mut issues: Vec<A11yIssue>, elements_checked: usize) -> Self {
+        issues.sort_by_key(|i| std::cmp::Reverse(i.impact));
+        let penalty: f32 = issues.iter().map(|i| i.impact.weight()).sum();
+        let size = elements_checked.max(1) as f32;
+        Self {
+            score: 1.0 - penalty / (penalty + size),
+            issues,
+            elements_checked,
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn describe_issues(&self) -> Vec<String> {
+        self.issues.iter().map(A11yIssue::describe).collect()
+    }
+}
+
+/// Accessibility rule engine
+#[derive(Debug, Default)]
+pub struct A11yAuditor;
+
+impl A11yAuditor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Audit raw HTML/CSS source
+    pub fn audit_source(&self, html: &str, css: Option<&str>) -> anyhow::Result<A11yReport> {
+        let html = HtmlParser::new().parse(html)?;
+        let css = match css {
+            Some(css) => CssParser::new().parse(css)?,
+            None => CssStructure {
+                rules: vec![],
+                design_tokens: vec![],
+                properties: vec![],
+                selectors: vec![],
+                mixins: vec![],
+            },
+        };
+        let tokens = TokenGraph::from_css(&css);
+        Ok(self.audit(&html, &css, &tokens))
+    }
+
+    /// Audit parsed structures, resolving colors through the token graph
+    pub fn audit(&self, html: &HtmlStructure, css: &CssStructure, tokens: &TokenGraph) -> A11yReport {
+        let mut issues = Vec::new();
+        let mut label_targets = HashSet::new();
+        for element in &html.elements {
+            collect_label_targets(element, &mut label_targets);
+        }
+
+        let mut walker = ElementWalker {
+            label_targets: &label_targets,
+            previous_heading: None,
+            elements_checked: 0,
+            issues: &mut issues,
+        };
+        for element in &html.elements {
+            walker.visit(element, false);
+        }
+        let elements_checked = walker.elements_checked;
+
+        check_contrast(html, css, tokens, &mut issues);
+
+        A11yReport::new(issues, elements_checked)
+    }
+}
+
+fn collect_label_targets(element: &HtmlElement, out: &mut HashSet<String>) {
+    if element.tag == "label" {
+        if let Some(target) = attr(element, "for") {
+            out.insert(target.to_string());
+        }
+    }
+    for child in &element.children {
+        collect_label_targets(child, out);
+    }
+}
+
+struct ElementWalker<'a> {
+    label_targets: &'a HashSet<String>,
+    previous_heading: Option<u8>,
+    elements_checked: usize,
+    issues: &'a mut Vec<A11yIssue>,
+}
+
+impl ElementWalker<'_> {
+    fn visit(&mut self, element: &HtmlElement, in_label: bool) {
+        self.elements_checked += 1;
+        let name = describe(element);
+        let role = attr(element, "role").unwrap_or("");
+        let input_type = attr(element, "type").unwrap_or("text").to_lowercase();
+
+        // image-alt
+        let decorative = matches!(role, "presentation" | "none");
+        let is_image_input = element.tag == "input" && input_type == "image";
+        if (element.tag == "img" || is_image_input) && attr(element, "alt").is_none() && !decorative {
+            self.push(A11yRule::ImageAlt, &name, "Image has no alt attribute");
+        }
+
+        // button-name
+        let is_button = element.tag == "button"
+            || role == "button"
+            || (element.tag == "input" && input_type == "button");
+        if is_button && !has_accessible_name(element) {
+            self.push(A11yRule::ButtonName, &name, "Button has no accessible name");
+        }
+
+        // label
+        let needs_label = matches!(element.tag.as_str(), "select" | "textarea")
+            || (element.tag == "input" && !UNLABELED_INPUT_TYPES.contains(&input_type.as_str()));
+        if needs_label && !in_label && !self.is_labeled(element) {
+            self.push(A11yRule::Label, &name, "Form control has no associated label");
+        }
+
+        // heading-order
+        if let Some(level) = heading_level(element) {
+            if let Some(previous) = self.previous_heading {
+                if level > previous + 1 {
+                    let message = format!("Heading level jumps from h{} to h{}", previous, level);
+                    self.push(A11yRule::HeadingOrder, &name, message);
+                }
+            }
+            self.previous_heading = Some(level);
+        }
+
+        // aria-valid-role
+        for token in role.split_whitespace() {
+            if !ARIA_ROLES.contains(&token.to_lowercase().as_str()) {
+                self.push(A11yRule::AriaValidRole, &name, format!("Invalid ARIA role \"{}\"", token));
+            }
+        }
+
+        // aria-valid-attr
+        for (attribute, _) in &element.attributes {
+            if let Some(suffix) = attribute.strip_prefix("aria-") {
+                if !ARIA_ATTRIBUTES.contains(&suffix) {
+                    self.push(
+                        A11yRule::AriaValidAttr,
+                        &name,
+                        format!("Unknown ARIA attribute \"{}\"", attribute),
+                    );
+                }
+            }
+        }
+
+        // tabindex
+        if let Some(tabindex) = attr(element, "tabindex").and_then(|v| v.trim().parse::<i32>().ok()) {
+            if tabindex > 0 {
+                let message = format!("Positive tabindex ({}) overrides the natural focus order", tabindex);
+                self.push(A11yRule::Tabindex, &name, message);
+            }
+        }
+
+        let in_label = in_label || element.tag == "label";
+        for child in &element.children {
+            self.visit(child, in_label);
+        }
+    }
+
+    fn is_labeled(&self, element: &HtmlElement) -> bool {
+        ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|a| attr(element, a).is_some_and(|v| !v.trim().is_empty()))
+            || element
+                .id
+                .as_ref()
+                .is_some_and(|id| self.label_targets.contains(id))
+    }
+
+    fn push(&mut self, rule: A11yRule, element: &str, message: impl Into<String>) {
+        self.issues.push(A11yIssue::new(rule, element, message));
+    }
+}
+
+fn attr<'a>(element: &'a HtmlElement, name: &str) -> Option<&'a str> {
+    element
+        .attributes
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+fn describe(element: &HtmlElement) -> String {
+    let mut out = element.tag.clone();
+    if let Some(id) = &element.id {
+        out.push('#');
+        out.push_str(id);
+    }
+    for class in &element.classes {
+        out.push('.');
+        out.push_str(class);
+    }
+    out
+}
+
+fn heading_level(element: &HtmlElement) -> Option<u8> {
+    let digits = element.tag.strip_prefix('h')?;
+    match digits.parse::<u8>() {
+        Ok(level @ 1..=6) => Some(level),
+        _ => None,
+    }
+}
+
+/// Accessible name from ARIA/title, the element's value or any descendant text/alt
+fn has_accessible_name(element: &HtmlElement) -> bool {
+    let named = ["aria-label", "aria-labelledby", "title", "value"]
+        .iter()
+        .any(|a| attr(element, a).is_some_and(|v| !v.trim().is_empty()));
+    named || has_text_or_alt(element)
+}
+
+fn has_text_or_alt(element: &HtmlElement) -> bool {
+    if attr(element, "aria-hidden") == Some("true") {
+        return false;
+    }
+    element.text_content.as_ref().is_some_and(|t| !t.trim().is_empty())
+        || (element.tag == "img" && attr(element, "alt").is_some_and(|a| !a.trim().is_empty()))
+        || attr(element, "aria-label").is_some_and(|v| !v.trim().is_empty())
+        || element.children.iter().any(has_text_or_alt)
+}
+
+// ==================== Color contrast ====================
+
+/// Foreground/background declared by the rules that apply to an element
+#[derive(Default)]
+struct ColorPair<'a> {
+    foreground: Option<(&'a str, &'a str)>,
+    background: Option<(&'a str, &'a str)>,
+    font_size_px: Option<f64>,
+    bold: bool,
+}
+
+impl<'a> ColorPair<'a> {
+    fn apply(&mut self, rule: &'a CssRule) {
+        for prop in &rule.properties {
+            match prop.name.as_str() {
+                "color" => self.foreground = Some((rule.selector.as_str(), prop.value.as_str())),
+                "background-color" | "background" => {
+                    self.background = Some((rule.selector.as_str(), prop.value.as_str()))
+                }
+                "font-size" => self.font_size_px = parse_px(&prop.value).or(self.font_size_px),
+                "font-weight" => {
+                    self.bold = matches!(prop.value.trim(), "bold" | "bolder")
+                        || prop.value.trim().parse::<u32>().is_ok_and(|w| w >= 700)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn is_large_text(&self) -> bool {
+        self.font_size_px
+            .is_some_and(|size| size >= 24.0 || (self.bold && size >= 18.66))
+    }
+}
+
+fn check_contrast(html: &HtmlStructure, css: &CssStructure, tokens: &TokenGraph, issues: &mut Vec<A11yIssue>) {
+    let mut themes: Vec<Option<&str>> = vec![None];
+    for rule in &css.rules {
+        if let Some(theme) = rule.theme.as_deref() {
+            if !themes.contains(&Some(theme)) {
+                themes.push(Some(theme));
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for theme in themes {
+        let rules: Vec<&CssRule> = css
+            .rules
+            .iter()
+            .filter(|r| r.theme.is_none() || r.theme.as_deref() == theme)
+            .collect();
+
+        // Pairs declared together in a single rule
+        for rule in &rules {
+            let mut pair = ColorPair::default();
+            pair.apply(rule);
+            check_pair(&pair, theme, tokens, &mut seen, issues);
+        }
+
+        // Pairs combined from separate rules matching the same element
+        let mut stack: Vec<&HtmlElement> = html.elements.iter().collect();
+        while let Some(element) = stack.pop() {
+            let mut pair = ColorPair::default();
+            for rule in rules.iter().filter(|r| selector_matches(&r.selector, element)) {
+                pair.apply(rule);
+            }
+            check_pair(&pair, theme, tokens, &mut seen, issues);
+            stack.extend(element.children.iter());
+        }
+    }
+}
+
+fn check_pair(
+    pair: &ColorPair,
+    theme: Option<&str>,
+    tokens: &TokenGraph,
+    seen: &mut HashSet<(String, String, Option<String>)>,
+    issues: &mut Vec<A11yIssue>,
+) {
+    let (Some((fg_selector, fg_value)), Some((bg_selector, bg_value))) = (pair.foreground, pair.background) else {
+        return;
+    };
+    let key = (fg_selector.to_string(), bg_selector.to_string(), theme.map(String::from));
+    if !seen.insert(key) {
+        return;
+    }
+
+    let resolve = |value: &str| tokens.resolve_value(value, theme).and_then(|v| parse_color(&v));
+    let (Some(fg), Some(bg)) = (resolve(fg_value), resolve(bg_value)) else {
+        return;
+    };
+
+    let ratio = contrast_ratio(fg, bg);
+    let required = if pair.is_large_text() { MIN_CONTRAST_LARGE } else { MIN_CONTRAST };
+    if ratio < required {
+        let location = if fg_selector == bg_selector {
+            fg_selector.to_string()
+        } else {
+            format!("{} / {}", fg_selector, bg_selector)
+        };
+        let theme_note = theme.map(|t| format!(" in theme \"{}\"", t)).unwrap_or_default();
+        issues.push(A11yIssue::new(
+            A11yRule::ColorContrast,
+            location,
+            format!(
+                "Contrast ratio {:.2}:1 between {} and {}{} is below {}:1",
+                ratio,
+                fg_value.trim(),
+                bg_value.trim(),
+                theme_note,
+                required
+            ),
+        ));
+    }
+}
+
+fn parse_px(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(px) = value.strip_suffix("px") {
+        px.trim().parse().ok()
+    } else if let Some(rem) = value.strip_suffix("rem") {
+        rem.trim().parse::<f64>().ok().map(|r| r * 16.0)
+    } else {
+        None
+    }
+}
+
+/// Parse an opaque CSS color (hex, rgb(), hsl() or a basic named color).
+/// Translucent colors return `None` since their contrast depends on the backdrop.
+pub fn parse_color(value: &str) -> Option<[u8; 3]> {
+    // `background` shorthand: take the first token that parses as a color
+    let value = value.trim().to_lowercase();
+    if !value.contains('(') && value.contains(' ') {
+        return value.split_whitespace().find_map(parse_color);
+    }
+
+    if let Some(hex) = value.strip_prefix('#') {
+        // Channels are sliced by byte, which is only safe on ASCII digits
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let expanded: String = match hex.len() {
+            3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => hex.to_string(),
+            _ => return None,
+        };
+        let channel = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).ok();
+        if expanded.len() == 8 && channel(6)? != 255 {
+            return None;
+        }
+        return Some([channel(0)?, channel(2)?, channel(4)?]);
+    }
+
+    if let Some(args) = function_args(&value, &["rgb", "rgba"]) {
+        if args.len() == 4 && parse_alpha(&args[3])? < 1.0 {
+            return None;
+        }
+        let channel = |s: &str| -> Option<u8> {
+            let v = match s.strip_suffix('%') {
+                Some(p) => p.parse::<f64>().ok()? * 2.55,
+                None => s.parse::<f64>().ok()?,
+            };
+            Some(v.round().clamp(0.0, 255.0) as u8)
+        };
+        return Some([channel(&args[0])?, channel(&args[1])?, channel(&args[2])?]);
+    }
+
+    if let Some(args) = function_args(&value, &["hsl", "hsla"]) {
+        if args.len() == 4 && parse_alpha(&args[3])? < 1.0 {
+            return None;
+        }
+        let h = args[0].trim_end_matches("deg").parse::<f64>().ok()?;
+        let s = args[1].trim_end_matches('%').parse::<f64>().ok()? / 100.0;
+        let l = args[2].trim_end_matches('%').parse::<f64>().ok()? / 100.0;
+        return Some(hsl_to_rgb(h, s, l));
+    }
+
+    let named = match value.as_str() {
+        "black" => [0, 0, 0],
+        "white" => [255, 255, 255],
+        "red" => [255, 0, 0],
+        "green" => [0, 128, 0],
+        "blue" => [0, 0, 255],
+        "yellow" => [255, 255, 0],
+        "orange" => [255, 165, 0],
+        "purple" => [128, 0, 128],
+        "gray" | "grey" => [128, 128, 128],
+        "silver" => [192, 192, 192],
+        "lightgray" | "lightgrey" => [211, 211, 211],
+        "darkgray" | "darkgrey" => [169, 169, 169],
+        "navy" => [0, 0, 128],
+        "teal" => [0, 128, 128],
+        "maroon" => [128, 0, 0],
+        _ => return None,
+    };
+    Some(named)
+}
+
+/// Split `name(a, b, c / d)` into its arguments if `name` is one of `names`
+fn function_args(value: &str, names: &[&str]) -> Option<Vec<String>> {
+    let open = value.find('(')?;
+    if !names.contains(&&value[..open]) || !value.ends_with(')') {
+        return None;
+    }
+    let inner = &value[open + 1..value.len() - 1];
+    let args: Vec<String> = inner
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect();
+    (3..=4).contains(&args.len()).then_some(args)
+}
+
+fn parse_alpha(value: &str) -> Option<f64> {
+    match value.strip_suffix('%') {
+        Some(p) => p.parse::<f64>().ok().map(|v| v / 100.0),
+        None => value.parse().ok(),
+    }
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> [u8; 3] {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let to_u8 = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    [to_u8(r), to_u8(g), to_u8(b)]
+}
+
+fn relative_luminance([r, g, b]: [u8; 3]) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// WCAG contrast ratio between two colors (1.0 - 21.0)
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(report: &A11yReport) -> Vec<A11yRule> {
+        report.issues.iter().map(|i| i.rule).collect()
+    }
+
+    #[test]
+    fn test_accessible_markup_is_clean() {
+        let html = r#"
+<main>
+  <h1>Account</h1>
+  <h2>Sign in</h2>
+  <form>
+    <label for="email">Email</label>
+    <input id="email" type="email">
+    <label>Password <input type="password"></label>
+    <button type="submit" class="btn"><img src="go.svg" alt="Sign in"></button>
+  </form>
+  <img src="divider.png" alt="">
+</main>"#;
+        let css = ".btn { color: #ffffff; background-color: #1d4ed8; }";
+
+        let report = A11yAuditor::new().audit_source(html, Some(css)).unwrap();
+        assert!(report.is_clean(), "{:?}", report.describe_issues());
+        assert_eq!(report.score, 1.0);
+    }
+
+    #[test]
+    fn test_detects_common_failures() {
+        let html = r#"
+<div>
+  <h1>Title</h1>
+  <h4>Skipped</h4>
+  <img src="logo.png">
+  <button class="icon-btn"><svg aria-hidden="true"></svg></button>
+  <input type="text" placeholder="Search">
+  <div role="buton" aria-lable="x" tabindex="3">Menu</div>
+</div>"#;
+
+        let report = A11yAuditor::new().audit_source(html, None).unwrap();
+        let found = rules(&report);
+
+        for rule in [
+            A11yRule::HeadingOrder,
+            A11yRule::ImageAlt,
+            A11yRule::ButtonName,
+            A11yRule::Label,
+            A11yRule::AriaValidRole,
+            A11yRule::AriaValidAttr,
+            A11yRule::Tabindex,
+        ] {
+            assert!(found.contains(&rule), "missing {:?} in {:?}", rule, report.describe_issues());
+        }
+        assert_eq!(report.issues[0].impact, A11yImpact::Critical);
+        assert!(report.score < 0.75);
+    }
+
+    #[test]
+    fn test_parse_color_rejects_non_hex_digits() {
+        assert_eq!(parse_color("#0af"), Some([0, 170, 255]));
+        assert_eq!(parse_color("#336699ff"), Some([51, 102, 153]));
+        // 6 bytes, so slicing by byte would split the `é`
+        assert_eq!(parse_color("#aébc"), None);
+        assert_eq!(parse_color("#12345g"), None);
+    }
+
+    #[test]
+    fn test_contrast_resolved_from_tokens() {
+        let html = r#"<p class="muted">Fine print</p>"#;
+        let css = r#"
+:root { --gray-400: #9ca3af; --text-muted: var(--gray-400); --surface: #ffffff; }
+[data-theme="dark"] { --surface: #111827; }
+.muted { color: var(--text-muted); }
+p { background-color: var(--surface); }
+"#;
+
+        let report = A11yAuditor::new().audit_source(html, Some(css)).unwrap();
+        let contrast: Vec<&A11yIssue> = report
+            .issues
+            .iter()
+            .filter(|i| i.rule == A11yRule::ColorContrast)
+            .collect();
+
+        // #9ca3af on white fails AA; on the dark surface it passes
+        assert_eq!(contrast.len(), 1, "{:?}", report.describe_issues());
+        assert_eq!(contrast[0].element, ".muted / p");
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 0.01);
+    }
+}
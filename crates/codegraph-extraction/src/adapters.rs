@@ -0,0 +1,640 @@
+//! Framework input adapters - Vue SFC, Svelte and Angular components
+//!
+//! Splits single-file components into the template/script/style strings the
+//! pipeline parsers expect, and rewrites framework control flow that is not
+//! markup (`{#if}`/`{#each}` in Svelte, `@if`/`@for` in Angular) into
+//! `<template>` wrappers so tree-sitter-html sees a proper tree. After
+//! parsing, [`extract_directives`] unwraps those wrappers (plus Vue
+//! `<template v-if>` and Angular `<ng-container>`), turning event directives
+//! (`@click`, `(click)`, `on:click`) into [`EventHandler`]s and recording
+//! conditional/repeated structure (`v-if`, `*ngFor`, `{#each}`).
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+use crate::html::{HtmlElement, HtmlStructure};
+use crate::javascript::EventHandler;
+use crate::pipeline::ExtractionInput;
+
+static BLOCK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<(script|style)\b([^>]*)>(.*?)</(?:script|style)\s*>").unwrap());
+
+static LANG_ATTR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)\blang\s*=\s*["']?([\w-]+)"#).unwrap());
+
+static SCOPED_ATTR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bscoped\b").unwrap());
+
+/// `:deep(.x)`, `::v-deep(.x)`, `:global(.x)` → `.x`
+static SCOPE_PSEUDO_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"::?(?:deep|v-deep|global|slotted)\(([^)]*)\)").unwrap());
+
+/// Legacy `>>>`, `/deep/` and bare `::v-deep` combinators
+static DEEP_COMBINATOR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*(?:>>>|/deep/|::v-deep)\s*").unwrap());
+
+static SVELTE_BLOCK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([#:/])(\w+)(?:\s+([^}]*))?\}").unwrap());
+
+static SVELTE_ATTR_EXPR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(\s[\w:|.-]+)=\{([^}]*)\}"#).unwrap());
+
+static ANGULAR_BLOCK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^@(else if|if|else|for|switch|case|default|defer|placeholder|loading|error|empty)\s*(?:\(((?:[^()]|\([^()]*\))*)\))?\s*\{")
+        .unwrap()
+});
+
+static ANGULAR_TEMPLATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)\btemplate\s*:\s*`(.*?)`").unwrap());
+
+static ANGULAR_STYLES_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)\bstyles\s*:\s*\[?\s*`(.*?)`").unwrap());
+
+static HANDLER_NAME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([A-Za-z_$][\w$.]*)\s*(?:\(.*\))?;?$").unwrap());
+
+/// Attribute names used on synthetic `<template>` wrappers
+const DIRECTIVE_ATTR: &str = "data-directive";
+const EXPRESSION_ATTR: &str = "data-expression";
+
+/// Supported component frameworks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Framework {
+    Vue,
+    Svelte,
+    Angular,
+}
+
+impl Framework {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Vue => "vue",
+            Self::Svelte => "svelte",
+            Self::Angular => "angular",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "vue" => Some(Self::Vue),
+            "svelte" => Some(Self::Svelte),
+            "angular" | "ng" => Some(Self::Angular),
+            _ => None,
+        }
+    }
+}
+
+/// A framework component split into pipeline inputs
+#[derive(Debug, Clone)]
+pub struct ComponentSource {
+    pub framework: Framework,
+    /// Markup ready for the HTML parser
+    pub template: String,
+    pub script: Option<String>,
+    /// Script language (`ts`), if declared
+    pub script_lang: Option<String>,
+    pub style: Option<String>,
+    /// Style language (`scss`, `less`), if declared
+    pub style_lang: Option<String>,
+    /// Whether any style block was component-scoped
+    pub scoped: bool,
+}
+
+impl ComponentSource {
+    /// Split a Vue single-file component (`<template>`, `<script>`, `<style>`)
+    pub fn from_vue(source: &str) -> Self {
+        let blocks = split_blocks(source);
+        let template = outer_template(&blocks.rest).unwrap_or_default();
+
+        Self {
+            framework: Framework::Vue,
+            template: template.trim().to_string(),
+            script: blocks.script,
+            script_lang: blocks.script_lang,
+            style: blocks.style.map(|s| unscope_selectors(&s)),
+            style_lang: blocks.style_lang,
+            scoped: blocks.scoped,
+        }
+    }
+
+    /// Split a Svelte component; everything outside `<script>`/`<style>` is markup
+    pub fn from_svelte(source: &str) -> Self {
+        let blocks = split_blocks(source);
+
+        Self {
+            framework: Framework::Svelte,
+            template: preprocess_svelte(blocks.rest.trim()),
+            script: blocks.script,
+            script_lang: blocks.script_lang,
+            style: blocks.style.map(|s| unscope_selectors(&s)),
+            style_lang: blocks.style_lang,
+            // Svelte styles are always component-scoped
+            scoped: true,
+        }
+    }
+
+    /// Pair an Angular component class with its template and styles.
+    ///
+    /// `template`/`styles` are the contents of `templateUrl`/`styleUrls`; when
+    /// absent, inline `template:`/`styles:` from the decorator are used.
+    pub fn from_angular(component: &str, template: Option<&str>, styles: Option<&str>) -> Self {
+        let template = template
+            .map(String::from)
+            .or_else(|| ANGULAR_TEMPLATE_RE.captures(component).map(|c| c[1].to_string()))
+            .unwrap_or_default();
+        let style = styles
+            .map(String::from)
+            .or_else(|| ANGULAR_STYLES_RE.captures(component).map(|c| c[1].to_string()));
+
+        Self {
+            framework: Framework::Angular,
+            template: preprocess_angular(template.trim()),
+            script: Some(component.to_string()),
+            script_lang: Some("ts".to_string()),
+            style: style.map(|s| unscope_selectors(&s)),
+            style_lang: None,
+            // Emulated view encapsulation is the Angular default
+            scoped: true,
+        }
+    }
+}
+
+impl From<ComponentSource> for ExtractionInput {
+    fn from(component: ComponentSource) -> Self {
+        let mut input = ExtractionInput::new(component.template).with_framework(component.framework);
+        if let Some(script) = component.script {
+            input = input.with_js(script);
+        }
+        if let Some(style) = component.style {
+            input = input.with_css(style);
+        }
+        if let Some(lang) = component.style_lang {
+            input = input.with_style_lang(lang);
+        }
+        input
+    }
+}
+
+struct Blocks {
+    script: Option<String>,
+    script_lang: Option<String>,
+    style: Option<String>,
+    style_lang: Option<String>,
+    scoped: bool,
+    /// Source with script/style blocks removed
+    rest: String,
+}
+
+/// Pull out all `<script>`/`<style>` blocks, concatenating repeats
+/// (`<script>` + `<script setup>`, multiple `<style>` blocks)
+fn split_blocks(source: &str) -> Blocks {
+    let mut scripts = Vec::new();
+    let mut styles = Vec::new();
+    let mut script_lang = None;
+    let mut style_lang = None;
+    let mut scoped = false;
+
+    for caps in BLOCK_RE.captures_iter(source) {
+        let attrs = &caps[2];
+        let lang = LANG_ATTR_RE.captures(attrs).map(|c| c[1].to_lowercase());
+        let body = caps[3].trim().to_string();
+        if caps[1].eq_ignore_ascii_case("script") {
+            script_lang = script_lang.or(lang);
+            scripts.push(body);
+        } else {
+            style_lang = style_lang.or(lang);
+            scoped |= SCOPED_ATTR_RE.is_match(attrs);
+            styles.push(body);
+        }
+    }
+
+    let join = |parts: Vec<String>| (!parts.is_empty()).then(|| parts.join("\n\n"));
+    Blocks {
+        script: join(scripts),
+        script_lang,
+        style: join(styles),
+        style_lang,
+        scoped,
+        rest: BLOCK_RE.replace_all(source, "").into_owned(),
+    }
+}
+
+/// Contents of the outermost `<template>` (which may contain nested templates)
+fn outer_template(source: &str) -> Option<&str> {
+    let open = source.find("<template")?;
+    let start = open + source[open..].find('>')? + 1;
+    let end = source.rfind("</template")?;
+    (end >= start).then(|| &source[start..end])
+}
+
+/// Reduce scoped-style selectors to plain selectors the CSS parser understands
+fn unscope_selectors(css: &str) -> String {
+    let css = SCOPE_PSEUDO_RE.replace_all(css, " $1");
+    DEEP_COMBINATOR_RE.replace_all(&css, " ").into_owned()
+}
+
+fn template_open(directive: &str, expression: &str) -> String {
+    format!(
+        r#"<template {}="{}" {}="{}">"#,
+        DIRECTIVE_ATTR,
+        directive,
+        EXPRESSION_ATTR,
+        expression.trim().replace('"', "'")
+    )
+}
+
+/// Rewrite Svelte logic blocks as `<template>` wrappers and quote `attr={expr}`
+fn preprocess_svelte(markup: &str) -> String {
+    let markup = SVELTE_ATTR_EXPR_RE.replace_all(markup, |caps: &Captures| {
+        format!(r#"{}="{}""#, &caps[1], caps[2].trim().replace('"', "'"))
+    });
+
+    SVELTE_BLOCK_RE
+        .replace_all(&markup, |caps: &Captures| {
+            let kind = &caps[2];
+            let expression = caps.get(3).map(|m| m.as_str()).unwrap_or("");
+            match &caps[1] {
+                "#" => template_open(&format!("{{#{}}}", kind), expression),
+                ":" => {
+                    // `{:else if cond}` arrives as kind `else` + expression `if cond`
+                    let (directive, expression) = match expression.strip_prefix("if ") {
+                        Some(cond) if kind == "else" => ("{:else if}".to_string(), cond),
+                        _ => (format!("{{:{}}}", kind), expression),
+                    };
+                    format!("</template>{}", template_open(&directive, expression))
+                }
+                _ => "</template>".to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Rewrite Angular built-in control flow (`@if (...) { }`) as `<template>` wrappers
+fn preprocess_angular(markup: &str) -> String {
+    let mut out = String::with_capacity(markup.len());
+    let mut open_blocks = 0usize;
+    let mut rest = markup;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") {
+            // Interpolation: copy through the closing braces untouched
+            let end = rest.find("}}").map(|i| i + 2).unwrap_or(rest.len());
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if c == '@' {
+            if let Some(caps) = ANGULAR_BLOCK_RE.captures(rest) {
+                let expression = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+                out.push_str(&template_open(&format!("@{}", &caps[1]), expression));
+                open_blocks += 1;
+                rest = &rest[caps[0].len()..];
+                continue;
+            }
+        }
+        if c == '}' && open_blocks > 0 {
+            out.push_str("</template>");
+            open_blocks -= 1;
+            rest = &rest[1..];
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
+/// What a template directive does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectiveKind {
+    Event,
+    Conditional,
+    Repeat,
+}
+
+/// A framework directive found in a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateDirective {
+    pub kind: DirectiveKind,
+    /// Directive attribute name (`v-if`, `*ngfor`, `{#each}`, `@click.prevent`)
+    pub directive: String,
+    pub expression: String,
+    /// Short element descriptor (e.g., `li.item`)
+    pub element: String,
+    /// Index of the root element the directive belongs to
+    pub root_index: usize,
+    /// Depth of the element within that root (0 = the root itself)
+    pub depth: usize,
+}
+
+/// Directives and event handlers found in a framework template
+#[derive(Debug, Clone, Default)]
+pub struct TemplateDirectives {
+    pub directives: Vec<TemplateDirective>,
+    pub event_handlers: Vec<EventHandler>,
+}
+
+/// Unwrap non-rendering wrappers and collect directives from a parsed template
+pub fn extract_directives(html: &mut HtmlStructure, framework: Framework) -> TemplateDirectives {
+    let elements = std::mem::take(&mut html.elements);
+    html.elements = unwrap_wrappers(elements, framework);
+
+    let mut tags = Vec::new();
+    let mut result = TemplateDirectives::default();
+    for (root_index, root) in html.elements.iter().enumerate() {
+        collect_directives(root, root_index, 0, &mut tags, &mut result);
+    }
+    tags.sort();
+    tags.dedup();
+    html.tags = tags;
+
+    result
+}
+
+fn is_wrapper(element: &HtmlElement, framework: Framework) -> bool {
+    match framework {
+        Framework::Angular => matches!(element.tag.as_str(), "template" | "ng-container" | "ng-template"),
+        Framework::Vue | Framework::Svelte => element.tag == "template",
+    }
+}
+
+/// Replace wrapper elements with their children, moving the wrapper's
+/// directives onto each child
+fn unwrap_wrappers(elements: Vec<HtmlElement>, framework: Framework) -> Vec<HtmlElement> {
+    let mut out = Vec::new();
+    for mut element in elements {
+        element.children = unwrap_wrappers(std::mem::take(&mut element.children), framework);
+        if !is_wrapper(&element, framework) {
+            out.push(element);
+            continue;
+        }
+
+        let carried = wrapper_directives(&element, framework);
+        for mut child in element.children {
+            let mut attributes = carried.clone();
+            attributes.append(&mut child.attributes);
+            child.attributes = attributes;
+            shift_depth(&mut child);
+            out.push(child);
+        }
+    }
+    out
+}
+
+fn wrapper_directives(element: &HtmlElement, framework: Framework) -> Vec<(String, String)> {
+    let value = |name: &str| {
+        element
+            .attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+    };
+    if let Some(directive) = value(DIRECTIVE_ATTR) {
+        return vec![(directive, value(EXPRESSION_ATTR).unwrap_or_default())];
+    }
+    element
+        .attributes
+        .iter()
+        .filter(|(name, _)| classify(name, framework).is_some())
+        .cloned()
+        .collect()
+}
+
+fn shift_depth(element: &mut HtmlElement) {
+    element.depth = element.depth.saturating_sub(1);
+    for child in &mut element.children {
+        shift_depth(child);
+    }
+}
+
+fn collect_directives(
+    element: &HtmlElement,
+    root_index: usize,
+    depth: usize,
+    tags: &mut Vec<String>,
+    out: &mut TemplateDirectives,
+) {
+    tags.push(element.tag.clone());
+    let descriptor = describe(element);
+
+    for (name, value) in &element.attributes {
+        let Some(kind) = classify_any(name) else {
+            continue;
+        };
+        if kind == DirectiveKind::Event {
+            out.event_handlers.push(EventHandler {
+                event_type: event_type(name),
+                handler_name: HANDLER_NAME_RE
+                    .captures(value.trim())
+                    .map(|c| c[1].to_string()),
+                target_selector: Some(descriptor.clone()),
+            });
+        }
+        out.directives.push(TemplateDirective {
+            kind,
+            directive: name.clone(),
+            expression: value.clone(),
+            element: descriptor.clone(),
+            root_index,
+            depth,
+        });
+    }
+
+    for child in &element.children {
+        collect_directives(child, root_index, depth + 1, tags, out);
+    }
+}
+
+/// Classify an attribute name as a directive of `framework`
+fn classify(name: &str, framework: Framework) -> Option<DirectiveKind> {
+    let kind = classify_any(name)?;
+    let matches = match framework {
+        Framework::Vue => name.starts_with("v-") || name.starts_with('@') || name.starts_with('{'),
+        Framework::Svelte => name.starts_with("on:") || name.starts_with('{'),
+        Framework::Angular => name.starts_with(['(', '*', '@']),
+    };
+    matches.then_some(kind)
+}
+
+/// Classify an attribute name as a directive of any supported framework
+fn classify_any(name: &str) -> Option<DirectiveKind> {
+    let is_event = (name.starts_with('@') && !is_angular_block(name))
+        || name.starts_with("v-on:")
+        || name.starts_with("on:")
+        || (name.starts_with('(') && name.ends_with(')'));
+    if is_event {
+        return Some(DirectiveKind::Event);
+    }
+
+    // The HTML parser lowercases attribute names (`*ngFor` → `*ngfor`)
+    match name.to_ascii_lowercase().as_str() {
+        "v-for" | "*ngfor" | "{#each}" | "@for" => Some(DirectiveKind::Repeat),
+        "v-if" | "v-else-if" | "v-else" | "v-show" | "*ngif" | "*ngswitchcase" | "*ngswitchdefault"
+        | "{#if}" | "{:else if}" | "{:else}" | "{#await}" | "{:then}" | "{:catch}" | "@if"
+        | "@else if" | "@else" | "@switch" | "@case" | "@default" | "@defer" | "@empty" => {
+            Some(DirectiveKind::Conditional)
+        }
+        _ => None,
+    }
+}
+
+fn is_angular_block(name: &str) -> bool {
+    matches!(
+        name,
+        "@if" | "@else" | "@else if" | "@for" | "@switch" | "@case" | "@default" | "@defer" | "@empty"
+            | "@placeholder" | "@loading" | "@error"
+    )
+}
+
+/// `@click.prevent` / `v-on:click` / `(keyup.enter)` / `on:click|once` → event type
+fn event_type(name: &str) -> String {
+    let name = name
+        .trim_start_matches("v-on:")
+        .trim_start_matches("on:")
+        .trim_start_matches('@')
+        .trim_start_matches('(')
+        .trim_end_matches(')');
+    name.split(['.', '|']).next().unwrap_or(name).to_string()
+}
+
+fn describe(element: &HtmlElement) -> String {
+    let mut out = element.tag.clone();
+    for class in &element.classes {
+        out.push('.');
+        out.push_str(class);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::HtmlParser;
+
+    fn parse(component: &ComponentSource) -> (HtmlStructure, TemplateDirectives) {
+        let mut html = HtmlParser::new().parse(&component.template).unwrap();
+        let directives = extract_directives(&mut html, component.framework);
+        (html, directives)
+    }
+
+    fn kinds(directives: &TemplateDirectives, kind: DirectiveKind) -> Vec<&str> {
+        directives
+            .directives
+            .iter()
+            .filter(|d| d.kind == kind)
+            .map(|d| d.directive.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_vue_sfc() {
+        let source = r#"
+<template>
+  <ul class="todo-list">
+    <template v-if="items.length">
+      <li v-for="item in items" :key="item.id" class="item" @click.prevent="select(item)">{{ item.title }}</li>
+    </template>
+    <li v-else class="empty">Nothing to do</li>
+  </ul>
+</template>
+
+<script setup lang="ts">
+const props = defineProps<{ items: Item[] }>();
+</script>
+
+<style scoped lang="scss">
+.todo-list :deep(.item) { padding: 4px; }
+</style>
+"#;
+        let component = ComponentSource::from_vue(source);
+        assert_eq!(component.script_lang.as_deref(), Some("ts"));
+        assert_eq!(component.style_lang.as_deref(), Some("scss"));
+        assert!(component.scoped);
+        assert_eq!(component.style.as_deref(), Some(".todo-list  .item { padding: 4px; }"));
+
+        let (html, directives) = parse(&component);
+        let list = &html.elements[0];
+        let tags: Vec<&str> = list.children.iter().map(|c| c.tag.as_str()).collect();
+        assert_eq!(tags, vec!["li", "li"]);
+        assert_eq!(list.children[0].depth, 1);
+
+        assert_eq!(kinds(&directives, DirectiveKind::Repeat), vec!["v-for"]);
+        assert_eq!(kinds(&directives, DirectiveKind::Conditional), vec!["v-if", "v-else"]);
+        assert_eq!(directives.event_handlers.len(), 1);
+        assert_eq!(directives.event_handlers[0].event_type, "click");
+        assert_eq!(directives.event_handlers[0].handler_name.as_deref(), Some("select"));
+    }
+
+    #[test]
+    fn test_svelte_blocks() {
+        let source = r#"
+<script>
+  export let todos = [];
+</script>
+
+{#if todos.length > 0}
+  <ul>
+    {#each todos as todo (todo.id)}
+      <li on:click|once={() => toggle(todo)}>{todo.text}</li>
+    {/each}
+  </ul>
+{:else}
+  <p class="empty">Nothing to do</p>
+{/if}
+
+<style>
+  :global(body) { margin: 0; }
+</style>
+"#;
+        let component = ComponentSource::from_svelte(source);
+        let (html, directives) = parse(&component);
+
+        let tags: Vec<&str> = html.elements.iter().map(|e| e.tag.as_str()).collect();
+        assert_eq!(tags, vec!["ul", "p"]);
+        assert_eq!(html.elements[0].depth, 0);
+        assert!(!html.tags.contains(&"template".to_string()));
+
+        assert_eq!(kinds(&directives, DirectiveKind::Conditional), vec!["{#if}", "{:else}"]);
+        assert_eq!(kinds(&directives, DirectiveKind::Repeat), vec!["{#each}"]);
+        assert_eq!(directives.directives[1].expression, "todos as todo (todo.id)");
+        assert_eq!(directives.event_handlers[0].event_type, "click");
+        assert_eq!(directives.event_handlers[0].handler_name, None);
+    }
+
+    #[test]
+    fn test_angular_component() {
+        let component_ts = r#"
+@Component({
+  selector: 'app-users',
+  template: `
+    <ng-container *ngIf="users$ | async as users">
+      <app-user-card *ngFor="let user of users" [user]="user" (select)="open(user)"></app-user-card>
+    </ng-container>
+    @if (loading) {
+      <mat-spinner></mat-spinner>
+    } @else {
+      <button mat-button (click)="reload()">Reload {{ count }}</button>
+    }
+  `,
+  styles: [`:host { display: block; }`]
+})
+export class UsersComponent {}
+"#;
+        let component = ComponentSource::from_angular(component_ts, None, None);
+        assert_eq!(component.style.as_deref(), Some(":host { display: block; }"));
+
+        let (html, directives) = parse(&component);
+        let tags: Vec<&str> = html.elements.iter().map(|e| e.tag.as_str()).collect();
+        assert_eq!(tags, vec!["app-user-card", "mat-spinner", "button"]);
+
+        assert_eq!(kinds(&directives, DirectiveKind::Repeat), vec!["*ngfor"]);
+        assert_eq!(kinds(&directives, DirectiveKind::Conditional), vec!["*ngif", "@if", "@else"]);
+        let events: Vec<(&str, Option<&str>)> = directives
+            .event_handlers
+            .iter()
+            .map(|h| (h.event_type.as_str(), h.handler_name.as_deref()))
+            .collect();
+        assert_eq!(events, vec![("select", Some("open")), ("click", Some("reload"))]);
+    }
+}
@@ -5,14 +5,19 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use tree_sitter::{Node, Parser};
 
+use crate::scss::{self, StyleSyntax};
+
 static CSS_LANGUAGE: Lazy<tree_sitter::Language> = Lazy::new(|| tree_sitter_css::LANGUAGE.into());
 
-/// CSS custom property (design token)
+/// CSS custom property or SCSS/Less variable (design token)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DesignToken {
     pub name: String,
     pub value: String,
     pub category: TokenCategory,
+    /// Theme the token is declared in (`None` for the default `:root` theme)
+    #[serde(default)]
+    pub theme: Option<String>,
 }
 
 /// Token category for design tokens
@@ -27,11 +32,51 @@ pub enum TokenCategory {
     Other,
 }
 
+impl TokenCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Color => "color",
+            Self::Spacing => "spacing",
+            Self::Typography => "typography",
+            Self::BorderRadius => "border-radius",
+            Self::Shadow => "shadow",
+            Self::Animation => "animation",
+            Self::Other => "other",
+        }
+    }
+
+    /// Parse a category name produced by [`TokenCategory::as_str`]
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "color" => Self::Color,
+            "spacing" => Self::Spacing,
+            "typography" => Self::Typography,
+            "border-radius" => Self::BorderRadius,
+            "shadow" => Self::Shadow,
+            "animation" => Self::Animation,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// CSS rule with selector and properties
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CssRule {
     pub selector: String,
     pub properties: Vec<CssProperty>,
+    /// Theme context the rule applies to (`None` for the default theme)
+    #[serde(default)]
+    pub theme: Option<String>,
+}
+
+/// A mixin applied inside a rule (SCSS `@include`, Less `.mixin()`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MixinUsage {
+    pub name: String,
+    /// Raw argument list, without parentheses
+    pub arguments: Option<String>,
+    /// Flattened selector of the rule including the mixin
+    pub selector: Option<String>,
 }
 
 /// CSS property declaration
@@ -46,12 +91,15 @@ pub struct CssProperty {
 pub struct CssStructure {
     /// All CSS rules
     pub rules: Vec<CssRule>,
-    /// Detected design tokens (CSS custom properties)
+    /// Detected design tokens (custom properties, SCSS/Less variables)
     pub design_tokens: Vec<DesignToken>,
     /// All unique property names
     pub properties: Vec<String>,
     /// All selectors used
     pub selectors: Vec<String>,
+    /// Mixins included by rules (SCSS/Less only)
+    #[serde(default)]
+    pub mixins: Vec<MixinUsage>,
 }
 
 /// CSS parser using tree-sitter
@@ -59,6 +107,8 @@ pub struct CssParser {
     parser: Parser,
     color_re: Regex,
     spacing_re: Regex,
+    theme_attr_re: Regex,
+    theme_class_re: Regex,
 }
 
 impl Default for CssParser {
@@ -78,6 +128,8 @@ impl CssParser {
             parser,
             color_re: Regex::new(r"(?i)(color|background|border-color|fill|stroke)").unwrap(),
             spacing_re: Regex::new(r"(?i)(margin|padding|gap|space|size|width|height)").unwrap(),
+            theme_attr_re: Regex::new(r#"\[data-(?:bs-)?theme\s*=\s*["']?([\w-]+)["']?\s*\]"#).unwrap(),
+            theme_class_re: Regex::new(r"\.(?:theme-)?(dark|light)\b").unwrap(),
         }
     }
 
@@ -94,7 +146,7 @@ impl CssParser {
         let mut all_properties = Vec::new();
         let mut all_selectors = Vec::new();
 
-        self.extract_rules(root, css, &mut rules, &mut design_tokens, &mut all_properties, &mut all_selectors);
+        self.extract_rules(root, css, None, &mut rules, &mut design_tokens, &mut all_properties, &mut all_selectors);
 
         all_properties.sort();
         all_properties.dedup();
@@ -106,13 +158,27 @@ impl CssParser {
             design_tokens,
             properties: all_properties,
             selectors: all_selectors,
+            mixins: Vec::new(),
         })
     }
 
+    /// Parse a stylesheet written in `lang` (`css`, `scss`, `sass`, `less`).
+    ///
+    /// Preprocessor sources are flattened into the same [`CssStructure`]
+    /// shape; see [`crate::scss`].
+    pub fn parse_lang(&mut self, source: &str, lang: Option<&str>) -> anyhow::Result<CssStructure> {
+        match StyleSyntax::from_lang(lang) {
+            StyleSyntax::Css => self.parse(source),
+            syntax => Ok(scss::flatten(self, source, syntax)),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn extract_rules(
         &self,
         node: Node,
         source: &str,
+        media_theme: Option<&str>,
         rules: &mut Vec<CssRule>,
         tokens: &mut Vec<DesignToken>,
         all_properties: &mut Vec<String>,
@@ -120,15 +186,23 @@ impl CssParser {
     ) {
         match node.kind() {
             "rule_set" => {
-                if let Some(rule) = self.extract_rule(node, source, tokens, all_properties) {
+                if let Some(rule) = self.extract_rule(node, source, media_theme, tokens, all_properties) {
                     all_selectors.push(rule.selector.clone());
                     rules.push(rule);
                 }
             }
+            "media_statement" => {
+                // `@media (prefers-color-scheme: dark)` scopes nested rules to a theme
+                let theme = self.theme_from_media(node, source).or(media_theme.map(str::to_string));
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.extract_rules(child, source, theme.as_deref(), rules, tokens, all_properties, all_selectors);
+                }
+            }
             _ => {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
-                    self.extract_rules(child, source, rules, tokens, all_properties, all_selectors);
+                    self.extract_rules(child, source, media_theme, rules, tokens, all_properties, all_selectors);
                 }
             }
         }
@@ -138,20 +212,25 @@ impl CssParser {
         &self,
         node: Node,
         source: &str,
+        media_theme: Option<&str>,
         tokens: &mut Vec<DesignToken>,
         all_properties: &mut Vec<String>,
     ) -> Option<CssRule> {
         let mut selector = String::new();
         let mut properties = Vec::new();
+        let mut theme = media_theme.map(str::to_string);
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             match child.kind() {
                 "selectors" => {
                     selector = self.node_text(child, source).trim().to_string();
+                    if let Some(selector_theme) = self.theme_from_selector(&selector) {
+                        theme = Some(selector_theme);
+                    }
                 }
                 "block" => {
-                    self.extract_declarations(child, source, &mut properties, tokens, all_properties);
+                    self.extract_declarations(child, source, theme.as_deref(), &mut properties, tokens, all_properties);
                 }
                 _ => {}
             }
@@ -161,13 +240,43 @@ impl CssParser {
             return None;
         }
 
-        Some(CssRule { selector, properties })
+        Some(CssRule { selector, properties, theme })
+    }
+
+    /// Detect a theme from selectors like `[data-theme="dark"]`, `.dark` or `.theme-light`
+    pub(crate) fn theme_from_selector(&self, selector: &str) -> Option<String> {
+        if let Some(caps) = self.theme_attr_re.captures(selector) {
+            return Some(caps[1].to_lowercase());
+        }
+        self.theme_class_re
+            .captures(selector)
+            .map(|caps| caps[1].to_lowercase())
+    }
+
+    /// Detect a theme from `@media (prefers-color-scheme: ...)` queries
+    fn theme_from_media(&self, node: Node, source: &str) -> Option<String> {
+        let text = self.node_text(node, source);
+        self.theme_from_media_query(&text[..text.find('{').unwrap_or(text.len())])
+    }
+
+    /// Theme selected by a media query prelude, if any
+    pub(crate) fn theme_from_media_query(&self, query: &str) -> Option<String> {
+        let compact: String = query.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+
+        if compact.contains("prefers-color-scheme:dark") {
+            Some("dark".to_string())
+        } else if compact.contains("prefers-color-scheme:light") {
+            Some("light".to_string())
+        } else {
+            None
+        }
     }
 
     fn extract_declarations(
         &self,
         node: Node,
         source: &str,
+        theme: Option<&str>,
         properties: &mut Vec<CssProperty>,
         tokens: &mut Vec<DesignToken>,
         all_properties: &mut Vec<String>,
@@ -185,6 +294,7 @@ impl CssParser {
                             name: name.clone(),
                             value: value.clone(),
                             category,
+                            theme: theme.map(str::to_string),
                         });
                     }
 
@@ -226,7 +336,7 @@ impl CssParser {
         }
     }
 
-    fn categorize_token(&self, name: &str, value: &str) -> TokenCategory {
+    pub(crate) fn categorize_token(&self, name: &str, value: &str) -> TokenCategory {
         let name_lower = name.to_lowercase();
         let value_lower = value.to_lowercase();
 
@@ -275,4 +385,27 @@ mod tests {
         assert!(result.design_tokens.iter().any(|t| t.name == "--color-primary"));
         assert!(result.selectors.contains(&".btn".to_string()));
     }
+
+    #[test]
+    fn test_token_themes() {
+        let mut parser = CssParser::new();
+        let css = r#"
+            :root { --bg: #ffffff; }
+            [data-theme="dark"] { --bg: #111827; }
+            @media (prefers-color-scheme: dark) {
+                :root { --fg: #f9fafb; }
+            }
+        "#;
+
+        let result = parser.parse(css).unwrap();
+        let themes: Vec<_> = result
+            .design_tokens
+            .iter()
+            .map(|t| (t.name.as_str(), t.theme.as_deref()))
+            .collect();
+
+        assert!(themes.contains(&("--bg", None)));
+        assert!(themes.contains(&("--bg", Some("dark"))));
+        assert!(themes.contains(&("--fg", Some("dark"))));
+    }
 }
@@ -31,6 +31,21 @@ impl DesignSystemType {
             Self::Unknown => "unknown",
         }
     }
+
+    /// Parse a design system name, accepting [`DesignSystemType::as_str`]
+    /// names and common aliases (`mui`, `antd`, `chakra-ui`, ...)
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "material-ui" | "materialui" | "mui" | "material" => Self::MaterialUI,
+            "tailwind" | "tailwindcss" | "tailwind-css" => Self::Tailwind,
+            "chakra" | "chakra-ui" | "chakraui" => Self::Chakra,
+            "bootstrap" | "react-bootstrap" => Self::Bootstrap,
+            "ant-design" | "antdesign" | "antd" | "ant" => Self::AntDesign,
+            "shadcn" | "shadcn-ui" | "shadcn/ui" => Self::Shadcn,
+            "custom" => Self::Custom,
+            _ => Self::Unknown,
+        }
+    }
 }
 
 /// Detection result with confidence score
@@ -331,4 +346,21 @@ mod tests {
         let result = detector.detect_from_content("", "", js);
         assert_eq!(result.design_system, DesignSystemType::MaterialUI);
     }
+
+    #[test]
+    fn test_from_name_round_trips() {
+        for ds in [
+            DesignSystemType::MaterialUI,
+            DesignSystemType::Tailwind,
+            DesignSystemType::Chakra,
+            DesignSystemType::Bootstrap,
+            DesignSystemType::AntDesign,
+            DesignSystemType::Shadcn,
+        ] {
+            assert_eq!(DesignSystemType::from_name(ds.as_str()), ds);
+        }
+        assert_eq!(DesignSystemType::from_name("MUI"), DesignSystemType::MaterialUI);
+        assert_eq!(DesignSystemType::from_name("antd"), DesignSystemType::AntDesign);
+        assert_eq!(DesignSystemType::from_name("whatever"), DesignSystemType::Unknown);
+    }
 }
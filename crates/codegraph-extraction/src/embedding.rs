@@ -27,6 +27,20 @@ impl Default for EmbeddingConfig {
     }
 }
 
+impl EmbeddingConfig {
+    /// Create config from `OPENAI_EMBEDDING_MODEL` and `OPENAI_EMBEDDING_DIMENSIONS`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            model: env::var("OPENAI_EMBEDDING_MODEL").unwrap_or(defaults.model),
+            dimensions: env::var("OPENAI_EMBEDDING_DIMENSIONS")
+                .ok()
+                .and_then(|d| d.parse().ok())
+                .unwrap_or(defaults.dimensions),
+        }
+    }
+}
+
 /// Generated embedding result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingResult {
@@ -59,7 +73,7 @@ impl EmbeddingGenerator {
 
         Self {
             client,
-            config: EmbeddingConfig::default(),
+            config: EmbeddingConfig::from_env(),
         }
     }
 
@@ -132,6 +146,10 @@ impl EmbeddingGenerator {
             if !element.classes.is_empty() {
                 parts.push(format!("classes: {}", element.classes.join(" ")));
             }
+            if !element.utilities.is_empty() {
+                let styles: Vec<String> = element.utilities.iter().map(|u| u.describe()).collect();
+                parts.push(format!("styles: {}", styles.join(", ")));
+            }
             if element.has_interactivity {
                 parts.push("interactive: yes".to_string());
             }
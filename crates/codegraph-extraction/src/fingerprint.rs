@@ -0,0 +1,246 @@
+//! Structural fingerprints and near-duplicate detection
+//!
+//! A fingerprint combines the normalized DOM shape of an element, its class
+//! set and the design tokens it consumes into a MinHash signature. Signatures
+//! estimate Jaccard similarity and are bucketed by an LSH index, so
+//! near-identical components can be found without comparing every pair.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::html::HtmlElement;
+
+/// Number of MinHash permutations per signature
+pub const SIGNATURE_LEN: usize = 64;
+
+/// LSH bands (`SIGNATURE_LEN / LSH_BANDS` rows each); candidates start around 0.5 similarity
+const LSH_BANDS: usize = 16;
+
+/// Minimum similarity for a `SIMILAR_TO` edge
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.8;
+
+/// Minimum similarity (same category) for proposing `CAN_REPLACE`
+pub const DEFAULT_REPLACE_THRESHOLD: f32 = 0.95;
+
+/// Structural fingerprint of an element subtree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// Normalized DOM shape, e.g. `button(svg,span)`
+    pub shape: String,
+    /// MinHash signature over shape paths, classes and tokens
+    pub signature: Vec<u32>,
+}
+
+impl Fingerprint {
+    /// Fingerprint an element subtree and the tokens it consumes
+    pub fn compute(element: &HtmlElement, tokens: &[String]) -> Self {
+        let mut features = HashSet::new();
+        collect_shape_paths(element, "", &mut features);
+        collect_classes(element, &mut features);
+        features.extend(tokens.iter().map(|t| format!("token:{}", t)));
+
+        Self {
+            shape: shape(element),
+            signature: minhash(&features),
+        }
+    }
+
+    /// Build from a stored signature
+    pub fn from_signature(shape: impl Into<String>, signature: Vec<u32>) -> Self {
+        Self {
+            shape: shape.into(),
+            signature,
+        }
+    }
+
+    /// Estimated Jaccard similarity of the feature sets (0.0 - 1.0)
+    pub fn similarity(&self, other: &Fingerprint) -> f32 {
+        if self.signature.len() != other.signature.len() || self.signature.is_empty() {
+            return 0.0;
+        }
+        let equal = self
+            .signature
+            .iter()
+            .zip(&other.signature)
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f32 / self.signature.len() as f32
+    }
+}
+
+/// Normalized DOM shape: tags only, children in document order
+fn shape(element: &HtmlElement) -> String {
+    if element.children.is_empty() {
+        return element.tag.to_lowercase();
+    }
+    let children: Vec<String> = element.children.iter().map(shape).collect();
+    format!("{}({})", element.tag.to_lowercase(), children.join(","))
+}
+
+/// Root-to-node tag paths (`shape:div/button/span`)
+fn collect_shape_paths(element: &HtmlElement, prefix: &str, features: &mut HashSet<String>) {
+    let path = if prefix.is_empty() {
+        element.tag.to_lowercase()
+    } else {
+        format!("{}/{}", prefix, element.tag.to_lowercase())
+    };
+    features.insert(format!("shape:{}", path));
+    for child in &element.children {
+        collect_shape_paths(child, &path, features);
+    }
+}
+
+/// Classes of the whole subtree, lowercased
+fn collect_classes(element: &HtmlElement, features: &mut HashSet<String>) {
+    features.extend(element.classes.iter().map(|c| format!("class:{}", c.to_lowercase())));
+    for child in &element.children {
+        collect_classes(child, features);
+    }
+}
+
+/// FNV-1a, stable across builds so stored signatures stay comparable
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// SplitMix64 finalizer, used to derive the permutations from one base hash
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+fn minhash(features: &HashSet<String>) -> Vec<u32> {
+    let mut signature = vec![u32::MAX; SIGNATURE_LEN];
+    for feature in features {
+        let base = fnv1a(feature.as_bytes());
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            let hash = mix(base ^ mix(seed as u64 + 1)) as u32;
+            *slot = (*slot).min(hash);
+        }
+    }
+    signature
+}
+
+/// Banded LSH index over fingerprint signatures, keyed by element ID
+#[derive(Debug)]
+pub struct LshIndex<K> {
+    buckets: HashMap<(usize, u64), Vec<K>>,
+    fingerprints: HashMap<K, Fingerprint>,
+}
+
+impl<K> Default for LshIndex<K> {
+    fn default() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            fingerprints: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash + Ord> LshIndex<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    /// Add (or replace) an element's fingerprint
+    pub fn insert(&mut self, id: K, fingerprint: Fingerprint) {
+        self.remove(id);
+        for key in band_keys(&fingerprint) {
+            self.buckets.entry(key).or_default().push(id);
+        }
+        self.fingerprints.insert(id, fingerprint);
+    }
+
+    /// Remove an element from the index
+    pub fn remove(&mut self, id: K) {
+        let Some(fingerprint) = self.fingerprints.remove(&id) else {
+            return;
+        };
+        for key in band_keys(&fingerprint) {
+            if let Some(bucket) = self.buckets.get_mut(&key) {
+                bucket.retain(|other| *other != id);
+            }
+        }
+    }
+
+    /// Indexed elements at least `threshold` similar to `fingerprint`, best first
+    pub fn query(&self, fingerprint: &Fingerprint, threshold: f32) -> Vec<(K, f32)> {
+        let mut seen = HashSet::new();
+        let mut matches: Vec<(K, f32)> = band_keys(fingerprint)
+            .filter_map(|key| self.buckets.get(&key))
+            .flatten()
+            .filter(|id| seen.insert(**id))
+            .filter_map(|id| {
+                let score = self.fingerprints[id].similarity(fingerprint);
+                (score >= threshold).then_some((*id, score))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        matches
+    }
+}
+
+fn band_keys(fingerprint: &Fingerprint) -> impl Iterator<Item = (usize, u64)> + '_ {
+    let rows = (fingerprint.signature.len() / LSH_BANDS).max(1);
+    fingerprint.signature.chunks(rows).enumerate().map(|(band, chunk)| {
+        let bytes: Vec<u8> = chunk.iter().flat_map(|v| v.to_le_bytes()).collect();
+        (band, fnv1a(&bytes))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::HtmlParser;
+
+    fn fingerprint(html: &str) -> Fingerprint {
+        let structure = HtmlParser::new().parse(html).unwrap();
+        Fingerprint::compute(&structure.elements[0], &[])
+    }
+
+    #[test]
+    fn test_fingerprint_similarity() {
+        let a = fingerprint(r#"<button class="btn btn-primary"><svg class="icon"></svg><span>Save</span></button>"#);
+        let b = fingerprint(r#"<button class="btn btn-primary"><svg class="icon"></svg><span>Submit</span></button>"#);
+        let c = fingerprint(r#"<div class="card"><img src="x.png"><h3>Title</h3><p>Body</p></div>"#);
+
+        assert_eq!(a.shape, "button(svg,span)");
+        assert_eq!(a, b, "text content is not part of the fingerprint");
+        assert_eq!(a.similarity(&b), 1.0);
+        assert!(a.similarity(&c) < 0.2);
+    }
+
+    #[test]
+    fn test_lsh_index_finds_near_duplicates() {
+        let base = fingerprint(r#"<button class="btn btn-primary rounded px-4 py-2 font-medium shadow"><span>Go</span></button>"#);
+        let near = fingerprint(r#"<button class="btn btn-primary rounded px-4 py-2 font-medium shadow-sm"><span>Go</span></button>"#);
+        let other = fingerprint(r#"<nav class="navbar"><a href="/">Home</a><a href="/about">About</a></nav>"#);
+
+        let (base_id, near_id, other_id) = (1u32, 2, 3);
+        let mut index = LshIndex::new();
+        index.insert(base_id, base.clone());
+        index.insert(other_id, other);
+
+        let matches = index.query(&near, 0.5);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0, base_id);
+        assert!(matches[0].1 > 0.6);
+
+        index.insert(near_id, near);
+        index.remove(base_id);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.query(&base, 0.5)[0].0, near_id);
+    }
+}
@@ -25,6 +25,104 @@ pub struct HtmlElement {
     pub depth: usize,
 }
 
+/// Elements serialized without a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+/// Tags users interact with directly
+const INTERACTIVE_TAGS: &[&str] = &["a", "button", "details", "dialog", "input", "select", "summary", "textarea"];
+
+/// ARIA roles implying interaction
+const INTERACTIVE_ROLES: &[&str] = &[
+    "button", "checkbox", "combobox", "link", "menuitem", "option", "radio", "slider", "switch", "tab",
+    "textbox",
+];
+
+/// ARIA attributes exposing interactive state
+const STATE_ATTRIBUTES: &[&str] = &["aria-checked", "aria-expanded", "aria-pressed", "aria-selected"];
+
+impl HtmlElement {
+    /// Framework-neutral description of how the subtree can be interacted with.
+    ///
+    /// Collects interactive tags (inputs by type), roles, ARIA state attributes
+    /// and event names from `onclick`, `@click`, `v-on:click`, `(click)`,
+    /// `on:click` and `onClick` handlers. Sorted and deduplicated, so equal
+    /// signatures mean equivalent behavior regardless of styling.
+    pub fn interaction_signature(&self) -> Vec<String> {
+        let mut signature = std::collections::BTreeSet::new();
+        self.collect_interactions(&mut signature);
+        signature.into_iter().collect()
+    }
+
+    fn collect_interactions(&self, signature: &mut std::collections::BTreeSet<String>) {
+        let tag = self.tag.to_lowercase();
+        if tag == "input" {
+            let kind = self
+                .attributes
+                .iter()
+                .find(|(name, _)| name == "type")
+                .map_or("text", |(_, value)| value.as_str());
+            signature.insert(format!("tag:input:{}", kind.to_lowercase()));
+        } else if INTERACTIVE_TAGS.contains(&tag.as_str()) {
+            signature.insert(format!("tag:{}", tag));
+        }
+
+        for (name, value) in &self.attributes {
+            // Bound attributes (`:aria-expanded`, `v-bind:role`) count as the attribute itself
+            let bound = name.strip_prefix("v-bind:").or_else(|| name.strip_prefix(':')).unwrap_or(name);
+            if bound == "role" && INTERACTIVE_ROLES.contains(&value.as_str()) {
+                signature.insert(format!("role:{}", value));
+            } else if STATE_ATTRIBUTES.contains(&bound) {
+                signature.insert(format!("state:{}", bound));
+            } else if let Some(event) = event_name(name) {
+                signature.insert(format!("event:{}", event));
+            }
+        }
+
+        for child in &self.children {
+            child.collect_interactions(signature);
+        }
+    }
+
+    /// Serialize the element and its subtree back to HTML.
+    ///
+    /// Text is emitted before child elements, since only the last text node
+    /// of an element is kept during parsing.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        self.write_html(&mut out);
+        out
+    }
+
+    fn write_html(&self, out: &mut String) {
+        out.push('<');
+        out.push_str(&self.tag);
+        for (name, value) in &self.attributes {
+            out.push(' ');
+            out.push_str(name);
+            if !value.is_empty() {
+                out.push_str("=\"");
+                out.push_str(&value.replace('"', "&quot;"));
+                out.push('"');
+            }
+        }
+        out.push('>');
+        if VOID_ELEMENTS.contains(&self.tag.as_str()) {
+            return;
+        }
+        if let Some(text) = &self.text_content {
+            out.push_str(text);
+        }
+        for child in &self.children {
+            child.write_html(out);
+        }
+        out.push_str("</");
+        out.push_str(&self.tag);
+        out.push('>');
+    }
+}
+
 /// Extracted structure from HTML
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HtmlStructure {
@@ -38,6 +136,15 @@ pub struct HtmlStructure {
     pub ids: Vec<String>,
 }
 
+impl HtmlStructure {
+    /// Element at a child-index path (`[root, child, ...]`)
+    pub fn element_at(&self, path: &[usize]) -> Option<&HtmlElement> {
+        let (first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.elements.get(*first)?, |element, index| element.children.get(*index))
+    }
+}
+
 /// HTML parser using tree-sitter
 pub struct HtmlParser {
     parser: Parser,
@@ -223,6 +330,19 @@ impl HtmlParser {
     }
 }
 
+/// Event name of a handler attribute in any supported template syntax
+fn event_name(attribute: &str) -> Option<String> {
+    let event = attribute
+        .strip_prefix("v-on:")
+        .or_else(|| attribute.strip_prefix('@'))
+        .or_else(|| attribute.strip_prefix("on:"))
+        .or_else(|| attribute.strip_prefix('(').and_then(|a| a.strip_suffix(')')))
+        .or_else(|| attribute.strip_prefix("on").filter(|rest| rest.len() > 2))?;
+    // Drop modifiers (`@click.prevent`, `(keyup.enter)`)
+    let event = event.split('.').next().unwrap_or(event);
+    (!event.is_empty()).then(|| event.to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +360,32 @@ mod tests {
         assert!(result.classes.contains(&"btn".to_string()));
         assert!(result.ids.contains(&"submit".to_string()));
     }
+
+    #[test]
+    fn test_to_html_round_trip() {
+        let mut parser = HtmlParser::new();
+        let html = r#"<form class="login"><label for="u">User</label><input id="u" type="text" required><button class="btn">Go</button></form>"#;
+
+        let result = parser.parse(html).unwrap();
+        assert_eq!(result.elements[0].to_html(), html);
+    }
+
+    #[test]
+    fn test_interaction_signature() {
+        let mut parser = HtmlParser::new();
+        let vue = parser
+            .parse(r#"<div class="v-toggle"><button @click.prevent="toggle" :aria-expanded="open">Menu</button></div>"#)
+            .unwrap();
+        let html = parser
+            .parse(r#"<div class="dropdown"><button class="btn" onclick="toggle()" aria-expanded="false">Menu</button></div>"#)
+            .unwrap();
+        let form = parser.parse(r#"<form><input type="email"><input></form>"#).unwrap();
+
+        assert_eq!(
+            html.elements[0].interaction_signature(),
+            vec!["event:click", "state:aria-expanded", "tag:button"]
+        );
+        assert_eq!(vue.elements[0].interaction_signature(), html.elements[0].interaction_signature());
+        assert_eq!(form.elements[0].interaction_signature(), vec!["tag:input:email", "tag:input:text"]);
+    }
 }
@@ -0,0 +1,453 @@
+//! JSX/TSX parser - extracts component trees from React code
+//!
+//! Uses the tree-sitter TSX grammar (a superset of JSX) and turns JSX elements
+//! into [`HtmlElement`] trees so the rest of the pipeline can treat React
+//! components like markup. Component names (`Button`, `Card.Header`) are kept
+//! as the tag, with aliased imports (`import { Card as C }`) resolved to the
+//! imported name, so `<C.Header>` becomes `Card.Header`. Props become attributes (`className` → `class`, `htmlFor` →
+//! `for`, expression props like `sx={{ p: 2 }}` keep their source text), and
+//! elements returned from expressions such as `items.map(...)` are nested as
+//! children.
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Parser};
+
+use crate::html::{HtmlElement, HtmlStructure};
+use crate::javascript::{EventHandler, JsImport};
+
+static TSX_LANGUAGE: Lazy<tree_sitter::Language> =
+    Lazy::new(|| tree_sitter_typescript::LANGUAGE_TSX.into());
+
+/// Extracted JSX/TSX structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsxStructure {
+    /// JSX element trees as HTML-equivalent elements
+    pub html: HtmlStructure,
+    /// Unique component names used (capitalized JSX tags, aliases resolved)
+    pub components: Vec<String>,
+    /// Import statements
+    pub imports: Vec<JsImport>,
+    /// Event handler props (`onClick={save}`)
+    pub event_handlers: Vec<EventHandler>,
+}
+
+/// JSX/TSX parser using tree-sitter
+pub struct JsxParser {
+    parser: Parser,
+}
+
+impl Default for JsxParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Accumulates element-wide lists while walking the tree
+#[derive(Default)]
+struct Collected {
+    /// Imported name of each aliased local binding (`C` → `Card`)
+    aliases: HashMap<String, String>,
+    tags: Vec<String>,
+    classes: Vec<String>,
+    ids: Vec<String>,
+    components: Vec<String>,
+    event_handlers: Vec<EventHandler>,
+}
+
+impl JsxParser {
+    pub fn new() -> Self {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&TSX_LANGUAGE)
+            .expect("Failed to set TSX language");
+        Self { parser }
+    }
+
+    /// Parse JSX/TSX source and extract component trees and imports
+    pub fn parse(&mut self, source: &str) -> anyhow::Result<JsxStructure> {
+        let tree = self
+            .parser
+            .parse(source, None)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse JSX"))?;
+
+        let root = tree.root_node();
+        let mut collected = Collected::default();
+        let mut elements = Vec::new();
+        let mut imports = Vec::new();
+
+        let mut cursor = root.walk();
+        for child in root.children(&mut cursor) {
+            if child.kind() == "import_statement" {
+                if let Some(import) = self.extract_import(child, source, &mut collected.aliases) {
+                    imports.push(import);
+                }
+            }
+        }
+
+        self.collect_elements(root, source, 0, &mut elements, &mut collected);
+
+        for list in [
+            &mut collected.tags,
+            &mut collected.classes,
+            &mut collected.ids,
+            &mut collected.components,
+        ] {
+            list.sort();
+            list.dedup();
+        }
+
+        Ok(JsxStructure {
+            html: HtmlStructure {
+                elements,
+                tags: collected.tags,
+                classes: collected.classes,
+                ids: collected.ids,
+            },
+            components: collected.components,
+            imports,
+            event_handlers: collected.event_handlers,
+        })
+    }
+
+    /// Find the outermost JSX elements under `node`
+    fn collect_elements(
+        &self,
+        node: Node,
+        source: &str,
+        depth: usize,
+        out: &mut Vec<HtmlElement>,
+        collected: &mut Collected,
+    ) {
+        match node.kind() {
+            "jsx_element" | "jsx_self_closing_element" => {
+                self.extract_element(node, source, depth, out, collected);
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.collect_elements(child, source, depth, out, collected);
+                }
+            }
+        }
+    }
+
+    /// Convert a JSX element; fragments push their children directly into `out`
+    fn extract_element(
+        &self,
+        node: Node,
+        source: &str,
+        depth: usize,
+        out: &mut Vec<HtmlElement>,
+        collected: &mut Collected,
+    ) {
+        let opening = if node.kind() == "jsx_self_closing_element" {
+            node
+        } else {
+            match node.child_by_field_name("open_tag") {
+                Some(open) => open,
+                None => return,
+            }
+        };
+
+        let Some(name) = opening.child_by_field_name("name") else {
+            // Fragment: <>...</>
+            self.extract_children(node, source, depth, out, collected);
+            return;
+        };
+
+        let tag = resolve_alias(self.node_text(name, source), &collected.aliases);
+        if tag.starts_with(|c: char| c.is_ascii_uppercase()) {
+            collected.components.push(tag.clone());
+        }
+        collected.tags.push(tag.clone());
+
+        let mut id = None;
+        let mut classes = Vec::new();
+        let mut attributes = Vec::new();
+
+        let mut cursor = opening.walk();
+        for attr in opening.children_by_field_name("attribute", &mut cursor) {
+            if attr.kind() != "jsx_attribute" {
+                continue;
+            }
+            let Some((prop, value)) = self.extract_attribute(attr, source) else {
+                continue;
+            };
+            match prop.as_str() {
+                "class" => {
+                    classes = self.class_names(attr, source);
+                    collected.classes.extend(classes.iter().cloned());
+                }
+                "id" => {
+                    id = Some(value.clone());
+                    collected.ids.push(value.clone());
+                }
+                _ => {
+                    if let Some(event) = event_name(&prop) {
+                        let is_identifier = !value.is_empty()
+                            && value.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.');
+                        collected.event_handlers.push(EventHandler {
+                            event_type: event,
+                            handler_name: is_identifier.then(|| value.clone()),
+                            target_selector: Some(tag.clone()),
+                        });
+                    }
+                }
+            }
+            attributes.push((prop, value));
+        }
+
+        let mut children = Vec::new();
+        let mut text_parts = Vec::new();
+        if node.kind() == "jsx_element" {
+            self.extract_children(node, source, depth + 1, &mut children, collected);
+
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if child.kind() == "jsx_text" {
+                    let text = self.node_text(child, source).trim();
+                    if !text.is_empty() {
+                        text_parts.push(text.split_whitespace().collect::<Vec<_>>().join(" "));
+                    }
+                }
+            }
+        }
+
+        out.push(HtmlElement {
+            tag,
+            id,
+            classes,
+            attributes,
+            children,
+            text_content: (!text_parts.is_empty()).then(|| text_parts.join(" ")),
+            depth,
+        });
+    }
+
+    fn extract_children(
+        &self,
+        node: Node,
+        source: &str,
+        depth: usize,
+        out: &mut Vec<HtmlElement>,
+        collected: &mut Collected,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "jsx_element" | "jsx_self_closing_element" => {
+                    self.extract_element(child, source, depth, out, collected);
+                }
+                // `{cond && <A/>}`, `{items.map(i => <Item/>)}`
+                "jsx_expression" => self.collect_elements(child, source, depth, out, collected),
+                _ => {}
+            }
+        }
+    }
+
+    /// Prop name and value; expression values keep their source without braces
+    fn extract_attribute(&self, node: Node, source: &str) -> Option<(String, String)> {
+        let mut cursor = node.walk();
+        let mut children = node.named_children(&mut cursor);
+        let name = children.next()?;
+        let prop = match self.node_text(name, source) {
+            "className" => "class".to_string(),
+            "htmlFor" => "for".to_string(),
+            other => other.to_string(),
+        };
+
+        let value = match children.next() {
+            None => String::new(),
+            Some(value) if value.kind() == "string" => self
+                .node_text(value, source)
+                .trim_matches('"')
+                .trim_matches('\'')
+                .to_string(),
+            Some(value) if value.kind() == "jsx_expression" => {
+                let text = self.node_text(value, source);
+                text.strip_prefix('{')
+                    .and_then(|t| t.strip_suffix('}'))
+                    .unwrap_or(text)
+                    .trim()
+                    .to_string()
+            }
+            Some(value) => self.node_text(value, source).to_string(),
+        };
+
+        Some((prop, value))
+    }
+
+    /// Static class names from `className`, including literals inside
+    /// `cn(...)`/`clsx(...)` calls and template strings
+    fn class_names(&self, attr: Node, source: &str) -> Vec<String> {
+        let mut fragments = Vec::new();
+        self.string_fragments(attr, source, &mut fragments);
+        fragments
+            .iter()
+            .flat_map(|f| f.split_whitespace())
+            .map(String::from)
+            .collect()
+    }
+
+    fn string_fragments<'a>(&self, node: Node, source: &'a str, out: &mut Vec<&'a str>) {
+        if node.kind() == "string_fragment" {
+            out.push(self.node_text(node, source));
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.string_fragments(child, source, out);
+        }
+    }
+
+    /// Import statement; aliased named imports are recorded in `aliases`
+    fn extract_import(&self, node: Node, source: &str, aliases: &mut HashMap<String, String>) -> Option<JsImport> {
+        let source_module = node
+            .child_by_field_name("source")
+            .map(|s| {
+                self.node_text(s, source)
+                    .trim_matches('"')
+                    .trim_matches('\'')
+                    .to_string()
+            })
+            .filter(|s| !s.is_empty())?;
+
+        let mut specifiers = Vec::new();
+        let mut is_default = false;
+
+        let mut cursor = node.walk();
+        for clause in node.children(&mut cursor).filter(|c| c.kind() == "import_clause") {
+            let mut clause_cursor = clause.walk();
+            for child in clause.children(&mut clause_cursor) {
+                match child.kind() {
+                    "identifier" => {
+                        is_default = true;
+                        specifiers.push(self.node_text(child, source).to_string());
+                    }
+                    "namespace_import" => {
+                        if let Some(ident) = child.named_child(0) {
+                            specifiers.push(self.node_text(ident, source).to_string());
+                        }
+                    }
+                    "named_imports" => {
+                        let mut named_cursor = child.walk();
+                        for spec in child
+                            .named_children(&mut named_cursor)
+                            .filter(|s| s.kind() == "import_specifier")
+                        {
+                            // Local binding: `Card as C` is used as `<C>`
+                            let name = spec.child_by_field_name("name").map(|n| self.node_text(n, source));
+                            let alias = spec.child_by_field_name("alias").map(|a| self.node_text(a, source));
+                            if let (Some(name), Some(alias)) = (name, alias) {
+                                aliases.insert(alias.to_string(), name.to_string());
+                            }
+                            if let Some(local) = alias.or(name) {
+                                specifiers.push(local.to_string());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Some(JsImport {
+            source: source_module,
+            specifiers,
+            is_default,
+        })
+    }
+
+    fn node_text<'a>(&self, node: Node, source: &'a str) -> &'a str {
+        &source[node.start_byte()..node.end_byte()]
+    }
+}
+
+/// Tag with an aliased root binding replaced by its imported name (`C.Header` → `Card.Header`)
+fn resolve_alias(tag: &str, aliases: &HashMap<String, String>) -> String {
+    let (root, rest) = tag.split_at(tag.find('.').unwrap_or(tag.len()));
+    match aliases.get(root) {
+        Some(imported) => format!("{}{}", imported, rest),
+        None => tag.to_string(),
+    }
+}
+
+/// `onClick` → `click`, `onMouseEnter` → `mouseenter`
+fn event_name(prop: &str) -> Option<String> {
+    let rest = prop.strip_prefix("on")?;
+    rest.starts_with(|c: char| c.is_ascii_uppercase())
+        .then(|| rest.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mui_component() {
+        let mut parser = JsxParser::new();
+        let source = r#"
+import { Button, Card as MuiCard } from '@mui/material';
+
+export function Checkout({ items }: { items: string[] }) {
+  return (
+    <MuiCard className="checkout" sx={{ p: 2 }}>
+      <Button variant="contained" disabled onClick={() => pay()}>
+        Pay now
+      </Button>
+      <MuiCard.Actions />
+    </MuiCard>
+  );
+}
+"#;
+        let result = parser.parse(source).unwrap();
+
+        assert_eq!(result.html.elements.len(), 1);
+        let card = &result.html.elements[0];
+        assert_eq!(card.tag, "Card");
+        assert_eq!(card.classes, vec!["checkout"]);
+        assert!(card.attributes.contains(&("sx".to_string(), "{ p: 2 }".to_string())));
+
+        let button = &card.children[0];
+        assert_eq!(button.tag, "Button");
+        assert_eq!(button.depth, 1);
+        assert_eq!(button.text_content.as_deref(), Some("Pay now"));
+        assert!(button.attributes.contains(&("variant".to_string(), "contained".to_string())));
+        assert!(button.attributes.contains(&("disabled".to_string(), String::new())));
+
+        assert_eq!(card.children[1].tag, "Card.Actions");
+        assert_eq!(result.components, vec!["Button", "Card", "Card.Actions"]);
+        assert_eq!(result.event_handlers.len(), 1);
+        assert_eq!(result.event_handlers[0].event_type, "click");
+        assert_eq!(result.event_handlers[0].handler_name, None);
+        assert_eq!(result.imports[0].specifiers, vec!["Button", "MuiCard"]);
+    }
+
+    #[test]
+    fn test_fragments_and_expression_children() {
+        let mut parser = JsxParser::new();
+        let source = r#"
+const List = ({ items, active }) => (
+  <>
+    <ul className={cn("space-y-2", active && "ring-1")}>
+      {items.map((item) => <li key={item.id}>{item.label}</li>)}
+    </ul>
+    <label htmlFor="q">Search</label>
+  </>
+);
+"#;
+        let result = parser.parse(source).unwrap();
+
+        let tags: Vec<&str> = result.html.elements.iter().map(|e| e.tag.as_str()).collect();
+        assert_eq!(tags, vec!["ul", "label"]);
+        assert_eq!(result.html.elements[0].classes, vec!["space-y-2", "ring-1"]);
+        assert_eq!(result.html.elements[0].children[0].tag, "li");
+        assert!(result.html.elements[1]
+            .attributes
+            .contains(&("for".to_string(), "q".to_string())));
+        assert!(result.components.is_empty());
+    }
+}
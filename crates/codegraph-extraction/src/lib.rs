@@ -2,17 +2,34 @@
 //!
 //! Extracts ontological entities from UI code snippets using tree-sitter.
 
+pub mod a11y;
+pub mod adapters;
 pub mod css;
 pub mod design_system;
 pub mod embedding;
+pub mod fingerprint;
 pub mod html;
 pub mod javascript;
+pub mod jsx;
 pub mod narsese_gen;
 pub mod ontology;
 pub mod pipeline;
+pub mod scss;
+pub mod segment;
+pub mod tailwind;
+pub mod token_import;
+pub mod tokens;
 
+pub use a11y::{A11yAuditor, A11yReport};
+pub use adapters::{ComponentSource, Framework};
 pub use design_system::DesignSystemDetector;
 pub use embedding::EmbeddingGenerator;
+pub use fingerprint::{Fingerprint, LshIndex};
+pub use jsx::{JsxParser, JsxStructure};
 pub use narsese_gen::NarseseGenerator;
 pub use ontology::OntologyMapper;
 pub use pipeline::{ExtractionInput, ExtractionPipeline, ExtractionResult};
+pub use segment::{Segment, Segmenter};
+pub use tailwind::{TailwindResolver, TailwindTheme};
+pub use token_import::{ImportedTokenSet, TokenImporter};
+pub use tokens::TokenGraph;
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::design_system::DesignSystemType;
 use crate::ontology::{MappedElement, OntologyMapping, UICategory};
+use crate::token_import::ImportedTokenSet;
 
 /// Truth value for NARS statements
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -118,12 +119,130 @@ impl NarseseGenerator {
             statements.push(self.generate_token_statement(name, value, category));
         }
 
+        // Alias tokens are interchangeable with their targets
+        for token in &mapping.token_graph.tokens {
+            if let Some(target) = &token.alias_of {
+                statements.push(NarseseStatement {
+                    statement: format!(
+                        "{} <-> {}. %1.00;0.90%",
+                        self.sanitize_term(&token.name),
+                        self.sanitize_term(target)
+                    ),
+                    truth_value: TruthValue::certain(),
+                    statement_type: StatementType::Similarity,
+                });
+            }
+        }
+
+        // Token usage by elements
+        for element in &mapping.elements {
+            let element_id = self.element_id(element);
+            for token in &element.tokens_used {
+                statements.push(NarseseStatement {
+                    statement: format!(
+                        "{} --> [uses{}]. %{:.2};{:.2}%",
+                        element_id,
+                        self.sanitize_term(token),
+                        self.default_truth.frequency,
+                        self.default_truth.confidence
+                    ),
+                    truth_value: self.default_truth,
+                    statement_type: StatementType::Property,
+                });
+            }
+        }
+
+        // Sub-components and the atomic elements they own
+        for segment in &mapping.segments {
+            statements.extend(self.generate_element_statements(&segment.element));
+            let segment_id = self.element_id(&segment.element);
+            for child in &segment.children {
+                statements.push(NarseseStatement {
+                    statement: format!(
+                        "{} --> [has{}]. %{:.2};{:.2}%",
+                        segment_id,
+                        self.category_term(child.category),
+                        self.default_truth.frequency,
+                        self.default_truth.confidence
+                    ),
+                    truth_value: self.default_truth,
+                    statement_type: StatementType::Property,
+                });
+            }
+        }
+
         // Generate category relationships
         statements.extend(self.generate_category_hierarchy(&mapping.categories_used));
 
         NarseseKB { statements }
     }
 
+    /// Generate statements for an imported (official) design token set
+    ///
+    /// Imported tokens are authoritative, so they use certain truth values.
+    pub fn generate_token_set(&self, set: &ImportedTokenSet) -> NarseseKB {
+        let mut statements = Vec::new();
+        let ds_term = format!("DS_{}", self.sanitize_term(&set.design_system));
+        let truth = TruthValue::certain();
+
+        for token in &set.tokens {
+            let token_term = self.sanitize_term(&token.name);
+
+            // Token category and design system membership
+            statements.push(NarseseStatement {
+                statement: format!(
+                    "{{{}}} --> {}. %{:.2};{:.2}%",
+                    token_term,
+                    self.token_category_term(token.category),
+                    truth.frequency,
+                    truth.confidence
+                ),
+                truth_value: truth,
+                statement_type: StatementType::Instance,
+            });
+            statements.push(NarseseStatement {
+                statement: format!(
+                    "{{{}}} --> {}. %{:.2};{:.2}%",
+                    token_term, ds_term, truth.frequency, truth.confidence
+                ),
+                truth_value: truth,
+                statement_type: StatementType::Instance,
+            });
+
+            // Resolved value as a property
+            if let Some(value) = &token.resolved_value {
+                statements.push(NarseseStatement {
+                    statement: format!(
+                        "{} --> [value_{}]. %{:.2};{:.2}%",
+                        token_term,
+                        self.sanitize_term(value),
+                        truth.frequency,
+                        truth.confidence
+                    ),
+                    truth_value: truth,
+                    statement_type: StatementType::Property,
+                });
+            }
+
+            // Aliases are interchangeable with their targets
+            if let Some(target) = &token.alias_of {
+                statements.push(NarseseStatement {
+                    statement: format!(
+                        "{} <-> {}. %{:.2};{:.2}%",
+                        token_term,
+                        self.sanitize_term(target),
+                        truth.frequency,
+                        truth.confidence
+                    ),
+                    truth_value: truth,
+                    statement_type: StatementType::Similarity,
+                });
+            }
+        }
+
+        NarseseKB { statements }
+    }
+
     /// Generate statements for a single element
     fn generate_element_statements(&self, element: &MappedElement) -> Vec<NarseseStatement> {
         let mut statements = Vec::new();
@@ -171,6 +290,20 @@ impl NarseseGenerator {
             });
         }
 
+        // Template control flow (framework directives)
+        for (flag, property) in [(element.conditional, "conditional"), (element.repeated, "repeated")] {
+            if flag {
+                statements.push(NarseseStatement {
+                    statement: format!(
+                        "{} --> [{}]. %{:.2};{:.2}%",
+                        element_id, property, self.default_truth.frequency, self.default_truth.confidence
+                    ),
+                    truth_value: self.default_truth,
+                    statement_type: StatementType::Property,
+                });
+            }
+        }
+
         statements
     }
 
@@ -318,6 +451,7 @@ impl NarseseGenerator {
             .replace('/', "_")
             .replace('(', "")
             .replace(')', "")
+            .replace(',', "_")
     }
 
     /// Capitalize first letter
@@ -355,6 +489,17 @@ mod tests {
         assert!(kb.statements.iter().any(|s| s.statement.contains("UIButton")));
     }
 
+    #[test]
+    fn test_generate_token_set() {
+        let json = r##"{ "color": { "$type": "color", "primary": { "$value": "#3b82f6" } } }"##;
+        let set = crate::token_import::TokenImporter::new().import_str(json, "acme").unwrap();
+
+        let kb = NarseseGenerator::new().generate_token_set(&set);
+
+        assert!(kb.statements.iter().any(|s| s.statement.contains("DesignToken_Color")));
+        assert!(kb.statements.iter().any(|s| s.statement.contains("DS_acme")));
+    }
+
     #[test]
     fn test_truth_values() {
         let tv = TruthValue::extracted();
@@ -3,10 +3,15 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+use crate::adapters::{DirectiveKind, TemplateDirective};
 use crate::css::{CssStructure, TokenCategory};
 use crate::design_system::{DesignSystemType, DetectionResult};
+use crate::fingerprint::Fingerprint;
 use crate::html::HtmlStructure;
 use crate::javascript::JsStructure;
+use crate::segment::Segment;
+use crate::tailwind::UtilityDeclaration;
+use crate::tokens::TokenGraph;
 
 /// UI element category in the ontology
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -82,6 +87,43 @@ pub enum UICategory {
 }
 
 impl UICategory {
+    pub const ALL: [UICategory; 49] = [
+        Self::Container, Self::Grid, Self::Flex, Self::Stack, Self::Navigation, Self::Menu, Self::Breadcrumb,
+        Self::Tabs, Self::Pagination, Self::Form, Self::Input, Self::Select, Self::Checkbox, Self::Radio,
+        Self::Switch, Self::Slider, Self::DatePicker, Self::Button, Self::Link, Self::IconButton, Self::FAB,
+        Self::Card, Self::List, Self::Table, Self::Avatar, Self::Badge, Self::Chip, Self::Tag, Self::Alert,
+        Self::Toast, Self::Snackbar, Self::Progress, Self::Spinner, Self::Skeleton, Self::Modal, Self::Dialog,
+        Self::Drawer, Self::Popover, Self::Tooltip, Self::ContextMenu, Self::Image, Self::Video, Self::Icon,
+        Self::Heading, Self::Text, Self::Label, Self::Divider, Self::Spacer, Self::Unknown,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == name)
+    }
+
+    /// Family the category belongs to, as grouped in the ontology
+    pub fn group(&self) -> &'static str {
+        match self {
+            Self::Container | Self::Grid | Self::Flex | Self::Stack => "layout",
+            Self::Navigation | Self::Menu | Self::Breadcrumb | Self::Tabs | Self::Pagination => "navigation",
+            Self::Form
+            | Self::Input
+            | Self::Select
+            | Self::Checkbox
+            | Self::Radio
+            | Self::Switch
+            | Self::Slider
+            | Self::DatePicker => "form",
+            Self::Button | Self::Link | Self::IconButton | Self::FAB => "action",
+            Self::Card | Self::List | Self::Table | Self::Avatar | Self::Badge | Self::Chip | Self::Tag => "display",
+            Self::Alert | Self::Toast | Self::Snackbar | Self::Progress | Self::Spinner | Self::Skeleton => "feedback",
+            Self::Modal | Self::Dialog | Self::Drawer | Self::Popover | Self::Tooltip | Self::ContextMenu => "overlay",
+            Self::Image | Self::Video | Self::Icon => "media",
+            Self::Heading | Self::Text | Self::Label => "typography",
+            Self::Divider | Self::Spacer | Self::Unknown => "other",
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Container => "container",
@@ -147,6 +189,21 @@ pub struct MappedElement {
     pub properties: Vec<(String, String)>,
     pub children_categories: Vec<UICategory>,
     pub has_interactivity: bool,
+    /// Design tokens consumed by CSS rules matching this element
+    #[serde(default)]
+    pub tokens_used: Vec<String>,
+    /// Rendered conditionally (`v-if`, `*ngIf`, `{#if}`)
+    #[serde(default)]
+    pub conditional: bool,
+    /// Rendered once per item of a collection (`v-for`, `*ngFor`, `{#each}`)
+    #[serde(default)]
+    pub repeated: bool,
+    /// CSS declarations expanded from Tailwind utility classes
+    #[serde(default)]
+    pub utilities: Vec<UtilityDeclaration>,
+    /// Structural fingerprint of the element subtree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<Fingerprint>,
 }
 
 /// Ontology mapping result
@@ -156,12 +213,40 @@ pub struct OntologyMapping {
     pub categories_used: Vec<UICategory>,
     pub design_tokens: Vec<(String, String, TokenCategory)>,
     pub design_system: Option<DesignSystemType>,
+    /// Resolved token graph (themes, aliases, usages)
+    #[serde(default)]
+    pub token_graph: TokenGraph,
+    /// Framework template directives (Vue, Svelte, Angular)
+    #[serde(default)]
+    pub directives: Vec<TemplateDirective>,
+    /// Sub-components detected inside the snippet
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+}
+
+impl OntologyMapping {
+    /// Record template directives, flagging root elements that are
+    /// conditional or repeated
+    pub fn apply_directives(&mut self, directives: Vec<TemplateDirective>) {
+        for directive in directives.iter().filter(|d| d.depth == 0) {
+            let Some(element) = self.elements.get_mut(directive.root_index) else {
+                continue;
+            };
+            match directive.kind {
+                DirectiveKind::Conditional => element.conditional = true,
+                DirectiveKind::Repeat => element.repeated = true,
+                DirectiveKind::Event => element.has_interactivity = true,
+            }
+        }
+        self.directives = directives;
+    }
 }
 
 /// Maps extracted code structures to UI ontology
 pub struct OntologyMapper {
     tag_mappings: Vec<(&'static str, UICategory)>,
     class_mappings: Vec<(&'static str, UICategory)>,
+    component_mappings: Vec<(&'static str, UICategory)>,
 }
 
 impl Default for OntologyMapper {
@@ -239,9 +324,90 @@ impl OntologyMapper {
             ("pagination", UICategory::Pagination),
         ];
 
+        // JSX component name to category mappings (MUI, Chakra, Ant, shadcn/Radix)
+        let component_mappings = vec![
+            ("Button", UICategory::Button),
+            ("IconButton", UICategory::IconButton),
+            ("Fab", UICategory::FAB),
+            ("Link", UICategory::Link),
+            ("TextField", UICategory::Input),
+            ("Input", UICategory::Input),
+            ("Textarea", UICategory::Input),
+            ("TextArea", UICategory::Input),
+            ("Select", UICategory::Select),
+            ("Autocomplete", UICategory::Select),
+            ("Combobox", UICategory::Select),
+            ("Checkbox", UICategory::Checkbox),
+            ("Radio", UICategory::Radio),
+            ("RadioGroup", UICategory::Radio),
+            ("Switch", UICategory::Switch),
+            ("Toggle", UICategory::Switch),
+            ("Slider", UICategory::Slider),
+            ("DatePicker", UICategory::DatePicker),
+            ("Calendar", UICategory::DatePicker),
+            ("Form", UICategory::Form),
+            ("Label", UICategory::Label),
+            ("FormLabel", UICategory::Label),
+            ("Card", UICategory::Card),
+            ("Paper", UICategory::Card),
+            ("List", UICategory::List),
+            ("ListItem", UICategory::List),
+            ("Table", UICategory::Table),
+            ("DataGrid", UICategory::Table),
+            ("Avatar", UICategory::Avatar),
+            ("Badge", UICategory::Badge),
+            ("Chip", UICategory::Chip),
+            ("Tag", UICategory::Tag),
+            ("Alert", UICategory::Alert),
+            ("Toast", UICategory::Toast),
+            ("Toaster", UICategory::Toast),
+            ("Snackbar", UICategory::Snackbar),
+            ("Progress", UICategory::Progress),
+            ("Spinner", UICategory::Spinner),
+            ("Spin", UICategory::Spinner),
+            ("Skeleton", UICategory::Skeleton),
+            ("Modal", UICategory::Modal),
+            ("Dialog", UICategory::Dialog),
+            ("Drawer", UICategory::Drawer),
+            ("Sheet", UICategory::Drawer),
+            ("Popover", UICategory::Popover),
+            ("HoverCard", UICategory::Popover),
+            ("Tooltip", UICategory::Tooltip),
+            ("Menu", UICategory::Menu),
+            ("ContextMenu", UICategory::ContextMenu),
+            ("Tabs", UICategory::Tabs),
+            ("Tab", UICategory::Tabs),
+            ("Breadcrumb", UICategory::Breadcrumb),
+            ("Breadcrumbs", UICategory::Breadcrumb),
+            ("Pagination", UICategory::Pagination),
+            ("AppBar", UICategory::Navigation),
+            ("Navbar", UICategory::Navigation),
+            ("NavigationMenu", UICategory::Navigation),
+            ("Toolbar", UICategory::Navigation),
+            ("Box", UICategory::Container),
+            ("Container", UICategory::Container),
+            ("Layout", UICategory::Container),
+            ("Grid", UICategory::Grid),
+            ("SimpleGrid", UICategory::Grid),
+            ("Flex", UICategory::Flex),
+            ("Stack", UICategory::Stack),
+            ("Image", UICategory::Image),
+            ("Img", UICategory::Image),
+            ("Icon", UICategory::Icon),
+            ("SvgIcon", UICategory::Icon),
+            ("Heading", UICategory::Heading),
+            ("Title", UICategory::Heading),
+            ("Typography", UICategory::Text),
+            ("Text", UICategory::Text),
+            ("Divider", UICategory::Divider),
+            ("Separator", UICategory::Divider),
+            ("Spacer", UICategory::Spacer),
+        ];
+
         Self {
             tag_mappings,
             class_mappings,
+            component_mappings,
         }
     }
 
@@ -266,6 +432,9 @@ impl OntologyMapper {
             categories_used: categories_used.into_iter().collect(),
             design_tokens: Vec::new(),
             design_system: Some(ds_result.design_system),
+            token_graph: TokenGraph::default(),
+            directives: Vec::new(),
+            segments: Vec::new(),
         }
     }
 
@@ -282,8 +451,42 @@ impl OntologyMapper {
         !js.event_handlers.is_empty() || !js.dom_calls.is_empty()
     }
 
+    /// Category for a JSX component name.
+    ///
+    /// Tries an exact match on the last segment (`Card.Header` → `Header`,
+    /// then `Card`), then the longest known suffix (`LoadingButton`,
+    /// `CircularProgress`, `AlertDialog`), then the longest known prefix
+    /// (`CardContent`, `ListItemText`).
+    pub fn component_category(&self, component: &str) -> Option<UICategory> {
+        let segments: Vec<&str> = component.rsplit('.').collect();
+
+        for name in &segments {
+            if let Some((_, cat)) = self.component_mappings.iter().find(|(n, _)| n == name) {
+                return Some(*cat);
+            }
+        }
+
+        for name in &segments {
+            let longest = |matches: &dyn Fn(&str) -> bool| {
+                self.component_mappings
+                    .iter()
+                    .filter(|(n, _)| matches(n))
+                    .max_by_key(|(n, _)| n.len())
+                    .map(|(_, cat)| *cat)
+            };
+            if let Some(cat) = longest(&|n| name.ends_with(n)) {
+                return Some(cat);
+            }
+            if let Some(cat) = longest(&|n| name.starts_with(n)) {
+                return Some(cat);
+            }
+        }
+
+        None
+    }
+
     /// Map a single element to ontology category
-    fn map_element(
+    pub(crate) fn map_element(
         &self,
         tag: &str,
         classes: &[String],
@@ -300,14 +503,23 @@ impl OntologyMapper {
             }
         }
 
-        // Then, check class mappings (may override tag)
+        // JSX components are named after what they are
+        let is_component = tag.starts_with(|c: char| c.is_ascii_uppercase());
+        if is_component {
+            category = self.component_category(tag).unwrap_or(UICategory::Unknown);
+        }
+
+        // Then, check class mappings (may override tag, but not a known component
+        // whose utility classes like `flex` say nothing about its role)
         let classes_lower: Vec<String> = classes.iter().map(|c| c.to_lowercase()).collect();
         let all_classes = classes_lower.join(" ");
 
-        for (pattern, cat) in &self.class_mappings {
-            if all_classes.contains(pattern) {
-                category = *cat;
-                break;
+        if !is_component || category == UICategory::Unknown {
+            for (pattern, cat) in &self.class_mappings {
+                if all_classes.contains(pattern) {
+                    category = *cat;
+                    break;
+                }
             }
         }
 
@@ -367,6 +579,11 @@ impl OntologyMapper {
             properties: attributes.to_vec(),
             children_categories: Vec::new(),
             has_interactivity: false,
+            tokens_used: Vec::new(),
+            conditional: false,
+            repeated: false,
+            utilities: Vec::new(),
+            fingerprint: None,
         }
     }
 
@@ -380,10 +597,13 @@ impl OntologyMapper {
     ) -> OntologyMapping {
         let mut mapping = self.map_html(html, ds_result);
         mapping.design_tokens = self.map_css(css);
+        mapping.token_graph = TokenGraph::from_css(css);
 
         let has_interactive = self.has_interactivity(js);
-        for elem in &mut mapping.elements {
+        for (elem, source) in mapping.elements.iter_mut().zip(&html.elements) {
             elem.has_interactivity = has_interactive;
+            elem.tokens_used = mapping.token_graph.tokens_for_element(source);
+            elem.fingerprint = Some(Fingerprint::compute(source, &elem.tokens_used));
         }
 
         mapping
@@ -396,6 +616,14 @@ mod tests {
     use crate::design_system::DesignSystemDetector;
     use crate::html::HtmlParser;
 
+    #[test]
+    fn test_every_category_round_trips_by_name() {
+        for category in UICategory::ALL {
+            assert_eq!(UICategory::from_name(category.as_str()), Some(category));
+        }
+        assert_eq!(UICategory::IconButton.group(), "action");
+    }
+
     #[test]
     fn test_map_button() {
         let mapper = OntologyMapper::new();
@@ -410,4 +638,26 @@ mod tests {
         assert!(!mapping.elements.is_empty());
         assert_eq!(mapping.elements[0].category, UICategory::Button);
     }
+
+    #[test]
+    fn test_map_jsx_components() {
+        let mapper = OntologyMapper::new();
+
+        assert_eq!(mapper.component_category("Button"), Some(UICategory::Button));
+        assert_eq!(mapper.component_category("LoadingButton"), Some(UICategory::Button));
+        assert_eq!(mapper.component_category("CircularProgress"), Some(UICategory::Progress));
+        assert_eq!(mapper.component_category("CardContent"), Some(UICategory::Card));
+        assert_eq!(mapper.component_category("Dialog.Content"), Some(UICategory::Dialog));
+        assert_eq!(mapper.component_category("AlertDialog"), Some(UICategory::Dialog));
+        assert_eq!(mapper.component_category("Checkout"), None);
+
+        let mapped = mapper.map_element(
+            "Button",
+            &["flex".to_string(), "gap-2".to_string()],
+            &[("variant".to_string(), "contained".to_string())],
+            DesignSystemType::MaterialUI,
+        );
+        assert_eq!(mapped.category, UICategory::Button);
+        assert_eq!(mapped.element_type, "material-ui-button");
+    }
 }
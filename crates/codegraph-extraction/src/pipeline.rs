@@ -4,13 +4,19 @@ use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
+use crate::a11y::{A11yAuditor, A11yReport};
+use crate::adapters::{self, Framework, TemplateDirective};
 use crate::css::{CssParser, CssStructure};
 use crate::design_system::{DesignSystemDetector, DetectionResult};
 use crate::embedding::{EmbeddingGenerator, EmbeddingResult};
+use crate::fingerprint::Fingerprint;
 use crate::html::{HtmlParser, HtmlStructure};
 use crate::javascript::{JsParser, JsStructure};
+use crate::jsx::JsxParser;
 use crate::narsese_gen::{NarseseGenerator, NarseseKB};
 use crate::ontology::{OntologyMapper, OntologyMapping};
+use crate::segment::Segmenter;
+use crate::tailwind::{TailwindResolver, TailwindTheme};
 
 /// Complete extraction result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +35,9 @@ pub struct ExtractionResult {
     pub narsese: NarseseKB,
     /// Generated embedding (if requested)
     pub embedding: Option<EmbeddingResult>,
+    /// Accessibility audit of the snippet
+    #[serde(default)]
+    pub a11y: A11yReport,
     /// Processing time in milliseconds
     pub processing_time_ms: u64,
 }
@@ -39,6 +48,12 @@ pub struct ExtractionInput {
     pub html: String,
     pub css: Option<String>,
     pub js: Option<String>,
+    /// React component source (JSX/TSX)
+    pub jsx: Option<String>,
+    /// Framework the HTML template was written for
+    pub framework: Option<Framework>,
+    /// Style preprocessor language of `css` (`scss`, `sass`, `less`)
+    pub style_lang: Option<String>,
 }
 
 impl ExtractionInput {
@@ -47,9 +62,17 @@ impl ExtractionInput {
             html: html.into(),
             css: None,
             js: None,
+            jsx: None,
+            framework: None,
+            style_lang: None,
         }
     }
 
+    /// Input from a JSX/TSX component file (no separate HTML)
+    pub fn from_jsx(jsx: impl Into<String>) -> Self {
+        Self::new(String::new()).with_jsx(jsx)
+    }
+
     pub fn with_css(mut self, css: impl Into<String>) -> Self {
         self.css = Some(css.into());
         self
@@ -59,6 +82,32 @@ impl ExtractionInput {
         self.js = Some(js.into());
         self
     }
+
+    pub fn with_jsx(mut self, jsx: impl Into<String>) -> Self {
+        self.jsx = Some(jsx.into());
+        self
+    }
+
+    pub fn with_framework(mut self, framework: Framework) -> Self {
+        self.framework = Some(framework);
+        self
+    }
+
+    /// Parse `css` as `scss`, `sass` or `less` instead of plain CSS
+    pub fn with_style_lang(mut self, lang: impl Into<String>) -> Self {
+        self.style_lang = Some(lang.into());
+        self
+    }
+
+    /// JS and JSX source combined, so import sources and component usages
+    /// count as design system evidence
+    fn script_source(&self) -> String {
+        [self.js.as_deref(), self.jsx.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 /// Pipeline configuration
@@ -87,6 +136,9 @@ pub struct ExtractionPipeline {
     html_parser: HtmlParser,
     css_parser: CssParser,
     js_parser: JsParser,
+    jsx_parser: JsxParser,
+    tailwind: TailwindResolver,
+    segmenter: Segmenter,
     design_detector: DesignSystemDetector,
     ontology_mapper: OntologyMapper,
     narsese_generator: NarseseGenerator,
@@ -106,6 +158,9 @@ impl ExtractionPipeline {
             html_parser: HtmlParser::new(),
             css_parser: CssParser::new(),
             js_parser: JsParser::new(),
+            jsx_parser: JsxParser::new(),
+            tailwind: TailwindResolver::new(),
+            segmenter: Segmenter::new(),
             design_detector: DesignSystemDetector::new(),
             ontology_mapper: OntologyMapper::new(),
             narsese_generator: NarseseGenerator::new(),
@@ -120,6 +175,9 @@ impl ExtractionPipeline {
             html_parser: HtmlParser::new(),
             css_parser: CssParser::new(),
             js_parser: JsParser::new(),
+            jsx_parser: JsxParser::new(),
+            tailwind: TailwindResolver::new(),
+            segmenter: Segmenter::new(),
             design_detector: DesignSystemDetector::new(),
             ontology_mapper: OntologyMapper::new(),
             narsese_generator: NarseseGenerator::new(),
@@ -128,25 +186,32 @@ impl ExtractionPipeline {
         }
     }
 
+    /// Resolve Tailwind utilities against a project theme instead of the defaults
+    pub fn with_tailwind_theme(mut self, theme: TailwindTheme) -> Self {
+        self.tailwind = TailwindResolver::with_theme(theme);
+        self
+    }
+
     /// Run full extraction pipeline
     pub async fn extract(&mut self, input: ExtractionInput) -> anyhow::Result<ExtractionResult> {
         let start = Instant::now();
 
         // Phase 1: Parse all input (parallel would be ideal, but parsers are mutable)
         debug!("Phase 1: Parsing input");
-        let html = self.html_parser.parse(&input.html)?;
-        let css = input
+        let mut html = self.html_parser.parse(&input.html)?;
+        let mut css = input
             .css
             .as_ref()
-            .map(|c| self.css_parser.parse(c))
+            .map(|c| self.css_parser.parse_lang(c, input.style_lang.as_deref()))
             .transpose()?
             .unwrap_or_else(|| CssStructure {
                 rules: vec![],
                 design_tokens: vec![],
                 properties: vec![],
                 selectors: vec![],
+                mixins: vec![],
             });
-        let js = input
+        let mut js = input
             .js
             .as_ref()
             .map(|j| self.js_parser.parse(j))
@@ -159,6 +224,9 @@ impl ExtractionPipeline {
                 dom_calls: vec![],
             });
 
+        let directives = Self::apply_template_directives(&input, &mut html, &mut js);
+        self.merge_jsx(&input, &mut html, &mut js)?;
+
         let parse_time = start.elapsed().as_millis();
         debug!("Parsing completed in {}ms", parse_time);
 
@@ -167,7 +235,7 @@ impl ExtractionPipeline {
         let design_system = self.design_detector.detect_from_content(
             &input.html,
             input.css.as_deref().unwrap_or(""),
-            input.js.as_deref().unwrap_or(""),
+            &input.script_source(),
         );
         debug!(
             "Detected design system: {:?} (confidence: {:.2})",
@@ -176,14 +244,29 @@ impl ExtractionPipeline {
 
         // Phase 3: Ontology mapping
         debug!("Phase 3: Ontology mapping");
-        let ontology = self.ontology_mapper.map_full(&html, &css, &js, &design_system);
-        debug!("Mapped {} elements to ontology", ontology.elements.len());
+        let uses_tailwind = self.merge_tailwind(&html, &mut css, &design_system);
+        let mut ontology = self.ontology_mapper.map_full(&html, &css, &js, &design_system);
+        ontology.apply_directives(directives);
+        if uses_tailwind {
+            for element in &mut ontology.elements {
+                element.utilities = self.tailwind.resolve_classes(&element.classes);
+            }
+        }
+        self.apply_segments(&html, &design_system, uses_tailwind, &mut ontology);
+        debug!(
+            "Mapped {} elements and {} segments to ontology",
+            ontology.elements.len(),
+            ontology.segments.len()
+        );
 
         // Phase 4: Narsese generation
         debug!("Phase 4: Narsese generation");
         let narsese = self.narsese_generator.generate(&ontology);
         debug!("Generated {} Narsese statements", narsese.statements.len());
 
+        let a11y = A11yAuditor::new().audit(&html, &css, &ontology.token_graph);
+        debug!("Accessibility score: {:.2} ({} issues)", a11y.score, a11y.issues.len());
+
         // Phase 5: Embedding generation (optional, async)
         debug!("Phase 5: Embedding generation");
         let embedding = if self.config.generate_embeddings {
@@ -204,6 +287,19 @@ impl ExtractionPipeline {
         } else {
             None
         };
+        if self.config.generate_embeddings {
+            for segment in &mut ontology.segments {
+                let mapping = segment.mapping(ontology.design_system);
+                match self
+                    .embedding_generator
+                    .generate_element_embedding(&mapping, Some(&segment.html))
+                    .await
+                {
+                    Ok(emb) => segment.embedding = Some(emb.embedding),
+                    Err(e) => warn!("Segment embedding generation failed: {}", e),
+                }
+            }
+        }
 
         let processing_time_ms = start.elapsed().as_millis() as u64;
 
@@ -225,6 +321,7 @@ impl ExtractionPipeline {
             ontology,
             narsese,
             embedding,
+            a11y,
             processing_time_ms,
         })
     }
@@ -233,19 +330,20 @@ impl ExtractionPipeline {
     pub fn extract_sync(&mut self, input: ExtractionInput) -> anyhow::Result<ExtractionResult> {
         let start = Instant::now();
 
-        let html = self.html_parser.parse(&input.html)?;
-        let css = input
+        let mut html = self.html_parser.parse(&input.html)?;
+        let mut css = input
             .css
             .as_ref()
-            .map(|c| self.css_parser.parse(c))
+            .map(|c| self.css_parser.parse_lang(c, input.style_lang.as_deref()))
             .transpose()?
             .unwrap_or_else(|| CssStructure {
                 rules: vec![],
                 design_tokens: vec![],
                 properties: vec![],
                 selectors: vec![],
+                mixins: vec![],
             });
-        let js = input
+        let mut js = input
             .js
             .as_ref()
             .map(|j| self.js_parser.parse(j))
@@ -257,15 +355,26 @@ impl ExtractionPipeline {
                 variables: vec![],
                 dom_calls: vec![],
             });
+        let directives = Self::apply_template_directives(&input, &mut html, &mut js);
+        self.merge_jsx(&input, &mut html, &mut js)?;
 
         let design_system = self.design_detector.detect_from_content(
             &input.html,
             input.css.as_deref().unwrap_or(""),
-            input.js.as_deref().unwrap_or(""),
+            &input.script_source(),
         );
 
-        let ontology = self.ontology_mapper.map_full(&html, &css, &js, &design_system);
+        let uses_tailwind = self.merge_tailwind(&html, &mut css, &design_system);
+        let mut ontology = self.ontology_mapper.map_full(&html, &css, &js, &design_system);
+        ontology.apply_directives(directives);
+        if uses_tailwind {
+            for element in &mut ontology.elements {
+                element.utilities = self.tailwind.resolve_classes(&element.classes);
+            }
+        }
+        self.apply_segments(&html, &design_system, uses_tailwind, &mut ontology);
         let narsese = self.narsese_generator.generate(&ontology);
+        let a11y = A11yAuditor::new().audit(&html, &css, &ontology.token_graph);
 
         let processing_time_ms = start.elapsed().as_millis() as u64;
 
@@ -284,10 +393,108 @@ impl ExtractionPipeline {
             ontology,
             narsese,
             embedding: None,
+            a11y,
             processing_time_ms,
         })
     }
 
+    /// Detect sub-components and attach their and their atoms' tokens, utilities and fingerprints
+    fn apply_segments(
+        &self,
+        html: &HtmlStructure,
+        design_system: &DetectionResult,
+        uses_tailwind: bool,
+        ontology: &mut OntologyMapping,
+    ) {
+        let mut segments = self.segmenter.segment(html, design_system.design_system);
+        for segment in &mut segments {
+            if let Some(source) = html.element_at(&segment.path) {
+                segment.element.tokens_used = ontology.token_graph.tokens_for_element(source);
+                segment.element.fingerprint = Some(Fingerprint::compute(source, &segment.element.tokens_used));
+            }
+            for (atom, path) in segment.children.iter_mut().zip(&segment.child_paths) {
+                if let Some(source) = html.element_at(path) {
+                    atom.tokens_used = ontology.token_graph.tokens_for_element(source);
+                    atom.fingerprint = Some(Fingerprint::compute(source, &atom.tokens_used));
+                }
+            }
+            if uses_tailwind {
+                for mapped in std::iter::once(&mut segment.element).chain(segment.children.iter_mut()) {
+                    mapped.utilities = self.tailwind.resolve_classes(&mapped.classes);
+                }
+            }
+        }
+        ontology.segments = segments;
+    }
+
+    /// Add rules and theme tokens for Tailwind utility classes, so they feed
+    /// the token graph and accessibility audit. Returns whether it applied.
+    fn merge_tailwind(&self, html: &HtmlStructure, css: &mut CssStructure, design_system: &DetectionResult) -> bool {
+        if !matches!(design_system.design_system, DesignSystemType::Tailwind | DesignSystemType::Shadcn) {
+            return false;
+        }
+
+        let utilities = self.tailwind.to_css(&html.classes);
+        debug!("Resolved {} Tailwind utility rules", utilities.rules.len());
+        for token in utilities.design_tokens {
+            // Authored tokens win over theme defaults
+            if !css.design_tokens.iter().any(|t| t.name == token.name) {
+                css.design_tokens.push(token);
+            }
+        }
+        css.rules.extend(utilities.rules);
+        css.properties.extend(utilities.properties);
+        css.properties.sort();
+        css.properties.dedup();
+        css.selectors.extend(utilities.selectors);
+        css.selectors.sort();
+        css.selectors.dedup();
+        true
+    }
+
+    /// Unwrap framework template wrappers and turn directives into handlers.
+    /// Runs before JSX merging so directive root indices match the template.
+    fn apply_template_directives(
+        input: &ExtractionInput,
+        html: &mut HtmlStructure,
+        js: &mut JsStructure,
+    ) -> Vec<TemplateDirective> {
+        let Some(framework) = input.framework else {
+            return Vec::new();
+        };
+        let template = adapters::extract_directives(html, framework);
+        debug!(
+            "Found {} {} template directives",
+            template.directives.len(),
+            framework.as_str()
+        );
+        js.event_handlers.extend(template.event_handlers);
+        template.directives
+    }
+
+    /// Parse JSX input and merge its element trees, imports and handlers
+    fn merge_jsx(&mut self, input: &ExtractionInput, html: &mut HtmlStructure, js: &mut JsStructure) -> anyhow::Result<()> {
+        let Some(source) = input.jsx.as_deref() else {
+            return Ok(());
+        };
+        let jsx = self.jsx_parser.parse(source)?;
+        debug!("Extracted {} JSX components", jsx.components.len());
+
+        html.elements.extend(jsx.html.elements);
+        for (all, extra) in [
+            (&mut html.tags, jsx.html.tags),
+            (&mut html.classes, jsx.html.classes),
+            (&mut html.ids, jsx.html.ids),
+        ] {
+            all.extend(extra);
+            all.sort();
+            all.dedup();
+        }
+        js.imports.extend(jsx.imports);
+        js.event_handlers.extend(jsx.event_handlers);
+        Ok(())
+    }
+
     /// Get design system detector reference
     pub fn design_detector(&self) -> &DesignSystemDetector {
         &self.design_detector
@@ -318,6 +525,8 @@ pub use crate::narsese_gen::TruthValue;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::design_system::DesignSystemType;
+    use crate::ontology::UICategory;
 
     #[test]
     fn test_sync_extraction() {
@@ -345,6 +554,100 @@ mod tests {
         assert!(result.processing_time_ms < 1000); // Should be well under 1 second
     }
 
+    #[test]
+    fn test_jsx_extraction() {
+        let mut pipeline = ExtractionPipeline::new();
+
+        let input = ExtractionInput::from_jsx(
+            r#"
+            import { Button } from '@mui/material';
+
+            export default function Save() {
+                return <Button variant="contained" onClick={save}>Save</Button>;
+            }
+        "#,
+        );
+
+        let result = pipeline.extract_sync(input).unwrap();
+
+        assert_eq!(result.design_system.design_system, DesignSystemType::MaterialUI);
+        assert_eq!(result.ontology.elements.len(), 1);
+        assert_eq!(result.ontology.elements[0].category, UICategory::Button);
+        assert!(result.ontology.elements[0].has_interactivity);
+        assert_eq!(result.js.imports[0].source, "@mui/material");
+    }
+
+    #[test]
+    fn test_jsx_aliased_import_keeps_category() {
+        let mut pipeline = ExtractionPipeline::new();
+
+        let input = ExtractionInput::from_jsx(
+            r#"
+            import { Button as Btn } from '@mui/material';
+
+            export default function Save() {
+                return <Btn variant="contained">Save</Btn>;
+            }
+        "#,
+        );
+
+        let result = pipeline.extract_sync(input).unwrap();
+
+        assert_eq!(result.ontology.elements.len(), 1);
+        assert_eq!(result.ontology.elements[0].category, UICategory::Button);
+    }
+
+    #[test]
+    fn test_tailwind_extraction() {
+        let mut pipeline = ExtractionPipeline::new();
+
+        let input = ExtractionInput::new(
+            r#"<button class="bg-blue-500 hover:bg-blue-700 text-white font-bold md:px-4 py-2 rounded">Save</button>"#,
+        );
+        let result = pipeline.extract_sync(input).unwrap();
+
+        assert_eq!(result.design_system.design_system, DesignSystemType::Tailwind);
+        let element = &result.ontology.elements[0];
+        assert!(element.tokens_used.contains(&"--color-blue-500".to_string()));
+        let hover = element.utilities.iter().find(|u| u.class == "hover:bg-blue-700").unwrap();
+        assert_eq!((hover.property.as_str(), hover.value.as_str()), ("background-color", "#1d4ed8"));
+        assert!(result
+            .ontology
+            .token_graph
+            .get("--color-white", None)
+            .is_some_and(|t| t.resolved_value.as_deref() == Some("#ffffff")));
+    }
+
+    #[test]
+    fn test_vue_extraction() {
+        let mut pipeline = ExtractionPipeline::new();
+
+        let component = crate::adapters::ComponentSource::from_vue(
+            r#"
+            <template>
+                <ul class="list-group">
+                    <li v-for="item in items" class="list-group-item" @click="select(item)">{{ item }}</li>
+                </ul>
+                <div v-if="error" class="alert alert-danger">{{ error }}</div>
+            </template>
+            <style scoped>.list-group { margin: 0; }</style>
+        "#,
+        );
+
+        let result = pipeline.extract_sync(component.into()).unwrap();
+
+        assert_eq!(result.ontology.elements.len(), 2);
+        assert!(!result.ontology.elements[0].conditional);
+        assert!(result.ontology.elements[1].conditional);
+        assert_eq!(result.js.event_handlers[0].handler_name.as_deref(), Some("select"));
+        assert!(result.ontology.directives.iter().any(|d| d.directive == "v-for" && d.depth == 1));
+        assert!(result
+            .narsese
+            .statements
+            .iter()
+            .any(|s| s.statement.contains("[conditional]")));
+    }
+
     #[test]
     fn test_extraction_performance() {
         let mut pipeline = ExtractionPipeline::with_config(PipelineConfig {
@@ -0,0 +1,623 @@
+//! SCSS/Sass/Less flattening - preprocessor stylesheets as plain CSS rules
+//!
+//! tree-sitter-css rejects nesting, variables and mixins, so preprocessor
+//! sources are read with a small block scanner instead and flattened into
+//! the regular [`CssStructure`]: nested selectors are joined (`&` is replaced
+//! by the parent), SCSS `$name` and Less `@name` variables become
+//! [`DesignToken`]s next to custom properties, and `@include` / `.mixin()`
+//! calls are recorded as [`MixinUsage`]s. Values are kept as written, so
+//! variable references are resolved later by the token graph.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::css::{CssParser, CssProperty, CssRule, CssStructure, DesignToken, MixinUsage};
+
+/// `.mixin(...)` / `#ns.mixin(...)` definition or `when` guard (Less)
+static LESS_MIXIN_DEF_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[.#][\w-]+(?:\s*[.>]?\s*[.#][\w-]+)*\s*\(|\bwhen\b").unwrap());
+
+/// `#{...}` interpolation
+static INTERPOLATION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"#\{([^}]*)\}").unwrap());
+
+/// `!default` / `!global` variable flags
+static VARIABLE_FLAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*!(?:default|global)\b").unwrap());
+
+/// At-rules whose body is flattened into the enclosing context
+const TRANSPARENT_AT_RULES: &[&str] = &[
+    "media", "supports", "layer", "container", "at-root", "if", "else", "each", "for", "while", "document",
+];
+
+/// Stylesheet syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleSyntax {
+    Css,
+    Scss,
+    /// Indented Sass syntax, read as SCSS once braces are restored
+    Sass,
+    Less,
+}
+
+impl StyleSyntax {
+    /// Syntax for a `lang` attribute or file extension (`scss`, `sass`, `less`)
+    pub fn from_lang(lang: Option<&str>) -> Self {
+        match lang.map(|l| l.trim_start_matches('.').to_lowercase()).as_deref() {
+            Some("scss") => Self::Scss,
+            Some("sass") => Self::Sass,
+            Some("less") => Self::Less,
+            _ => Self::Css,
+        }
+    }
+}
+
+/// A parsed stylesheet node
+#[derive(Debug)]
+enum Item {
+    /// `prelude { ... }`
+    Block { prelude: String, children: Vec<Item> },
+    /// `text;`
+    Statement(String),
+}
+
+/// Flatten a SCSS/Sass/Less stylesheet into a [`CssStructure`]
+pub(crate) fn flatten(css: &CssParser, source: &str, syntax: StyleSyntax) -> CssStructure {
+    let mut cleaned = strip_comments(source);
+    let syntax = match syntax {
+        StyleSyntax::Sass => {
+            cleaned = braces_from_indentation(&cleaned);
+            StyleSyntax::Scss
+        }
+        other => other,
+    };
+    let mut chars = cleaned.chars().peekable();
+    let items = parse_items(&mut chars);
+
+    let mut flattener = Flattener {
+        css,
+        syntax,
+        structure: CssStructure {
+            rules: vec![],
+            design_tokens: vec![],
+            properties: vec![],
+            selectors: vec![],
+            mixins: vec![],
+        },
+    };
+    flattener.walk(&items, &[], None);
+
+    let structure = &mut flattener.structure;
+    structure.properties.sort();
+    structure.properties.dedup();
+    structure.selectors.sort();
+    structure.selectors.dedup();
+    flattener.structure
+}
+
+/// Rewrite indented Sass with braces and semicolons: a line opens a block
+/// when the next line is indented deeper, `=name` defines a mixin and
+/// `+name` includes one. Selector lists may continue after a trailing comma.
+fn braces_from_indentation(source: &str) -> String {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut continued: Option<(usize, String)> = None;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let (indent, text) = match continued.take() {
+            Some((indent, head)) => (indent, format!("{} {}", head, trimmed)),
+            None => (line.len() - line.trim_start().len(), trimmed.to_string()),
+        };
+        if text.ends_with(',') {
+            continued = Some((indent, text));
+            continue;
+        }
+        let text = if let Some(mixin) = text.strip_prefix('=') {
+            format!("@mixin {}", mixin.trim_start())
+        } else if let Some(include) = text.strip_prefix('+').filter(|r| r.starts_with(|c: char| c.is_alphabetic() || c == '_')) {
+            format!("@include {}", include)
+        } else {
+            text
+        };
+        lines.push((indent, text));
+    }
+    lines.extend(continued);
+
+    let mut out = String::with_capacity(source.len());
+    let mut open: Vec<usize> = Vec::new();
+    for (i, (indent, text)) in lines.iter().enumerate() {
+        while open.last().is_some_and(|outer| outer >= indent) {
+            open.pop();
+            out.push_str("}\n");
+        }
+        out.push_str(text);
+        if lines.get(i + 1).is_some_and(|(next, _)| next > indent) {
+            out.push_str(" {\n");
+            open.push(*indent);
+        } else {
+            out.push_str(";\n");
+        }
+    }
+    for _ in open {
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// Remove `/* */` comments and `//` line comments (outside strings and `url()`)
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+    let mut parens = 0usize;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                out.extend(chars.next());
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            ('/', Some('/')) if parens == 0 => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            _ => {
+                match c {
+                    '"' | '\'' => quote = Some(c),
+                    '(' => parens += 1,
+                    ')' => parens = parens.saturating_sub(1),
+                    _ => {}
+                }
+                out.push(c);
+            }
+        }
+    }
+
+    out
+}
+
+/// Parse items until the closing `}` of the current block (or end of input)
+fn parse_items(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut buffer = String::new();
+    let mut quote = None;
+    let mut parens = 0usize;
+
+    let flush = |buffer: &mut String, items: &mut Vec<Item>| {
+        let text = buffer.trim();
+        if !text.is_empty() {
+            items.push(Item::Statement(text.to_string()));
+        }
+        buffer.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            buffer.push(c);
+            if c == '\\' {
+                buffer.extend(chars.next());
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                buffer.push(c);
+            }
+            '#' if chars.peek() == Some(&'{') => {
+                // Interpolation braces are part of the text, not a block
+                buffer.push(c);
+                for c in chars.by_ref() {
+                    buffer.push(c);
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            '(' => {
+                parens += 1;
+                buffer.push(c);
+            }
+            ')' => {
+                parens = parens.saturating_sub(1);
+                buffer.push(c);
+            }
+            ';' if parens == 0 => flush(&mut buffer, &mut items),
+            '{' => {
+                let prelude = buffer.trim().to_string();
+                buffer.clear();
+                let children = parse_items(chars);
+                items.push(Item::Block { prelude, children });
+            }
+            '}' => break,
+            _ => buffer.push(c),
+        }
+    }
+
+    flush(&mut buffer, &mut items);
+    items
+}
+
+struct Flattener<'a> {
+    css: &'a CssParser,
+    syntax: StyleSyntax,
+    structure: CssStructure,
+}
+
+impl Flattener<'_> {
+    /// Flatten `items` nested under `parents` (flattened parent selectors)
+    fn walk(&mut self, items: &[Item], parents: &[String], theme: Option<&str>) {
+        let selector = (!parents.is_empty()).then(|| parents.join(", "));
+        let mut properties = Vec::new();
+
+        // Declarations first, so a rule is emitted before the rules nested in it
+        for item in items {
+            match item {
+                Item::Statement(text) => {
+                    self.statement(text, selector.as_deref(), theme, "", &mut properties);
+                }
+                Item::Block { prelude, children } => {
+                    if let Some(property) = prelude.strip_suffix(':') {
+                        // SCSS nested properties: `font: { family: x; }` → `font-family: x`
+                        let prefix = format!("{}-", property.trim());
+                        for child in children {
+                            if let Item::Statement(text) = child {
+                                self.statement(text, None, theme, &prefix, &mut properties);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(selector) = selector {
+            if !properties.is_empty() {
+                self.push_rule(selector, properties, theme);
+            }
+        }
+
+        for item in items {
+            if let Item::Block { prelude, children } = item {
+                if !prelude.ends_with(':') {
+                    self.block(prelude, children, parents, theme);
+                }
+            }
+        }
+    }
+
+    fn block(&mut self, prelude: &str, children: &[Item], parents: &[String], theme: Option<&str>) {
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name = at_rule.split(|c: char| !(c.is_alphanumeric() || c == '-')).next().unwrap_or("");
+            if name == "include" {
+                // `@include breakpoint(md) { ... }` - content block applies to the current rule
+                self.mixin(&at_rule["include".len()..], parents);
+                self.walk(children, parents, theme);
+            } else if TRANSPARENT_AT_RULES.contains(&name) {
+                let media_theme = (name == "media")
+                    .then(|| self.css.theme_from_media_query(at_rule))
+                    .flatten();
+                self.walk(children, parents, media_theme.as_deref().or(theme));
+            }
+            // `@mixin`, `@function`, `@keyframes`, `@font-face`, ... produce no rules
+            return;
+        }
+
+        if self.syntax == StyleSyntax::Less && LESS_MIXIN_DEF_RE.is_match(prelude) {
+            return;
+        }
+
+        let selectors = nest_selectors(parents, &interpolate(prelude));
+        if selectors.iter().all(|s| s.starts_with('%')) {
+            // SCSS placeholder selectors only exist for `@extend`
+            return;
+        }
+        let theme = self
+            .css
+            .theme_from_selector(&selectors.join(", "))
+            .or(theme.map(str::to_string));
+        self.walk(children, &selectors, theme.as_deref());
+    }
+
+    fn statement(
+        &mut self,
+        text: &str,
+        selector: Option<&str>,
+        theme: Option<&str>,
+        prefix: &str,
+        properties: &mut Vec<CssProperty>,
+    ) {
+        if let Some(include) = text.strip_prefix("@include") {
+            self.mixin(include, &selector.map(|s| vec![s.to_string()]).unwrap_or_default());
+            return;
+        }
+        if let Some((name, value)) = self.variable(text) {
+            let value = VARIABLE_FLAG_RE.replace_all(value, "").trim().to_string();
+            self.push_token(name.to_string(), value, theme);
+            return;
+        }
+        if text.starts_with('@') || text.starts_with('&') {
+            // `@use`, `@import`, `@extend`, `&:extend(...)`
+            return;
+        }
+        if self.syntax == StyleSyntax::Less && text.starts_with(['.', '#']) && is_less_mixin_call(text) {
+            let parents = selector.map(|s| vec![s.to_string()]).unwrap_or_default();
+            self.mixin(text, &parents);
+            return;
+        }
+
+        let Some((name, value)) = text.split_once(':') else {
+            return;
+        };
+        let name = format!("{}{}", prefix, interpolate(name.trim()));
+        let value = interpolate(value.trim());
+        if name.starts_with("--") {
+            self.push_token(name.clone(), value.clone(), theme);
+        }
+        self.structure.properties.push(name.clone());
+        properties.push(CssProperty { name, value });
+    }
+
+    /// `$name: value` (SCSS) or `@name: value` (Less)
+    fn variable<'t>(&self, text: &'t str) -> Option<(&'t str, &'t str)> {
+        let sigil = match self.syntax {
+            StyleSyntax::Less => '@',
+            _ => '$',
+        };
+        if !text.starts_with(sigil) {
+            return None;
+        }
+        let (name, value) = text.split_once(':')?;
+        let name = name.trim();
+        name[1..]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+            .then_some((name, value))
+    }
+
+    /// Record a mixin call: `name(args)` after `@include`, or a Less `.mixin(args)`
+    fn mixin(&mut self, call: &str, parents: &[String]) {
+        let call = call.trim().trim_end_matches("!important").trim();
+        let (name, arguments) = match call.find('(') {
+            Some(open) => (
+                &call[..open],
+                Some(call[open + 1..].trim_end().trim_end_matches(')').trim().to_string()),
+            ),
+            None => (call, None),
+        };
+        self.structure.mixins.push(MixinUsage {
+            name: name.trim().to_string(),
+            arguments: arguments.filter(|a| !a.is_empty()),
+            selector: (!parents.is_empty()).then(|| parents.join(", ")),
+        });
+    }
+
+    fn push_token(&mut self, name: String, value: String, theme: Option<&str>) {
+        let category = self.css.categorize_token(name.trim_start_matches(['$', '@']), &value);
+        self.structure.design_tokens.push(DesignToken {
+            name,
+            value,
+            category,
+            theme: theme.map(str::to_string),
+        });
+    }
+
+    fn push_rule(&mut self, selector: String, properties: Vec<CssProperty>, theme: Option<&str>) {
+        self.structure.selectors.push(selector.clone());
+        self.structure.rules.push(CssRule {
+            selector,
+            properties,
+            theme: theme.map(str::to_string),
+        });
+    }
+}
+
+/// Combine a nested selector list with its parents, replacing `&`
+fn nest_selectors(parents: &[String], prelude: &str) -> Vec<String> {
+    let children = split_top_level(prelude);
+    if parents.is_empty() {
+        return children.iter().map(|c| c.replace('&', "")).collect();
+    }
+
+    let mut out = Vec::new();
+    for parent in parents {
+        for child in &children {
+            if child.contains('&') {
+                out.push(child.replace('&', parent));
+            } else {
+                out.push(format!("{} {}", parent, child));
+            }
+        }
+    }
+    out
+}
+
+/// Split a selector list on commas outside parentheses/brackets
+fn split_top_level(list: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in list.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(current.split_whitespace().collect::<Vec<_>>().join(" "));
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current.split_whitespace().collect::<Vec<_>>().join(" "));
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+/// `.text-#{$name}` → `.text-$name`
+fn interpolate(text: &str) -> String {
+    INTERPOLATION_RE.replace_all(text, "$1").into_owned()
+}
+
+/// `.mixin;`, `.mixin();`, `#ns > .mixin(@a);` - a selector-like statement with no declaration colon
+fn is_less_mixin_call(text: &str) -> bool {
+    let head = &text[..text.find('(').unwrap_or(text.len())];
+    !head.contains(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule<'a>(structure: &'a CssStructure, selector: &str) -> &'a CssRule {
+        structure
+            .rules
+            .iter()
+            .find(|r| r.selector == selector)
+            .unwrap_or_else(|| panic!("no rule for {selector}: {:?}", structure.selectors))
+    }
+
+    #[test]
+    fn test_flatten_scss() {
+        let scss = r#"
+            @use "sass:math";
+            // Brand colors
+            $blue: #0d6efd !default;
+            $primary: $blue !default;
+            $spacer: 1rem;
+
+            @mixin button-variant($background, $border) {
+                background: $background;
+            }
+
+            .btn {
+                padding: $spacer math.div($spacer, 2);
+                @include button-variant($primary, $primary);
+
+                &:hover { color: #fff; }
+                &-lg, .group & { font: { size: 1.25rem; weight: 500; } }
+
+                .icon { margin-right: #{$spacer}; }
+
+                @media (prefers-color-scheme: dark) {
+                    background: #212529;
+                }
+            }
+
+            %placeholder { color: red; }
+        "#;
+
+        let result = CssParser::new().parse_lang(scss, Some("scss")).unwrap();
+
+        let tokens: Vec<_> = result.design_tokens.iter().map(|t| (t.name.as_str(), t.value.as_str())).collect();
+        assert_eq!(tokens, vec![("$blue", "#0d6efd"), ("$primary", "$blue"), ("$spacer", "1rem")]);
+
+        assert_eq!(rule(&result, ".btn").properties[0].value, "$spacer math.div($spacer, 2)");
+        assert_eq!(rule(&result, ".btn:hover").properties[0].name, "color");
+        let large = rule(&result, ".btn-lg, .group .btn");
+        assert_eq!(large.properties[0].name, "font-size");
+        assert_eq!(rule(&result, ".btn .icon").properties[0].value, "$spacer");
+        assert!(result.rules.iter().any(|r| r.selector == ".btn" && r.theme.as_deref() == Some("dark")));
+        assert!(!result.selectors.iter().any(|s| s.starts_with('%')));
+
+        assert_eq!(result.mixins.len(), 1);
+        assert_eq!(result.mixins[0].name, "button-variant");
+        assert_eq!(result.mixins[0].arguments.as_deref(), Some("$primary, $primary"));
+        assert_eq!(result.mixins[0].selector.as_deref(), Some(".btn"));
+
+        // Variable references resolve through the token graph
+        let graph = crate::tokens::TokenGraph::from_css(&result);
+        assert_eq!(graph.get("$primary", None).unwrap().resolved_value.as_deref(), Some("#0d6efd"));
+        assert_eq!(graph.get("$primary", None).unwrap().alias_of.as_deref(), Some("$blue"));
+    }
+
+    #[test]
+    fn test_flatten_sass() {
+        let sass = "
+// Indented syntax
+$brand: #0af
+$accent: $brand
+
+=rounded($radius)
+  border-radius: $radius
+
+.btn
+  color: $brand
+  +rounded(4px)
+  &:hover
+    color: #fff
+  .icon
+    margin-right: 0.5rem
+
+h1,
+h2
+  font-weight: 600
+";
+
+        let result = CssParser::new().parse_lang(sass, Some("sass")).unwrap();
+
+        let tokens: Vec<_> = result.design_tokens.iter().map(|t| (t.name.as_str(), t.value.as_str())).collect();
+        assert_eq!(tokens, vec![("$brand", "#0af"), ("$accent", "$brand")]);
+        assert_eq!(rule(&result, ".btn").properties[0].value, "$brand");
+        assert_eq!(rule(&result, ".btn:hover").properties[0].value, "#fff");
+        assert_eq!(rule(&result, ".btn .icon").properties[0].name, "margin-right");
+        assert_eq!(rule(&result, "h1, h2").properties[0].name, "font-weight");
+        assert_eq!(result.mixins.len(), 1);
+        assert_eq!(result.mixins[0].name, "rounded");
+        assert_eq!(result.mixins[0].arguments.as_deref(), Some("4px"));
+        assert_eq!(result.mixins[0].selector.as_deref(), Some(".btn"));
+    }
+
+    #[test]
+    fn test_flatten_less() {
+        let less = r#"
+            @import (reference) "variables.less";
+            @brand: #337ab7;
+            @link-color: @brand;
+
+            .bordered(@width: 2px) {
+                border: @width solid black;
+            }
+
+            #header {
+                color: @link-color;
+                .navigation { font-size: 12px; }
+                .bordered(4px);
+                &:extend(.banner all);
+            }
+        "#;
+
+        let result = CssParser::new().parse_lang(less, Some("less")).unwrap();
+
+        let names: Vec<_> = result.design_tokens.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["@brand", "@link-color"]);
+        assert_eq!(result.selectors, vec!["#header", "#header .navigation"]);
+        assert_eq!(rule(&result, "#header").properties.len(), 1);
+        assert_eq!(result.mixins[0].name, ".bordered");
+        assert_eq!(result.mixins[0].arguments.as_deref(), Some("4px"));
+
+        let graph = crate::tokens::TokenGraph::from_css(&result);
+        assert_eq!(graph.resolve_value("@link-color", None).as_deref(), Some("#337ab7"));
+    }
+}
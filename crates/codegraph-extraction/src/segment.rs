@@ -0,0 +1,375 @@
+//! Sub-component segmentation
+//!
+//! Splits large snippets (full pages, layouts) into reusable components.
+//! Boundaries are detected from ARIA landmarks, BEM blocks, component-like
+//! class roots (`card`, `navbar`, `product-card`) and repeated sibling
+//! structures. Each segment becomes a composite element owning the atomic
+//! elements inside it; nested segments are linked to their parent.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::design_system::DesignSystemType;
+use crate::html::{HtmlElement, HtmlStructure};
+use crate::ontology::{MappedElement, OntologyMapper, OntologyMapping, UICategory};
+use crate::tokens::TokenGraph;
+
+/// `element_type` given to segment elements
+pub const COMPOSITE_ELEMENT_TYPE: &str = "composite";
+
+/// Landmark tags that always start a segment
+const LANDMARK_TAGS: &[&str] = &["header", "nav", "main", "footer", "aside", "section", "form", "dialog"];
+
+/// Landmark ARIA roles that always start a segment
+const LANDMARK_ROLES: &[&str] = &[
+    "banner",
+    "navigation",
+    "main",
+    "contentinfo",
+    "complementary",
+    "region",
+    "search",
+    "form",
+    "dialog",
+];
+
+/// Class roots that name a component (`card`, `card--featured`, `product-card`)
+const COMPONENT_CLASSES: &[&str] = &[
+    "accordion",
+    "alert",
+    "banner",
+    "breadcrumb",
+    "card",
+    "carousel",
+    "dropdown",
+    "footer",
+    "header",
+    "hero",
+    "jumbotron",
+    "list-group",
+    "media",
+    "menu",
+    "modal",
+    "navbar",
+    "pagination",
+    "panel",
+    "sidebar",
+    "tabs",
+    "tile",
+    "toast",
+    "toolbar",
+];
+
+/// Why an element was chosen as a segment boundary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentReason {
+    Landmark,
+    BemBlock,
+    ComponentClass,
+    Repeated,
+}
+
+/// A detected sub-component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    /// Component name (class root, BEM block, role or tag)
+    pub name: String,
+    pub reason: SegmentReason,
+    /// Composite element for the segment root
+    pub element: MappedElement,
+    /// Atomic elements owned by this segment (excluding nested segments)
+    pub children: Vec<MappedElement>,
+    /// Child-index path of each atom in `children`
+    #[serde(default)]
+    pub child_paths: Vec<Vec<usize>>,
+    /// Index of the enclosing segment, if nested
+    pub parent: Option<usize>,
+    /// Child-index path from the snippet roots (`[root, child, ...]`)
+    pub path: Vec<usize>,
+    /// HTML template of the segment subtree
+    pub html: String,
+    /// Embedding of the segment (set by the pipeline)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl Segment {
+    /// Ontology mapping of the segment alone, used to embed it
+    pub fn mapping(&self, design_system: Option<DesignSystemType>) -> OntologyMapping {
+        let elements: Vec<MappedElement> = std::iter::once(self.element.clone())
+            .chain(self.children.iter().cloned())
+            .collect();
+        let mut categories_used: Vec<UICategory> = Vec::new();
+        for element in &elements {
+            if !categories_used.contains(&element.category) {
+                categories_used.push(element.category);
+            }
+        }
+
+        OntologyMapping {
+            elements,
+            categories_used,
+            design_tokens: Vec::new(),
+            design_system,
+            token_graph: TokenGraph::default(),
+            directives: Vec::new(),
+            segments: Vec::new(),
+        }
+    }
+}
+
+/// Detects component boundaries inside a parsed HTML structure
+pub struct Segmenter {
+    mapper: OntologyMapper,
+    min_repeats: usize,
+}
+
+impl Default for Segmenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Segmenter {
+    pub fn new() -> Self {
+        Self {
+            mapper: OntologyMapper::new(),
+            min_repeats: 2,
+        }
+    }
+
+    /// Minimum number of structurally identical siblings treated as repeated components
+    pub fn with_min_repeats(mut self, min_repeats: usize) -> Self {
+        self.min_repeats = min_repeats.max(2);
+        self
+    }
+
+    /// Segment a snippet.
+    ///
+    /// A snippet with a single root is already one component, so its root is
+    /// never a segment itself; only its descendants are considered.
+    pub fn segment(&self, html: &HtmlStructure, design_system: DesignSystemType) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let single_root = html.elements.len() == 1;
+        let repeated = self.repeated_siblings(&html.elements);
+
+        for (index, element) in html.elements.iter().enumerate() {
+            let boundary = if single_root {
+                None
+            } else {
+                self.boundary(element, repeated[index])
+            };
+            self.visit(element, vec![index], boundary, None, design_system, &mut segments);
+        }
+        segments
+    }
+
+    fn visit(
+        &self,
+        element: &HtmlElement,
+        path: Vec<usize>,
+        boundary: Option<(SegmentReason, String)>,
+        parent: Option<usize>,
+        design_system: DesignSystemType,
+        segments: &mut Vec<Segment>,
+    ) {
+        let owner = match boundary {
+            Some((reason, name)) => {
+                let mut composite = self.map(element, design_system);
+                composite.element_type = COMPOSITE_ELEMENT_TYPE.to_string();
+                segments.push(Segment {
+                    name,
+                    reason,
+                    element: composite,
+                    children: Vec::new(),
+                    child_paths: Vec::new(),
+                    parent,
+                    path: path.clone(),
+                    html: element.to_html(),
+                    embedding: None,
+                });
+                Some(segments.len() - 1)
+            }
+            None => {
+                if let Some(owner) = parent {
+                    let mapped = self.map(element, design_system);
+                    if mapped.category != UICategory::Unknown {
+                        segments[owner].element.children_categories.push(mapped.category);
+                        segments[owner].children.push(mapped);
+                        segments[owner].child_paths.push(path.clone());
+                    }
+                }
+                parent
+            }
+        };
+
+        let repeated = self.repeated_siblings(&element.children);
+        for (index, child) in element.children.iter().enumerate() {
+            let mut child_path = path.clone();
+            child_path.push(index);
+            let boundary = self.boundary(child, repeated[index]);
+            self.visit(child, child_path, boundary, owner, design_system, segments);
+        }
+    }
+
+    fn map(&self, element: &HtmlElement, design_system: DesignSystemType) -> MappedElement {
+        self.mapper
+            .map_element(&element.tag, &element.classes, &element.attributes, design_system)
+    }
+
+    /// Reason and name if `element` starts a segment
+    fn boundary(&self, element: &HtmlElement, repeated: bool) -> Option<(SegmentReason, String)> {
+        let role = element
+            .attributes
+            .iter()
+            .find(|(name, _)| name == "role")
+            .map(|(_, value)| value.as_str());
+        if let Some(role) = role.filter(|r| LANDMARK_ROLES.contains(r)) {
+            return Some((SegmentReason::Landmark, role.to_string()));
+        }
+        if LANDMARK_TAGS.contains(&element.tag.as_str()) {
+            return Some((SegmentReason::Landmark, element.tag.clone()));
+        }
+
+        if let Some(block) = element.classes.iter().find(|class| is_bem_block(class, element)) {
+            return Some((SegmentReason::BemBlock, block.clone()));
+        }
+
+        if let Some(class) = element.classes.iter().find_map(|class| component_class(class)) {
+            return Some((SegmentReason::ComponentClass, class.to_string()));
+        }
+
+        if repeated {
+            let name = element.classes.first().unwrap_or(&element.tag).clone();
+            return Some((SegmentReason::Repeated, name));
+        }
+
+        None
+    }
+
+    /// Flags siblings sharing a structural signature at least `min_repeats` times
+    fn repeated_siblings(&self, siblings: &[HtmlElement]) -> Vec<bool> {
+        let signatures: Vec<Option<String>> = siblings.iter().map(signature).collect();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for signature in signatures.iter().flatten() {
+            *counts.entry(signature).or_default() += 1;
+        }
+        signatures
+            .iter()
+            .map(|s| s.as_deref().is_some_and(|s| counts[s] >= self.min_repeats))
+            .collect()
+    }
+}
+
+/// Tag, classes and child tags of a non-leaf element
+fn signature(element: &HtmlElement) -> Option<String> {
+    if element.children.is_empty() {
+        return None;
+    }
+    let mut classes = element.classes.clone();
+    classes.sort();
+    let children: Vec<&str> = element.children.iter().map(|c| c.tag.as_str()).collect();
+    Some(format!("{}.{}>{}", element.tag, classes.join("."), children.join(",")))
+}
+
+/// `class` is a BEM block: a plain class with `class__element` descendants
+fn is_bem_block(class: &str, element: &HtmlElement) -> bool {
+    if class.contains("__") || class.contains("--") {
+        return false;
+    }
+    let prefix = format!("{}__", class);
+    fn has_element(element: &HtmlElement, prefix: &str) -> bool {
+        element
+            .children
+            .iter()
+            .any(|c| c.classes.iter().any(|class| class.starts_with(prefix)) || has_element(c, prefix))
+    }
+    has_element(element, &prefix)
+}
+
+/// Component name if `class` is a component class root
+///
+/// `card`, `card--featured` and `product-card` qualify; parts such as
+/// `card-body` or `card__title` do not.
+fn component_class(class: &str) -> Option<&str> {
+    let root = class.split("--").next().unwrap_or(class);
+    if root.contains("__") {
+        return None;
+    }
+    COMPONENT_CLASSES
+        .iter()
+        .any(|name| root == *name || root.ends_with(&format!("-{}", name)))
+        .then_some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::HtmlParser;
+
+    fn segment(html: &str) -> Vec<Segment> {
+        let structure = HtmlParser::new().parse(html).unwrap();
+        Segmenter::new().segment(&structure, DesignSystemType::Custom)
+    }
+
+    #[test]
+    fn test_segment_page() {
+        let segments = segment(
+            r#"<body>
+                <nav class="navbar"><a href="/">Home</a><button class="btn">Menu</button></nav>
+                <div class="grid">
+                    <div class="product-card"><img src="a.png"><h3>A</h3><button>Buy</button></div>
+                    <div class="product-card"><img src="b.png"><h3>B</h3><button>Buy</button></div>
+                </div>
+                <div class="search"><div class="search__field"><input type="text"></div></div>
+            </body>"#,
+        );
+
+        let summary: Vec<_> = segments.iter().map(|s| (s.name.as_str(), s.reason, s.parent)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("nav", SegmentReason::Landmark, None),
+                ("product-card", SegmentReason::ComponentClass, None),
+                ("product-card", SegmentReason::ComponentClass, None),
+                ("search", SegmentReason::BemBlock, None),
+            ]
+        );
+
+        let nav = &segments[0];
+        assert_eq!(nav.element.element_type, COMPOSITE_ELEMENT_TYPE);
+        assert_eq!(nav.element.category, UICategory::Navigation);
+        let categories: Vec<_> = nav.children.iter().map(|c| c.category).collect();
+        assert_eq!(categories, vec![UICategory::Link, UICategory::Button]);
+        assert!(nav.html.starts_with(r#"<nav class="navbar"><a href="/">Home</a>"#));
+
+        assert_eq!(segments[1].path, vec![0, 1, 0]);
+        assert_eq!(segments[1].children.len(), 3);
+        assert_eq!(segments[3].children[0].category, UICategory::Input);
+    }
+
+    #[test]
+    fn test_repeated_and_nested_segments() {
+        let segments = segment(
+            r#"<main>
+                <ul><li class="item"><span>1</span><a href="/1">One</a></li><li class="item"><span>2</span><a href="/2">Two</a></li></ul>
+            </main><footer><p>Footer</p></footer>"#,
+        );
+
+        let summary: Vec<_> = segments.iter().map(|s| (s.name.as_str(), s.reason, s.parent)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("main", SegmentReason::Landmark, None),
+                ("item", SegmentReason::Repeated, Some(0)),
+                ("item", SegmentReason::Repeated, Some(0)),
+                ("footer", SegmentReason::Landmark, None),
+            ]
+        );
+        // The list belongs to `main`; the list items own their own atoms
+        assert_eq!(segments[0].children.len(), 1);
+        assert_eq!(segments[1].children.len(), 2);
+        assert_eq!(segments[1].child_paths, vec![vec![0, 0, 0, 0], vec![0, 0, 0, 1]]);
+    }
+}
@@ -0,0 +1,993 @@
+//! Tailwind utility resolver - expands utility classes into CSS declarations
+//!
+//! `bg-blue-500 hover:bg-blue-700 md:px-4` becomes (variants, breakpoint,
+//! property, value) tuples using the default Tailwind theme, optionally
+//! overridden by a project `tailwind.config` exported as JSON. Resolved
+//! classes can also be emitted as a synthetic [`CssStructure`] whose values
+//! reference theme tokens (`var(--color-blue-500)`), so the token graph,
+//! accessibility audit and ontology treat Tailwind markup like authored CSS.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::css::{CssProperty, CssRule, CssStructure, DesignToken, TokenCategory};
+
+/// Default color palette, shades 50-950
+const PALETTE: &[(&str, [&str; 11])] = &[
+    ("slate", ["#f8fafc", "#f1f5f9", "#e2e8f0", "#cbd5e1", "#94a3b8", "#64748b", "#475569", "#334155", "#1e293b", "#0f172a", "#020617"]),
+    ("gray", ["#f9fafb", "#f3f4f6", "#e5e7eb", "#d1d5db", "#9ca3af", "#6b7280", "#4b5563", "#374151", "#1f2937", "#111827", "#030712"]),
+    ("zinc", ["#fafafa", "#f4f4f5", "#e4e4e7", "#d4d4d8", "#a1a1aa", "#71717a", "#52525b", "#3f3f46", "#27272a", "#18181b", "#09090b"]),
+    ("neutral", ["#fafafa", "#f5f5f5", "#e5e5e5", "#d4d4d4", "#a3a3a3", "#737373", "#525252", "#404040", "#262626", "#171717", "#0a0a0a"]),
+    ("stone", ["#fafaf9", "#f5f5f4", "#e7e5e4", "#d6d3d1", "#a8a29e", "#78716c", "#57534e", "#44403c", "#292524", "#1c1917", "#0c0a09"]),
+    ("red", ["#fef2f2", "#fee2e2", "#fecaca", "#fca5a5", "#f87171", "#ef4444", "#dc2626", "#b91c1c", "#991b1b", "#7f1d1d", "#450a0a"]),
+    ("orange", ["#fff7ed", "#ffedd5", "#fed7aa", "#fdba74", "#fb923c", "#f97316", "#ea580c", "#c2410c", "#9a3412", "#7c2d12", "#431407"]),
+    ("amber", ["#fffbeb", "#fef3c7", "#fde68a", "#fcd34d", "#fbbf24", "#f59e0b", "#d97706", "#b45309", "#92400e", "#78350f", "#451a03"]),
+    ("yellow", ["#fefce8", "#fef9c3", "#fef08a", "#fde047", "#facc15", "#eab308", "#ca8a04", "#a16207", "#854d0e", "#713f12", "#422006"]),
+    ("lime", ["#f7fee7", "#ecfccb", "#d9f99d", "#bef264", "#a3e635", "#84cc16", "#65a30d", "#4d7c0f", "#3f6212", "#365314", "#1a2e05"]),
+    ("green", ["#f0fdf4", "#dcfce7", "#bbf7d0", "#86efac", "#4ade80", "#22c55e", "#16a34a", "#15803d", "#166534", "#14532d", "#052e16"]),
+    ("emerald", ["#ecfdf5", "#d1fae5", "#a7f3d0", "#6ee7b7", "#34d399", "#10b981", "#059669", "#047857", "#065f46", "#064e3b", "#022c22"]),
+    ("teal", ["#f0fdfa", "#ccfbf1", "#99f6e4", "#5eead4", "#2dd4bf", "#14b8a6", "#0d9488", "#0f766e", "#115e59", "#134e4a", "#042f2e"]),
+    ("cyan", ["#ecfeff", "#cffafe", "#a5f3fc", "#67e8f9", "#22d3ee", "#06b6d4", "#0891b2", "#0e7490", "#155e75", "#164e63", "#083344"]),
+    ("sky", ["#f0f9ff", "#e0f2fe", "#bae6fd", "#7dd3fc", "#38bdf8", "#0ea5e9", "#0284c7", "#0369a1", "#075985", "#0c4a6e", "#082f49"]),
+    ("blue", ["#eff6ff", "#dbeafe", "#bfdbfe", "#93c5fd", "#60a5fa", "#3b82f6", "#2563eb", "#1d4ed8", "#1e40af", "#1e3a8a", "#172554"]),
+    ("indigo", ["#eef2ff", "#e0e7ff", "#c7d2fe", "#a5b4fc", "#818cf8", "#6366f1", "#4f46e5", "#4338ca", "#3730a3", "#312e81", "#1e1b4b"]),
+    ("violet", ["#f5f3ff", "#ede9fe", "#ddd6fe", "#c4b5fd", "#a78bfa", "#8b5cf6", "#7c3aed", "#6d28d9", "#5b21b6", "#4c1d95", "#2e1065"]),
+    ("purple", ["#faf5ff", "#f3e8ff", "#e9d5ff", "#d8b4fe", "#c084fc", "#a855f7", "#9333ea", "#7e22ce", "#6b21a8", "#581c87", "#3b0764"]),
+    ("fuchsia", ["#fdf4ff", "#fae8ff", "#f5d0fe", "#f0abfc", "#e879f9", "#d946ef", "#c026d3", "#a21caf", "#86198f", "#701a75", "#4a044e"]),
+    ("pink", ["#fdf2f8", "#fce7f3", "#fbcfe8", "#f9a8d4", "#f472b6", "#ec4899", "#db2777", "#be185d", "#9d174d", "#831843", "#500724"]),
+    ("rose", ["#fff1f2", "#ffe4e6", "#fecdd3", "#fda4af", "#fb7185", "#f43f5e", "#e11d48", "#be123c", "#9f1239", "#881337", "#4c0519"]),
+];
+
+const SHADES: [&str; 11] = ["50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950"];
+
+/// Spacing scale keys (value = key × 0.25rem)
+const SPACING_SCALE: &[f32] = &[
+    0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 14.0, 16.0, 20.0, 24.0,
+    28.0, 32.0, 36.0, 40.0, 44.0, 48.0, 52.0, 56.0, 60.0, 64.0, 72.0, 80.0, 96.0,
+];
+
+const FONT_SIZES: &[(&str, &str)] = &[
+    ("xs", "0.75rem"), ("sm", "0.875rem"), ("base", "1rem"), ("lg", "1.125rem"), ("xl", "1.25rem"),
+    ("2xl", "1.5rem"), ("3xl", "1.875rem"), ("4xl", "2.25rem"), ("5xl", "3rem"), ("6xl", "3.75rem"),
+    ("7xl", "4.5rem"), ("8xl", "6rem"), ("9xl", "8rem"),
+];
+
+const FONT_WEIGHTS: &[(&str, &str)] = &[
+    ("thin", "100"), ("extralight", "200"), ("light", "300"), ("normal", "400"), ("medium", "500"),
+    ("semibold", "600"), ("bold", "700"), ("extrabold", "800"), ("black", "900"),
+];
+
+/// Border radius scale; `""` is the bare `rounded` class
+const BORDER_RADIUS: &[(&str, &str)] = &[
+    ("none", "0px"), ("sm", "0.125rem"), ("", "0.25rem"), ("md", "0.375rem"), ("lg", "0.5rem"),
+    ("xl", "0.75rem"), ("2xl", "1rem"), ("3xl", "1.5rem"), ("full", "9999px"),
+];
+
+/// Box shadow scale; `""` is the bare `shadow` class
+const BOX_SHADOWS: &[(&str, &str)] = &[
+    ("sm", "0 1px 2px 0 rgb(0 0 0 / 0.05)"),
+    ("", "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)"),
+    ("md", "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)"),
+    ("lg", "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)"),
+    ("xl", "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)"),
+    ("2xl", "0 25px 50px -12px rgb(0 0 0 / 0.25)"),
+    ("inner", "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)"),
+    ("none", "0 0 #0000"),
+];
+
+const SCREENS: &[(&str, &str)] = &[("sm", "640px"), ("md", "768px"), ("lg", "1024px"), ("xl", "1280px"), ("2xl", "1536px")];
+
+/// Keyword utilities that map to fixed declarations
+const STATIC_UTILITIES: &[(&str, &str, &str)] = &[
+    ("block", "display", "block"),
+    ("inline-block", "display", "inline-block"),
+    ("inline", "display", "inline"),
+    ("flex", "display", "flex"),
+    ("inline-flex", "display", "inline-flex"),
+    ("grid", "display", "grid"),
+    ("inline-grid", "display", "inline-grid"),
+    ("contents", "display", "contents"),
+    ("table", "display", "table"),
+    ("hidden", "display", "none"),
+    ("static", "position", "static"),
+    ("fixed", "position", "fixed"),
+    ("absolute", "position", "absolute"),
+    ("relative", "position", "relative"),
+    ("sticky", "position", "sticky"),
+    ("flex-row", "flex-direction", "row"),
+    ("flex-row-reverse", "flex-direction", "row-reverse"),
+    ("flex-col", "flex-direction", "column"),
+    ("flex-col-reverse", "flex-direction", "column-reverse"),
+    ("flex-wrap", "flex-wrap", "wrap"),
+    ("flex-nowrap", "flex-wrap", "nowrap"),
+    ("flex-1", "flex", "1 1 0%"),
+    ("flex-auto", "flex", "1 1 auto"),
+    ("flex-initial", "flex", "0 1 auto"),
+    ("flex-none", "flex", "none"),
+    ("grow", "flex-grow", "1"),
+    ("grow-0", "flex-grow", "0"),
+    ("shrink", "flex-shrink", "1"),
+    ("shrink-0", "flex-shrink", "0"),
+    ("items-start", "align-items", "flex-start"),
+    ("items-end", "align-items", "flex-end"),
+    ("items-center", "align-items", "center"),
+    ("items-baseline", "align-items", "baseline"),
+    ("items-stretch", "align-items", "stretch"),
+    ("justify-start", "justify-content", "flex-start"),
+    ("justify-end", "justify-content", "flex-end"),
+    ("justify-center", "justify-content", "center"),
+    ("justify-between", "justify-content", "space-between"),
+    ("justify-around", "justify-content", "space-around"),
+    ("justify-evenly", "justify-content", "space-evenly"),
+    ("self-auto", "align-self", "auto"),
+    ("self-start", "align-self", "flex-start"),
+    ("self-end", "align-self", "flex-end"),
+    ("self-center", "align-self", "center"),
+    ("self-stretch", "align-self", "stretch"),
+    ("text-left", "text-align", "left"),
+    ("text-center", "text-align", "center"),
+    ("text-right", "text-align", "right"),
+    ("text-justify", "text-align", "justify"),
+    ("font-sans", "font-family", "ui-sans-serif, system-ui, sans-serif"),
+    ("font-serif", "font-family", "ui-serif, Georgia, serif"),
+    ("font-mono", "font-family", "ui-monospace, monospace"),
+    ("italic", "font-style", "italic"),
+    ("not-italic", "font-style", "normal"),
+    ("underline", "text-decoration-line", "underline"),
+    ("line-through", "text-decoration-line", "line-through"),
+    ("no-underline", "text-decoration-line", "none"),
+    ("uppercase", "text-transform", "uppercase"),
+    ("lowercase", "text-transform", "lowercase"),
+    ("capitalize", "text-transform", "capitalize"),
+    ("truncate", "overflow", "hidden"),
+    ("truncate", "text-overflow", "ellipsis"),
+    ("truncate", "white-space", "nowrap"),
+    ("whitespace-nowrap", "white-space", "nowrap"),
+    ("leading-none", "line-height", "1"),
+    ("leading-tight", "line-height", "1.25"),
+    ("leading-snug", "line-height", "1.375"),
+    ("leading-normal", "line-height", "1.5"),
+    ("leading-relaxed", "line-height", "1.625"),
+    ("leading-loose", "line-height", "2"),
+    ("tracking-tight", "letter-spacing", "-0.025em"),
+    ("tracking-normal", "letter-spacing", "0em"),
+    ("tracking-wide", "letter-spacing", "0.025em"),
+    ("overflow-auto", "overflow", "auto"),
+    ("overflow-hidden", "overflow", "hidden"),
+    ("overflow-scroll", "overflow", "scroll"),
+    ("overflow-x-auto", "overflow-x", "auto"),
+    ("overflow-y-auto", "overflow-y", "auto"),
+    ("border-solid", "border-style", "solid"),
+    ("border-dashed", "border-style", "dashed"),
+    ("border-dotted", "border-style", "dotted"),
+    ("border-none", "border-style", "none"),
+    ("cursor-pointer", "cursor", "pointer"),
+    ("cursor-not-allowed", "cursor", "not-allowed"),
+    ("pointer-events-none", "pointer-events", "none"),
+    ("select-none", "user-select", "none"),
+    ("outline-none", "outline", "2px solid transparent"),
+    ("ring", "box-shadow", "0 0 0 3px var(--tw-ring-color)"),
+    ("transition", "transition-property", "color, background-color, border-color, text-decoration-color, fill, stroke, opacity, box-shadow, transform, filter"),
+    ("transition-colors", "transition-property", "color, background-color, border-color, text-decoration-color, fill, stroke"),
+    ("transition-all", "transition-property", "all"),
+    ("sr-only", "position", "absolute"),
+    ("sr-only", "width", "1px"),
+    ("sr-only", "height", "1px"),
+    ("sr-only", "overflow", "hidden"),
+    ("container", "width", "100%"),
+];
+
+/// Utility prefixes whose value comes from the spacing scale
+const SPACING_UTILITIES: &[(&str, &[&str])] = &[
+    ("p", &["padding"]),
+    ("px", &["padding-left", "padding-right"]),
+    ("py", &["padding-top", "padding-bottom"]),
+    ("pt", &["padding-top"]),
+    ("pr", &["padding-right"]),
+    ("pb", &["padding-bottom"]),
+    ("pl", &["padding-left"]),
+    ("ps", &["padding-inline-start"]),
+    ("pe", &["padding-inline-end"]),
+    ("m", &["margin"]),
+    ("mx", &["margin-left", "margin-right"]),
+    ("my", &["margin-top", "margin-bottom"]),
+    ("mt", &["margin-top"]),
+    ("mr", &["margin-right"]),
+    ("mb", &["margin-bottom"]),
+    ("ml", &["margin-left"]),
+    ("ms", &["margin-inline-start"]),
+    ("me", &["margin-inline-end"]),
+    ("gap", &["gap"]),
+    ("gap-x", &["column-gap"]),
+    ("gap-y", &["row-gap"]),
+    ("w", &["width"]),
+    ("h", &["height"]),
+    ("size", &["width", "height"]),
+    ("min-w", &["min-width"]),
+    ("min-h", &["min-height"]),
+    ("max-h", &["max-height"]),
+    ("inset", &["inset"]),
+    ("inset-x", &["left", "right"]),
+    ("inset-y", &["top", "bottom"]),
+    ("top", &["top"]),
+    ("right", &["right"]),
+    ("bottom", &["bottom"]),
+    ("left", &["left"]),
+    ("translate-x", &["--tw-translate-x"]),
+    ("translate-y", &["--tw-translate-y"]),
+];
+
+/// Utility prefixes whose value comes from the color palette
+const COLOR_UTILITIES: &[(&str, &str)] = &[
+    ("bg", "background-color"),
+    ("text", "color"),
+    ("border", "border-color"),
+    ("divide", "border-color"),
+    ("ring", "--tw-ring-color"),
+    ("outline", "outline-color"),
+    ("decoration", "text-decoration-color"),
+    ("placeholder", "--tw-placeholder-color"),
+    ("accent", "accent-color"),
+    ("caret", "caret-color"),
+    ("fill", "fill"),
+    ("stroke", "stroke"),
+    ("shadow", "--tw-shadow-color"),
+    ("from", "--tw-gradient-from"),
+    ("via", "--tw-gradient-via"),
+    ("to", "--tw-gradient-to"),
+];
+
+/// Rounded-corner side suffixes
+const RADIUS_SIDES: &[(&str, &[&str])] = &[
+    ("t", &["border-top-left-radius", "border-top-right-radius"]),
+    ("r", &["border-top-right-radius", "border-bottom-right-radius"]),
+    ("b", &["border-bottom-right-radius", "border-bottom-left-radius"]),
+    ("l", &["border-top-left-radius", "border-bottom-left-radius"]),
+    ("tl", &["border-top-left-radius"]),
+    ("tr", &["border-top-right-radius"]),
+    ("br", &["border-bottom-right-radius"]),
+    ("bl", &["border-bottom-left-radius"]),
+];
+
+/// Border-width side suffixes
+const BORDER_SIDES: &[(&str, &[&str])] = &[
+    ("x", &["border-left-width", "border-right-width"]),
+    ("y", &["border-top-width", "border-bottom-width"]),
+    ("t", &["border-top-width"]),
+    ("r", &["border-right-width"]),
+    ("b", &["border-bottom-width"]),
+    ("l", &["border-left-width"]),
+];
+
+/// One CSS declaration produced by a utility class
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UtilityDeclaration {
+    /// Class as written (`md:hover:bg-blue-700`)
+    pub class: String,
+    /// State/theme variants (`hover`, `focus`, `dark`, `group-hover`)
+    pub variants: Vec<String>,
+    /// Responsive breakpoint (`md`)
+    pub breakpoint: Option<String>,
+    pub property: String,
+    pub value: String,
+    /// Theme token the value comes from (`--color-blue-500`)
+    pub token: Option<String>,
+}
+
+impl UtilityDeclaration {
+    /// Short text form for embeddings: `md hover background-color blue-700`
+    pub fn describe(&self) -> String {
+        let value = self
+            .token
+            .as_deref()
+            .and_then(token_key)
+            .unwrap_or(&self.value);
+        let mut parts: Vec<&str> = self.breakpoint.iter().map(String::as_str).collect();
+        parts.extend(self.variants.iter().map(String::as_str));
+        parts.push(&self.property);
+        parts.push(value);
+        parts.join(" ")
+    }
+}
+
+/// Tailwind theme scales used to resolve utility values
+#[derive(Debug, Clone)]
+pub struct TailwindTheme {
+    /// Flattened palette: `blue-500` → `#3b82f6`, `brand` → `#...`
+    pub colors: BTreeMap<String, String>,
+    pub spacing: BTreeMap<String, String>,
+    pub font_size: BTreeMap<String, String>,
+    pub font_weight: BTreeMap<String, String>,
+    /// `""` is the bare `rounded` value
+    pub border_radius: BTreeMap<String, String>,
+    /// `""` is the bare `shadow` value
+    pub box_shadow: BTreeMap<String, String>,
+    pub screens: BTreeMap<String, String>,
+}
+
+impl Default for TailwindTheme {
+    fn default() -> Self {
+        let owned = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+
+        let mut colors = owned(&[
+            ("black", "#000000"),
+            ("white", "#ffffff"),
+            ("transparent", "transparent"),
+            ("current", "currentColor"),
+            ("inherit", "inherit"),
+        ]);
+        for (name, shades) in PALETTE {
+            for (shade, value) in SHADES.iter().zip(shades) {
+                colors.insert(format!("{}-{}", name, shade), value.to_string());
+            }
+        }
+
+        let mut spacing = owned(&[("0", "0px"), ("px", "1px")]);
+        for step in SPACING_SCALE {
+            spacing.insert(step.to_string(), format!("{}rem", step * 0.25));
+        }
+
+        Self {
+            colors,
+            spacing,
+            font_size: owned(FONT_SIZES),
+            font_weight: owned(FONT_WEIGHTS),
+            border_radius: owned(BORDER_RADIUS),
+            box_shadow: owned(BOX_SHADOWS),
+            screens: owned(SCREENS),
+        }
+    }
+}
+
+impl TailwindTheme {
+    /// Build a theme from a `tailwind.config` JSON export.
+    ///
+    /// Accepts either the whole config (`{"theme": {...}}`) or just the theme
+    /// object. Sections under `theme` replace the defaults; sections under
+    /// `theme.extend` are merged into them.
+    pub fn from_config_json(json: &str) -> anyhow::Result<Self> {
+        let config: Value = serde_json::from_str(json)?;
+        let theme = config.get("theme").unwrap_or(&config);
+        if !theme.is_object() {
+            anyhow::bail!("Tailwind config theme must be an object");
+        }
+
+        let mut result = Self::default();
+        for (section, replace) in [(Some(theme), true), (theme.get("extend"), false)] {
+            let Some(section) = section else {
+                continue;
+            };
+            result.apply_section(section, replace);
+        }
+        Ok(result)
+    }
+
+    fn apply_section(&mut self, theme: &Value, replace: bool) {
+        let scales: [(&str, &mut BTreeMap<String, String>); 7] = [
+            ("colors", &mut self.colors),
+            ("spacing", &mut self.spacing),
+            ("fontSize", &mut self.font_size),
+            ("fontWeight", &mut self.font_weight),
+            ("borderRadius", &mut self.border_radius),
+            ("boxShadow", &mut self.box_shadow),
+            ("screens", &mut self.screens),
+        ];
+        for (key, scale) in scales {
+            let Some(values) = theme.get(key) else {
+                continue;
+            };
+            if replace {
+                scale.clear();
+            }
+            flatten_scale("", values, scale);
+        }
+    }
+}
+
+/// Flatten nested config values: `{"brand": {"DEFAULT": "#f00", "500": "#e00"}}`
+/// → `brand`, `brand-500`; `["1rem", {"lineHeight": ...}]` → `1rem`;
+/// `{"min": "640px"}` (screens) → `640px`
+fn flatten_scale(prefix: &str, value: &Value, out: &mut BTreeMap<String, String>) {
+    let key_for = |key: &str| match (prefix, key) {
+        (p, "DEFAULT") => p.to_string(),
+        ("", k) => k.to_string(),
+        (p, k) => format!("{}-{}", p, k),
+    };
+
+    match value {
+        Value::Object(map) => {
+            if let Some(min) = map.get("min").and_then(Value::as_str) {
+                out.insert(prefix.to_string(), min.to_string());
+                return;
+            }
+            for (key, value) in map {
+                flatten_scale(&key_for(key), value, out);
+            }
+        }
+        Value::Array(items) => match items.first() {
+            // fontSize: ["0.875rem", { lineHeight: "1.25rem" }]
+            Some(Value::String(first)) if items.len() == 2 && items[1].is_object() => {
+                out.insert(prefix.to_string(), first.clone());
+            }
+            _ => {
+                let joined: Vec<String> = items.iter().filter_map(scalar).collect();
+                out.insert(prefix.to_string(), joined.join(", "));
+            }
+        },
+        other => {
+            if let Some(value) = scalar(other) {
+                out.insert(prefix.to_string(), value);
+            }
+        }
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Declarations for one utility and the theme token its value came from
+struct Resolved {
+    declarations: Vec<(String, String)>,
+    token: Option<(String, TokenCategory)>,
+}
+
+impl Resolved {
+    fn new(properties: &[&str], value: impl Into<String>, token: Option<(String, TokenCategory)>) -> Self {
+        let value = value.into();
+        Self {
+            declarations: properties.iter().map(|p| (p.to_string(), value.clone())).collect(),
+            token,
+        }
+    }
+}
+
+/// Expands Tailwind utility classes using a [`TailwindTheme`]
+#[derive(Debug, Clone, Default)]
+pub struct TailwindResolver {
+    theme: TailwindTheme,
+}
+
+impl TailwindResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_theme(theme: TailwindTheme) -> Self {
+        Self { theme }
+    }
+
+    pub fn theme(&self) -> &TailwindTheme {
+        &self.theme
+    }
+
+    /// Declarations for every class (unknown classes are skipped)
+    pub fn resolve_classes(&self, classes: &[String]) -> Vec<UtilityDeclaration> {
+        classes.iter().flat_map(|class| self.resolve(class)).collect()
+    }
+
+    /// Declarations produced by a single class (empty if not a Tailwind utility)
+    pub fn resolve(&self, class: &str) -> Vec<UtilityDeclaration> {
+        let (prefixes, utility) = split_variants(class);
+        let mut variants = Vec::new();
+        let mut breakpoint = None;
+        for prefix in prefixes {
+            if self.theme.screens.contains_key(prefix) {
+                breakpoint = Some(prefix.to_string());
+            } else {
+                variants.push(prefix.to_string());
+            }
+        }
+
+        let utility = utility.trim_start_matches('!');
+        let (negative, utility) = match utility.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, utility),
+        };
+
+        let Some(resolved) = self.expand(utility, negative) else {
+            return Vec::new();
+        };
+        resolved
+            .declarations
+            .into_iter()
+            .map(|(property, value)| UtilityDeclaration {
+                class: class.to_string(),
+                variants: variants.clone(),
+                breakpoint: breakpoint.clone(),
+                property,
+                value,
+                token: resolved.token.as_ref().map(|(name, _)| name.clone()),
+            })
+            .collect()
+    }
+
+    /// Synthetic stylesheet for `classes`: one rule per resolved class, with
+    /// values referencing the theme tokens they use
+    pub fn to_css(&self, classes: &[String]) -> CssStructure {
+        let mut rules = Vec::new();
+        let mut tokens: BTreeMap<String, DesignToken> = BTreeMap::new();
+        let mut properties = BTreeSet::new();
+
+        for class in classes {
+            let (prefixes, utility) = split_variants(class);
+            let utility = utility.trim_start_matches('!');
+            let (negative, utility) = match utility.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, utility),
+            };
+            let Some(resolved) = self.expand(utility, negative) else {
+                continue;
+            };
+
+            // Reference the token when the value is exactly the token's value
+            let token_value = resolved.token.as_ref().and_then(|(name, category)| {
+                let value = self.token_value(name)?;
+                tokens.entry(name.clone()).or_insert_with(|| DesignToken {
+                    name: name.clone(),
+                    value: value.clone(),
+                    category: *category,
+                    theme: None,
+                });
+                Some((name, value))
+            });
+
+            let declarations = resolved
+                .declarations
+                .iter()
+                .map(|(name, value)| {
+                    properties.insert(name.clone());
+                    let value = match &token_value {
+                        Some((token, token_value)) if token_value == value => format!("var({})", token),
+                        _ => value.clone(),
+                    };
+                    CssProperty { name: name.clone(), value }
+                })
+                .collect();
+            rules.push(CssRule {
+                selector: self.selector(class, &prefixes),
+                properties: declarations,
+                theme: prefixes.contains(&"dark").then(|| "dark".to_string()),
+            });
+        }
+
+        let mut selectors: Vec<String> = rules.iter().map(|r| r.selector.clone()).collect();
+        selectors.sort();
+        selectors.dedup();
+        CssStructure {
+            rules,
+            design_tokens: tokens.into_values().collect(),
+            properties: properties.into_iter().collect(),
+            selectors,
+            mixins: vec![],
+        }
+    }
+
+    /// Selector Tailwind would generate for a class: `.hover\:bg-blue-700:hover`
+    fn selector(&self, class: &str, prefixes: &[&str]) -> String {
+        let mut selector = format!(".{}", escape_class(class));
+        let mut ancestor = String::new();
+        for prefix in prefixes {
+            match *prefix {
+                "first" => selector.push_str(":first-child"),
+                "last" => selector.push_str(":last-child"),
+                "odd" => selector.push_str(":nth-child(odd)"),
+                "even" => selector.push_str(":nth-child(even)"),
+                p if p.starts_with("group-") => ancestor = format!(".group:{} ", &p["group-".len()..]),
+                p if p.starts_with("peer-") => ancestor = format!(".peer:{} ~ ", &p["peer-".len()..]),
+                "dark" => {}
+                p if self.theme.screens.contains_key(p) => {}
+                p => {
+                    selector.push(':');
+                    selector.push_str(p);
+                }
+            }
+        }
+        format!("{}{}", ancestor, selector)
+    }
+
+    fn token_value(&self, token: &str) -> Option<String> {
+        let (scale, key) = self.token_scale(token)?;
+        scale.get(key).cloned()
+    }
+
+    fn token_scale<'a>(&self, token: &'a str) -> Option<(&BTreeMap<String, String>, &'a str)> {
+        let name = token.strip_prefix("--")?;
+        let scales = [
+            ("color", &self.theme.colors),
+            ("spacing", &self.theme.spacing),
+            ("text", &self.theme.font_size),
+            ("font-weight", &self.theme.font_weight),
+            ("radius", &self.theme.border_radius),
+            ("shadow", &self.theme.box_shadow),
+        ];
+        scales.into_iter().find_map(|(prefix, scale)| {
+            if name == prefix {
+                return Some((scale, ""));
+            }
+            name.strip_prefix(prefix)?.strip_prefix('-').map(|key| (scale, key))
+        })
+    }
+
+    fn expand(&self, utility: &str, negative: bool) -> Option<Resolved> {
+        let declarations: Vec<(String, String)> = STATIC_UTILITIES
+            .iter()
+            .filter(|(class, _, _)| *class == utility)
+            .map(|(_, property, value)| (property.to_string(), value.to_string()))
+            .collect();
+        if !declarations.is_empty() && !negative {
+            return Some(Resolved { declarations, token: None });
+        }
+
+        if let Some(resolved) = self.expand_scaled(utility, negative) {
+            return Some(resolved);
+        }
+        if negative {
+            return None;
+        }
+        self.expand_color(utility)
+    }
+
+    fn expand_scaled(&self, utility: &str, negative: bool) -> Option<Resolved> {
+        let sign = |value: String| if negative { format!("-{}", value) } else { value };
+
+        // Spacing: p-4, mx-auto, w-1/2, -mt-2, gap-x-[3px]
+        let mut spacing: Vec<&(&str, &[&str])> = SPACING_UTILITIES.iter().collect();
+        spacing.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        for (prefix, properties) in spacing {
+            let Some(key) = utility.strip_prefix(prefix).and_then(|r| r.strip_prefix('-')) else {
+                continue;
+            };
+            if let Some(value) = self.theme.spacing.get(key) {
+                let token = Some((format!("--spacing-{}", key), TokenCategory::Spacing));
+                return Some(Resolved::new(properties, sign(value.clone()), token));
+            }
+            if let Some(value) = size_keyword(key, properties[0]).or_else(|| arbitrary(key)) {
+                return Some(Resolved::new(properties, sign(value), None));
+            }
+        }
+        if negative {
+            return None;
+        }
+
+        let (head, key) = match utility.split_once('-') {
+            Some((head, key)) => (head, key),
+            None => (utility, ""),
+        };
+        match head {
+            "text" => {
+                if let Some(value) = self.theme.font_size.get(key) {
+                    let token = Some((format!("--text-{}", key), TokenCategory::Typography));
+                    return Some(Resolved::new(&["font-size"], value.clone(), token));
+                }
+                if let Some(value) = arbitrary(key).filter(|v| !looks_like_color(v)) {
+                    return Some(Resolved::new(&["font-size"], value, None));
+                }
+            }
+            "font" => {
+                if let Some(value) = self.theme.font_weight.get(key) {
+                    let token = Some((format!("--font-weight-{}", key), TokenCategory::Typography));
+                    return Some(Resolved::new(&["font-weight"], value.clone(), token));
+                }
+            }
+            "rounded" => return self.expand_radius(key),
+            "shadow" => {
+                if let Some(value) = self.theme.box_shadow.get(key) {
+                    let token = Some((token_name("--shadow", key), TokenCategory::Shadow));
+                    return Some(Resolved::new(&["box-shadow"], value.clone(), token));
+                }
+            }
+            "border" => {
+                if let Some(width) = border_width(key) {
+                    return Some(Resolved::new(&["border-width"], width, None));
+                }
+                for (side, properties) in BORDER_SIDES {
+                    let width = match key.strip_prefix(side) {
+                        Some("") => Some("1px".to_string()),
+                        Some(rest) => rest.strip_prefix('-').and_then(border_width),
+                        None => None,
+                    };
+                    if let Some(width) = width {
+                        return Some(Resolved::new(properties, width, None));
+                    }
+                }
+            }
+            "ring" => {
+                if let Ok(width) = key.parse::<u32>() {
+                    return Some(Resolved::new(
+                        &["box-shadow"],
+                        format!("0 0 0 {}px var(--tw-ring-color)", width),
+                        None,
+                    ));
+                }
+            }
+            "opacity" => {
+                let percent = key.parse::<f32>().ok()?;
+                return Some(Resolved::new(&["opacity"], (percent / 100.0).to_string(), None));
+            }
+            "z" => {
+                return Some(Resolved::new(&["z-index"], key.parse::<i32>().ok()?.to_string(), None));
+            }
+            "duration" => {
+                return Some(Resolved::new(&["transition-duration"], format!("{}ms", key.parse::<u32>().ok()?), None));
+            }
+            "grid" => {
+                let columns = key.strip_prefix("cols-")?.parse::<u32>().ok()?;
+                return Some(Resolved::new(
+                    &["grid-template-columns"],
+                    format!("repeat({}, minmax(0, 1fr))", columns),
+                    None,
+                ));
+            }
+            "col" => {
+                let span = key.strip_prefix("span-")?;
+                let value = match span {
+                    "full" => "1 / -1".to_string(),
+                    n => format!("span {0} / span {0}", n.parse::<u32>().ok()?),
+                };
+                return Some(Resolved::new(&["grid-column"], value, None));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn expand_radius(&self, key: &str) -> Option<Resolved> {
+        let radius = |size: &str| {
+            self.theme.border_radius.get(size).map(|value| {
+                (value.clone(), Some((token_name("--radius", size), TokenCategory::BorderRadius)))
+            })
+        };
+
+        if let Some((value, token)) = radius(key) {
+            return Some(Resolved::new(&["border-radius"], value, token));
+        }
+        for (side, properties) in RADIUS_SIDES {
+            let size = match key.strip_prefix(side) {
+                Some("") => "",
+                Some(rest) => match rest.strip_prefix('-') {
+                    Some(size) => size,
+                    None => continue,
+                },
+                None => continue,
+            };
+            if let Some((value, token)) = radius(size) {
+                return Some(Resolved::new(properties, value, token));
+            }
+        }
+        None
+    }
+
+    /// `bg-blue-500`, `text-white`, `border-brand/50`, `bg-[#1da1f2]`
+    fn expand_color(&self, utility: &str) -> Option<Resolved> {
+        let mut colors: Vec<&(&str, &str)> = COLOR_UTILITIES.iter().collect();
+        colors.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        for (prefix, property) in colors {
+            let Some(key) = utility.strip_prefix(prefix).and_then(|r| r.strip_prefix('-')) else {
+                continue;
+            };
+            let (key, opacity) = match key.rsplit_once('/') {
+                Some((key, opacity)) if !key.starts_with('[') || key.ends_with(']') => (key, Some(opacity)),
+                _ => (key, None),
+            };
+
+            let (value, token) = match self.theme.colors.get(key) {
+                Some(value) => (value.clone(), Some((format!("--color-{}", key), TokenCategory::Color))),
+                None => match arbitrary(key).filter(|v| looks_like_color(v)) {
+                    Some(value) => (value, None),
+                    None => continue,
+                },
+            };
+            let value = match opacity.and_then(|o| o.parse::<f32>().ok()) {
+                Some(percent) => with_opacity(&value, percent),
+                None => value,
+            };
+            return Some(Resolved::new(&[property], value, token));
+        }
+        None
+    }
+}
+
+/// Split `md:hover:bg-blue-500` into `["md", "hover"]` and `bg-blue-500`,
+/// ignoring colons inside arbitrary values (`bg-[url(a:b)]`)
+fn split_variants(class: &str) -> (Vec<&str>, &str) {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => {
+                parts.push(&class[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    (parts, &class[start..])
+}
+
+/// `--radius` for the bare scale entry, `--radius-lg` otherwise
+fn token_name(base: &str, key: &str) -> String {
+    if key.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}-{}", base, key)
+    }
+}
+
+/// Scale key of a resolver token name (`--color-blue-500` → `blue-500`)
+fn token_key(token: &str) -> Option<&str> {
+    ["--color-", "--spacing-", "--text-", "--font-weight-", "--radius-", "--shadow-"]
+        .iter()
+        .find_map(|prefix| token.strip_prefix(prefix))
+}
+
+/// `[#1da1f2]` / `[300px]` / `[calc(100%_-_1rem)]` → the raw value
+fn arbitrary(key: &str) -> Option<String> {
+    let inner = key.strip_prefix('[')?.strip_suffix(']')?;
+    Some(inner.replace('_', " "))
+}
+
+fn looks_like_color(value: &str) -> bool {
+    let value = value.to_lowercase();
+    value.starts_with('#')
+        || value.starts_with("rgb")
+        || value.starts_with("hsl")
+        || value.starts_with("oklch")
+        || value.starts_with("color:")
+}
+
+/// Sizing keywords and fractions shared by width/height/inset utilities
+fn size_keyword(key: &str, property: &str) -> Option<String> {
+    let viewport = if property.contains("height") || property == "top" || property == "bottom" {
+        "100vh"
+    } else {
+        "100vw"
+    };
+    let value = match key {
+        "auto" => "auto".to_string(),
+        "full" => "100%".to_string(),
+        "screen" => viewport.to_string(),
+        "min" => "min-content".to_string(),
+        "max" => "max-content".to_string(),
+        "fit" => "fit-content".to_string(),
+        fraction => {
+            let (n, d) = fraction.split_once('/')?;
+            let (n, d) = (n.parse::<f32>().ok()?, d.parse::<f32>().ok()?);
+            if d == 0.0 {
+                return None;
+            }
+            let percent = (n / d * 100.0 * 1_000_000.0).round() / 1_000_000.0;
+            format!("{}%", percent)
+        }
+    };
+    Some(value)
+}
+
+fn border_width(key: &str) -> Option<String> {
+    match key {
+        "" => Some("1px".to_string()),
+        "0" | "2" | "4" | "8" => Some(format!("{}px", key)),
+        _ => arbitrary(key).filter(|v| !looks_like_color(v)),
+    }
+}
+
+/// Apply an opacity modifier (`/50`) to a color value
+fn with_opacity(color: &str, percent: f32) -> String {
+    let alpha = percent / 100.0;
+    let hex = color.trim_start_matches('#');
+    if color.starts_with('#') && hex.len() == 6 {
+        if let Ok(rgb) = u32::from_str_radix(hex, 16) {
+            return format!("rgb({} {} {} / {})", rgb >> 16, (rgb >> 8) & 0xff, rgb & 0xff, alpha);
+        }
+    }
+    format!("color-mix(in srgb, {} {}%, transparent)", color, percent)
+}
+
+/// Escape a class name for use in a selector
+fn escape_class(class: &str) -> String {
+    let mut out = String::with_capacity(class.len());
+    for c in class.chars() {
+        if !(c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuples(resolver: &TailwindResolver, class: &str) -> Vec<(Vec<String>, Option<String>, String, String)> {
+        resolver
+            .resolve(class)
+            .into_iter()
+            .map(|d| (d.variants, d.breakpoint, d.property, d.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_utilities() {
+        let resolver = TailwindResolver::new();
+
+        let bg = resolver.resolve("bg-blue-500");
+        assert_eq!(bg[0].property, "background-color");
+        assert_eq!(bg[0].value, "#3b82f6");
+        assert_eq!(bg[0].token.as_deref(), Some("--color-blue-500"));
+        assert_eq!(bg[0].describe(), "background-color blue-500");
+
+        assert_eq!(
+            tuples(&resolver, "md:hover:bg-blue-700"),
+            vec![(vec!["hover".to_string()], Some("md".to_string()), "background-color".to_string(), "#1d4ed8".to_string())]
+        );
+        let px: Vec<_> = resolver.resolve("md:px-4").into_iter().map(|d| (d.property, d.value)).collect();
+        assert_eq!(
+            px,
+            vec![("padding-left".to_string(), "1rem".to_string()), ("padding-right".to_string(), "1rem".to_string())]
+        );
+
+        let value = |class: &str| resolver.resolve(class).first().map(|d| d.value.clone());
+        assert_eq!(value("-mt-2").as_deref(), Some("-0.5rem"));
+        assert_eq!(value("w-1/2").as_deref(), Some("50%"));
+        assert_eq!(value("text-sm").as_deref(), Some("0.875rem"));
+        assert_eq!(value("text-center").as_deref(), Some("center"));
+        assert_eq!(value("text-white").as_deref(), Some("#ffffff"));
+        assert_eq!(value("bg-black/50").as_deref(), Some("rgb(0 0 0 / 0.5)"));
+        assert_eq!(value("bg-[#1da1f2]").as_deref(), Some("#1da1f2"));
+        assert_eq!(value("rounded-t-lg").as_deref(), Some("0.5rem"));
+        assert_eq!(value("border").as_deref(), Some("1px"));
+        assert_eq!(value("!font-bold").as_deref(), Some("700"));
+        assert_eq!(value("btn-primary"), None);
+    }
+
+    #[test]
+    fn test_config_theme_and_css() {
+        let config = r##"{
+            "theme": {
+                "screens": { "tablet": "640px", "desktop": { "min": "1280px" } },
+                "extend": {
+                    "colors": { "brand": { "DEFAULT": "#7c3aed", "dark": "#5b21b6" } },
+                    "spacing": { "18": "4.5rem" }
+                }
+            }
+        }"##;
+        let resolver = TailwindResolver::with_theme(TailwindTheme::from_config_json(config).unwrap());
+
+        assert_eq!(
+            tuples(&resolver, "tablet:bg-brand"),
+            vec![(vec![], Some("tablet".to_string()), "background-color".to_string(), "#7c3aed".to_string())]
+        );
+        assert_eq!(resolver.resolve("p-18")[0].value, "4.5rem");
+        // Default colors are kept when only extended
+        assert_eq!(resolver.resolve("text-gray-900")[0].value, "#111827");
+        // `md` is no longer a screen, so it is treated as a variant
+        assert_eq!(resolver.resolve("md:p-4")[0].variants, vec!["md".to_string()]);
+
+        let classes: Vec<String> = ["bg-brand", "hover:bg-brand-dark", "dark:text-white", "flex"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let css = resolver.to_css(&classes);
+        let selectors: Vec<_> = css.rules.iter().map(|r| r.selector.as_str()).collect();
+        assert_eq!(selectors, vec![".bg-brand", ".hover\\:bg-brand-dark:hover", ".dark\\:text-white", ".flex"]);
+        assert_eq!(css.rules[0].properties[0].value, "var(--color-brand)");
+        assert_eq!(css.rules[2].theme.as_deref(), Some("dark"));
+        let tokens: Vec<_> = css.design_tokens.iter().map(|t| (t.name.as_str(), t.value.as_str())).collect();
+        assert_eq!(
+            tokens,
+            vec![("--color-brand", "#7c3aed"), ("--color-brand-dark", "#5b21b6"), ("--color-white", "#ffffff")]
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Design token importer - W3C Design Tokens (DTCG) and Style Dictionary JSON
+//!
+//! Both formats describe tokens as nested groups. DTCG uses `$value`/`$type`
+//! (with `$type` inherited from parent groups), Style Dictionary uses
+//! `value`/`type`. References like `{color.brand.500}` are resolved against
+//! the same file, and composite values (typography, shadow, border, ...) are
+//! flattened into CSS-equivalent strings.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::css::TokenCategory;
+
+/// Maximum reference nesting depth followed during resolution
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+/// Source format of a token file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenFormat {
+    /// W3C Design Tokens Community Group format (`$value`, `$type`)
+    Dtcg,
+    /// Style Dictionary format (`value`, `type`)
+    StyleDictionary,
+}
+
+/// A token read from a DTCG / Style Dictionary file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedToken {
+    /// Dotted path in the source file (e.g., "color.brand.500")
+    pub path: String,
+    /// CSS custom property name (e.g., "--color-brand-500")
+    pub name: String,
+    /// Declared value, flattened to a string (references kept as-is)
+    pub value: String,
+    /// Value with all references resolved (`None` if unresolvable)
+    pub resolved_value: Option<String>,
+    /// Declared or inherited `$type`
+    pub token_type: Option<String>,
+    pub category: TokenCategory,
+    /// CSS name of the referenced token when the value is a single reference
+    pub alias_of: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Tokens imported from one file for a design system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedTokenSet {
+    pub design_system: String,
+    pub format: TokenFormat,
+    pub tokens: Vec<ImportedToken>,
+}
+
+/// Raw token before reference resolution
+struct RawToken {
+    path: String,
+    value: Value,
+    token_type: Option<String>,
+    description: Option<String>,
+}
+
+/// Imports design tokens from DTCG or Style Dictionary JSON
+pub struct TokenImporter;
+
+impl Default for TokenImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenImporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a JSON document, detecting the format automatically
+    pub fn import_str(&self, json: &str, design_system: impl Into<String>) -> anyhow::Result<ImportedTokenSet> {
+        let root: Value = serde_json::from_str(json)?;
+        self.import_value(&root, design_system)
+    }
+
+    /// Import from an already-parsed JSON value
+    pub fn import_value(&self, root: &Value, design_system: impl Into<String>) -> anyhow::Result<ImportedTokenSet> {
+        let object = root
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("Token file root must be a JSON object"))?;
+
+        let format = Self::detect_format(root);
+        let mut raw = Vec::new();
+        Self::collect(object, format, &mut Vec::new(), None, &mut raw);
+
+        let by_path: HashMap<&str, &RawToken> = raw.iter().map(|t| (t.path.as_str(), t)).collect();
+        let tokens = raw
+            .iter()
+            .map(|token| {
+                let value = flatten_value(&token.value);
+                let resolved_value = resolve(&token.value, &by_path, &mut HashSet::from([token.path.clone()]), 0)
+                    .map(|v| flatten_value(&v));
+                let alias_of = single_reference(&token.value).map(|path| css_name(&path));
+                let token_type = token.token_type.clone().or_else(|| {
+                    // Aliases inherit the type of their target
+                    single_reference(&token.value)
+                        .and_then(|path| by_path.get(path.as_str()).and_then(|t| t.token_type.clone()))
+                });
+
+                ImportedToken {
+                    name: css_name(&token.path),
+                    path: token.path.clone(),
+                    category: categorize(token_type.as_deref(), &token.path),
+                    value,
+                    resolved_value,
+                    token_type,
+                    alias_of,
+                    description: token.description.clone(),
+                }
+            })
+            .collect();
+
+        Ok(ImportedTokenSet {
+            design_system: design_system.into(),
+            format,
+            tokens,
+        })
+    }
+
+    /// DTCG files use `$value` somewhere in the tree
+    fn detect_format(value: &Value) -> TokenFormat {
+        fn has_dollar_value(value: &Value) -> bool {
+            match value {
+                Value::Object(map) => map.contains_key("$value") || map.values().any(has_dollar_value),
+                _ => false,
+            }
+        }
+
+        if has_dollar_value(value) {
+            TokenFormat::Dtcg
+        } else {
+            TokenFormat::StyleDictionary
+        }
+    }
+
+    fn collect(
+        group: &Map<String, Value>,
+        format: TokenFormat,
+        path: &mut Vec<String>,
+        inherited_type: Option<&str>,
+        out: &mut Vec<RawToken>,
+    ) {
+        let (value_key, type_key, description_key) = match format {
+            TokenFormat::Dtcg => ("$value", "$type", "$description"),
+            TokenFormat::StyleDictionary => ("value", "type", "comment"),
+        };
+
+        let group_type = group
+            .get(type_key)
+            .and_then(Value::as_str)
+            .or(inherited_type);
+
+        if let Some(value) = group.get(value_key) {
+            if !path.is_empty() {
+                let token_type = group_type.map(str::to_string).or_else(|| {
+                    // Style Dictionary's CTI convention stores the category in attributes
+                    group
+                        .get("attributes")
+                        .and_then(|a| a.get("category"))
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                });
+                out.push(RawToken {
+                    path: path.join("."),
+                    value: value.clone(),
+                    token_type,
+                    description: group
+                        .get(description_key)
+                        .or_else(|| group.get("description"))
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                });
+            }
+            return;
+        }
+
+        for (key, child) in group {
+            if key.starts_with('$') {
+                continue;
+            }
+            if let Value::Object(child_group) = child {
+                path.push(key.clone());
+                Self::collect(child_group, format, path, group_type, out);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Convert a dotted token path into a CSS custom property name
+pub fn css_name(path: &str) -> String {
+    let slug: Vec<String> = path
+        .split('.')
+        .map(|segment| {
+            segment
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '-' })
+                .collect()
+        })
+        .collect();
+    format!("--{}", slug.join("-"))
+}
+
+/// Path of a value that is exactly one `{reference}`
+fn single_reference(value: &Value) -> Option<String> {
+    let s = value.as_str()?.trim();
+    if s.starts_with('{') && s.ends_with('}') && s[1..].find('{').is_none() {
+        Some(normalize_reference(&s[1..s.len() - 1]))
+    } else {
+        None
+    }
+}
+
+/// Style Dictionary allows `{color.base.red.value}`
+fn normalize_reference(path: &str) -> String {
+    path.trim()
+        .trim_end_matches(".$value")
+        .trim_end_matches(".value")
+        .to_string()
+}
+
+/// Resolve all `{references}` in a value, recursing into composite values
+fn resolve(value: &Value, tokens: &HashMap<&str, &RawToken>, visiting: &mut HashSet<String>, depth: usize) -> Option<Value> {
+    if depth > MAX_REFERENCE_DEPTH {
+        return None;
+    }
+
+    match value {
+        Value::String(s) => {
+            // Whole-value references keep the target's (possibly composite) shape
+            if let Some(path) = single_reference(value) {
+                return resolve_path(&path, tokens, visiting, depth);
+            }
+
+            let mut out = String::new();
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find('{') {
+                let end = start + rest[start..].find('}')?;
+                out.push_str(&rest[..start]);
+                let target = resolve_path(&normalize_reference(&rest[start + 1..end]), tokens, visiting, depth)?;
+                out.push_str(&flatten_value(&target));
+                rest = &rest[end + 1..];
+            }
+            out.push_str(rest);
+            Some(Value::String(out))
+        }
+        Value::Array(items) => items
+            .iter()
+            .map(|item| resolve(item, tokens, visiting, depth + 1))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| resolve(v, tokens, visiting, depth + 1).map(|v| (k.clone(), v)))
+            .collect::<Option<Map<_, _>>>()
+            .map(Value::Object),
+        other => Some(other.clone()),
+    }
+}
+
+fn resolve_path(path: &str, tokens: &HashMap<&str, &RawToken>, visiting: &mut HashSet<String>, depth: usize) -> Option<Value> {
+    if !visiting.insert(path.to_string()) {
+        return None;
+    }
+    let target = tokens.get(path)?;
+    let resolved = resolve(&target.value, tokens, visiting, depth + 1);
+    visiting.remove(path);
+    resolved
+}
+
+/// Flatten a (possibly composite) token value into a CSS-equivalent string
+fn flatten_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => String::new(),
+        Value::Array(items) => {
+            // cubicBezier is a 4-number array
+            if items.len() == 4 && items.iter().all(Value::is_number) {
+                let parts: Vec<String> = items.iter().map(flatten_value).collect();
+                return format!("cubic-bezier({})", parts.join(", "));
+            }
+            items.iter().map(flatten_value).collect::<Vec<_>>().join(", ")
+        }
+        Value::Object(map) => flatten_composite(map),
+    }
+}
+
+fn flatten_composite(map: &Map<String, Value>) -> String {
+    let field = |key: &str| map.get(key).map(flatten_value);
+    let join = |parts: Vec<Option<String>>| {
+        parts
+            .into_iter()
+            .flatten()
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    // Dimension objects: {"value": 16, "unit": "px"}
+    if let (Some(v), Some(unit)) = (map.get("value"), map.get("unit")) {
+        return format!("{}{}", flatten_value(v), flatten_value(unit));
+    }
+
+    // Typography: font shorthand
+    if map.contains_key("fontFamily") || map.contains_key("fontSize") {
+        let size = match (field("fontSize"), field("lineHeight")) {
+            (Some(size), Some(line)) => Some(format!("{}/{}", size, line)),
+            (size, _) => size,
+        };
+        return join(vec![field("fontStyle"), field("fontWeight"), size, field("fontFamily")]);
+    }
+
+    // Shadow: box-shadow syntax
+    if map.contains_key("offsetX") || map.contains_key("blur") {
+        let inset = map
+            .get("inset")
+            .and_then(Value::as_bool)
+            .filter(|inset| *inset)
+            .map(|_| "inset".to_string());
+        return join(vec![
+            inset,
+            field("offsetX"),
+            field("offsetY"),
+            field("blur"),
+            field("spread"),
+            field("color"),
+        ]);
+    }
+
+    // Border: width style color
+    if map.contains_key("width") && map.contains_key("style") {
+        return join(vec![field("width"), field("style"), field("color")]);
+    }
+
+    // Transition: duration timing-function delay
+    if map.contains_key("duration") {
+        return join(vec![field("duration"), field("timingFunction"), field("delay")]);
+    }
+
+    Value::Object(map.clone()).to_string()
+}
+
+/// Map a DTCG `$type` (or the token path, when untyped) to a token category
+fn categorize(token_type: Option<&str>, path: &str) -> TokenCategory {
+    let token_type = token_type.unwrap_or("").to_lowercase();
+    match token_type.as_str() {
+        "color" => return TokenCategory::Color,
+        "dimension" | "spacing" | "sizing" | "size" => {
+            if path.to_lowercase().contains("radius") {
+                return TokenCategory::BorderRadius;
+            }
+            return TokenCategory::Spacing;
+        }
+        "fontfamily" | "fontweight" | "fontsize" | "lineheight" | "letterspacing" | "typography" | "font" => {
+            return TokenCategory::Typography
+        }
+        "shadow" | "boxshadow" => return TokenCategory::Shadow,
+        "duration" | "cubicbezier" | "transition" | "animation" => return TokenCategory::Animation,
+        "borderradius" | "radius" => return TokenCategory::BorderRadius,
+        _ => {}
+    }
+
+    let path = path.to_lowercase();
+    if path.contains("color") || path.contains("palette") {
+        TokenCategory::Color
+    } else if path.contains("radius") {
+        TokenCategory::BorderRadius
+    } else if path.contains("shadow") || path.contains("elevation") {
+        TokenCategory::Shadow
+    } else if path.contains("font") || path.contains("typography") || path.contains("line-height") {
+        TokenCategory::Typography
+    } else if path.contains("spacing") || path.contains("space") || path.contains("size") {
+        TokenCategory::Spacing
+    } else if path.contains("duration") || path.contains("easing") || path.contains("motion") {
+        TokenCategory::Animation
+    } else {
+        TokenCategory::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_dtcg() {
+        let json = r##"{
+            "color": {
+                "$type": "color",
+                "brand": { "500": { "$value": "#3b82f6" } },
+                "primary": { "$value": "{color.brand.500}", "$description": "Main action colour" }
+            },
+            "shadow": {
+                "card": {
+                    "$type": "shadow",
+                    "$value": { "color": "{color.brand.500}", "offsetX": "0px", "offsetY": "2px", "blur": "4px", "spread": "0px" }
+                }
+            },
+            "font": {
+                "body": {
+                    "$type": "typography",
+                    "$value": { "fontFamily": ["Inter", "sans-serif"], "fontSize": "16px", "fontWeight": 400, "lineHeight": 1.5 }
+                }
+            }
+        }"##;
+
+        let set = TokenImporter::new().import_str(json, "acme").unwrap();
+        assert_eq!(set.format, TokenFormat::Dtcg);
+
+        let primary = set.tokens.iter().find(|t| t.path == "color.primary").unwrap();
+        assert_eq!(primary.name, "--color-primary");
+        assert_eq!(primary.category, TokenCategory::Color);
+        assert_eq!(primary.alias_of.as_deref(), Some("--color-brand-500"));
+        assert_eq!(primary.resolved_value.as_deref(), Some("#3b82f6"));
+        assert_eq!(primary.description.as_deref(), Some("Main action colour"));
+
+        let shadow = set.tokens.iter().find(|t| t.path == "shadow.card").unwrap();
+        assert_eq!(shadow.category, TokenCategory::Shadow);
+        assert_eq!(shadow.resolved_value.as_deref(), Some("0px 2px 4px 0px #3b82f6"));
+
+        let body = set.tokens.iter().find(|t| t.path == "font.body").unwrap();
+        assert_eq!(body.category, TokenCategory::Typography);
+        assert_eq!(body.value, "400 16px/1.5 Inter, sans-serif");
+    }
+
+    #[test]
+    fn test_import_style_dictionary() {
+        let json = r##"{
+            "size": { "padding": { "base": { "value": "8px" }, "large": { "value": "{size.padding.base.value}" } } },
+            "color": { "base": { "red": { "value": "#ff0000" } } }
+        }"##;
+
+        let set = TokenImporter::new().import_str(json, "legacy").unwrap();
+        assert_eq!(set.format, TokenFormat::StyleDictionary);
+
+        let large = set.tokens.iter().find(|t| t.path == "size.padding.large").unwrap();
+        assert_eq!(large.category, TokenCategory::Spacing);
+        assert_eq!(large.resolved_value.as_deref(), Some("8px"));
+        assert_eq!(large.alias_of.as_deref(), Some("--size-padding-base"));
+
+        let red = set.tokens.iter().find(|t| t.path == "color.base.red").unwrap();
+        assert_eq!(red.category, TokenCategory::Color);
+    }
+
+    #[test]
+    fn test_reference_cycle_is_unresolved() {
+        let json = r#"{ "a": { "$value": "{b}" }, "b": { "$value": "{a}" } }"#;
+
+        let set = TokenImporter::new().import_str(json, "loop").unwrap();
+        assert!(set.tokens.iter().all(|t| t.resolved_value.is_none()));
+    }
+}
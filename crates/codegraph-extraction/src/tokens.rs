@@ -0,0 +1,434 @@
+//! Design token graph - resolves `var()` chains, aliases and themes
+//!
+//! Turns the flat custom-property list from [`CssParser`](crate::css::CssParser)
+//! into a token graph: each token is resolved per theme, alias tokens point to
+//! their target, and CSS rules record which tokens they consume. SCSS `$name`
+//! and Less `@name` variables are resolved the same way as `var(--name)`.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+
+use crate::css::{CssStructure, TokenCategory};
+use crate::html::HtmlElement;
+
+/// Name used for tokens declared outside any theme context
+pub const DEFAULT_THEME: &str = "default";
+
+/// Maximum `var()` nesting depth followed during resolution
+const MAX_RESOLUTION_DEPTH: usize = 16;
+
+static VAR_NAME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"var\(\s*(--[\w-]+)|([$@][A-Za-z_][\w-]*)").unwrap());
+
+/// SCSS `$name` / Less `@name` variable reference
+static PREPROCESSOR_VAR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[$@][A-Za-z_][\w-]*").unwrap());
+
+/// A `var(--name, fallback)` reference inside a CSS value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenReference {
+    pub name: String,
+    pub fallback: Option<String>,
+}
+
+/// A design token resolved within a theme
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedToken {
+    pub name: String,
+    /// Raw declared value
+    pub value: String,
+    /// Value after following all `var()` references (`None` if unresolvable)
+    pub resolved_value: Option<String>,
+    pub category: TokenCategory,
+    /// Theme name ([`DEFAULT_THEME`] for `:root`)
+    pub theme: String,
+    /// Target token when the value is a single `var()` reference
+    pub alias_of: Option<String>,
+}
+
+/// Tokens consumed by a CSS rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub selector: String,
+    pub property: String,
+    pub tokens: Vec<String>,
+}
+
+/// Resolved design tokens grouped by theme, plus rule usages
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenGraph {
+    pub tokens: Vec<ResolvedToken>,
+    pub themes: Vec<String>,
+    pub usages: Vec<TokenUsage>,
+}
+
+impl TokenGraph {
+    /// Build the token graph from a parsed CSS structure
+    pub fn from_css(css: &CssStructure) -> Self {
+        // theme -> name -> (value, category)
+        let mut by_theme: HashMap<String, HashMap<String, (String, TokenCategory)>> = HashMap::new();
+        for token in &css.design_tokens {
+            let theme = token.theme.clone().unwrap_or_else(|| DEFAULT_THEME.to_string());
+            by_theme
+                .entry(theme)
+                .or_default()
+                .insert(token.name.clone(), (token.value.clone(), token.category));
+        }
+
+        let mut themes: Vec<String> = by_theme.keys().cloned().collect();
+        themes.sort_by_key(|t| (t != DEFAULT_THEME, t.clone()));
+
+        let resolver = TokenResolver { by_theme: &by_theme };
+        let mut tokens = Vec::new();
+        for theme in &themes {
+            let mut names: Vec<&String> = by_theme[theme].keys().collect();
+            names.sort();
+            for name in names {
+                let (value, category) = &by_theme[theme][name];
+                tokens.push(ResolvedToken {
+                    name: name.clone(),
+                    value: value.clone(),
+                    resolved_value: resolver.resolve(name, theme),
+                    category: *category,
+                    theme: theme.clone(),
+                    alias_of: alias_target(value),
+                });
+            }
+        }
+
+        let usages = css
+            .rules
+            .iter()
+            .flat_map(|rule| {
+                rule.properties.iter().filter_map(|prop| {
+                    let referenced = referenced_tokens(&prop.value);
+                    if referenced.is_empty() || prop.name.starts_with("--") {
+                        return None;
+                    }
+                    Some(TokenUsage {
+                        selector: rule.selector.clone(),
+                        property: prop.name.clone(),
+                        tokens: referenced,
+                    })
+                })
+            })
+            .collect();
+
+        Self { tokens, themes, usages }
+    }
+
+    /// Look up a token in a theme, falling back to the default theme
+    pub fn get(&self, name: &str, theme: Option<&str>) -> Option<&ResolvedToken> {
+        let theme = theme.unwrap_or(DEFAULT_THEME);
+        self.tokens
+            .iter()
+            .find(|t| t.name == name && t.theme == theme)
+            .or_else(|| self.tokens.iter().find(|t| t.name == name && t.theme == DEFAULT_THEME))
+    }
+
+    /// Tokens declared in a given theme
+    pub fn tokens_in_theme<'a>(&'a self, theme: &'a str) -> impl Iterator<Item = &'a ResolvedToken> {
+        self.tokens.iter().filter(move |t| t.theme == theme)
+    }
+
+    /// Substitute `var()` references in a declaration value with resolved token values
+    pub fn resolve_value(&self, value: &str, theme: Option<&str>) -> Option<String> {
+        let mut out = String::new();
+        let mut rest = value;
+
+        while let Some((span, reference)) = next_reference(rest) {
+            out.push_str(&rest[..span.start]);
+            let reference = reference?;
+
+            let replacement = match self.get(&reference.name, theme).and_then(|t| t.resolved_value.clone()) {
+                Some(v) => v,
+                None => self.resolve_value(&reference.fallback?, theme)?,
+            };
+            out.push_str(&replacement);
+            rest = &rest[span.end..];
+        }
+
+        out.push_str(rest);
+        Some(out.trim().to_string())
+    }
+
+    /// Names of all tokens used by CSS rules matching an element
+    pub fn tokens_for_element(&self, element: &HtmlElement) -> Vec<String> {
+        let mut used = BTreeSet::new();
+        for usage in &self.usages {
+            if selector_matches(&usage.selector, element) {
+                for token in &usage.tokens {
+                    used.insert(token.clone());
+                    // Aliases pull in their targets so `--btn-bg: var(--color-primary)`
+                    // still counts as a usage of `--color-primary`
+                    let mut current = token.clone();
+                    let mut seen = HashSet::new();
+                    while let Some(target) = self.get(&current, None).and_then(|t| t.alias_of.clone()) {
+                        if !seen.insert(target.clone()) {
+                            break;
+                        }
+                        used.insert(target.clone());
+                        current = target;
+                    }
+                }
+            }
+        }
+        used.into_iter().collect()
+    }
+}
+
+/// Resolves token values by following `var()` chains within a theme
+struct TokenResolver<'a> {
+    by_theme: &'a HashMap<String, HashMap<String, (String, TokenCategory)>>,
+}
+
+impl TokenResolver<'_> {
+    fn resolve(&self, name: &str, theme: &str) -> Option<String> {
+        let mut visiting = HashSet::new();
+        self.resolve_name(name, theme, &mut visiting, 0)
+    }
+
+    fn lookup(&self, name: &str, theme: &str) -> Option<&str> {
+        self.by_theme
+            .get(theme)
+            .and_then(|tokens| tokens.get(name))
+            .or_else(|| self.by_theme.get(DEFAULT_THEME).and_then(|tokens| tokens.get(name)))
+            .map(|(value, _)| value.as_str())
+    }
+
+    fn resolve_name(&self, name: &str, theme: &str, visiting: &mut HashSet<String>, depth: usize) -> Option<String> {
+        if depth > MAX_RESOLUTION_DEPTH || !visiting.insert(name.to_string()) {
+            return None;
+        }
+        let value = self.lookup(name, theme)?;
+        let resolved = self.resolve_value(value, theme, visiting, depth + 1);
+        visiting.remove(name);
+        resolved
+    }
+
+    fn resolve_value(&self, value: &str, theme: &str, visiting: &mut HashSet<String>, depth: usize) -> Option<String> {
+        let mut out = String::new();
+        let mut rest = value;
+
+        while let Some((span, reference)) = next_reference(rest) {
+            out.push_str(&rest[..span.start]);
+            let reference = reference?;
+
+            let replacement = match self.resolve_name(&reference.name, theme, visiting, depth) {
+                Some(v) => v,
+                None => {
+                    let fallback = reference.fallback?;
+                    self.resolve_value(&fallback, theme, visiting, depth + 1)?
+                }
+            };
+            out.push_str(&replacement);
+            rest = &rest[span.end..];
+        }
+
+        out.push_str(rest);
+        Some(out.trim().to_string())
+    }
+}
+
+/// Next token reference in a value - `var(--name, fallback)` or a SCSS/Less
+/// variable - with the byte range it spans. The reference is `None` for a
+/// malformed `var()`.
+fn next_reference(value: &str) -> Option<(Range<usize>, Option<TokenReference>)> {
+    let var = value.find("var(");
+    let variable = PREPROCESSOR_VAR_RE.find(value);
+
+    if let Some(m) = variable.filter(|m| var.is_none_or(|start| m.start() < start)) {
+        let reference = TokenReference {
+            name: m.as_str().to_string(),
+            fallback: None,
+        };
+        return Some((m.range(), Some(reference)));
+    }
+
+    let start = var?;
+    Some(match matching_paren(value, start + 3) {
+        Some(close) => (start..close + 1, parse_reference(&value[start + 4..close])),
+        None => (start..value.len(), None),
+    })
+}
+
+/// Parse every top-level `var()` reference in a CSS value
+pub fn parse_var_references(value: &str) -> Vec<TokenReference> {
+    let mut references = Vec::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("var(") {
+        let Some(close) = matching_paren(rest, start + 3) else {
+            break;
+        };
+        if let Some(reference) = parse_reference(&rest[start + 4..close]) {
+            references.push(reference);
+        }
+        rest = &rest[close + 1..];
+    }
+
+    references
+}
+
+/// All token names referenced anywhere in a value, including inside fallbacks
+fn referenced_tokens(value: &str) -> Vec<String> {
+    let mut names: Vec<String> = VAR_NAME_RE
+        .captures_iter(value)
+        .filter_map(|caps| caps.get(1).or(caps.get(2)).map(|m| m.as_str().to_string()))
+        .collect();
+    names.dedup();
+    names
+}
+
+/// Alias target when the whole value is a single token reference
+fn alias_target(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    match next_reference(trimmed)? {
+        (span, Some(reference)) if span == (0..trimmed.len()) => Some(reference.name),
+        _ => None,
+    }
+}
+
+/// Parse the inside of `var( ... )` into name and optional fallback
+fn parse_reference(inner: &str) -> Option<TokenReference> {
+    let (name, fallback) = match inner.find(',') {
+        Some(comma) => (&inner[..comma], Some(inner[comma + 1..].trim().to_string())),
+        None => (inner, None),
+    };
+    let name = name.trim();
+    if !name.starts_with("--") {
+        return None;
+    }
+    Some(TokenReference {
+        name: name.to_string(),
+        fallback: fallback.filter(|f| !f.is_empty()),
+    })
+}
+
+/// Index of the `)` matching the `(` at `open`
+fn matching_paren(s: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Check whether any selector in a selector list targets the element.
+///
+/// Only the rightmost compound selector is compared (tag, `.class`, `#id`);
+/// pseudo-classes and attribute selectors are ignored.
+pub fn selector_matches(selector_list: &str, element: &HtmlElement) -> bool {
+    selector_list.split(',').any(|selector| {
+        let compound = selector
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '+' || c == '~')
+            .rfind(|part| !part.is_empty())
+            .unwrap_or("");
+        compound_matches(compound, element)
+    })
+}
+
+fn compound_matches(compound: &str, element: &HtmlElement) -> bool {
+    // Split into tag, `.class` and `#id` parts, honouring CSS escapes
+    // (`.hover\:bg-blue-700`, `.w-1\/2`) and stopping at pseudo-classes/elements
+    // and attribute selectors
+    let mut parts: Vec<(char, String)> = vec![(' ', String::new())];
+    let mut chars = compound.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => parts.last_mut().unwrap().1.extend(chars.next()),
+            ':' | '[' => break,
+            '.' | '#' => parts.push((c, String::new())),
+            _ => parts.last_mut().unwrap().1.push(c),
+        }
+    }
+
+    let (_, tag) = &parts[0];
+    if tag == "*" || (tag.is_empty() && parts.len() == 1) {
+        return false;
+    }
+    if !tag.is_empty() && !tag.eq_ignore_ascii_case(&element.tag) {
+        return false;
+    }
+
+    parts[1..].iter().all(|(marker, name)| match marker {
+        '.' => element.classes.iter().any(|c| c == name),
+        _ => element.id.as_deref() == Some(name.as_str()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::CssParser;
+    use crate::html::HtmlParser;
+
+    const THEMED_CSS: &str = r#"
+        :root {
+            --blue-500: #3b82f6;
+            --color-primary: var(--blue-500);
+            --btn-bg: var(--color-primary, #000);
+            --missing: var(--nope, 8px);
+            --loop-a: var(--loop-b);
+            --loop-b: var(--loop-a);
+        }
+        [data-theme="dark"] {
+            --blue-500: #1d4ed8;
+        }
+        .btn {
+            background: var(--btn-bg);
+        }
+    "#;
+
+    #[test]
+    fn test_resolve_alias_chain_and_fallback() {
+        let css = CssParser::new().parse(THEMED_CSS).unwrap();
+        let graph = TokenGraph::from_css(&css);
+
+        let primary = graph.get("--color-primary", None).unwrap();
+        assert_eq!(primary.alias_of.as_deref(), Some("--blue-500"));
+        assert_eq!(primary.resolved_value.as_deref(), Some("#3b82f6"));
+
+        let missing = graph.get("--missing", None).unwrap();
+        assert_eq!(missing.resolved_value.as_deref(), Some("8px"));
+
+        let looped = graph.get("--loop-a", None).unwrap();
+        assert!(looped.resolved_value.is_none());
+    }
+
+    #[test]
+    fn test_themes_grouped() {
+        let css = CssParser::new().parse(THEMED_CSS).unwrap();
+        let graph = TokenGraph::from_css(&css);
+
+        assert_eq!(graph.themes, vec!["default".to_string(), "dark".to_string()]);
+        assert_eq!(graph.tokens_in_theme("dark").count(), 1);
+        assert_eq!(
+            graph.get("--blue-500", Some("dark")).unwrap().resolved_value.as_deref(),
+            Some("#1d4ed8")
+        );
+    }
+
+    #[test]
+    fn test_tokens_for_element_follow_aliases() {
+        let css = CssParser::new().parse(THEMED_CSS).unwrap();
+        let graph = TokenGraph::from_css(&css);
+        let html = HtmlParser::new().parse(r#"<button class="btn">Go</button>"#).unwrap();
+
+        let used = graph.tokens_for_element(&html.elements[0]);
+        assert!(used.contains(&"--btn-bg".to_string()));
+        assert!(used.contains(&"--color-primary".to_string()));
+        assert!(used.contains(&"--blue-500".to_string()));
+    }
+}
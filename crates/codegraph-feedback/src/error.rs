@@ -24,6 +24,10 @@ pub enum FeedbackError {
     /// Serialization error
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    /// Schema migration refused
+    #[error("Migration error: {0}")]
+    Migration(String),
 }
 
 /// Result type for feedback operations
@@ -6,6 +6,7 @@
 //! ## Components
 //!
 //! - [`FeedbackRepository`] - Persists feedback in PostgreSQL
+//! - [`schema`] - Versioned PostgreSQL migrations
 //! - [`ConfidenceUpdater`] - Applies confidence deltas with clamping
 //! - [`FeedbackType`] - Positive (thumbs up) or negative (thumbs down) feedback
 
@@ -17,6 +18,7 @@ pub mod prometheus;
 pub mod propagation;
 pub mod repository;
 pub mod reward;
+pub mod schema;
 
 pub use confidence::{ConfidenceUpdate, ConfidenceUpdater, DEFAULT_CONFIDENCE, MAX_CONFIDENCE, MIN_CONFIDENCE};
 pub use error::FeedbackError;
@@ -28,6 +30,7 @@ pub use propagation::{
     DEFAULT_DECAY_FACTOR, DEFAULT_MAX_HOPS,
 };
 pub use repository::FeedbackRepository;
+pub use codegraph_core::traits::FeedbackStore;
 pub use reward::{
     normalize_connectivity, RewardComputer, RewardResult, RewardSignals, RewardWeights,
     WEIGHT_BASE_CONFIDENCE, WEIGHT_CONNECTIVITY_BONUS, WEIGHT_NEGATIVE_PENALTY, WEIGHT_SIMILARITY_BONUS,
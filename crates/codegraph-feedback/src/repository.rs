@@ -1,11 +1,15 @@
 //! FeedbackRepository - PostgreSQL persistence for feedback records
 
-use chrono::Utc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use codegraph_core::migrations::AppliedMigration;
+use codegraph_core::traits::FeedbackStore;
 use sqlx::PgPool;
 use tracing::{debug, info, instrument};
 use uuid::Uuid;
 
 use crate::error::{FeedbackError, Result};
+use crate::schema;
 use crate::models::{CreateFeedback, Feedback, FeedbackMetrics, FeedbackSummary, FeedbackType};
 
 /// Repository for persisting feedback in PostgreSQL
@@ -20,58 +24,24 @@ impl FeedbackRepository {
         Self { pool }
     }
 
+    /// Connect to the database at `url` (e.g. `DATABASE_URL`)
+    pub async fn connect(url: &str) -> Result<Self> {
+        Ok(Self::new(PgPool::connect(url).await?))
+    }
+
     /// Initialize the database schema for feedback storage
     #[instrument(skip(self))]
     pub async fn init_schema(&self) -> Result<()> {
-        info!("Initializing feedback schema");
-
-        // Create feedback_type enum if it doesn't exist
-        sqlx::query(
-            r#"
-            DO $$ BEGIN
-                CREATE TYPE feedback_type AS ENUM ('thumbs_up', 'thumbs_down');
-            EXCEPTION
-                WHEN duplicate_object THEN null;
-            END $$;
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
-
-        // Create feedback table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS feedback (
-                id UUID PRIMARY KEY,
-                generation_id UUID NOT NULL,
-                element_ids JSONB NOT NULL DEFAULT '[]',
-                feedback_type feedback_type NOT NULL,
-                query_context TEXT,
-                comment TEXT,
-                confidence_delta REAL NOT NULL,
-                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
-            );
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
-
-        // Create indexes for efficient queries
-        sqlx::query(
-            r#"
-            CREATE INDEX IF NOT EXISTS idx_feedback_generation_id ON feedback(generation_id);
-            CREATE INDEX IF NOT EXISTS idx_feedback_created_at ON feedback(created_at DESC);
-            CREATE INDEX IF NOT EXISTS idx_feedback_type ON feedback(feedback_type);
-            CREATE INDEX IF NOT EXISTS idx_feedback_element_ids ON feedback USING GIN(element_ids);
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
-
+        self.migrate().await?;
         info!("Feedback schema initialized successfully");
         Ok(())
     }
 
+    /// Run pending schema migrations, returning those applied
+    pub async fn migrate(&self) -> Result<Vec<AppliedMigration>> {
+        schema::migrate(&self.pool).await
+    }
+
     /// Save new feedback to the database
     #[instrument(skip(self, feedback), fields(generation_id = %feedback.generation_id))]
     pub async fn save(&self, feedback: CreateFeedback) -> Result<Feedback> {
@@ -239,6 +209,35 @@ impl FeedbackRepository {
             .collect::<Result<Vec<_>>>()
     }
 
+    /// Feedback created at or before `until`, oldest first, after the `(created_at, id)` key `after`
+    #[instrument(skip(self))]
+    pub async fn list_until(
+        &self,
+        until: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Vec<Feedback>> {
+        let rows = sqlx::query_as::<_, FeedbackRow>(
+            r#"
+            SELECT id, generation_id, element_ids, feedback_type, query_context, comment, confidence_delta, created_at
+            FROM feedback
+            WHERE created_at <= $1 AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3))
+            ORDER BY created_at, id
+            LIMIT $4
+            "#,
+        )
+        .bind(until)
+        .bind(after.map(|(created_at, _)| created_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<Vec<_>>>()
+    }
+
     /// Get aggregated feedback metrics
     #[instrument(skip(self))]
     pub async fn get_metrics(&self) -> Result<FeedbackMetrics> {
@@ -306,6 +305,90 @@ impl FeedbackRepository {
 
         Ok(deleted)
     }
+
+    /// Insert records with their original IDs and timestamps, skipping IDs
+    /// already stored
+    #[instrument(skip(self, records), fields(count = records.len()))]
+    pub async fn import(&self, records: &[Feedback]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut imported = 0;
+        for feedback in records {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO feedback (id, generation_id, element_ids, feedback_type, query_context, comment, confidence_delta, created_at)
+                VALUES ($1, $2, $3, $4::feedback_type, $5, $6, $7, $8)
+                ON CONFLICT (id) DO NOTHING
+                "#,
+            )
+            .bind(feedback.id)
+            .bind(feedback.generation_id)
+            .bind(serde_json::to_value(&feedback.element_ids)?)
+            .bind(feedback.feedback_type.to_string())
+            .bind(&feedback.query_context)
+            .bind(&feedback.comment)
+            .bind(feedback.confidence_delta)
+            .bind(feedback.created_at)
+            .execute(&mut *tx)
+            .await?;
+            imported += result.rows_affected();
+        }
+        tx.commit().await?;
+
+        info!(imported = imported, "Imported feedback records");
+        Ok(imported)
+    }
+}
+
+#[async_trait]
+impl FeedbackStore for FeedbackRepository {
+    async fn init_schema(&self) -> anyhow::Result<()> {
+        Ok(FeedbackRepository::init_schema(self).await?)
+    }
+
+    async fn save(&self, feedback: CreateFeedback) -> anyhow::Result<Feedback> {
+        Ok(FeedbackRepository::save(self, feedback).await?)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<Feedback>> {
+        Ok(FeedbackRepository::find_by_id(self, id).await?)
+    }
+
+    async fn find_by_generation(&self, generation_id: Uuid) -> anyhow::Result<Vec<Feedback>> {
+        Ok(FeedbackRepository::find_by_generation(self, generation_id).await?)
+    }
+
+    async fn find_by_element(&self, element_id: Uuid) -> anyhow::Result<Vec<Feedback>> {
+        Ok(FeedbackRepository::find_by_element(self, element_id).await?)
+    }
+
+    async fn get_element_summary(&self, element_id: Uuid) -> anyhow::Result<FeedbackSummary> {
+        Ok(FeedbackRepository::get_element_summary(self, element_id).await?)
+    }
+
+    async fn list_recent(&self, limit: i64, offset: i64) -> anyhow::Result<Vec<Feedback>> {
+        Ok(FeedbackRepository::list_recent(self, limit, offset).await?)
+    }
+
+    async fn list_until(
+        &self,
+        until: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Feedback>> {
+        Ok(FeedbackRepository::list_until(self, until, after, limit).await?)
+    }
+
+    async fn get_metrics(&self) -> anyhow::Result<FeedbackMetrics> {
+        Ok(FeedbackRepository::get_metrics(self).await?)
+    }
+
+    async fn delete_older_than(&self, days: i64) -> anyhow::Result<u64> {
+        Ok(FeedbackRepository::delete_older_than(self, days).await?)
+    }
+
+    async fn import_feedback(&self, records: &[Feedback]) -> anyhow::Result<u64> {
+        Ok(FeedbackRepository::import(self, records).await?)
+    }
 }
 
 // Internal row types for SQLx mapping
@@ -0,0 +1,89 @@
+//! Versioned PostgreSQL schema for feedback storage
+//!
+//! Applied versions are recorded in `schema_migrations`. Pending migrations
+//! run in a single transaction holding a lock on that table, so concurrent
+//! starts apply each migration once.
+
+use chrono::Utc;
+use codegraph_core::migrations::{self, AppliedMigration, Migration};
+use sqlx::{Executor, PgPool};
+use tracing::info;
+
+use crate::error::{FeedbackError, Result};
+
+/// Ordered migrations, each a batch of SQL statements
+pub const POSTGRES_MIGRATIONS: &[Migration<&str>] = &[
+    Migration {
+        version: 1,
+        description: "feedback table and indexes",
+        // Guarded so databases created before migrations were recorded adopt it
+        steps: r#"
+            DO $$ BEGIN
+                CREATE TYPE feedback_type AS ENUM ('thumbs_up', 'thumbs_down');
+            EXCEPTION
+                WHEN duplicate_object THEN null;
+            END $$;
+
+            CREATE TABLE IF NOT EXISTS feedback (
+                id UUID PRIMARY KEY,
+                generation_id UUID NOT NULL,
+                element_ids JSONB NOT NULL DEFAULT '[]',
+                feedback_type feedback_type NOT NULL,
+                query_context TEXT,
+                comment TEXT,
+                confidence_delta REAL NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_feedback_generation_id ON feedback(generation_id);
+            CREATE INDEX IF NOT EXISTS idx_feedback_created_at ON feedback(created_at DESC);
+            CREATE INDEX IF NOT EXISTS idx_feedback_type ON feedback(feedback_type);
+            CREATE INDEX IF NOT EXISTS idx_feedback_element_ids ON feedback USING GIN(element_ids);
+        "#,
+    },
+    Migration {
+        version: 2,
+        description: "feedback keyset index",
+        steps: "CREATE INDEX IF NOT EXISTS idx_feedback_created_at_id ON feedback(created_at, id);",
+    },
+];
+
+/// Run pending migrations, returning those applied
+pub async fn migrate(pool: &PgPool) -> Result<Vec<AppliedMigration>> {
+    sqlx::raw_sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TIMESTAMPTZ NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    let mut tx = pool.begin().await?;
+    tx.execute("LOCK TABLE schema_migrations IN EXCLUSIVE MODE").await?;
+    let applied_versions: Vec<i32> = sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version")
+        .fetch_all(&mut *tx)
+        .await?;
+    let applied_versions: Vec<u32> = applied_versions.into_iter().map(|v| v as u32).collect();
+
+    let pending = migrations::pending("PostgreSQL", POSTGRES_MIGRATIONS, &applied_versions)
+        .map_err(|e| FeedbackError::Migration(e.to_string()))?;
+    let mut applied = Vec::new();
+    for migration in pending {
+        tx.execute(migration.steps).await?;
+        sqlx::query("INSERT INTO schema_migrations (version, description, applied_at) VALUES ($1, $2, $3)")
+            .bind(migration.version as i32)
+            .bind(migration.description)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+        info!("Applied PostgreSQL migration {}: {}", migration.version, migration.description);
+        applied.push(AppliedMigration { version: migration.version, description: migration.description.to_string() });
+    }
+    tx.commit().await?;
+
+    Ok(applied)
+}
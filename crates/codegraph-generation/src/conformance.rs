@@ -0,0 +1,457 @@
+//! Design-system conformance validation for generated code
+//!
+//! Runs generated HTML/CSS back through the extraction pipeline and checks it
+//! against the target design system: known classes, registered color and
+//! spacing tokens instead of hard-coded literals, and known UI categories.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use codegraph_extraction::css::TokenCategory;
+use codegraph_extraction::pipeline::PipelineConfig;
+use codegraph_extraction::tokens::parse_var_references;
+use codegraph_extraction::{ExtractionInput, ExtractionPipeline, ExtractionResult};
+
+use crate::parser::ParsedCode;
+
+static COLOR_LITERAL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)#[0-9a-f]{3,8}\b|\b(?:rgba?|hsla?)\([^)]*\)").unwrap());
+
+static LENGTH_LITERAL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:^|[\s(,])(-?\d*\.?\d+(?:px|rem|em))\b").unwrap());
+
+static SPACING_PROPERTY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(margin|padding)(-\w+)*$|^(row-|column-)?gap$").unwrap());
+
+/// Violation severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Kind of conformance violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// Class not part of the design system's known class set
+    UnknownClass,
+    /// Hard-coded color literal instead of a color token
+    HardcodedColor,
+    /// Hard-coded length instead of a spacing token
+    HardcodedSpacing,
+    /// `var()` reference to a token the design system does not define
+    UnknownToken,
+    /// UI category the graph has no elements for
+    UnknownCategory,
+}
+
+/// A single conformance violation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub severity: Severity,
+    pub message: String,
+    /// Selector, class or category the violation was found on
+    pub location: Option<String>,
+    /// Suggested replacement (e.g., a matching token)
+    pub suggestion: Option<String>,
+}
+
+impl Violation {
+    fn new(kind: ViolationKind, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            severity,
+            message: message.into(),
+            location: None,
+            suggestion: None,
+        }
+    }
+
+    fn at(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    fn suggest(mut self, suggestion: Option<String>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+
+    /// Human-readable one-line description
+    pub fn describe(&self) -> String {
+        let mut text = format!("[{}] {}", self.severity.as_str(), self.message);
+        if let Some(suggestion) = &self.suggestion {
+            text.push_str(&format!(" (use {})", suggestion));
+        }
+        text
+    }
+}
+
+/// Registered token the generated code may reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintToken {
+    pub name: String,
+    pub value: String,
+    pub category: TokenCategory,
+}
+
+/// Constraints describing a target design system
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesignSystemConstraints {
+    pub design_system: String,
+    /// Classes known to belong to the design system
+    pub known_classes: HashSet<String>,
+    /// Class prefixes accepted without being in `known_classes` (e.g., "bg-")
+    pub class_prefixes: Vec<String>,
+    /// Registered design tokens
+    pub tokens: Vec<ConstraintToken>,
+    /// UI categories the graph knows about
+    pub known_categories: HashSet<String>,
+}
+
+impl DesignSystemConstraints {
+    pub fn new(design_system: impl Into<String>) -> Self {
+        Self {
+            design_system: design_system.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_known_classes(mut self, classes: impl IntoIterator<Item = String>) -> Self {
+        self.known_classes.extend(classes);
+        self
+    }
+
+    pub fn with_class_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.class_prefixes = prefixes;
+        self
+    }
+
+    pub fn with_token(mut self, name: impl Into<String>, value: impl Into<String>, category: TokenCategory) -> Self {
+        self.tokens.push(ConstraintToken {
+            name: name.into(),
+            value: value.into(),
+            category,
+        });
+        self
+    }
+
+    pub fn with_known_categories(mut self, categories: impl IntoIterator<Item = String>) -> Self {
+        self.known_categories.extend(categories);
+        self
+    }
+
+    fn has_tokens(&self, category: TokenCategory) -> bool {
+        self.tokens.iter().any(|t| t.category == category)
+    }
+
+    fn class_allowed(&self, class: &str) -> bool {
+        // Variant prefixes like `hover:` or `md:` don't change class identity
+        let base = class.rsplit(':').next().unwrap_or(class);
+        self.known_classes.contains(class)
+            || self.known_classes.contains(base)
+            || self.class_prefixes.iter().any(|p| base.starts_with(p.as_str()))
+    }
+
+    /// Token whose value matches a literal (case-insensitive)
+    fn token_for_value(&self, literal: &str, category: TokenCategory) -> Option<String> {
+        let literal = literal.trim().to_lowercase();
+        self.tokens
+            .iter()
+            .find(|t| t.category == category && t.value.trim().to_lowercase() == literal)
+            .map(|t| format!("var({})", t.name))
+    }
+}
+
+/// Result of validating generated code
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConformanceReport {
+    pub design_system: String,
+    pub violations: Vec<Violation>,
+}
+
+impl ConformanceReport {
+    /// True when there are no error-level violations
+    pub fn is_conformant(&self) -> bool {
+        !self.violations.iter().any(|v| v.severity == Severity::Error)
+    }
+
+    /// Violations at or above a severity
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &Violation> {
+        self.violations.iter().filter(move |v| v.severity >= severity)
+    }
+
+    /// Flatten into `GenerationResult.validation_errors` strings
+    pub fn to_validation_errors(&self) -> Vec<String> {
+        self.violations.iter().map(Violation::describe).collect()
+    }
+}
+
+/// Validates generated code against design system constraints
+pub struct ConformanceValidator {
+    pipeline: ExtractionPipeline,
+}
+
+impl Default for ConformanceValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConformanceValidator {
+    pub fn new() -> Self {
+        Self {
+            pipeline: ExtractionPipeline::with_config(PipelineConfig {
+                generate_embeddings: false,
+                target_time_ms: 500,
+                warn_on_slow: false,
+            }),
+        }
+    }
+
+    /// Validate parsed code blocks against the constraints
+    pub fn validate(&mut self, code: &ParsedCode, constraints: &DesignSystemConstraints) -> anyhow::Result<ConformanceReport> {
+        let mut input = ExtractionInput::new(code.html.clone().unwrap_or_default());
+        if let Some(css) = &code.css {
+            input = input.with_css(css);
+        }
+        if let Some(js) = &code.javascript {
+            input = input.with_js(js);
+        }
+
+        let extraction = self.pipeline.extract_sync(input)?;
+
+        let mut violations = Vec::new();
+        self.check_classes(&extraction, constraints, &mut violations);
+        self.check_literals(&extraction, constraints, &mut violations);
+        self.check_token_references(&extraction, constraints, &mut violations);
+        self.check_categories(&extraction, constraints, &mut violations);
+
+        violations.sort_by_key(|v| std::cmp::Reverse(v.severity));
+
+        Ok(ConformanceReport {
+            design_system: constraints.design_system.clone(),
+            violations,
+        })
+    }
+
+    fn check_classes(&self, extraction: &ExtractionResult, constraints: &DesignSystemConstraints, out: &mut Vec<Violation>) {
+        if constraints.known_classes.is_empty() && constraints.class_prefixes.is_empty() {
+            return;
+        }
+
+        // Classes styled by the generated CSS itself are local, not design system classes
+        let local: HashSet<&str> = extraction
+            .css
+            .selectors
+            .iter()
+            .flat_map(|s| s.split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.')))
+            .filter_map(|part| part.strip_prefix('.'))
+            .collect();
+
+        for class in &extraction.html.classes {
+            if local.contains(class.as_str()) || constraints.class_allowed(class) {
+                continue;
+            }
+            out.push(
+                Violation::new(
+                    ViolationKind::UnknownClass,
+                    Severity::Warning,
+                    format!("Class '{}' is not part of {}", class, constraints.design_system),
+                )
+                .at(class.clone()),
+            );
+        }
+    }
+
+    fn check_literals(&self, extraction: &ExtractionResult, constraints: &DesignSystemConstraints, out: &mut Vec<Violation>) {
+        let check_colors = constraints.has_tokens(TokenCategory::Color);
+        let check_spacing = constraints.has_tokens(TokenCategory::Spacing);
+
+        for rule in &extraction.css.rules {
+            for prop in &rule.properties {
+                // Token definitions are where literals belong
+                if prop.name.starts_with("--") {
+                    continue;
+                }
+                let value = strip_var_calls(&prop.value);
+
+                if check_colors {
+                    for literal in COLOR_LITERAL_RE.find_iter(&value) {
+                        out.push(
+                            Violation::new(
+                                ViolationKind::HardcodedColor,
+                                Severity::Error,
+                                format!("{} uses hard-coded color {} in '{}'", prop.name, literal.as_str(), rule.selector),
+                            )
+                            .at(rule.selector.clone())
+                            .suggest(constraints.token_for_value(literal.as_str(), TokenCategory::Color)),
+                        );
+                    }
+                }
+
+                if check_spacing && SPACING_PROPERTY_RE.is_match(&prop.name) {
+                    for caps in LENGTH_LITERAL_RE.captures_iter(&value) {
+                        let literal = &caps[1];
+                        if literal.trim_start_matches('-').trim_start_matches(['0', '.']).starts_with(char::is_alphabetic) {
+                            continue; // 0px, 0rem
+                        }
+                        out.push(
+                            Violation::new(
+                                ViolationKind::HardcodedSpacing,
+                                Severity::Warning,
+                                format!("{} uses hard-coded length {} in '{}'", prop.name, literal, rule.selector),
+                            )
+                            .at(rule.selector.clone())
+                            .suggest(constraints.token_for_value(literal, TokenCategory::Spacing)),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_token_references(&self, extraction: &ExtractionResult, constraints: &DesignSystemConstraints, out: &mut Vec<Violation>) {
+        if constraints.tokens.is_empty() {
+            return;
+        }
+
+        let local: HashSet<&str> = extraction.css.design_tokens.iter().map(|t| t.name.as_str()).collect();
+        let registered: HashSet<&str> = constraints.tokens.iter().map(|t| t.name.as_str()).collect();
+
+        for rule in &extraction.css.rules {
+            for prop in &rule.properties {
+                for reference in parse_var_references(&prop.value) {
+                    let name = reference.name.as_str();
+                    if local.contains(name) || registered.contains(name) {
+                        continue;
+                    }
+                    out.push(
+                        Violation::new(
+                            ViolationKind::UnknownToken,
+                            Severity::Warning,
+                            format!("Token {} is not defined by {}", name, constraints.design_system),
+                        )
+                        .at(rule.selector.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_categories(&self, extraction: &ExtractionResult, constraints: &DesignSystemConstraints, out: &mut Vec<Violation>) {
+        if constraints.known_categories.is_empty() {
+            return;
+        }
+
+        for category in &extraction.ontology.categories_used {
+            let name = category.as_str();
+            if name == "unknown" || constraints.known_categories.contains(name) {
+                continue;
+            }
+            out.push(
+                Violation::new(
+                    ViolationKind::UnknownCategory,
+                    Severity::Info,
+                    format!("Category '{}' has no reference elements in the graph", name),
+                )
+                .at(name.to_string()),
+            );
+        }
+    }
+}
+
+/// Remove `var(...)` calls (including fallbacks) so their contents aren't flagged
+fn strip_var_calls(value: &str) -> String {
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    let bytes = value.as_bytes();
+
+    while i < bytes.len() {
+        if depth == 0 && value[i..].starts_with("var(") {
+            depth = 1;
+            i += 4;
+            continue;
+        }
+        if depth > 0 {
+            match bytes[i] {
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        let ch = value[i..].chars().next().unwrap_or(' ');
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints() -> DesignSystemConstraints {
+        DesignSystemConstraints::new("acme")
+            .with_known_classes(vec!["btn".to_string(), "btn-primary".to_string()])
+            .with_token("--color-primary", "#3b82f6", TokenCategory::Color)
+            .with_token("--space-2", "8px", TokenCategory::Spacing)
+            .with_known_categories(vec!["button".to_string()])
+    }
+
+    #[test]
+    fn test_conformant_code() {
+        let code = ParsedCode {
+            html: Some(r#"<button class="btn btn-primary">Save</button>"#.to_string()),
+            css: Some(".btn { color: var(--color-primary); padding: var(--space-2, 8px); margin: 0; }".to_string()),
+            javascript: None,
+        };
+
+        let report = ConformanceValidator::new().validate(&code, &constraints()).unwrap();
+        assert!(report.violations.is_empty(), "{:?}", report.violations);
+        assert!(report.is_conformant());
+    }
+
+    #[test]
+    fn test_detects_violations() {
+        let code = ParsedCode {
+            html: Some(r#"<button class="btn fancy-btn">Save</button><table></table>"#.to_string()),
+            css: Some(".btn { color: #3B82F6; padding: 8px 12px; } .x { color: var(--brand-x); }".to_string()),
+            javascript: None,
+        };
+
+        let report = ConformanceValidator::new().validate(&code, &constraints()).unwrap();
+        let kinds: Vec<_> = report.violations.iter().map(|v| v.kind).collect();
+
+        assert!(!report.is_conformant());
+        assert!(kinds.contains(&ViolationKind::UnknownClass));
+        assert!(kinds.contains(&ViolationKind::HardcodedColor));
+        assert!(kinds.contains(&ViolationKind::HardcodedSpacing));
+        assert!(kinds.contains(&ViolationKind::UnknownToken));
+        assert!(kinds.contains(&ViolationKind::UnknownCategory));
+
+        let color = report.violations.iter().find(|v| v.kind == ViolationKind::HardcodedColor).unwrap();
+        assert_eq!(color.suggestion.as_deref(), Some("var(--color-primary)"));
+        assert_eq!(report.violations[0].severity, Severity::Error);
+    }
+}
@@ -8,11 +8,17 @@ use async_openai::{
     },
     Client,
 };
-use tracing::{debug, info};
+use std::sync::Mutex;
+use tracing::{debug, info, warn};
 
+use codegraph_extraction::a11y::{A11yAuditor, A11yReport};
+use codegraph_extraction::design_system::{DesignSystemDetector, DesignSystemType, DetectionResult};
+
+use crate::conformance::{ConformanceValidator, DesignSystemConstraints, Violation};
 use crate::parser::{CodeParser, ParsedCode};
 use crate::prompt::{self, SimilarElement, TemplateContext, SYSTEM_PROMPT};
 use crate::templates::TemplateEngine;
+use crate::translation::{TranslationRequest, TranslationResult};
 
 /// Result of code generation
 #[derive(Debug)]
@@ -20,6 +26,12 @@ pub struct GenerationResult {
     pub code: ParsedCode,
     pub html_document: String,
     pub validation_errors: Vec<String>,
+    /// Design system conformance violations (empty without constraints)
+    pub violations: Vec<Violation>,
+    /// Number of LLM calls made (1 + repair attempts)
+    pub attempts: u32,
+    /// Accessibility audit of the generated HTML/CSS (`None` without HTML)
+    pub a11y: Option<A11yReport>,
 }
 
 /// Request for code generation
@@ -29,6 +41,10 @@ pub struct GenerationRequest {
     pub similar_elements: Vec<SimilarElement>,
     pub reasoning: Option<String>,
     pub categories: Vec<String>,
+    /// Target design system constraints to validate against
+    pub constraints: Option<DesignSystemConstraints>,
+    /// How many times to regenerate with violation feedback
+    pub max_repair_attempts: u32,
 }
 
 /// VanillaCodeGenerator - generates HTML5/CSS3/ES6+ code using GPT-4o
@@ -36,6 +52,8 @@ pub struct VanillaCodeGenerator {
     client: Client<OpenAIConfig>,
     parser: CodeParser,
     template_engine: TemplateEngine,
+    validator: Mutex<ConformanceValidator>,
+    detector: DesignSystemDetector,
     model: String,
 }
 
@@ -46,6 +64,8 @@ impl VanillaCodeGenerator {
             client: Client::new(),
             parser: CodeParser::new(),
             template_engine: TemplateEngine::new(),
+            validator: Mutex::new(ConformanceValidator::new()),
+            detector: DesignSystemDetector::new(),
             model: "gpt-4o".to_string(),
         }
     }
@@ -56,6 +76,8 @@ impl VanillaCodeGenerator {
             client: Client::with_config(config),
             parser: CodeParser::new(),
             template_engine: TemplateEngine::new(),
+            validator: Mutex::new(ConformanceValidator::new()),
+            detector: DesignSystemDetector::new(),
             model: "gpt-4o".to_string(),
         }
     }
@@ -92,43 +114,179 @@ impl VanillaCodeGenerator {
 
         debug!("User prompt: {}", user_prompt);
 
-        // Call LLM
-        let response = self.call_llm(&user_prompt).await?;
+        let (result, _) = self
+            .complete(
+                SYSTEM_PROMPT,
+                user_prompt,
+                request.constraints.as_ref(),
+                request.max_repair_attempts,
+                None,
+            )
+            .await?;
+        Ok(result)
+    }
 
-        debug!("LLM response length: {} chars", response.len());
+    /// Translate a component into another design system
+    ///
+    /// The output is regenerated (up to `max_repair_attempts` times) while the
+    /// detector does not recognise it as the target design system.
+    pub async fn translate(&self, request: TranslationRequest) -> anyhow::Result<TranslationResult> {
+        info!(
+            "Translating {} from {} to {}",
+            request.name,
+            request.source_design_system.as_deref().unwrap_or("unknown"),
+            request.target_design_system.as_str()
+        );
 
-        // Parse code blocks
-        let code = self.parser.parse(&response);
+        let system_prompt = prompt::translation_system_prompt(request.target_design_system);
+        let user_prompt = prompt::build_translation_prompt(&request);
+        debug!("Translation prompt: {}", user_prompt);
 
-        if code.is_empty() {
-            anyhow::bail!("No code blocks found in LLM response");
-        }
+        let (generation, detection) = self
+            .complete(
+                &system_prompt,
+                user_prompt,
+                request.constraints.as_ref(),
+                request.max_repair_attempts,
+                Some(request.target_design_system),
+            )
+            .await?;
+        let detection = detection.ok_or_else(|| anyhow::anyhow!("Translation was not validated"))?;
+        let valid = detection.design_system == request.target_design_system;
+
+        Ok(TranslationResult {
+            generation,
+            detection,
+            valid,
+        })
+    }
+
+    /// Call the LLM and regenerate with violation feedback until the code
+    /// conforms to `constraints` and, with a `target`, is detected as it
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: String,
+        constraints: Option<&DesignSystemConstraints>,
+        max_repair_attempts: u32,
+        target: Option<DesignSystemType>,
+    ) -> anyhow::Result<(GenerationResult, Option<DetectionResult>)> {
+        let mut prompt = user_prompt.clone();
+        let mut attempts = 0;
 
-        // Validate HTML
-        let validation_errors = if let Some(ref html) = code.html {
-            match self.parser.validate_html(html) {
-                Ok(()) => vec![],
-                Err(errors) => errors,
+        loop {
+            attempts += 1;
+
+            // Call LLM
+            let response = self.call_llm(system_prompt, &prompt).await?;
+
+            debug!("LLM response length: {} chars", response.len());
+
+            // Parse code blocks
+            let code = self.parser.parse(&response);
+
+            if code.is_empty() {
+                anyhow::bail!("No code blocks found in LLM response");
             }
-        } else {
-            vec!["No HTML generated".to_string()]
-        };
 
-        // Generate full HTML document
-        let html_document = code.to_html_document();
+            // Validate HTML, CSS and JavaScript syntax
+            let mut validation_errors: Vec<String> = self
+                .parser
+                .validate(&code)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            if code.html.is_none() {
+                validation_errors.push("No HTML generated".to_string());
+            }
 
-        Ok(GenerationResult {
-            code,
-            html_document,
-            validation_errors,
-        })
+            // Validate against design system constraints
+            let report = match constraints {
+                Some(constraints) => Some(self.check_conformance(&code, constraints)?),
+                None => None,
+            };
+            let violations = report.as_ref().map(|r| r.violations.clone()).unwrap_or_default();
+            let mut feedback: Vec<String> = violations.iter().map(Violation::describe).collect();
+
+            // Validate that the output reads as the target design system
+            let detection = target.map(|target| (target, self.detect_design_system(&code)));
+            if let Some((target, detection)) = detection.as_ref().filter(|(t, d)| d.design_system != *t) {
+                feedback.push(format!(
+                    "Output was detected as {} instead of {}",
+                    detection.design_system.as_str(),
+                    target.as_str()
+                ));
+            }
+            validation_errors.extend(feedback.iter().cloned());
+
+            let conformant = report.as_ref().map(|r| r.is_conformant()).unwrap_or(true)
+                && detection.as_ref().is_none_or(|(target, d)| d.design_system == *target);
+            if !conformant && attempts <= max_repair_attempts {
+                warn!(
+                    "Generated code has {} design system violations, regenerating (attempt {})",
+                    feedback.len(),
+                    attempts + 1
+                );
+                prompt = prompt::build_repair_prompt(&user_prompt, &response, &feedback);
+                continue;
+            }
+
+            let a11y = self.check_accessibility(&code);
+
+            // Generate full HTML document
+            let html_document = code.to_html_document();
+
+            let result = GenerationResult {
+                code,
+                html_document,
+                validation_errors,
+                violations,
+                attempts,
+                a11y,
+            };
+            return Ok((result, detection.map(|(_, d)| d)));
+        }
+    }
+
+    /// Detect the design system of generated code
+    pub fn detect_design_system(&self, code: &ParsedCode) -> DetectionResult {
+        self.detector.detect_from_content(
+            code.html.as_deref().unwrap_or_default(),
+            code.css.as_deref().unwrap_or_default(),
+            code.javascript.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// Validate generated code against design system constraints
+    pub fn check_conformance(
+        &self,
+        code: &ParsedCode,
+        constraints: &DesignSystemConstraints,
+    ) -> anyhow::Result<crate::conformance::ConformanceReport> {
+        let mut validator = self
+            .validator
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Conformance validator lock poisoned"))?;
+        validator.validate(code, constraints)
+    }
+
+    /// Run the accessibility rule engine over generated code
+    pub fn check_accessibility(&self, code: &ParsedCode) -> Option<A11yReport> {
+        let html = code.html.as_deref()?;
+        match A11yAuditor::new().audit_source(html, code.css.as_deref()) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                warn!("Accessibility audit failed: {}", e);
+                None
+            }
+        }
     }
 
     /// Call the LLM with system and user prompts
-    async fn call_llm(&self, user_prompt: &str) -> anyhow::Result<String> {
+    async fn call_llm(&self, system_prompt: &str, user_prompt: &str) -> anyhow::Result<String> {
         let messages: Vec<ChatCompletionRequestMessage> = vec![
             ChatCompletionRequestSystemMessageArgs::default()
-                .content(SYSTEM_PROMPT)
+                .content(system_prompt)
                 .build()?
                 .into(),
             ChatCompletionRequestUserMessageArgs::default()
@@ -2,12 +2,18 @@
 //!
 //! Generates HTML5/CSS3/ES6+ code from graph retrieval results using GPT-4o.
 
+pub mod conformance;
 pub mod generator;
 pub mod parser;
 pub mod prompt;
 pub mod templates;
+pub mod translation;
+pub mod validation;
 
+pub use conformance::{ConformanceReport, ConformanceValidator, DesignSystemConstraints, Severity, Violation};
 pub use generator::{GenerationRequest, GenerationResult, VanillaCodeGenerator};
 pub use parser::{CodeParser, ParsedCode};
 pub use prompt::SimilarElement;
 pub use templates::TemplateEngine;
+pub use translation::{map_tokens, ReferenceComponent, TokenMapping, TokenRef, TranslationRequest, TranslationResult};
+pub use validation::{CodeValidator, Diagnostic};
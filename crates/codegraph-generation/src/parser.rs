@@ -1,7 +1,17 @@
 //! Code parser - extracts HTML, CSS, JS blocks from LLM response
 
+use std::cell::RefCell;
+
 use regex::Regex;
 
+use crate::validation::{CodeValidator, Diagnostic};
+
+thread_local! {
+    /// Tree-sitter parsers are costly to set up and not `Sync`, so each
+    /// thread builds one validator and reuses it
+    static VALIDATOR: RefCell<CodeValidator> = RefCell::new(CodeValidator::new());
+}
+
 /// Parsed code blocks from LLM response
 #[derive(Debug, Clone, Default)]
 pub struct ParsedCode {
@@ -97,45 +107,21 @@ impl CodeParser {
             .filter(|s| !s.is_empty())
     }
 
-    /// Validate HTML is well-formed (basic check)
+    /// Validate HTML is well-formed, returning formatted diagnostics on failure
     pub fn validate_html(&self, html: &str) -> Result<(), Vec<String>> {
-        let mut errors = Vec::new();
-
-        // Check for unclosed tags (simple heuristic)
-        let open_tags: Vec<&str> = vec![
-            "<div", "<span", "<p", "<section", "<article", "<header", "<footer", "<nav", "<main",
-            "<aside", "<form", "<button", "<ul", "<ol", "<li", "<table", "<thead", "<tbody", "<tr",
-            "<td", "<th",
-        ];
-
-        for tag in open_tags {
-            let tag_name = &tag[1..];
-            let open_count = html.matches(tag).count();
-            let close_pattern = format!("</{}", tag_name);
-            let close_count = html.matches(&close_pattern).count();
-
-            // Self-closing or void elements don't need closing tags
-            if tag_name != "br" && tag_name != "hr" && tag_name != "img" && tag_name != "input" {
-                if open_count > close_count {
-                    errors.push(format!(
-                        "Potentially unclosed <{}> tag: {} open, {} close",
-                        tag_name, open_count, close_count
-                    ));
-                }
-            }
-        }
-
-        // Check for DOCTYPE in full documents
-        if html.contains("<html") && !html.to_lowercase().contains("<!doctype") {
-            errors.push("Missing <!DOCTYPE html> declaration".to_string());
-        }
+        let diagnostics = VALIDATOR.with(|validator| validator.borrow_mut().validate_html(html));
 
-        if errors.is_empty() {
+        if diagnostics.is_empty() {
             Ok(())
         } else {
-            Err(errors)
+            Err(diagnostics.iter().map(ToString::to_string).collect())
         }
     }
+
+    /// Validate all parsed code blocks (HTML, CSS and JavaScript)
+    pub fn validate(&self, code: &ParsedCode) -> Vec<Diagnostic> {
+        VALIDATOR.with(|validator| validator.borrow_mut().validate(code))
+    }
 }
 
 #[cfg(test)]
@@ -170,4 +156,16 @@ console.log('hello');
         assert_eq!(parsed.css, Some(".btn { color: blue; }".to_string()));
         assert_eq!(parsed.javascript, Some("console.log('hello');".to_string()));
     }
+
+    #[test]
+    fn test_validate_html_tag_prefixes() {
+        let parser = CodeParser::new();
+
+        // <path>, <pre> and <progress> must not be mistaken for <p>
+        let html = r#"<pre>x</pre><progress></progress><svg><path d="M0 0"/></svg><p>ok</p>"#;
+        assert!(parser.validate_html(html).is_ok());
+
+        let errors = parser.validate_html("<section>\n  <div>open\n</section>").unwrap_err();
+        assert_eq!(errors, vec!["html:2:3: error: Unclosed <div> element".to_string()]);
+    }
 }
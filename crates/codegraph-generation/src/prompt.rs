@@ -1,5 +1,9 @@
 //! System prompts for LLM code generation
 
+use codegraph_extraction::design_system::DesignSystemType;
+
+use crate::translation::TranslationRequest;
+
 /// System prompt for vanilla HTML/CSS/JS code generation
 pub const SYSTEM_PROMPT: &str = r#"You are an expert frontend developer specializing in vanilla HTML5, CSS3, and ES6+ JavaScript.
 
@@ -95,6 +99,107 @@ pub fn build_user_prompt(
     prompt
 }
 
+/// Build a follow-up prompt asking the LLM to fix conformance violations
+pub fn build_repair_prompt(original_prompt: &str, previous_response: &str, violations: &[String]) -> String {
+    let mut prompt = String::from(original_prompt);
+    prompt.push_str("\n\n## Previous Attempt\n\n");
+    prompt.push_str(previous_response.trim());
+    prompt.push_str("\n\n## Design System Violations\n\n");
+    for violation in violations {
+        prompt.push_str(&format!("- {}\n", violation));
+    }
+    prompt.push_str("\nRegenerate the component fixing every violation above. Use the design system's classes and tokens instead of hard-coded values.");
+    prompt
+}
+
+/// System prompt for translating a component into a target design system
+pub fn translation_system_prompt(target: DesignSystemType) -> String {
+    format!(
+        "{}\n## Target Design System: {}\n\n{}\n\nThe translated component must be recognisable as {} by its markup alone.\n",
+        SYSTEM_PROMPT,
+        target.as_str(),
+        design_system_guidance(target),
+        target.as_str()
+    )
+}
+
+/// Markup conventions the LLM must follow for a target design system
+pub fn design_system_guidance(design_system: DesignSystemType) -> &'static str {
+    match design_system {
+        DesignSystemType::Tailwind => {
+            "- Style exclusively with Tailwind utility classes (flex, p-4, text-sm, bg-blue-600, rounded-md, hover:, focus:)\n\
+             - Keep the CSS block empty except for custom properties the utilities cannot express"
+        }
+        DesignSystemType::Bootstrap => {
+            "- Use Bootstrap 5 component classes (btn btn-primary, card, card-body, form-control, navbar, modal)\n\
+             - Use Bootstrap layout and utility classes (container, row, col-*, d-flex, justify-content-*, align-items-*)\n\
+             - Use data-bs-* attributes for interactive behaviour"
+        }
+        DesignSystemType::MaterialUI => {
+            "- Emit the DOM Material UI renders, with its generated classes (MuiButton-root MuiButton-contained, MuiPaper-root)\n\
+             - Add a JavaScript block with the equivalent React usage, importing components from '@mui/material'"
+        }
+        DesignSystemType::Chakra => {
+            "- Emit the DOM Chakra UI renders, using its CSS variables (--chakra-colors-*, --chakra-space-*)\n\
+             - Add a JavaScript block with the equivalent React usage (<Box>, <Button>, <Stack>) importing from '@chakra-ui/react'"
+        }
+        DesignSystemType::AntDesign => {
+            "- Use Ant Design class names (ant-btn ant-btn-primary, ant-card, ant-input, ant-modal)\n\
+             - Use Ant Design tokens for colors and radii (#1677ff primary, 6px radius)"
+        }
+        DesignSystemType::Shadcn => {
+            "- Emit the DOM shadcn/ui renders: Tailwind utilities driven by its CSS variables (bg-primary, text-muted-foreground, border-input)\n\
+             - Add a JavaScript block with the equivalent React usage importing from '@/components/ui/<component>'"
+        }
+        DesignSystemType::Custom | DesignSystemType::Unknown => {
+            "- Use plain semantic HTML and CSS custom properties for every themable value"
+        }
+    }
+}
+
+/// Build the user prompt for translating a component into another design system
+pub fn build_translation_prompt(request: &TranslationRequest) -> String {
+    let source = request.source_design_system.as_deref().unwrap_or("unknown");
+    let mut prompt = format!(
+        "Translate this {} component ({}) from {} to {}.\n\n## Source\n\n```html\n{}\n```\n\n",
+        request.category,
+        request.name,
+        source,
+        request.target_design_system.as_str(),
+        request.source_html.trim()
+    );
+    if let Some(css) = request.source_css.as_deref().filter(|css| !css.trim().is_empty()) {
+        prompt.push_str(&format!("```css\n{}\n```\n\n", css.trim()));
+    }
+
+    if !request.references.is_empty() {
+        prompt.push_str(&format!("## {} Reference Components\n\n", request.target_design_system.as_str()));
+        for reference in request.references.iter().take(5) {
+            prompt.push_str(&format!(
+                "### {} ({}, score: {:.2})\n```html\n{}\n```\n\n",
+                reference.name,
+                reference.relation,
+                reference.score,
+                reference.html.trim()
+            ));
+        }
+    }
+
+    if !request.token_mappings.is_empty() {
+        prompt.push_str("## Token Mapping\n\n");
+        for mapping in &request.token_mappings {
+            prompt.push_str(&format!(
+                "- {} ({}) -> {} ({})\n",
+                mapping.source, mapping.source_value, mapping.target, mapping.target_value
+            ));
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str("Preserve the component's structure, content, states and interactions. Replace every source design system class and token with its target equivalent.");
+    prompt
+}
+
 /// Similar element from graph retrieval
 #[derive(Debug, Clone)]
 pub struct SimilarElement {
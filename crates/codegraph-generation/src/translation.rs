@@ -0,0 +1,208 @@
+//! Cross-design-system translation
+//!
+//! Rewrites a component from one design system into another. Counterparts of
+//! the source in the target system (`CAN_REPLACE`/`SIMILAR_TO` neighbours) are
+//! given to the LLM as references, and the source's design tokens are mapped
+//! onto the nearest target tokens so hard-coded values can be swapped for the
+//! target's variables.
+
+use serde::{Deserialize, Serialize};
+
+use codegraph_extraction::a11y::parse_color;
+use codegraph_extraction::css::TokenCategory;
+use codegraph_extraction::design_system::{DesignSystemType, DetectionResult};
+
+use crate::conformance::DesignSystemConstraints;
+use crate::generator::GenerationResult;
+
+/// Maximum normalized distance for a token mapping to be proposed
+const MAX_TOKEN_DISTANCE: f32 = 0.25;
+
+/// Design token of either side of a translation
+#[derive(Debug, Clone)]
+pub struct TokenRef {
+    pub name: String,
+    pub value: String,
+    pub category: TokenCategory,
+}
+
+impl TokenRef {
+    pub fn new(name: impl Into<String>, value: impl Into<String>, category: TokenCategory) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            category,
+        }
+    }
+}
+
+/// Source token mapped onto its closest target token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenMapping {
+    pub source: String,
+    pub source_value: String,
+    pub target: String,
+    pub target_value: String,
+    /// Values are identical rather than merely close
+    pub exact: bool,
+}
+
+/// Target-system component used as a reference for the translation
+#[derive(Debug, Clone)]
+pub struct ReferenceComponent {
+    pub name: String,
+    pub html: String,
+    /// Relationship to the source (`CAN_REPLACE`, `SIMILAR_TO`, `SAME_CATEGORY`)
+    pub relation: String,
+    pub score: f32,
+}
+
+/// Request for translating a component into another design system
+#[derive(Debug, Clone)]
+pub struct TranslationRequest {
+    pub name: String,
+    pub category: String,
+    pub source_html: String,
+    pub source_css: Option<String>,
+    pub source_design_system: Option<String>,
+    pub target_design_system: DesignSystemType,
+    pub references: Vec<ReferenceComponent>,
+    pub token_mappings: Vec<TokenMapping>,
+    /// Target design system constraints to validate against
+    pub constraints: Option<DesignSystemConstraints>,
+    /// How many times to regenerate when the output is not detected as the
+    /// target design system or violates its constraints
+    pub max_repair_attempts: u32,
+}
+
+/// Result of a translation
+#[derive(Debug)]
+pub struct TranslationResult {
+    pub generation: GenerationResult,
+    /// Design system detected on the generated code
+    pub detection: DetectionResult,
+    /// Whether the detected design system is the requested target
+    pub valid: bool,
+}
+
+/// Map every source token onto the closest target token of the same category
+///
+/// Colors are compared in RGB space and lengths by relative difference; other
+/// values only match when equal. Ties (and unparseable values) prefer tokens
+/// whose names end in the same word (`--bs-primary` ↔ `--color-primary`).
+pub fn map_tokens(source: &[TokenRef], target: &[TokenRef]) -> Vec<TokenMapping> {
+    source
+        .iter()
+        .filter_map(|token| {
+            let key = name_key(&token.name);
+            let (best, distance) = target
+                .iter()
+                .filter(|candidate| candidate.category == token.category)
+                .map(|candidate| (candidate, token_distance(token, candidate)))
+                .min_by(|(a, da), (b, db)| {
+                    let renamed = |t: &TokenRef| name_key(&t.name) != key;
+                    da.total_cmp(db).then_with(|| renamed(a).cmp(&renamed(b)))
+                })?;
+            (distance <= MAX_TOKEN_DISTANCE).then(|| TokenMapping {
+                source: token.name.clone(),
+                source_value: token.value.clone(),
+                target: best.name.clone(),
+                target_value: best.value.clone(),
+                exact: distance == 0.0,
+            })
+        })
+        .collect()
+}
+
+/// Normalized distance between two token values in `0.0..=1.0`
+fn token_distance(a: &TokenRef, b: &TokenRef) -> f32 {
+    let measured = match a.category {
+        TokenCategory::Color => parse_color(&a.value).zip(parse_color(&b.value)).map(|(x, y)| {
+            let squared: f32 = x.iter().zip(y).map(|(&p, q)| (p as f32 - q as f32).powi(2)).sum();
+            squared.sqrt() / (3.0 * 255.0f32.powi(2)).sqrt()
+        }),
+        TokenCategory::Spacing | TokenCategory::Typography | TokenCategory::BorderRadius => {
+            parse_length(&a.value).zip(parse_length(&b.value)).map(|(x, y)| {
+                if x == y {
+                    0.0
+                } else {
+                    (x - y).abs() / x.abs().max(y.abs())
+                }
+            })
+        }
+        _ => None,
+    };
+
+    measured.unwrap_or_else(|| {
+        if a.value.trim().eq_ignore_ascii_case(b.value.trim()) {
+            0.0
+        } else if name_key(&a.name) == name_key(&b.name) {
+            MAX_TOKEN_DISTANCE
+        } else {
+            1.0
+        }
+    })
+}
+
+/// Length in pixels (`rem`/`em` at 16px); unitless numbers are taken as-is
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim().to_lowercase();
+    let (number, scale) = if let Some(n) = value.strip_suffix("rem") {
+        (n, 16.0)
+    } else if let Some(n) = value.strip_suffix("em") {
+        (n, 16.0)
+    } else if let Some(n) = value.strip_suffix("px") {
+        (n, 1.0)
+    } else {
+        (value.as_str(), 1.0)
+    };
+    number.trim().parse::<f32>().ok().map(|n| n * scale)
+}
+
+/// Last alphabetic word of a token name (`--bs-primary` → `primary`)
+fn name_key(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .rev()
+        .find(|word| word.chars().any(|c| c.is_ascii_alphabetic()))
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_tokens_by_value_and_name() {
+        let source = vec![
+            TokenRef::new("--bs-primary", "#0d6efd", TokenCategory::Color),
+            TokenRef::new("--bs-border-radius", "0.375rem", TokenCategory::BorderRadius),
+            TokenRef::new("--bs-font-family", "system-ui", TokenCategory::Typography),
+        ];
+        let target = vec![
+            TokenRef::new("--color-blue-600", "#2563eb", TokenCategory::Color),
+            TokenRef::new("--color-red-600", "#dc2626", TokenCategory::Color),
+            TokenRef::new("--radius-md", "6px", TokenCategory::BorderRadius),
+            TokenRef::new("--spacing-md", "6px", TokenCategory::Spacing),
+        ];
+
+        let mappings = map_tokens(&source, &target);
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(mappings[0].target, "--color-blue-600");
+        assert!(!mappings[0].exact);
+        assert_eq!(mappings[1].target, "--radius-md");
+        assert!(mappings[1].exact);
+    }
+
+    #[test]
+    fn test_map_tokens_prefers_matching_names_on_ties() {
+        let source = vec![TokenRef::new("--bs-primary", "#000", TokenCategory::Color)];
+        let target = vec![
+            TokenRef::new("--text", "#000000", TokenCategory::Color),
+            TokenRef::new("--color-primary", "#000000", TokenCategory::Color),
+        ];
+
+        let mappings = map_tokens(&source, &target);
+        assert_eq!(mappings[0].target, "--color-primary");
+    }
+}
@@ -0,0 +1,564 @@
+//! Syntax validation for generated code
+//!
+//! Parses generated HTML, CSS and JavaScript with tree-sitter and reports
+//! line/column diagnostics: syntax errors, unclosed or misnested tags,
+//! duplicate IDs and invalid values for common attributes. CSS and JS
+//! embedded in `<style>`/`<script>` elements are validated as well.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use tree_sitter::{Node, Parser, Point};
+
+use crate::conformance::Severity;
+use crate::parser::ParsedCode;
+
+static HTML_LANGUAGE: Lazy<tree_sitter::Language> = Lazy::new(|| tree_sitter_html::LANGUAGE.into());
+static CSS_LANGUAGE: Lazy<tree_sitter::Language> = Lazy::new(|| tree_sitter_css::LANGUAGE.into());
+static JS_LANGUAGE: Lazy<tree_sitter::Language> =
+    Lazy::new(|| tree_sitter_javascript::LANGUAGE.into());
+
+/// Elements that never have content or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose closing tag may be omitted
+const OPTIONAL_END_ELEMENTS: &[&str] = &[
+    "html", "head", "body", "p", "li", "dt", "dd", "option", "optgroup", "tr", "td", "th",
+    "thead", "tbody", "tfoot", "colgroup", "caption", "rb", "rt", "rp",
+];
+
+/// Foreign content roots where XML-style self-closing tags are valid
+const FOREIGN_ELEMENTS: &[&str] = &["svg", "math"];
+
+const INPUT_TYPES: &[&str] = &[
+    "button", "checkbox", "color", "date", "datetime-local", "email", "file", "hidden", "image",
+    "month", "number", "password", "radio", "range", "reset", "search", "submit", "tel", "text",
+    "time", "url", "week",
+];
+
+const ARIA_BOOLEAN: &[&str] = &[
+    "aria-atomic", "aria-busy", "aria-disabled", "aria-hidden", "aria-modal", "aria-multiline",
+    "aria-multiselectable", "aria-readonly", "aria-required",
+];
+
+const ARIA_TRISTATE: &[&str] = &["aria-checked", "aria-pressed"];
+
+const NON_NEGATIVE_INTEGER: &[&str] = &[
+    "colspan", "rowspan", "maxlength", "minlength", "size", "rows", "cols", "span",
+];
+
+/// Source language a diagnostic refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceLanguage {
+    Html,
+    Css,
+    JavaScript,
+}
+
+impl SourceLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Css => "css",
+            Self::JavaScript => "js",
+        }
+    }
+}
+
+/// A single validation finding with a 1-based source position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub language: SourceLanguage,
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn at(language: SourceLanguage, severity: Severity, point: Point, message: impl Into<String>) -> Self {
+        Self {
+            language,
+            severity,
+            line: point.row + 1,
+            column: point.column + 1,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.language.as_str(),
+            self.line,
+            self.column,
+            self.severity.as_str(),
+            self.message
+        )
+    }
+}
+
+/// tree-sitter based validator for HTML, CSS and JavaScript
+pub struct CodeValidator {
+    html: Parser,
+    css: Parser,
+    js: Parser,
+}
+
+impl Default for CodeValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeValidator {
+    pub fn new() -> Self {
+        let mut html = Parser::new();
+        html.set_language(&HTML_LANGUAGE)
+            .expect("Failed to set HTML language");
+        let mut css = Parser::new();
+        css.set_language(&CSS_LANGUAGE)
+            .expect("Failed to set CSS language");
+        let mut js = Parser::new();
+        js.set_language(&JS_LANGUAGE)
+            .expect("Failed to set JavaScript language");
+        Self { html, css, js }
+    }
+
+    /// Validate all code blocks, sorted by severity then position
+    pub fn validate(&mut self, code: &ParsedCode) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if let Some(html) = &code.html {
+            diagnostics.extend(self.validate_html(html));
+        }
+        if let Some(css) = &code.css {
+            diagnostics.extend(self.validate_css(css));
+        }
+        if let Some(js) = &code.javascript {
+            diagnostics.extend(self.validate_javascript(js));
+        }
+        diagnostics.sort_by_key(|d| (std::cmp::Reverse(d.severity), d.line, d.column));
+        diagnostics
+    }
+
+    /// Validate an HTML fragment or document, including embedded styles and scripts
+    pub fn validate_html(&mut self, html: &str) -> Vec<Diagnostic> {
+        let Some(tree) = self.html.parse(html, None) else {
+            return vec![Diagnostic::at(
+                SourceLanguage::Html,
+                Severity::Error,
+                Point::default(),
+                "Failed to parse HTML",
+            )];
+        };
+
+        let mut walker = HtmlWalker {
+            source: html,
+            diagnostics: Vec::new(),
+            ids: HashMap::new(),
+            embedded: Vec::new(),
+        };
+        walker.visit(tree.root_node(), false);
+
+        let HtmlWalker {
+            mut diagnostics,
+            embedded,
+            ..
+        } = walker;
+
+        for (language, start, text) in embedded {
+            let nested = match language {
+                SourceLanguage::Css => self.validate_css(text),
+                _ => self.validate_javascript(text),
+            };
+            diagnostics.extend(nested.into_iter().map(|d| offset(d, start)));
+        }
+
+        if html.contains("<html") && !html.to_lowercase().contains("<!doctype") {
+            diagnostics.push(Diagnostic::at(
+                SourceLanguage::Html,
+                Severity::Warning,
+                Point::default(),
+                "Missing <!DOCTYPE html> declaration",
+            ));
+        }
+
+        diagnostics
+    }
+
+    /// Validate a stylesheet
+    pub fn validate_css(&mut self, css: &str) -> Vec<Diagnostic> {
+        syntax_errors(&mut self.css, SourceLanguage::Css, css)
+    }
+
+    /// Validate a script
+    pub fn validate_javascript(&mut self, js: &str) -> Vec<Diagnostic> {
+        syntax_errors(&mut self.js, SourceLanguage::JavaScript, js)
+    }
+}
+
+/// Shift a diagnostic from an embedded block to its position in the host document
+fn offset(mut diagnostic: Diagnostic, start: Point) -> Diagnostic {
+    if diagnostic.line == 1 {
+        diagnostic.column += start.column;
+    }
+    diagnostic.line += start.row;
+    diagnostic
+}
+
+/// Collect ERROR and MISSING nodes from a parse
+fn syntax_errors(parser: &mut Parser, language: SourceLanguage, source: &str) -> Vec<Diagnostic> {
+    let Some(tree) = parser.parse(source, None) else {
+        return vec![Diagnostic::at(
+            language,
+            Severity::Error,
+            Point::default(),
+            format!("Failed to parse {}", language.as_str()),
+        )];
+    };
+
+    let mut diagnostics = Vec::new();
+    if tree.root_node().has_error() {
+        collect_syntax_errors(tree.root_node(), source, language, &mut diagnostics);
+    }
+    diagnostics
+}
+
+fn collect_syntax_errors(node: Node, source: &str, language: SourceLanguage, out: &mut Vec<Diagnostic>) {
+    if node.is_missing() {
+        out.push(Diagnostic::at(
+            language,
+            Severity::Error,
+            node.start_position(),
+            format!("Missing `{}`", node.kind()),
+        ));
+        return;
+    }
+    if node.is_error() {
+        out.push(Diagnostic::at(
+            language,
+            Severity::Error,
+            node.start_position(),
+            format!("Syntax error near `{}`", snippet(node, source)),
+        ));
+        return;
+    }
+    if !node.has_error() {
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_syntax_errors(child, source, language, out);
+    }
+}
+
+fn snippet<'a>(node: Node, source: &'a str) -> &'a str {
+    let text = source[node.start_byte()..node.end_byte()].trim();
+    let end = text
+        .char_indices()
+        .nth(40)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let text = &text[..end];
+    text.lines().next().unwrap_or(text)
+}
+
+struct HtmlWalker<'a> {
+    source: &'a str,
+    diagnostics: Vec<Diagnostic>,
+    /// First position each id was defined at
+    ids: HashMap<String, Point>,
+    /// Embedded `<style>`/`<script>` contents to validate afterwards
+    embedded: Vec<(SourceLanguage, Point, &'a str)>,
+}
+
+impl<'a> HtmlWalker<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        &self.source[node.start_byte()..node.end_byte()]
+    }
+
+    fn report(&mut self, severity: Severity, point: Point, message: impl Into<String>) {
+        self.diagnostics
+            .push(Diagnostic::at(SourceLanguage::Html, severity, point, message));
+    }
+
+    fn visit(&mut self, node: Node, foreign: bool) {
+        if node.is_missing() {
+            self.report(Severity::Error, node.start_position(), format!("Missing `{}`", node.kind()));
+            return;
+        }
+
+        match node.kind() {
+            "ERROR" => {
+                let message = match self.text(node).trim() {
+                    text if text.starts_with("</") => {
+                        format!("Misnested or unexpected closing tag `{}`", snippet(node, self.source))
+                    }
+                    _ => format!("Syntax error near `{}`", snippet(node, self.source)),
+                };
+                self.report(Severity::Error, node.start_position(), message);
+            }
+            "erroneous_end_tag" => {
+                let message = format!("Unexpected closing tag `{}`", self.text(node).trim());
+                self.report(Severity::Error, node.start_position(), message);
+            }
+            "element" => self.visit_element(node, foreign),
+            "script_element" | "style_element" => {
+                if let Some(tag) = child_of_kind(node, "start_tag") {
+                    self.check_attributes(tag, "");
+                }
+                if let Some(raw) = child_of_kind(node, "raw_text") {
+                    let language = if node.kind() == "style_element" {
+                        SourceLanguage::Css
+                    } else {
+                        SourceLanguage::JavaScript
+                    };
+                    self.embedded
+                        .push((language, raw.start_position(), self.text(raw)));
+                }
+            }
+            _ => self.visit_children(node, foreign),
+        }
+    }
+
+    fn visit_children(&mut self, node: Node, foreign: bool) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child, foreign);
+        }
+    }
+
+    fn visit_element(&mut self, node: Node, foreign: bool) {
+        let start = child_of_kind(node, "start_tag").or_else(|| child_of_kind(node, "self_closing_tag"));
+        let Some(start) = start else {
+            self.visit_children(node, foreign);
+            return;
+        };
+
+        let tag = child_of_kind(start, "tag_name")
+            .map(|n| self.text(n).to_lowercase())
+            .unwrap_or_default();
+        let foreign = foreign || FOREIGN_ELEMENTS.contains(&tag.as_str());
+        let position = start.start_position();
+
+        self.check_attributes(start, &tag);
+
+        if !foreign {
+            let is_void = VOID_ELEMENTS.contains(&tag.as_str());
+            let end_tag = child_of_kind(node, "end_tag");
+
+            if start.kind() == "self_closing_tag" && !is_void {
+                self.report(
+                    Severity::Warning,
+                    position,
+                    format!("Self-closing syntax is ignored on non-void element <{}>", tag),
+                );
+            } else if is_void {
+                if let Some(end) = end_tag {
+                    self.report(
+                        Severity::Error,
+                        end.start_position(),
+                        format!("Void element <{}> must not have a closing tag", tag),
+                    );
+                }
+            } else if end_tag.is_none() && !OPTIONAL_END_ELEMENTS.contains(&tag.as_str()) {
+                self.report(Severity::Error, position, format!("Unclosed <{}> element", tag));
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.id() != start.id() && child.kind() != "end_tag" {
+                self.visit(child, foreign);
+            }
+        }
+    }
+
+    fn check_attributes(&mut self, tag_node: Node, tag: &str) {
+        let mut cursor = tag_node.walk();
+        for attr in tag_node.children(&mut cursor) {
+            if attr.kind() != "attribute" {
+                continue;
+            }
+            let Some(name) = child_of_kind(attr, "attribute_name").map(|n| self.text(n).to_lowercase()) else {
+                continue;
+            };
+            let value = attribute_value(attr, self.source);
+            let position = attr.start_position();
+
+            if name == "id" {
+                let Some(id) = value else {
+                    self.report(Severity::Error, position, "Empty id attribute");
+                    continue;
+                };
+                if id.is_empty() || id.chars().any(char::is_whitespace) {
+                    self.report(
+                        Severity::Error,
+                        position,
+                        format!("Invalid id \"{}\": must be non-empty without whitespace", id),
+                    );
+                } else if let Some(first) = self.ids.get(id) {
+                    let message = format!(
+                        "Duplicate id \"{}\" (first defined at {}:{})",
+                        id,
+                        first.row + 1,
+                        first.column + 1
+                    );
+                    self.report(Severity::Error, position, message);
+                } else {
+                    self.ids.insert(id.to_string(), position);
+                }
+                continue;
+            }
+
+            if let Some(message) = invalid_attribute(tag, &name, value) {
+                self.report(Severity::Warning, position, message);
+            }
+        }
+    }
+}
+
+fn child_of_kind<'t>(node: Node<'t>, kind: &str) -> Option<Node<'t>> {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).find(|c| c.kind() == kind);
+    found
+}
+
+fn attribute_value<'a>(attr: Node, source: &'a str) -> Option<&'a str> {
+    let mut cursor = attr.walk();
+    for child in attr.children(&mut cursor) {
+        match child.kind() {
+            "attribute_value" => return Some(&source[child.start_byte()..child.end_byte()]),
+            "quoted_attribute_value" => {
+                let text = &source[child.start_byte()..child.end_byte()];
+                return Some(text.trim_matches('"').trim_matches('\''));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Check a common attribute's value; returns a message when invalid
+fn invalid_attribute(tag: &str, name: &str, value: Option<&str>) -> Option<String> {
+    let raw = value.unwrap_or("");
+    let lower = raw.trim().to_lowercase();
+    let one_of = |allowed: &[&str]| -> Option<String> {
+        (!allowed.contains(&lower.as_str())).then(|| {
+            format!(
+                "Invalid value \"{}\" for {} (expected one of: {})",
+                raw,
+                name,
+                allowed.join(", ")
+            )
+        })
+    };
+
+    match name {
+        "type" if tag == "input" => one_of(INPUT_TYPES),
+        "type" if tag == "button" => one_of(&["submit", "reset", "button"]),
+        "method" if tag == "form" => one_of(&["get", "post", "dialog"]),
+        "dir" => one_of(&["ltr", "rtl", "auto"]),
+        "loading" => one_of(&["lazy", "eager"]),
+        "decoding" => one_of(&["sync", "async", "auto"]),
+        "draggable" => one_of(&["true", "false"]),
+        "spellcheck" => one_of(&["", "true", "false"]),
+        "contenteditable" => one_of(&["", "true", "false", "plaintext-only"]),
+        "aria-expanded" | "aria-selected" => one_of(&["true", "false", "undefined"]),
+        n if ARIA_BOOLEAN.contains(&n) => one_of(&["true", "false"]),
+        n if ARIA_TRISTATE.contains(&n) => one_of(&["true", "false", "mixed"]),
+        "tabindex" => lower
+            .parse::<i32>()
+            .is_err()
+            .then(|| format!("Invalid value \"{}\" for tabindex (expected an integer)", raw)),
+        "width" | "height" if matches!(tag, "img" | "video" | "canvas" | "iframe" | "input" | "embed" | "object" | "source") => {
+            lower
+                .parse::<u32>()
+                .is_err()
+                .then(|| format!("Invalid value \"{}\" for {} (expected a non-negative integer)", raw, name))
+        }
+        n if NON_NEGATIVE_INTEGER.contains(&n) => lower
+            .parse::<u32>()
+            .is_err()
+            .then(|| format!("Invalid value \"{}\" for {} (expected a non-negative integer)", raw, name)),
+        "target" if lower.starts_with('_') => one_of(&["_blank", "_self", "_parent", "_top"]),
+        "href" if tag == "a" && lower.is_empty() => Some("Empty href on <a>".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_valid_html_has_no_diagnostics() {
+        let mut validator = CodeValidator::new();
+        let html = r#"<nav id="main"><ul><li>One<li>Two</ul>
+<svg viewBox="0 0 24 24"><path d="M0 0"/></svg><pre>x</pre><progress value="1"></progress>
+<img src="a.png" alt="" width="32"><br><input type="email" tabindex="0"></nav>"#;
+
+        let diagnostics = validator.validate_html(html);
+        assert!(diagnostics.is_empty(), "{:?}", messages(&diagnostics));
+    }
+
+    #[test]
+    fn test_html_structure_errors() {
+        let mut validator = CodeValidator::new();
+        let html = "<div id=\"a\">\n  <span id=\"a\">x</div>\n<b><i>y</b></i><br></br>";
+
+        let diagnostics = validator.validate_html(html);
+        let text = messages(&diagnostics);
+
+        assert!(text.iter().any(|m| m.starts_with("html:2:3: error: Unclosed <span>")), "{:?}", text);
+        assert!(text.iter().any(|m| m.contains("Duplicate id \"a\" (first defined at 1:6)")), "{:?}", text);
+        assert!(text.iter().any(|m| m.contains("closing tag `</i>`")), "{:?}", text);
+        assert!(text.iter().any(|m| m.contains("Void element <br>")), "{:?}", text);
+    }
+
+    #[test]
+    fn test_invalid_attribute_values() {
+        let mut validator = CodeValidator::new();
+        let html = r#"<button type="link" aria-hidden="yes" tabindex="first">Go</button>"#;
+
+        let diagnostics = validator.validate_html(html);
+        assert_eq!(diagnostics.len(), 3, "{:?}", messages(&diagnostics));
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn test_css_and_js_syntax_errors() {
+        let mut validator = CodeValidator::new();
+        let code = ParsedCode {
+            html: Some("<style>\n.a { color: red; }\n.b { color: }}\n</style><p>ok</p>".to_string()),
+            css: Some(".btn { color: blue; }".to_string()),
+            javascript: Some("const x = ;\nconsole.log(x);".to_string()),
+        };
+
+        let diagnostics = validator.validate(&code);
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.language == SourceLanguage::Css && d.line == 3));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.language == SourceLanguage::JavaScript && d.line == 1));
+    }
+}
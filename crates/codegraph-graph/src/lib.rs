@@ -7,7 +7,12 @@ pub mod relations;
 pub mod repository;
 pub mod schema;
 
-pub use entities::{DesignSystem, Snippet, SnippetSummary, UIElement};
-pub use relations::RelationManager;
+pub use entities::{
+    Counterpart, DesignSystem, DesignToken, ElementPage, ElementQuery, ElementSort, GraphDump, GraphNodeRef,
+    GraphRelation, ListedElement, Neighborhood, NeighborhoodEdge, NeighborhoodNode, NeighborhoodNodeKind, NeighborhoodQuery, NeighborhoodRoot, Snippet,
+    SnippetSummary, SortDirection, UIElement,
+};
+pub use relations::{RelationManager, SimilarityBasis};
 pub use repository::Neo4jRepository;
 pub use schema::SchemaManager;
+pub use codegraph_core::traits::GraphStore;
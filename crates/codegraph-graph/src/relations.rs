@@ -21,9 +21,31 @@ pub enum RelationType {
     SimilarTo,
     /// Element can replace another
     CanReplace,
+    /// Element consumes a design token
+    UsesToken,
+    /// Design token aliases another token
+    AliasOf,
 }
 
 impl RelationType {
+    pub const ALL: [RelationType; 9] = [
+        Self::HasChild,
+        Self::HasStyle,
+        Self::HasState,
+        Self::TriggersEvent,
+        Self::BelongsToDesignSystem,
+        Self::SimilarTo,
+        Self::CanReplace,
+        Self::UsesToken,
+        Self::AliasOf,
+    ];
+
+    /// Whether `name` is a relationship type of the graph, including the
+    /// snippet-to-element `HAS_ELEMENT`
+    pub fn is_known(name: &str) -> bool {
+        name == "HAS_ELEMENT" || Self::ALL.iter().any(|t| t.as_str() == name)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::HasChild => "HAS_CHILD",
@@ -33,10 +55,14 @@ impl RelationType {
             Self::BelongsToDesignSystem => "BELONGS_TO_DESIGN_SYSTEM",
             Self::SimilarTo => "SIMILAR_TO",
             Self::CanReplace => "CAN_REPLACE",
+            Self::UsesToken => "USES_TOKEN",
+            Self::AliasOf => "ALIAS_OF",
         }
     }
 }
 
+pub use codegraph_core::entities::SimilarityBasis;
+
 /// Manages relationships between graph nodes
 pub struct RelationManager {
     graph: Graph,
@@ -127,6 +153,86 @@ impl RelationManager {
         Ok(())
     }
 
+    /// Link UIElement to the design tokens it consumes (all themes)
+    pub async fn link_to_token(&self, element_id: Uuid, token_name: &str) -> anyhow::Result<()> {
+        let cypher = r#"
+            MATCH (e:UIElement {id: $element_id})
+            MATCH (t:DesignToken {name: $token_name})
+            MERGE (e)-[r:USES_TOKEN]->(t)
+            SET r.created_at = coalesce(r.created_at, datetime())
+            RETURN count(r) as linked
+        "#;
+
+        self.graph
+            .run(
+                query(cypher)
+                    .param("element_id", element_id.to_string())
+                    .param("token_name", token_name),
+            )
+            .await?;
+
+        debug!("Linked element {} to token {}", element_id, token_name);
+        Ok(())
+    }
+
+    /// Set the HAS_CHILD edges of composite elements.
+    ///
+    /// Existing HAS_CHILD edges of every parent in `links` are replaced, so
+    /// re-ingesting a changed snippet does not leave stale containment behind.
+    pub async fn link_children(&self, links: &[(Uuid, Uuid)]) -> anyhow::Result<()> {
+        if links.is_empty() {
+            return Ok(());
+        }
+
+        let cypher = r#"
+            MATCH (p:UIElement)-[old:HAS_CHILD]->(:UIElement)
+            WHERE p.id IN $parents
+            DELETE old
+            WITH count(*) AS removed
+            UNWIND range(0, size($parents) - 1) AS i
+            MATCH (p:UIElement {id: $parents[i]})
+            MATCH (c:UIElement {id: $children[i]})
+            MERGE (p)-[r:HAS_CHILD]->(c)
+            SET r.created_at = coalesce(r.created_at, datetime())
+        "#;
+
+        let parents: Vec<String> = links.iter().map(|(parent, _)| parent.to_string()).collect();
+        let children: Vec<String> = links.iter().map(|(_, child)| child.to_string()).collect();
+
+        self.graph
+            .run(query(cypher).param("parents", parents).param("children", children))
+            .await?;
+
+        debug!("Created {} HAS_CHILD relations", links.len());
+        Ok(())
+    }
+
+    /// Link an alias token to its target, preferring the same theme
+    pub async fn link_token_alias(&self, name: &str, alias_of: &str, theme: &str) -> anyhow::Result<()> {
+        let cypher = r#"
+            MATCH (a:DesignToken {name: $name, theme: $theme})
+            MATCH (b:DesignToken {name: $alias_of})
+            WHERE b.theme IN [$theme, 'default']
+            WITH a, b ORDER BY CASE b.theme WHEN $theme THEN 0 ELSE 1 END
+            LIMIT 1
+            MERGE (a)-[r:ALIAS_OF]->(b)
+            SET r.created_at = coalesce(r.created_at, datetime())
+            RETURN r
+        "#;
+
+        self.graph
+            .run(
+                query(cypher)
+                    .param("name", name)
+                    .param("alias_of", alias_of)
+                    .param("theme", theme),
+            )
+            .await?;
+
+        debug!("Created ALIAS_OF relation: {} -> {} ({})", name, alias_of, theme);
+        Ok(())
+    }
+
     /// Create similarity relationship with score
     pub async fn create_similarity(
         &self,
@@ -159,6 +265,128 @@ impl RelationManager {
         Ok(())
     }
 
+    /// Create scored `SIMILAR_TO` relations in one round trip
+    pub async fn create_similarities(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.merge_scored(pairs, "SIMILAR_TO", basis, false).await?;
+        debug!("Created {} SIMILAR_TO relations ({})", pairs.len(), basis.as_str());
+        Ok(())
+    }
+
+    /// Propose `CAN_REPLACE` relations (marked `proposed` until reviewed)
+    pub async fn propose_replacements(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.merge_scored(pairs, "CAN_REPLACE", basis, true).await?;
+        debug!("Proposed {} CAN_REPLACE relations ({})", pairs.len(), basis.as_str());
+        Ok(())
+    }
+
+    async fn merge_scored(
+        &self,
+        pairs: &[(Uuid, Uuid, f32)],
+        rel_type: &str,
+        basis: SimilarityBasis,
+        proposed: bool,
+    ) -> anyhow::Result<()> {
+        if pairs.is_empty() {
+            return Ok(());
+        }
+
+        // Curated (non-proposed) edges keep their flag
+        let cypher = format!(
+            r#"
+            UNWIND range(0, size($from_ids) - 1) AS i
+            MATCH (a:UIElement {{id: $from_ids[i]}})
+            MATCH (b:UIElement {{id: $to_ids[i]}})
+            MERGE (a)-[r:{} {{basis: $basis}}]->(b)
+            SET r.score = $scores[i],
+                r.proposed = coalesce(r.proposed, $proposed),
+                r.created_at = coalesce(r.created_at, datetime())
+            "#,
+            rel_type
+        );
+
+        let from_ids: Vec<String> = pairs.iter().map(|(from, _, _)| from.to_string()).collect();
+        let to_ids: Vec<String> = pairs.iter().map(|(_, to, _)| to.to_string()).collect();
+        let scores: Vec<f64> = pairs.iter().map(|(_, _, score)| *score as f64).collect();
+
+        self.graph
+            .run(
+                query(&cypher)
+                    .param("from_ids", from_ids)
+                    .param("to_ids", to_ids)
+                    .param("scores", scores)
+                    .param("basis", basis.as_str())
+                    .param("proposed", proposed),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Remove `SIMILAR_TO` and still-proposed `CAN_REPLACE` relations of one basis touching the given elements
+    pub async fn clear_similarity(&self, element_ids: &[Uuid], basis: SimilarityBasis) -> anyhow::Result<u64> {
+        if element_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let cypher = r#"
+            MATCH (e:UIElement)-[r:SIMILAR_TO|CAN_REPLACE]-(:UIElement)
+            WHERE e.id IN $ids AND r.basis = $basis AND (type(r) = 'SIMILAR_TO' OR r.proposed = true)
+            WITH DISTINCT r
+            DELETE r
+            RETURN count(r) AS removed
+        "#;
+
+        let ids: Vec<String> = element_ids.iter().map(|id| id.to_string()).collect();
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("ids", ids).param("basis", basis.as_str()))
+            .await?;
+
+        let removed = match result.next().await? {
+            Some(row) => row.get::<i64>("removed").unwrap_or(0) as u64,
+            None => 0,
+        };
+        debug!("Cleared {} similarity relations", removed);
+        Ok(removed)
+    }
+
+    /// All `SIMILAR_TO` relations of one basis scoring at least `min_score`
+    pub async fn similarity_edges(
+        &self,
+        min_score: f32,
+        basis: SimilarityBasis,
+    ) -> anyhow::Result<Vec<(Uuid, Uuid, f32)>> {
+        let cypher = r#"
+            MATCH (a:UIElement)-[r:SIMILAR_TO {basis: $basis}]->(b:UIElement)
+            WHERE r.score >= $min_score
+            RETURN a.id AS from_id, b.id AS to_id, r.score AS score
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(
+                query(cypher)
+                    .param("min_score", min_score as f64)
+                    .param("basis", basis.as_str()),
+            )
+            .await?;
+
+        let mut edges = Vec::new();
+        while let Some(row) = result.next().await? {
+            let (Ok(from), Ok(to), Ok(score)) = (
+                row.get::<String>("from_id"),
+                row.get::<String>("to_id"),
+                row.get::<f64>("score"),
+            ) else {
+                continue;
+            };
+            if let (Ok(from), Ok(to)) = (Uuid::parse_str(&from), Uuid::parse_str(&to)) {
+                edges.push((from, to, score as f32));
+            }
+        }
+
+        Ok(edges)
+    }
+
     /// Delete a specific relationship
     pub async fn delete_relation(
         &self,
@@ -216,8 +444,8 @@ impl RelationManager {
         let cypher = r#"
             MATCH (e:UIElement {id: $element_id})-[r:SIMILAR_TO]->(s:UIElement)
             WHERE r.score >= $min_score
-            RETURN s.id as similar_id, r.score as score
-            ORDER BY r.score DESC
+            RETURN s.id as similar_id, max(r.score) as score
+            ORDER BY score DESC
         "#;
 
         let mut result = self
@@ -2,16 +2,31 @@
 //!
 //! Optimized for <100ms latency on simple queries and 10k+ element support.
 
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::time::Instant;
 
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use codegraph_core::fingerprint::band_keys;
+use codegraph_core::migrations::AppliedMigration;
+use codegraph_core::traits::GraphStore;
 use neo4rs::{query, ConfigBuilder, Graph};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::entities::{DesignSystem, SimilarElement, Snippet, SnippetSummary, UIElement};
-use crate::relations::RelationManager;
-use crate::schema::SchemaManager;
+use crate::entities::{
+    Counterpart, DesignSystem, DesignToken, ElementPage, ElementQuery, ElementSort, GraphDump, GraphNodeRef,
+    GraphRelation, ListedElement, Neighborhood, NeighborhoodEdge, NeighborhoodNode, NeighborhoodNodeKind, NeighborhoodQuery, NeighborhoodRoot, SimilarElement,
+    Snippet, SnippetSummary, SortDirection, UIElement,
+};
+use crate::relations::{RelationManager, RelationType, SimilarityBasis};
+use crate::schema::{fingerprint_band_properties, fingerprint_band_property, SchemaManager};
+
+/// Columns describing node `m` in neighbourhood queries
+const NEIGHBOR_COLUMNS: &str = "elementId(m) AS m_key, labels(m) AS m_labels, m.id AS m_id, m.name AS m_name, \
+     m.category AS m_category, m.design_system AS m_design_system, m.theme AS m_theme, \
+     COUNT { (m)--() } AS m_degree";
 
 /// Neo4j repository for UI element persistence
 pub struct Neo4jRepository {
@@ -57,6 +72,11 @@ impl Neo4jRepository {
 
     /// Initialize schema (constraints + indexes)
     pub async fn initialize_schema(&self) -> anyhow::Result<()> {
+        self.schema.initialize().await.map(|_| ())
+    }
+
+    /// Run pending schema migrations, returning those applied
+    pub async fn migrate(&self) -> anyhow::Result<Vec<AppliedMigration>> {
         self.schema.initialize().await
     }
 
@@ -76,6 +96,30 @@ impl Neo4jRepository {
     pub async fn save(&self, element: &UIElement) -> anyhow::Result<()> {
         let start = Instant::now();
 
+        self.graph.run(Self::save_query(element)).await?;
+
+        self.check_latency("save", start);
+        Ok(())
+    }
+
+    /// Save many UIElements in a single transaction
+    pub async fn save_batch(&self, elements: &[UIElement]) -> anyhow::Result<usize> {
+        if elements.is_empty() {
+            return Ok(0);
+        }
+        let start = Instant::now();
+
+        let mut txn = self.graph.start_txn().await?;
+        txn.run_queries(elements.iter().map(Self::save_query)).await?;
+        txn.commit().await?;
+
+        debug!("Saved batch of {} elements", elements.len());
+        self.check_latency("save_batch", start);
+        Ok(elements.len())
+    }
+
+    /// Build the MERGE query shared by `save` and `save_batch`
+    fn save_query(element: &UIElement) -> neo4rs::Query {
         let cypher = r#"
             MERGE (e:UIElement {id: $id})
             SET e.name = $name,
@@ -87,6 +131,13 @@ impl Neo4jRepository {
                 e.css_classes_text = $css_classes_text,
                 e.tags = $tags,
                 e.embedding = $embedding,
+                e.a11y_score = $a11y_score,
+                e.confidence = coalesce($confidence, e.confidence),
+                e.source_path = $source_path,
+                e.content_hash = $content_hash,
+                e.shape = $shape,
+                e.fingerprint = $fingerprint,
+                e += $fingerprint_bands,
                 e.created_at = coalesce(e.created_at, datetime()),
                 e.updated_at = datetime()
             RETURN e
@@ -94,24 +145,30 @@ impl Neo4jRepository {
 
         let css_classes_text = element.css_classes.join(" ");
 
-        self.graph
-            .run(
-                query(cypher)
-                    .param("id", element.id.to_string())
-                    .param("name", element.name.clone())
-                    .param("category", element.category.clone())
-                    .param("element_type", element.element_type.clone())
-                    .param("design_system", element.design_system.clone())
-                    .param("html_template", element.html_template.clone())
-                    .param("css_classes", element.css_classes.clone())
-                    .param("css_classes_text", css_classes_text)
-                    .param("tags", element.tags.clone())
-                    .param("embedding", element.embedding.clone()),
+        query(cypher)
+            .param("id", element.id.to_string())
+            .param("name", element.name.clone())
+            .param("category", element.category.clone())
+            .param("element_type", element.element_type.clone())
+            .param("design_system", element.design_system.clone())
+            .param("html_template", element.html_template.clone())
+            .param("css_classes", element.css_classes.clone())
+            .param("css_classes_text", css_classes_text)
+            .param("tags", element.tags.clone())
+            .param("embedding", element.embedding.clone())
+            .param("a11y_score", element.a11y_score.map(|s| s as f64))
+            .param("confidence", element.confidence.map(|c| c as f64))
+            .param("source_path", element.source_path.clone())
+            .param("content_hash", element.content_hash.clone())
+            .param("shape", element.shape.clone())
+            .param(
+                "fingerprint",
+                element
+                    .fingerprint
+                    .as_ref()
+                    .map(|sig| sig.iter().map(|v| *v as i64).collect::<Vec<_>>()),
             )
-            .await?;
-
-        self.check_latency("save", start);
-        Ok(())
+            .param("fingerprint_bands", fingerprint_band_properties(element.fingerprint.as_deref()))
     }
 
     /// Find element by ID
@@ -163,6 +220,211 @@ impl Neo4jRepository {
         Ok(elements)
     }
 
+    /// One filtered, sorted page of elements with their degree
+    ///
+    /// Filters run in the `WHERE` clause, so `total` counts every matching
+    /// element and pages are never short before the last one.
+    pub async fn list_elements(&self, element_query: &ElementQuery) -> anyhow::Result<ElementPage> {
+        let start = Instant::now();
+
+        let mut where_clauses = Vec::new();
+        if element_query.category.is_some() {
+            where_clauses.push("e.category = $category");
+        }
+        if element_query.design_system.is_some() {
+            where_clauses.push("e.design_system = $design_system");
+        }
+        if !element_query.tags.is_empty() {
+            where_clauses.push("all(tag IN $tags WHERE tag IN coalesce(e.tags, []))");
+        }
+        if element_query.name_prefix.is_some() {
+            where_clauses.push("toLower(e.name) STARTS WITH $name_prefix");
+        }
+        let where_clause = if where_clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", where_clauses.join(" AND "))
+        };
+
+        let with_filters = |mut q: neo4rs::Query| {
+            if let Some(category) = &element_query.category {
+                q = q.param("category", category.as_str());
+            }
+            if let Some(ds) = &element_query.design_system {
+                q = q.param("design_system", ds.as_str());
+            }
+            if !element_query.tags.is_empty() {
+                q = q.param("tags", element_query.tags.clone());
+            }
+            if let Some(prefix) = &element_query.name_prefix {
+                q = q.param("name_prefix", prefix.to_lowercase());
+            }
+            q
+        };
+
+        // Count total
+        let count_cypher = format!("MATCH (e:UIElement) {} RETURN count(e) as total", where_clause);
+        let mut count_result = self.graph.execute(with_filters(query(&count_cypher))).await?;
+        let total: u64 = if let Some(row) = count_result.next().await? {
+            let count: i64 = row.get("total").unwrap_or(0);
+            count as u64
+        } else {
+            0
+        };
+
+        // Fetch page
+        let sort_key = match element_query.sort {
+            ElementSort::Name => "e.name",
+            ElementSort::Created => "e.created_at",
+            ElementSort::Confidence => "coalesce(e.confidence, 0.5)",
+            ElementSort::Degree => "degree",
+        };
+        let direction = match element_query.direction {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        };
+        let name_direction = if element_query.sort == ElementSort::Name { direction } else { "ASC" };
+        let list_cypher = format!(
+            r#"
+            MATCH (e:UIElement)
+            {}
+            WITH e, COUNT {{ (e)--() }} as degree
+            RETURN e, degree
+            ORDER BY {} {}, e.name {}, e.id ASC
+            SKIP $offset
+            LIMIT $limit
+            "#,
+            where_clause, sort_key, direction, name_direction
+        );
+        let list_query = with_filters(query(&list_cypher))
+            .param("offset", element_query.offset() as i64)
+            .param("limit", element_query.limit() as i64);
+
+        let mut result = self.graph.execute(list_query).await?;
+        let mut elements = Vec::new();
+        while let Some(row) = result.next().await? {
+            let degree: i64 = row.get("degree").unwrap_or(0);
+            elements.push(ListedElement {
+                element: self.row_to_element(&row)?,
+                degree: degree as u32,
+            });
+        }
+
+        self.check_latency("list_elements", start);
+        Ok(ElementPage { elements, total })
+    }
+
+    /// Find elements by ID (missing IDs are skipped)
+    pub async fn find_by_ids(&self, ids: &[Uuid]) -> anyhow::Result<Vec<UIElement>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (e:UIElement)
+            WHERE e.id IN $ids
+            RETURN e
+        "#;
+
+        let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        let mut result = self.graph.execute(query(cypher).param("ids", ids)).await?;
+
+        let mut elements = Vec::new();
+        while let Some(row) = result.next().await? {
+            elements.push(self.row_to_element(&row)?);
+        }
+
+        self.check_latency("find_by_ids", start);
+        Ok(elements)
+    }
+
+    /// Fingerprinted elements sharing at least one LSH band with `signature`
+    ///
+    /// Each band is looked up through its own property index.
+    pub async fn find_fingerprint_candidates(&self, signature: &[u32]) -> anyhow::Result<Vec<UIElement>> {
+        let start = Instant::now();
+
+        let keys = band_keys(signature);
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let lookups: Vec<String> = (0..keys.len())
+            .map(|band| {
+                format!(
+                    "MATCH (e:UIElement) WHERE e.{} = $band_{} RETURN e",
+                    fingerprint_band_property(band),
+                    band
+                )
+            })
+            .collect();
+        let cypher = format!("CALL {{ {} }} RETURN e", lookups.join(" UNION "));
+        let mut candidates = query(&cypher);
+        for (band, key) in keys.into_iter().enumerate() {
+            candidates = candidates.param(&format!("band_{}", band), key);
+        }
+
+        let mut result = self.graph.execute(candidates).await?;
+
+        let mut elements = Vec::new();
+        while let Some(row) = result.next().await? {
+            elements.push(self.row_to_element(&row)?);
+        }
+
+        self.check_latency("find_fingerprint_candidates", start);
+        Ok(elements)
+    }
+
+    /// Embedded elements not yet linked by the similarity job since their last update
+    pub async fn find_similarity_pending(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (e:UIElement)
+            WHERE e.embedding IS NOT NULL
+              AND (e.similarity_linked_at IS NULL OR e.similarity_linked_at < e.updated_at)
+            RETURN e
+            LIMIT $limit
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("limit", limit as i64))
+            .await?;
+
+        let mut elements = Vec::new();
+        while let Some(row) = result.next().await? {
+            elements.push(self.row_to_element(&row)?);
+        }
+
+        self.check_latency("find_similarity_pending", start);
+        Ok(elements)
+    }
+
+    /// Record that the similarity job has linked these elements
+    pub async fn mark_similarity_linked(&self, ids: &[Uuid]) -> anyhow::Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let cypher = r#"
+            MATCH (e:UIElement)
+            WHERE e.id IN $ids
+            SET e.similarity_linked_at = datetime()
+        "#;
+
+        let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        self.graph.run(query(cypher).param("ids", ids)).await?;
+        Ok(())
+    }
+
+    /// Mark every element as pending for the similarity job
+    pub async fn reset_similarity_linked(&self) -> anyhow::Result<()> {
+        let cypher = "MATCH (e:UIElement) REMOVE e.similarity_linked_at";
+        self.graph.run(query(cypher)).await?;
+        Ok(())
+    }
+
     /// Find elements by category
     pub async fn find_by_category(&self, category: &str) -> anyhow::Result<Vec<UIElement>> {
         let start = Instant::now();
@@ -226,6 +488,209 @@ impl Neo4jRepository {
         Ok(elements)
     }
 
+    /// Nodes within `depth` hops of an element or design system
+    ///
+    /// Walks one hop per query and remembers visited nodes, so cycles end
+    /// the walk. The last level is expanded too, to find the edges between
+    /// its nodes and mark those with neighbours left out. Elements belong to
+    /// their design system through the `design_system` property; that
+    /// membership is reported as a `BELONGS_TO_DESIGN_SYSTEM` edge.
+    pub async fn neighborhood(&self, neighborhood_query: &NeighborhoodQuery) -> anyhow::Result<Option<Neighborhood>> {
+        let start = Instant::now();
+        let (max_depth, max_nodes) = neighborhood_query.bounds();
+
+        let (root_match, root_param) = match &neighborhood_query.root {
+            NeighborhoodRoot::Element(id) => ("MATCH (m:UIElement {id: $root})", id.to_string()),
+            NeighborhoodRoot::DesignSystem(name) => ("MATCH (m:DesignSystem {name: $root})", name.clone()),
+        };
+        let root_cypher = format!("{} RETURN {}", root_match, NEIGHBOR_COLUMNS);
+        let mut result = self.graph.execute(query(&root_cypher).param("root", root_param)).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(None);
+        };
+        let Some(root) = Self::row_to_neighbor(&row, 0) else {
+            return Ok(None);
+        };
+
+        let hop_cypher = format!(
+            r#"
+            UNWIND $frontier AS key
+            MATCH (n) WHERE elementId(n) = key
+            CALL {{
+                WITH n
+                MATCH (n)-[r]-(m)
+                WHERE size($types) = 0 OR type(r) IN $types
+                RETURN m, elementId(r) AS rel_key, type(r) AS rel_type, startNode(r) = n AS outgoing,
+                       r.score AS score, toString(r.created_at) AS created_at
+                UNION ALL
+                WITH n
+                MATCH (m:DesignSystem)
+                WHERE $membership AND n:UIElement AND m.name = n.design_system
+                  AND NOT (n)-[:BELONGS_TO_DESIGN_SYSTEM]->(m)
+                RETURN m, 'member:' + elementId(n) AS rel_key, 'BELONGS_TO_DESIGN_SYSTEM' AS rel_type,
+                       true AS outgoing, null AS score, null AS created_at
+                UNION ALL
+                WITH n
+                MATCH (m:UIElement)
+                WHERE $membership AND n:DesignSystem AND m.design_system = n.name
+                  AND NOT (m)-[:BELONGS_TO_DESIGN_SYSTEM]->(n)
+                RETURN m, 'member:' + elementId(m) AS rel_key, 'BELONGS_TO_DESIGN_SYSTEM' AS rel_type,
+                       false AS outgoing, null AS score, null AS created_at
+            }}
+            RETURN key, rel_key, rel_type, outgoing, score, created_at, {}
+            "#,
+            NEIGHBOR_COLUMNS
+        );
+
+        let root_key: String = row.get("m_key")?;
+        let mut positions: HashMap<String, usize> = HashMap::from([(root_key.clone(), 0)]);
+        let mut nodes = vec![root];
+        let mut edges = Vec::new();
+        let mut seen_edges = HashSet::new();
+        let mut truncated = false;
+        let mut frontier = vec![root_key];
+
+        for depth in 0..=max_depth {
+            let hop = query(&hop_cypher)
+                .param("frontier", frontier.clone())
+                .param("types", neighborhood_query.relation_types.clone())
+                .param("membership", neighborhood_query.follows("BELONGS_TO_DESIGN_SYSTEM"));
+            let mut result = self.graph.execute(hop).await?;
+            let mut hops = Vec::new();
+            while let Some(row) = result.next().await? {
+                if let Some(neighbor) = Self::row_to_neighbor(&row, depth + 1) {
+                    hops.push((row, neighbor));
+                }
+            }
+            // Stable choice of the nodes kept under the cap
+            hops.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+
+            let mut next = Vec::new();
+            for (row, neighbor) in hops {
+                let from: String = row.get("key")?;
+                let to: String = row.get("m_key")?;
+                if !positions.contains_key(&to) {
+                    if depth == max_depth || nodes.len() == max_nodes {
+                        truncated |= depth < max_depth;
+                        nodes[positions[&from]].truncated = true;
+                        continue;
+                    }
+                    positions.insert(to.clone(), nodes.len());
+                    nodes.push(neighbor);
+                    next.push(to.clone());
+                }
+
+                let rel_key: String = row.get("rel_key")?;
+                if !seen_edges.insert(rel_key) {
+                    continue;
+                }
+                let (from_id, to_id) = (nodes[positions[&from]].id.clone(), nodes[positions[&to]].id.clone());
+                let outgoing: bool = row.get("outgoing")?;
+                let (source, target) = if outgoing { (from_id, to_id) } else { (to_id, from_id) };
+                let created_at: Option<String> = row.get("created_at").unwrap_or(None);
+                edges.push(NeighborhoodEdge {
+                    source,
+                    target,
+                    rel_type: row.get("rel_type")?,
+                    score: row.get::<Option<f64>>("score").unwrap_or(None).map(|s| s as f32),
+                    created_at: created_at
+                        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                        .map(|t| t.with_timezone(&Utc)),
+                });
+            }
+
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        self.check_latency("neighborhood", start);
+        Ok(Some(Neighborhood { nodes, edges, truncated }))
+    }
+
+    /// Neighbourhood node from the `m_*` columns (`None` for unlisted labels)
+    fn row_to_neighbor(row: &neo4rs::Row, depth: u32) -> Option<NeighborhoodNode> {
+        let labels: Vec<String> = row.get("m_labels").unwrap_or_default();
+        let text = |column: &str| row.get::<Option<String>>(column).unwrap_or(None);
+        let (id, name, category, design_system) = (text("m_id"), text("m_name"), text("m_category"), text("m_design_system"));
+
+        let (kind, id, name, design_system) = if labels.iter().any(|l| l == "UIElement") {
+            (NeighborhoodNodeKind::Element, id?, name.unwrap_or_default(), design_system)
+        } else if labels.iter().any(|l| l == "Snippet") {
+            let id = id?;
+            (NeighborhoodNodeKind::Snippet, id.clone(), name.unwrap_or(id), design_system)
+        } else if labels.iter().any(|l| l == "DesignToken") {
+            let name = name?;
+            let theme = text("m_theme").unwrap_or_else(|| "default".to_string());
+            let id = format!("design_token:{}:{}", name, theme);
+            (NeighborhoodNodeKind::DesignToken, id, name, design_system)
+        } else if labels.iter().any(|l| l == "DesignSystem") {
+            let name = name?;
+            (NeighborhoodNodeKind::DesignSystem, format!("design_system:{}", name), name.clone(), Some(name))
+        } else {
+            return None;
+        };
+
+        Some(NeighborhoodNode {
+            id,
+            kind,
+            name,
+            category,
+            design_system,
+            depth,
+            degree: row.get::<i64>("m_degree").unwrap_or(0) as u32,
+            truncated: false,
+        })
+    }
+
+    /// Elements of a design system linked to `id` by `CAN_REPLACE` or
+    /// `SIMILAR_TO`, replacements first, then by descending score
+    pub async fn find_counterparts(
+        &self,
+        id: Uuid,
+        design_system: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<Counterpart>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (:UIElement {id: $id})-[r:CAN_REPLACE|SIMILAR_TO]->(e:UIElement {design_system: $design_system})
+            WITH e, type(r) AS relation, max(r.score) AS score
+            RETURN e, relation, score
+            ORDER BY CASE relation WHEN 'CAN_REPLACE' THEN 0 ELSE 1 END, score DESC
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(
+                query(cypher)
+                    .param("id", id.to_string())
+                    .param("design_system", design_system),
+            )
+            .await?;
+
+        // An element linked by both relationships is listed once, as a replacement
+        let mut counterparts: Vec<Counterpart> = Vec::new();
+        while let Some(row) = result.next().await? {
+            let element = self.row_to_element(&row)?;
+            if counterparts.iter().any(|c| c.element.id == element.id) {
+                continue;
+            }
+            counterparts.push(Counterpart {
+                element,
+                relation: row.get("relation")?,
+                score: row.get::<f64>("score").unwrap_or(0.0) as f32,
+            });
+            if counterparts.len() == limit {
+                break;
+            }
+        }
+
+        self.check_latency("find_counterparts", start);
+        Ok(counterparts)
+    }
+
     /// Fulltext search across name, html_template, css_classes
     pub async fn fulltext_search(
         &self,
@@ -316,7 +781,157 @@ impl Neo4jRepository {
             )
             .await?;
 
-        Ok(())
+        Ok(())
+    }
+
+    // ==================== Design Token Operations ====================
+
+    /// Save a design token (keyed by name + theme)
+    pub async fn save_design_token(&self, token: &DesignToken) -> anyhow::Result<()> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MERGE (t:DesignToken {name: $name, theme: $theme})
+            SET t.value = $value,
+                t.resolved_value = $resolved_value,
+                t.category = $category,
+                t.alias_of = $alias_of,
+                t.design_system = $design_system,
+                t.created_at = coalesce(t.created_at, datetime())
+            RETURN t
+        "#;
+
+        self.graph
+            .run(
+                query(cypher)
+                    .param("name", token.name.clone())
+                    .param("theme", token.theme.clone())
+                    .param("value", token.value.clone())
+                    .param("resolved_value", token.resolved_value.clone())
+                    .param("category", token.category.clone())
+                    .param("alias_of", token.alias_of.clone())
+                    .param("design_system", token.design_system.clone()),
+            )
+            .await?;
+
+        if let Some(ds) = &token.design_system {
+            let ds_cypher = r#"
+                MATCH (t:DesignToken {name: $name, theme: $theme})
+                MATCH (d:DesignSystem {name: $ds_name})
+                MERGE (t)-[r:BELONGS_TO_DESIGN_SYSTEM]->(d)
+                SET r.created_at = coalesce(r.created_at, datetime())
+            "#;
+
+            self.graph
+                .run(
+                    query(ds_cypher)
+                        .param("name", token.name.clone())
+                        .param("theme", token.theme.clone())
+                        .param("ds_name", ds.clone()),
+                )
+                .await?;
+        }
+
+        self.check_latency("save_design_token", start);
+        Ok(())
+    }
+
+    /// List design tokens, optionally restricted to one theme
+    pub async fn find_design_tokens(&self, theme: Option<&str>) -> anyhow::Result<Vec<DesignToken>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (t:DesignToken)
+            WHERE $theme IS NULL OR t.theme = $theme
+            RETURN t
+            ORDER BY t.theme, t.name
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("theme", theme.map(str::to_string)))
+            .await?;
+
+        let mut tokens = Vec::new();
+        while let Some(row) = result.next().await? {
+            tokens.push(self.row_to_token(&row)?);
+        }
+
+        self.check_latency("find_design_tokens", start);
+        Ok(tokens)
+    }
+
+    /// List design tokens defined by a design system (all themes)
+    pub async fn find_design_tokens_by_design_system(&self, design_system: &str) -> anyhow::Result<Vec<DesignToken>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (t:DesignToken {design_system: $design_system})
+            RETURN t
+            ORDER BY t.theme, t.name
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("design_system", design_system))
+            .await?;
+
+        let mut tokens = Vec::new();
+        while let Some(row) = result.next().await? {
+            tokens.push(self.row_to_token(&row)?);
+        }
+
+        self.check_latency("find_design_tokens_by_design_system", start);
+        Ok(tokens)
+    }
+
+    /// Find elements that use a token directly or through an alias chain
+    pub async fn find_elements_using_token(&self, token_name: &str) -> anyhow::Result<Vec<UIElement>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (target:DesignToken {name: $token_name})
+            MATCH (e:UIElement)-[:USES_TOKEN]->(:DesignToken)-[:ALIAS_OF*0..5]->(target)
+            RETURN DISTINCT e
+            ORDER BY e.name
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("token_name", token_name))
+            .await?;
+
+        let mut elements = Vec::new();
+        while let Some(row) = result.next().await? {
+            elements.push(self.row_to_element(&row)?);
+        }
+
+        self.check_latency("find_elements_using_token", start);
+        Ok(elements)
+    }
+
+    /// Tokens an element uses directly
+    pub async fn find_tokens_used_by(&self, id: Uuid) -> anyhow::Result<Vec<DesignToken>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (:UIElement {id: $id})-[:USES_TOKEN]->(t:DesignToken)
+            RETURN DISTINCT t
+            ORDER BY t.name
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("id", id.to_string()))
+            .await?;
+
+        let mut tokens = Vec::new();
+        while let Some(row) = result.next().await? {
+            tokens.push(self.row_to_token(&row)?);
+        }
+
+        self.check_latency("find_tokens_used_by", start);
+        Ok(tokens)
     }
 
     // ==================== Snippet Operations ====================
@@ -335,6 +950,10 @@ impl Neo4jRepository {
                 s.design_system = $design_system,
                 s.tags = $tags,
                 s.element_count = $element_count,
+                s.source_path = $source_path,
+                s.git_commit = $git_commit,
+                s.source_root = $source_root,
+                s.content_hash = $content_hash,
                 s.created_at = coalesce(s.created_at, datetime()),
                 s.updated_at = datetime()
             RETURN s
@@ -350,7 +969,11 @@ impl Neo4jRepository {
                     .param("js", snippet.js.clone())
                     .param("design_system", snippet.design_system.clone())
                     .param("tags", snippet.tags.clone())
-                    .param("element_count", snippet.element_count as i64),
+                    .param("element_count", snippet.element_count as i64)
+                    .param("source_path", snippet.source_path.clone())
+                    .param("git_commit", snippet.git_commit.clone())
+                    .param("source_root", snippet.source_root.clone())
+                    .param("content_hash", snippet.content_hash.clone()),
             )
             .await?;
 
@@ -529,6 +1152,58 @@ impl Neo4jRepository {
         Ok(deleted)
     }
 
+    /// Find all snippets ingested from a source root, with their element IDs
+    pub async fn find_snippets_by_source_root(&self, source_root: &str) -> anyhow::Result<Vec<Snippet>> {
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (s:Snippet {source_root: $source_root})
+            OPTIONAL MATCH (s)-[:HAS_ELEMENT]->(e:UIElement)
+            WITH s, collect(e.id) as element_ids
+            RETURN s, element_ids
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("source_root", source_root))
+            .await?;
+
+        let mut snippets = Vec::new();
+        while let Some(row) = result.next().await? {
+            snippets.push(self.row_to_snippet(&row)?);
+        }
+
+        self.check_latency("find_snippets_by_source_root", start);
+        Ok(snippets)
+    }
+
+    /// Delete elements by ID (with all their relationships)
+    pub async fn delete_batch(&self, ids: &[Uuid]) -> anyhow::Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let start = Instant::now();
+
+        let cypher = r#"
+            MATCH (e:UIElement)
+            WHERE e.id IN $ids
+            DETACH DELETE e
+            RETURN count(e) as deleted
+        "#;
+
+        let ids: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+        let mut result = self.graph.execute(query(cypher).param("ids", ids)).await?;
+
+        let deleted = if let Some(row) = result.next().await? {
+            row.get::<i64>("deleted").unwrap_or(0) as u64
+        } else {
+            0
+        };
+
+        self.check_latency("delete_batch", start);
+        Ok(deleted)
+    }
+
     /// Count snippets
     pub async fn count_snippets(&self) -> anyhow::Result<u64> {
         let cypher = "MATCH (s:Snippet) RETURN count(s) as count";
@@ -574,11 +1249,36 @@ impl Neo4jRepository {
             css_classes: node.get("css_classes").unwrap_or_default(),
             tags: node.get("tags").unwrap_or_default(),
             embedding: node.get("embedding").ok(),
+            a11y_score: node.get::<f64>("a11y_score").ok().map(|s| s as f32),
+            confidence: node.get::<f64>("confidence").ok().map(|c| c as f32),
+            source_path: node.get("source_path").ok(),
+            content_hash: node.get("content_hash").ok(),
+            shape: node.get("shape").ok(),
+            fingerprint: node
+                .get::<Vec<i64>>("fingerprint")
+                .ok()
+                .map(|sig| sig.into_iter().map(|v| v as u32).collect()),
             created_at: chrono::Utc::now(), // TODO: parse from node
             updated_at: chrono::Utc::now(),
         })
     }
 
+    /// Convert Neo4j row to DesignToken
+    fn row_to_token(&self, row: &neo4rs::Row) -> anyhow::Result<DesignToken> {
+        let node: neo4rs::Node = row.get("t")?;
+
+        Ok(DesignToken {
+            name: node.get("name")?,
+            value: node.get("value").unwrap_or_default(),
+            resolved_value: node.get("resolved_value").ok(),
+            category: node.get("category").unwrap_or_default(),
+            theme: node.get("theme").unwrap_or_else(|_| "default".to_string()),
+            alias_of: node.get("alias_of").ok(),
+            design_system: node.get("design_system").ok(),
+            created_at: chrono::Utc::now(), // TODO: parse from node
+        })
+    }
+
     /// Convert Neo4j row to Snippet
     fn row_to_snippet(&self, row: &neo4rs::Row) -> anyhow::Result<Snippet> {
         let node: neo4rs::Node = row.get("s")?;
@@ -603,6 +1303,10 @@ impl Neo4jRepository {
             tags: node.get("tags").unwrap_or_default(),
             element_ids: element_ids.clone(),
             element_count: element_ids.len() as u32,
+            source_path: node.get("source_path").ok(),
+            git_commit: node.get("git_commit").ok(),
+            source_root: node.get("source_root").ok(),
+            content_hash: node.get("content_hash").ok(),
             created_at: chrono::Utc::now(), // TODO: parse from node
             updated_at: chrono::Utc::now(),
         })
@@ -688,6 +1392,29 @@ impl Neo4jRepository {
         Ok(counts)
     }
 
+    /// Distinct CSS classes used by elements of a design system
+    pub async fn list_css_classes(&self, design_system: &str) -> anyhow::Result<Vec<String>> {
+        let cypher = r#"
+            MATCH (e:UIElement {design_system: $design_system})
+            UNWIND e.css_classes as class
+            RETURN DISTINCT class
+        "#;
+
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("design_system", design_system))
+            .await?;
+        let mut classes = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            if let Ok(class) = row.get::<String>("class") {
+                classes.push(class);
+            }
+        }
+
+        Ok(classes)
+    }
+
     /// Count total relationships in the graph
     pub async fn count_relationships(&self) -> anyhow::Result<u64> {
         let cypher = "MATCH ()-[r]->() RETURN count(r) as count";
@@ -721,6 +1448,293 @@ impl Neo4jRepository {
         Ok(counts)
     }
 
+    // ==================== Export ====================
+
+    /// Every node and relationship of a known label and type
+    pub async fn export_graph(&self) -> anyhow::Result<GraphDump> {
+        let start = Instant::now();
+        let mut dump = GraphDump::default();
+
+        let mut result = self.graph.execute(query("MATCH (e:UIElement) RETURN e ORDER BY e.id")).await?;
+        while let Some(row) = result.next().await? {
+            dump.elements.push(self.row_to_element(&row)?);
+        }
+
+        let snippets_cypher = r#"
+            MATCH (s:Snippet)
+            OPTIONAL MATCH (s)-[:HAS_ELEMENT]->(e:UIElement)
+            WITH s, collect(e.id) as element_ids
+            RETURN s, element_ids
+            ORDER BY s.id
+        "#;
+        let mut result = self.graph.execute(query(snippets_cypher)).await?;
+        while let Some(row) = result.next().await? {
+            dump.snippets.push(self.row_to_snippet(&row)?);
+        }
+
+        let mut result = self.graph.execute(query("MATCH (d:DesignSystem) RETURN d ORDER BY d.name")).await?;
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("d")?;
+            dump.design_systems.push(DesignSystem {
+                name: node.get("name")?,
+                display_name: node.get("display_name").unwrap_or_default(),
+                version: node.get("version").ok(),
+                description: node.get("description").ok(),
+                docs_url: node.get("docs_url").ok(),
+                created_at: chrono::Utc::now(), // TODO: parse from node
+            });
+        }
+
+        dump.tokens = self.find_design_tokens(None).await?;
+
+        let relations_cypher = r#"
+            MATCH (a)-[r]->(b)
+            RETURN labels(a) AS a_labels, a.id AS a_id, a.name AS a_name, a.theme AS a_theme,
+                   labels(b) AS b_labels, b.id AS b_id, b.name AS b_name, b.theme AS b_theme,
+                   type(r) AS rel_type, r.score AS score, r.basis AS basis, r.proposed AS proposed,
+                   toString(r.created_at) AS created_at
+        "#;
+        let mut result = self.graph.execute(query(relations_cypher)).await?;
+        while let Some(row) = result.next().await? {
+            let rel_type: String = row.get("rel_type")?;
+            let (Some(from), Some(to)) = (Self::row_to_node_ref(&row, "a"), Self::row_to_node_ref(&row, "b")) else {
+                continue;
+            };
+            if !RelationType::is_known(&rel_type) {
+                continue;
+            }
+            let created_at: Option<String> = row.get("created_at").unwrap_or(None);
+            let basis: Option<String> = row.get("basis").unwrap_or(None);
+            dump.relations.push(GraphRelation {
+                from,
+                to,
+                rel_type,
+                score: row.get::<Option<f64>>("score").unwrap_or(None).map(|s| s as f32),
+                basis: basis.as_deref().and_then(SimilarityBasis::from_name),
+                proposed: row.get::<Option<bool>>("proposed").unwrap_or(None).unwrap_or(false),
+                created_at: created_at
+                    .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                    .map(|t| t.with_timezone(&Utc)),
+            });
+        }
+        dump.relations
+            .sort_by(|a, b| (&a.from, &a.to, &a.rel_type).cmp(&(&b.from, &b.to, &b.rel_type)));
+
+        self.check_latency("export_graph", start);
+        Ok(dump)
+    }
+
+    /// Merge a dump by node identity; relationships are merged per type,
+    /// endpoints and (for similarity) basis
+    pub async fn import_graph(&self, dump: &GraphDump) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        // Relationship types are interpolated into Cypher
+        if let Some(unknown) = dump.relations.iter().find(|r| !RelationType::is_known(&r.rel_type)) {
+            anyhow::bail!("unknown relationship type '{}'", unknown.rel_type);
+        }
+
+        for ds in &dump.design_systems {
+            self.save_design_system(ds).await?;
+        }
+        for token in &dump.tokens {
+            self.save_design_token(token).await?;
+        }
+        for batch in dump.elements.chunks(500) {
+            self.save_batch(batch).await?;
+        }
+        for snippet in &dump.snippets {
+            self.save_snippet(snippet).await?;
+        }
+
+        // One UNWIND per relationship type, endpoint labels and basis presence
+        let mut groups: HashMap<(&str, &str, &str, bool), Vec<&GraphRelation>> = HashMap::new();
+        for relation in &dump.relations {
+            let key = (
+                relation.rel_type.as_str(),
+                Self::node_label(&relation.from),
+                Self::node_label(&relation.to),
+                relation.basis.is_some(),
+            );
+            groups.entry(key).or_default().push(relation);
+        }
+
+        let mut written = 0;
+        for ((rel_type, from_label, to_label, scored), relations) in groups {
+            let merge = if scored {
+                format!(
+                    "MERGE (a)-[r:{} {{basis: $bases[i]}}]->(b) SET r.score = $scores[i], r.proposed = $proposed[i],",
+                    rel_type
+                )
+            } else {
+                format!("MERGE (a)-[r:{}]->(b) SET", rel_type)
+            };
+            let cypher = format!(
+                r#"
+                UNWIND range(0, size($from_keys) - 1) AS i
+                MATCH (a:{} {{{}}})
+                MATCH (b:{} {{{}}})
+                {}
+                    r.created_at = coalesce(datetime($created[i]), r.created_at, datetime())
+                RETURN count(r) AS written
+                "#,
+                from_label,
+                Self::node_key_pattern(from_label, "from"),
+                to_label,
+                Self::node_key_pattern(to_label, "to"),
+                merge
+            );
+
+            let (from_keys, from_themes): (Vec<String>, Vec<String>) =
+                relations.iter().map(|r| Self::node_key(&r.from)).unzip();
+            let (to_keys, to_themes): (Vec<String>, Vec<String>) =
+                relations.iter().map(|r| Self::node_key(&r.to)).unzip();
+            let bases: Vec<String> = relations
+                .iter()
+                .map(|r| r.basis.map(|b| b.as_str().to_string()).unwrap_or_default())
+                .collect();
+            let scores: Vec<f64> = relations.iter().map(|r| r.score.unwrap_or(0.0) as f64).collect();
+            let proposed: Vec<bool> = relations.iter().map(|r| r.proposed).collect();
+            let created: Vec<Option<String>> = relations
+                .iter()
+                .map(|r| r.created_at.map(|t| t.to_rfc3339()))
+                .collect();
+
+            let mut result = self
+                .graph
+                .execute(
+                    query(&cypher)
+                        .param("from_keys", from_keys)
+                        .param("from_themes", from_themes)
+                        .param("to_keys", to_keys)
+                        .param("to_themes", to_themes)
+                        .param("bases", bases)
+                        .param("scores", scores)
+                        .param("proposed", proposed)
+                        .param("created", created),
+                )
+                .await?;
+            if let Some(row) = result.next().await? {
+                written += row.get::<i64>("written").unwrap_or(0) as u64;
+            }
+        }
+
+        self.check_latency("import_graph", start);
+        Ok(written)
+    }
+
+    /// Delete relationships by type and endpoints, and by basis when one is given
+    pub async fn delete_relations(&self, relations: &[GraphRelation]) -> anyhow::Result<u64> {
+        let start = Instant::now();
+        // Relationship types are interpolated into Cypher
+        if let Some(unknown) = relations.iter().find(|r| !RelationType::is_known(&r.rel_type)) {
+            anyhow::bail!("unknown relationship type '{}'", unknown.rel_type);
+        }
+
+        let mut groups: HashMap<(&str, &str, &str), Vec<&GraphRelation>> = HashMap::new();
+        for relation in relations {
+            let key = (
+                relation.rel_type.as_str(),
+                Self::node_label(&relation.from),
+                Self::node_label(&relation.to),
+            );
+            groups.entry(key).or_default().push(relation);
+        }
+
+        let mut deleted = 0;
+        for ((rel_type, from_label, to_label), relations) in groups {
+            let cypher = format!(
+                r#"
+                UNWIND range(0, size($from_keys) - 1) AS i
+                MATCH (a:{} {{{}}})-[r:{}]->(b:{} {{{}}})
+                WHERE $bases[i] = '' OR r.basis = $bases[i]
+                DELETE r
+                RETURN count(r) AS deleted
+                "#,
+                from_label,
+                Self::node_key_pattern(from_label, "from"),
+                rel_type,
+                to_label,
+                Self::node_key_pattern(to_label, "to"),
+            );
+
+            let (from_keys, from_themes): (Vec<String>, Vec<String>) =
+                relations.iter().map(|r| Self::node_key(&r.from)).unzip();
+            let (to_keys, to_themes): (Vec<String>, Vec<String>) =
+                relations.iter().map(|r| Self::node_key(&r.to)).unzip();
+            let bases: Vec<String> = relations
+                .iter()
+                .map(|r| r.basis.map(|b| b.as_str().to_string()).unwrap_or_default())
+                .collect();
+
+            let mut result = self
+                .graph
+                .execute(
+                    query(&cypher)
+                        .param("from_keys", from_keys)
+                        .param("from_themes", from_themes)
+                        .param("to_keys", to_keys)
+                        .param("to_themes", to_themes)
+                        .param("bases", bases),
+                )
+                .await?;
+            if let Some(row) = result.next().await? {
+                deleted += row.get::<i64>("deleted").unwrap_or(0) as u64;
+            }
+        }
+
+        self.check_latency("delete_relations", start);
+        Ok(deleted)
+    }
+
+    /// Node identity from the `<prefix>_*` columns (`None` for unlisted labels)
+    fn row_to_node_ref(row: &neo4rs::Row, prefix: &str) -> Option<GraphNodeRef> {
+        let labels: Vec<String> = row.get(&format!("{}_labels", prefix)).unwrap_or_default();
+        let text = |column: &str| row.get::<Option<String>>(&format!("{}_{}", prefix, column)).unwrap_or(None);
+        let has = |label: &str| labels.iter().any(|l| l == label);
+
+        if has("UIElement") {
+            Some(GraphNodeRef::Element { id: text("id")?.parse().ok()? })
+        } else if has("Snippet") {
+            Some(GraphNodeRef::Snippet { id: text("id")?.parse().ok()? })
+        } else if has("DesignToken") {
+            Some(GraphNodeRef::DesignToken {
+                name: text("name")?,
+                theme: text("theme").unwrap_or_else(|| "default".to_string()),
+            })
+        } else if has("DesignSystem") {
+            Some(GraphNodeRef::DesignSystem { name: text("name")? })
+        } else {
+            None
+        }
+    }
+
+    fn node_label(node: &GraphNodeRef) -> &'static str {
+        match node {
+            GraphNodeRef::Element { .. } => "UIElement",
+            GraphNodeRef::Snippet { .. } => "Snippet",
+            GraphNodeRef::DesignToken { .. } => "DesignToken",
+            GraphNodeRef::DesignSystem { .. } => "DesignSystem",
+        }
+    }
+
+    /// Identifying property values of a node (the theme is empty except for tokens)
+    fn node_key(node: &GraphNodeRef) -> (String, String) {
+        match node {
+            GraphNodeRef::Element { id } | GraphNodeRef::Snippet { id } => (id.to_string(), String::new()),
+            GraphNodeRef::DesignToken { name, theme } => (name.clone(), theme.clone()),
+            GraphNodeRef::DesignSystem { name } => (name.clone(), String::new()),
+        }
+    }
+
+    /// Property map matching a node of `label` by the `$<side>_keys` / `$<side>_themes` parameters
+    fn node_key_pattern(label: &str, side: &str) -> String {
+        match label {
+            "UIElement" | "Snippet" => format!("id: ${}_keys[i]", side),
+            "DesignToken" => format!("name: ${0}_keys[i], theme: ${0}_themes[i]", side),
+            _ => format!("name: ${}_keys[i]", side),
+        }
+    }
+
     /// Count nodes by label
     pub async fn count_by_label(&self) -> anyhow::Result<Vec<(String, u64)>> {
         // Use a simpler query that works with more Neo4j versions
@@ -750,6 +1764,214 @@ impl Neo4jRepository {
             }
         }
 
+        // Count DesignTokens
+        let token_cypher = "MATCH (t:DesignToken) RETURN count(t) as count";
+        if let Ok(mut result) = self.graph.execute(query(token_cypher)).await {
+            if let Ok(Some(row)) = result.next().await {
+                let count: i64 = row.get("count").unwrap_or(0);
+                if count > 0 {
+                    counts.push(("DesignToken".to_string(), count as u64));
+                }
+            }
+        }
+
         Ok(counts)
     }
 }
+
+#[async_trait]
+impl GraphStore for Neo4jRepository {
+    async fn initialize_schema(&self) -> anyhow::Result<()> {
+        Neo4jRepository::initialize_schema(self).await
+    }
+
+    async fn save(&self, element: &UIElement) -> anyhow::Result<()> {
+        Neo4jRepository::save(self, element).await
+    }
+
+    async fn save_batch(&self, elements: &[UIElement]) -> anyhow::Result<usize> {
+        Neo4jRepository::save_batch(self, elements).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> anyhow::Result<Option<UIElement>> {
+        Neo4jRepository::find_by_id(self, id).await
+    }
+
+    async fn find_by_ids(&self, ids: &[Uuid]) -> anyhow::Result<Vec<UIElement>> {
+        Neo4jRepository::find_by_ids(self, ids).await
+    }
+
+    async fn list_all_elements(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        Neo4jRepository::list_all_elements(self, limit).await
+    }
+
+    async fn list_elements(&self, query: &ElementQuery) -> anyhow::Result<ElementPage> {
+        Neo4jRepository::list_elements(self, query).await
+    }
+
+    async fn find_by_category(&self, category: &str) -> anyhow::Result<Vec<UIElement>> {
+        Neo4jRepository::find_by_category(self, category).await
+    }
+
+    async fn fulltext_search(&self, search_term: &str, limit: usize) -> anyhow::Result<Vec<SimilarElement>> {
+        Neo4jRepository::fulltext_search(self, search_term, limit).await
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        Neo4jRepository::delete(self, id).await
+    }
+
+    async fn delete_batch(&self, ids: &[Uuid]) -> anyhow::Result<u64> {
+        Neo4jRepository::delete_batch(self, ids).await
+    }
+
+    async fn count(&self) -> anyhow::Result<u64> {
+        Neo4jRepository::count(self).await
+    }
+
+    async fn find_fingerprint_candidates(&self, signature: &[u32]) -> anyhow::Result<Vec<UIElement>> {
+        Neo4jRepository::find_fingerprint_candidates(self, signature).await
+    }
+
+    async fn find_similarity_pending(&self, limit: usize) -> anyhow::Result<Vec<UIElement>> {
+        Neo4jRepository::find_similarity_pending(self, limit).await
+    }
+
+    async fn mark_similarity_linked(&self, ids: &[Uuid]) -> anyhow::Result<()> {
+        Neo4jRepository::mark_similarity_linked(self, ids).await
+    }
+
+    async fn reset_similarity_linked(&self) -> anyhow::Result<()> {
+        Neo4jRepository::reset_similarity_linked(self).await
+    }
+
+    async fn find_counterparts(&self, id: Uuid, design_system: &str, limit: usize) -> anyhow::Result<Vec<Counterpart>> {
+        Neo4jRepository::find_counterparts(self, id, design_system, limit).await
+    }
+
+    async fn save_design_system(&self, ds: &DesignSystem) -> anyhow::Result<()> {
+        Neo4jRepository::save_design_system(self, ds).await
+    }
+
+    async fn save_design_token(&self, token: &DesignToken) -> anyhow::Result<()> {
+        Neo4jRepository::save_design_token(self, token).await
+    }
+
+    async fn find_design_tokens(&self, theme: Option<&str>) -> anyhow::Result<Vec<DesignToken>> {
+        Neo4jRepository::find_design_tokens(self, theme).await
+    }
+
+    async fn find_design_tokens_by_design_system(&self, design_system: &str) -> anyhow::Result<Vec<DesignToken>> {
+        Neo4jRepository::find_design_tokens_by_design_system(self, design_system).await
+    }
+
+    async fn find_elements_using_token(&self, token_name: &str) -> anyhow::Result<Vec<UIElement>> {
+        Neo4jRepository::find_elements_using_token(self, token_name).await
+    }
+
+    async fn find_tokens_used_by(&self, id: Uuid) -> anyhow::Result<Vec<DesignToken>> {
+        Neo4jRepository::find_tokens_used_by(self, id).await
+    }
+
+    async fn save_snippet(&self, snippet: &Snippet) -> anyhow::Result<()> {
+        Neo4jRepository::save_snippet(self, snippet).await
+    }
+
+    async fn find_snippet_by_id(&self, id: Uuid) -> anyhow::Result<Option<Snippet>> {
+        Neo4jRepository::find_snippet_by_id(self, id).await
+    }
+
+    async fn list_snippets(
+        &self,
+        page: u32,
+        per_page: u32,
+        design_system: Option<&str>,
+        category: Option<&str>,
+    ) -> anyhow::Result<(Vec<SnippetSummary>, u64)> {
+        Neo4jRepository::list_snippets(self, page, per_page, design_system, category).await
+    }
+
+    async fn delete_snippet(&self, id: Uuid, delete_orphans: bool) -> anyhow::Result<bool> {
+        Neo4jRepository::delete_snippet(self, id, delete_orphans).await
+    }
+
+    async fn find_snippets_by_source_root(&self, source_root: &str) -> anyhow::Result<Vec<Snippet>> {
+        Neo4jRepository::find_snippets_by_source_root(self, source_root).await
+    }
+
+    async fn count_snippets(&self) -> anyhow::Result<u64> {
+        Neo4jRepository::count_snippets(self).await
+    }
+
+    async fn link_children(&self, links: &[(Uuid, Uuid)]) -> anyhow::Result<()> {
+        self.relations.link_children(links).await
+    }
+
+    async fn link_to_token(&self, element_id: Uuid, token_name: &str) -> anyhow::Result<()> {
+        self.relations.link_to_token(element_id, token_name).await
+    }
+
+    async fn link_token_alias(&self, name: &str, alias_of: &str, theme: &str) -> anyhow::Result<()> {
+        self.relations.link_token_alias(name, alias_of, theme).await
+    }
+
+    async fn create_similarities(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.relations.create_similarities(pairs, basis).await
+    }
+
+    async fn propose_replacements(&self, pairs: &[(Uuid, Uuid, f32)], basis: SimilarityBasis) -> anyhow::Result<()> {
+        self.relations.propose_replacements(pairs, basis).await
+    }
+
+    async fn clear_similarity(&self, element_ids: &[Uuid], basis: SimilarityBasis) -> anyhow::Result<u64> {
+        self.relations.clear_similarity(element_ids, basis).await
+    }
+
+    async fn delete_relations(&self, relations: &[GraphRelation]) -> anyhow::Result<u64> {
+        Neo4jRepository::delete_relations(self, relations).await
+    }
+
+    async fn similarity_edges(&self, min_score: f32, basis: SimilarityBasis) -> anyhow::Result<Vec<(Uuid, Uuid, f32)>> {
+        self.relations.similarity_edges(min_score, basis).await
+    }
+
+    async fn get_degree(&self, element_id: Uuid) -> anyhow::Result<u32> {
+        self.relations.get_degree(element_id).await
+    }
+
+    async fn neighborhood(&self, query: &NeighborhoodQuery) -> anyhow::Result<Option<Neighborhood>> {
+        Neo4jRepository::neighborhood(self, query).await
+    }
+
+    async fn count_by_category(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        Neo4jRepository::count_by_category(self).await
+    }
+
+    async fn count_by_design_system(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        Neo4jRepository::count_by_design_system(self).await
+    }
+
+    async fn list_css_classes(&self, design_system: &str) -> anyhow::Result<Vec<String>> {
+        Neo4jRepository::list_css_classes(self, design_system).await
+    }
+
+    async fn count_relationships(&self) -> anyhow::Result<u64> {
+        Neo4jRepository::count_relationships(self).await
+    }
+
+    async fn count_relationships_by_type(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        Neo4jRepository::count_relationships_by_type(self).await
+    }
+
+    async fn count_by_label(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        Neo4jRepository::count_by_label(self).await
+    }
+
+    async fn export_graph(&self) -> anyhow::Result<GraphDump> {
+        Neo4jRepository::export_graph(self).await
+    }
+
+    async fn import_graph(&self, dump: &GraphDump) -> anyhow::Result<u64> {
+        Neo4jRepository::import_graph(self, dump).await
+    }
+}
@@ -1,163 +1,239 @@
-//! Schema management - Constraints, indexes, and vector indexes for Neo4j
+//! Schema management - Versioned migrations creating the constraints,
+//! property indexes, vector index and fulltext index of the graph
+//!
+//! Applied versions are recorded as `SchemaMigration` nodes, so each
+//! migration runs once per database.
 
-use neo4rs::Graph;
+use std::collections::HashMap;
+
+use anyhow::Context;
+use chrono::Utc;
+use codegraph_core::config::OpenAIConfig;
+use codegraph_core::fingerprint::{band_keys, FINGERPRINT_BANDS};
+use codegraph_core::migrations::{self, AppliedMigration, Migration};
+use neo4rs::{query, BoltType, Graph};
 use tracing::{info, warn};
 
+/// Name of the vector index over `UIElement.embedding`
+const VECTOR_INDEX: &str = "ui_element_embedding";
+
+/// Migration indexing the LSH bands of element fingerprints
+const FINGERPRINT_BAND_MIGRATION: u32 = 5;
+
+/// Elements whose fingerprint bands are backfilled per query
+const BACKFILL_BATCH: i64 = 1000;
+
+/// `UIElement` property holding the key of one fingerprint band
+pub fn fingerprint_band_property(band: usize) -> String {
+    format!("fingerprint_band_{}", band)
+}
+
+/// Band properties of a fingerprint, for `SET e += $map`; absent bands are
+/// null, which removes them
+pub fn fingerprint_band_properties(signature: Option<&[u32]>) -> HashMap<String, Option<i64>> {
+    let keys = signature.map(band_keys).unwrap_or_default();
+    (0..FINGERPRINT_BANDS)
+        .map(|band| (fingerprint_band_property(band), keys.get(band).copied()))
+        .collect()
+}
+
 /// Manages Neo4j schema: constraints and indexes
 pub struct SchemaManager {
     graph: Graph,
+    vector_dimensions: u64,
 }
 
 impl SchemaManager {
+    /// Vector index dimensions come from `OPENAI_EMBEDDING_DIMENSIONS`
     pub fn new(graph: Graph) -> Self {
-        Self { graph }
+        Self { graph, vector_dimensions: OpenAIConfig::from_env().embedding_dimensions }
     }
 
-    /// Initialize all schema elements (constraints + indexes)
-    pub async fn initialize(&self) -> anyhow::Result<()> {
-        info!("Initializing Neo4j schema...");
-
-        self.create_constraints().await?;
-        self.create_indexes().await?;
-        self.create_vector_index().await?;
-        self.create_fulltext_index().await?;
-
-        info!("Neo4j schema initialized successfully");
-        Ok(())
+    /// Set the dimensions of the embedding vector index
+    pub fn with_vector_dimensions(mut self, dimensions: u64) -> Self {
+        self.vector_dimensions = dimensions;
+        self
     }
 
-    /// Create uniqueness constraints
-    async fn create_constraints(&self) -> anyhow::Result<()> {
-        // UIElement.id must be unique
-        let ui_constraint = r#"
-            CREATE CONSTRAINT ui_element_id_unique IF NOT EXISTS
-            FOR (e:UIElement)
-            REQUIRE e.id IS UNIQUE
-        "#;
-
-        // DesignSystem.name must be unique
-        let ds_constraint = r#"
-            CREATE CONSTRAINT design_system_name_unique IF NOT EXISTS
-            FOR (d:DesignSystem)
-            REQUIRE d.name IS UNIQUE
-        "#;
-
-        // Snippet.id must be unique
-        let snippet_constraint = r#"
-            CREATE CONSTRAINT snippet_id_unique IF NOT EXISTS
-            FOR (s:Snippet)
-            REQUIRE s.id IS UNIQUE
-        "#;
-
-        match self.graph.run(neo4rs::query(ui_constraint)).await {
-            Ok(_) => info!("Created UIElement.id uniqueness constraint"),
-            Err(e) => warn!("UIElement constraint may already exist: {}", e),
-        }
-
-        match self.graph.run(neo4rs::query(ds_constraint)).await {
-            Ok(_) => info!("Created DesignSystem.name uniqueness constraint"),
-            Err(e) => warn!("DesignSystem constraint may already exist: {}", e),
-        }
-
-        match self.graph.run(neo4rs::query(snippet_constraint)).await {
-            Ok(_) => info!("Created Snippet.id uniqueness constraint"),
-            Err(e) => warn!("Snippet constraint may already exist: {}", e),
-        }
-
-        Ok(())
+    /// Ordered schema migrations, each a list of Cypher statements
+    pub fn migrations(&self) -> Vec<Migration<Vec<String>>> {
+        let statements = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        vec![
+            Migration {
+                version: 1,
+                description: "uniqueness constraints",
+                steps: statements(&[
+                    "CREATE CONSTRAINT ui_element_id_unique IF NOT EXISTS FOR (e:UIElement) REQUIRE e.id IS UNIQUE",
+                    "CREATE CONSTRAINT design_system_name_unique IF NOT EXISTS FOR (d:DesignSystem) REQUIRE d.name IS UNIQUE",
+                    "CREATE CONSTRAINT snippet_id_unique IF NOT EXISTS FOR (s:Snippet) REQUIRE s.id IS UNIQUE",
+                    // DesignToken is keyed by (name, theme)
+                    "CREATE CONSTRAINT design_token_name_theme_unique IF NOT EXISTS FOR (t:DesignToken) REQUIRE (t.name, t.theme) IS UNIQUE",
+                ]),
+            },
+            Migration {
+                version: 2,
+                description: "property indexes",
+                steps: statements(&[
+                    "CREATE INDEX ui_element_category IF NOT EXISTS FOR (e:UIElement) ON (e.category)",
+                    "CREATE INDEX ui_element_type IF NOT EXISTS FOR (e:UIElement) ON (e.element_type)",
+                    "CREATE INDEX ui_element_design_system IF NOT EXISTS FOR (e:UIElement) ON (e.design_system)",
+                    // Composite index for common query pattern
+                    "CREATE INDEX ui_element_category_type IF NOT EXISTS FOR (e:UIElement) ON (e.category, e.element_type)",
+                    "CREATE INDEX snippet_design_system IF NOT EXISTS FOR (s:Snippet) ON (s.design_system)",
+                    "CREATE INDEX snippet_created_at IF NOT EXISTS FOR (s:Snippet) ON (s.created_at)",
+                    "CREATE INDEX snippet_source IF NOT EXISTS FOR (s:Snippet) ON (s.source_root, s.source_path)",
+                    "CREATE INDEX design_token_name IF NOT EXISTS FOR (t:DesignToken) ON (t.name)",
+                    "CREATE INDEX design_token_category IF NOT EXISTS FOR (t:DesignToken) ON (t.category)",
+                ]),
+            },
+            Migration {
+                version: 3,
+                description: "embedding vector index",
+                steps: vec![format!(
+                    "CREATE VECTOR INDEX {} IF NOT EXISTS FOR (e:UIElement) ON (e.embedding) \
+                     OPTIONS {{ indexConfig: {{ `vector.dimensions`: {}, `vector.similarity_function`: 'cosine' }} }}",
+                    VECTOR_INDEX, self.vector_dimensions
+                )],
+            },
+            Migration {
+                version: 4,
+                description: "fulltext index",
+                steps: statements(&[
+                    "CREATE FULLTEXT INDEX ui_element_fulltext IF NOT EXISTS FOR (e:UIElement) \
+                     ON EACH [e.name, e.html_template, e.css_classes_text]",
+                ]),
+            },
+            Migration {
+                version: FINGERPRINT_BAND_MIGRATION,
+                description: "fingerprint band indexes",
+                steps: (0..FINGERPRINT_BANDS)
+                    .map(|band| {
+                        let property = fingerprint_band_property(band);
+                        format!(
+                            "CREATE INDEX ui_element_{} IF NOT EXISTS FOR (e:UIElement) ON (e.{})",
+                            property, property
+                        )
+                    })
+                    .collect(),
+            },
+        ]
     }
 
-    /// Create property indexes for fast queries
-    async fn create_indexes(&self) -> anyhow::Result<()> {
-        let indexes = vec![
-            // Index on category for filtering
-            r#"
-                CREATE INDEX ui_element_category IF NOT EXISTS
-                FOR (e:UIElement)
-                ON (e.category)
-            "#,
-            // Index on element_type
-            r#"
-                CREATE INDEX ui_element_type IF NOT EXISTS
-                FOR (e:UIElement)
-                ON (e.element_type)
-            "#,
-            // Index on design_system
-            r#"
-                CREATE INDEX ui_element_design_system IF NOT EXISTS
-                FOR (e:UIElement)
-                ON (e.design_system)
-            "#,
-            // Composite index for common query pattern
-            r#"
-                CREATE INDEX ui_element_category_type IF NOT EXISTS
-                FOR (e:UIElement)
-                ON (e.category, e.element_type)
-            "#,
-            // Snippet indexes
-            r#"
-                CREATE INDEX snippet_design_system IF NOT EXISTS
-                FOR (s:Snippet)
-                ON (s.design_system)
-            "#,
-            r#"
-                CREATE INDEX snippet_created_at IF NOT EXISTS
-                FOR (s:Snippet)
-                ON (s.created_at)
-            "#,
-        ];
+    /// Run pending migrations, returning those applied
+    ///
+    /// Fails if a statement fails, if the database was migrated by a newer
+    /// build, or if the vector index has other dimensions than configured.
+    pub async fn initialize(&self) -> anyhow::Result<Vec<AppliedMigration>> {
+        info!("Migrating Neo4j schema...");
 
-        for index_query in indexes {
-            match self.graph.run(neo4rs::query(index_query)).await {
-                Ok(_) => info!("Created index"),
-                Err(e) => warn!("Index may already exist: {}", e),
+        let migrations = self.migrations();
+        let applied_versions = self.applied_versions().await?;
+        let mut applied = Vec::new();
+        for migration in migrations::pending("Neo4j", &migrations, &applied_versions)? {
+            for statement in &migration.steps {
+                self.graph.run(query(statement)).await.with_context(|| {
+                    format!("Neo4j migration {} ({}) failed", migration.version, migration.description)
+                })?;
+            }
+            if migration.version == FINGERPRINT_BAND_MIGRATION {
+                self.backfill_fingerprint_bands().await.with_context(|| {
+                    format!("Neo4j migration {} ({}) failed", migration.version, migration.description)
+                })?;
             }
+            self.graph
+                .run(
+                    query("CREATE (:SchemaMigration {version: $version, description: $description, applied_at: $applied_at})")
+                        .param("version", migration.version as i64)
+                        .param("description", migration.description)
+                        .param("applied_at", Utc::now().to_rfc3339()),
+                )
+                .await
+                .with_context(|| format!("failed to record Neo4j migration {}", migration.version))?;
+            info!("Applied Neo4j migration {}: {}", migration.version, migration.description);
+            applied.push(AppliedMigration { version: migration.version, description: migration.description.to_string() });
         }
 
-        Ok(())
+        self.check_vector_dimensions().await?;
+        info!("Neo4j schema is at version {}", migrations.last().map_or(0, |m| m.version));
+        Ok(applied)
     }
 
-    /// Create vector index for semantic similarity search
-    /// Uses 1536 dimensions (OpenAI embedding size) with cosine distance
-    async fn create_vector_index(&self) -> anyhow::Result<()> {
-        // Neo4j 5.x vector index syntax
-        let vector_index = r#"
-            CREATE VECTOR INDEX ui_element_embedding IF NOT EXISTS
-            FOR (e:UIElement)
-            ON (e.embedding)
-            OPTIONS {
-                indexConfig: {
-                    `vector.dimensions`: 1536,
-                    `vector.similarity_function`: 'cosine'
-                }
+    /// Set the band properties of elements fingerprinted before they existed
+    async fn backfill_fingerprint_bands(&self) -> anyhow::Result<()> {
+        let cypher = format!(
+            "MATCH (e:UIElement) WHERE size(e.fingerprint) > 0 AND e.{} IS NULL \
+             RETURN e.id AS id, e.fingerprint AS fingerprint LIMIT $limit",
+            fingerprint_band_property(0)
+        );
+        let mut backfilled = 0;
+        loop {
+            let mut result = self.graph.execute(query(&cypher).param("limit", BACKFILL_BATCH)).await?;
+            let mut rows = Vec::new();
+            while let Some(row) = result.next().await? {
+                let id: String = row.get("id")?;
+                let fingerprint: Vec<i64> = row.get("fingerprint")?;
+                let signature: Vec<u32> = fingerprint.into_iter().map(|v| v as u32).collect();
+                rows.push(BoltType::from(HashMap::from([
+                    ("id", BoltType::from(id)),
+                    ("bands", BoltType::from(fingerprint_band_properties(Some(&signature)))),
+                ])));
             }
-        "#;
-
-        match self.graph.run(neo4rs::query(vector_index)).await {
-            Ok(_) => info!("Created vector index for embeddings (1536 dims, cosine)"),
-            Err(e) => warn!("Vector index may already exist or not supported: {}", e),
+            if rows.is_empty() {
+                break;
+            }
+            backfilled += rows.len();
+            self.graph
+                .run(query("UNWIND $rows AS row MATCH (e:UIElement {id: row.id}) SET e += row.bands").param("rows", rows))
+                .await?;
+        }
+        if backfilled > 0 {
+            info!("Indexed the fingerprint bands of {} elements", backfilled);
         }
-
         Ok(())
     }
 
-    /// Create fulltext index for semantic text search
-    async fn create_fulltext_index(&self) -> anyhow::Result<()> {
-        let fulltext_index = r#"
-            CREATE FULLTEXT INDEX ui_element_fulltext IF NOT EXISTS
-            FOR (e:UIElement)
-            ON EACH [e.name, e.html_template, e.css_classes_text]
-        "#;
-
-        match self.graph.run(neo4rs::query(fulltext_index)).await {
-            Ok(_) => info!("Created fulltext index for name, html_template, css_classes"),
-            Err(e) => warn!("Fulltext index may already exist: {}", e),
+    /// Versions recorded by `SchemaMigration` nodes
+    async fn applied_versions(&self) -> anyhow::Result<Vec<u32>> {
+        let mut result = self
+            .graph
+            .execute(query("MATCH (m:SchemaMigration) RETURN m.version AS version ORDER BY version"))
+            .await
+            .context("failed to read Neo4j schema migrations")?;
+        let mut versions = Vec::new();
+        while let Some(row) = result.next().await? {
+            let version: i64 = row.get("version")?;
+            versions.push(version as u32);
         }
+        Ok(versions)
+    }
 
+    /// Fail if the existing vector index was created for other dimensions
+    async fn check_vector_dimensions(&self) -> anyhow::Result<()> {
+        let cypher = "SHOW INDEXES YIELD name, options WHERE name = $name \
+                      RETURN options.indexConfig['vector.dimensions'] AS dimensions";
+        let mut result = self
+            .graph
+            .execute(query(cypher).param("name", VECTOR_INDEX))
+            .await
+            .context("failed to read Neo4j indexes")?;
+        if let Some(row) = result.next().await? {
+            let dimensions: i64 = row.get("dimensions")?;
+            if dimensions as u64 != self.vector_dimensions {
+                anyhow::bail!(
+                    "Neo4j vector index {} has {} dimensions but {} are configured; \
+                     set OPENAI_EMBEDDING_DIMENSIONS={} or drop the index and re-embed",
+                    VECTOR_INDEX,
+                    dimensions,
+                    self.vector_dimensions,
+                    dimensions
+                );
+            }
+        }
         Ok(())
     }
 
-    /// Drop all custom indexes and constraints (for testing/reset)
+    /// Drop all custom indexes and constraints and forget the applied
+    /// migrations (for testing/reset)
     pub async fn drop_all(&self) -> anyhow::Result<()> {
         warn!("Dropping all custom indexes and constraints");
 
@@ -165,6 +241,7 @@ impl SchemaManager {
             "DROP CONSTRAINT ui_element_id_unique IF EXISTS",
             "DROP CONSTRAINT design_system_name_unique IF EXISTS",
             "DROP CONSTRAINT snippet_id_unique IF EXISTS",
+            "DROP CONSTRAINT design_token_name_theme_unique IF EXISTS",
             "DROP INDEX ui_element_category IF EXISTS",
             "DROP INDEX ui_element_type IF EXISTS",
             "DROP INDEX ui_element_design_system IF EXISTS",
@@ -173,10 +250,14 @@ impl SchemaManager {
             "DROP INDEX ui_element_fulltext IF EXISTS",
             "DROP INDEX snippet_design_system IF EXISTS",
             "DROP INDEX snippet_created_at IF EXISTS",
+            "DROP INDEX snippet_source IF EXISTS",
+            "DROP INDEX design_token_name IF EXISTS",
+            "DROP INDEX design_token_category IF EXISTS",
+            "MATCH (m:SchemaMigration) DELETE m",
         ];
 
         for drop_query in drops {
-            let _ = self.graph.run(neo4rs::query(drop_query)).await;
+            let _ = self.graph.run(query(drop_query)).await;
         }
 
         Ok(())
@@ -16,6 +16,7 @@
 //! - `generate_code`: Generate UI code from natural language
 //! - `give_feedback`: RLKGF feedback for learning
 //! - `get_graph_stats`: Knowledge graph statistics
+//! - `translate_component`: Rewrite a component in another design system
 //!
 //! ## Resources
 //!
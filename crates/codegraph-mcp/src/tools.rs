@@ -6,6 +6,8 @@
 //! - generate_code: Generate UI code
 //! - give_feedback: RLKGF feedback loop
 //! - get_graph_stats: Graph statistics
+//! - translate_component: Rewrite a component in another design system
+//! - get_graph_neighborhood: Nodes and relationships around an element or design system
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -121,6 +123,62 @@ pub fn list_tools() -> Vec<Tool> {
                 "required": []
             }),
         },
+        Tool {
+            name: "translate_component".to_string(),
+            description: "Rewrite a stored UI component in another design system, using its graph counterparts as references".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "element_id": {
+                        "type": "string",
+                        "description": "ID of the element to translate"
+                    },
+                    "target_design_system": {
+                        "type": "string",
+                        "description": "Target design system (tailwind, bootstrap, material-ui, chakra, ant-design, shadcn)"
+                    },
+                    "max_repair_attempts": {
+                        "type": "integer",
+                        "description": "Regenerate up to N times when the output is not detected as the target",
+                        "default": 1
+                    }
+                },
+                "required": ["element_id", "target_design_system"]
+            }),
+        },
+        Tool {
+            name: "get_graph_neighborhood".to_string(),
+            description: "Get the nodes and typed relationships within N hops of an element or design system".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "element_id": {
+                        "type": "string",
+                        "description": "ID of the root element (or give design_system)"
+                    },
+                    "design_system": {
+                        "type": "string",
+                        "description": "Root design system (or give element_id)"
+                    },
+                    "depth": {
+                        "type": "integer",
+                        "description": "Hops from the root (at most 5)",
+                        "default": 1
+                    },
+                    "relation_types": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Relationship types to follow, e.g. SIMILAR_TO, HAS_CHILD (all if omitted)"
+                    },
+                    "max_nodes": {
+                        "type": "integer",
+                        "description": "Stop adding nodes once this many are collected",
+                        "default": 200
+                    }
+                },
+                "required": []
+            }),
+        },
     ]
 }
 
@@ -158,6 +216,23 @@ pub struct GiveFeedbackInput {
     pub thumbs_up: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TranslateComponentInput {
+    pub element_id: String,
+    pub target_design_system: String,
+    pub max_repair_attempts: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphNeighborhoodInput {
+    pub element_id: Option<String>,
+    pub design_system: Option<String>,
+    pub depth: Option<u32>,
+    #[serde(default)]
+    pub relation_types: Vec<String>,
+    pub max_nodes: Option<u32>,
+}
+
 /// Tool output types
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExtractSnippetOutput {
@@ -188,12 +263,62 @@ pub struct GenerateCodeOutput {
     pub javascript: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranslateComponentOutput {
+    pub element_id: String,
+    pub target_design_system: String,
+    pub html: String,
+    pub css: Option<String>,
+    pub javascript: Option<String>,
+    pub token_mappings: Vec<TokenMappingOutput>,
+    pub detected_design_system: String,
+    pub valid: bool,
+    #[serde(default)]
+    pub validation_errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenMappingOutput {
+    pub source: String,
+    pub target: String,
+    pub exact: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GiveFeedbackOutput {
     pub updated_confidence: f32,
     pub elements_affected: u32,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphNeighborhoodOutput {
+    pub nodes: Vec<NeighborhoodNodeOutput>,
+    pub links: Vec<NeighborhoodLinkOutput>,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NeighborhoodNodeOutput {
+    pub id: String,
+    pub kind: String,
+    pub name: String,
+    pub category: String,
+    pub design_system: String,
+    pub connections: u32,
+    pub depth: u32,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NeighborhoodLinkOutput {
+    pub source: String,
+    pub target: String,
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    pub score: Option<f32>,
+    pub created_at: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GraphStatsOutput {
     pub total_elements: u64,
@@ -235,6 +360,20 @@ pub async fn call_tool(name: &str, arguments: Option<Value>, api_url: &str) -> C
             give_feedback(input, api_url).await
         }
         "get_graph_stats" => get_graph_stats(api_url).await,
+        "translate_component" => {
+            let input: TranslateComponentInput = match parse_arguments(arguments) {
+                Ok(i) => i,
+                Err(e) => return error_result(e),
+            };
+            translate_component(input, api_url).await
+        }
+        "get_graph_neighborhood" => {
+            let input: GraphNeighborhoodInput = match parse_arguments(arguments) {
+                Ok(i) => i,
+                Err(e) => return error_result(e),
+            };
+            get_graph_neighborhood(input, api_url).await
+        }
         _ => CallToolResult {
             content: vec![ContentBlock::text(format!("Unknown tool: {}", name))],
             is_error: Some(true),
@@ -468,6 +607,73 @@ async fn get_graph_stats(api_url: &str) -> CallToolResult {
     }
 }
 
+async fn translate_component(input: TranslateComponentInput, api_url: &str) -> CallToolResult {
+    let url = format!("{}/api/translate", api_url);
+
+    let body = json!({
+        "element_id": input.element_id,
+        "target_design_system": input.target_design_system,
+        "max_repair_attempts": input.max_repair_attempts.unwrap_or(1)
+    });
+
+    match reqwest::Client::new().post(&url).json(&body).send().await {
+        Ok(resp) => {
+            if resp.status().is_success() {
+                match resp.json::<TranslateComponentOutput>().await {
+                    Ok(output) => CallToolResult {
+                        content: vec![ContentBlock::json(&serde_json::to_value(output).unwrap())],
+                        is_error: None,
+                    },
+                    Err(e) => error_result(format!("Failed to parse response: {}", e)),
+                }
+            } else {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                error_result(format!("API error {}: {}", status, body))
+            }
+        }
+        Err(e) => {
+            // A mock translation would be indistinguishable from a real one
+            error!(error = %e, "Failed to call translate API");
+            error_result(format!("Translate API unavailable: {}", e))
+        }
+    }
+}
+
+async fn get_graph_neighborhood(input: GraphNeighborhoodInput, api_url: &str) -> CallToolResult {
+    let url = format!("{}/api/graph/neighborhood", api_url);
+
+    let body = json!({
+        "element_id": input.element_id,
+        "design_system": input.design_system,
+        "depth": input.depth.unwrap_or(1),
+        "relation_types": input.relation_types,
+        "max_nodes": input.max_nodes.unwrap_or(200)
+    });
+
+    match reqwest::Client::new().post(&url).json(&body).send().await {
+        Ok(resp) => {
+            if resp.status().is_success() {
+                match resp.json::<GraphNeighborhoodOutput>().await {
+                    Ok(output) => CallToolResult {
+                        content: vec![ContentBlock::json(&serde_json::to_value(output).unwrap())],
+                        is_error: None,
+                    },
+                    Err(e) => error_result(format!("Failed to parse response: {}", e)),
+                }
+            } else {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                error_result(format!("API error {}: {}", status, body))
+            }
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to call neighborhood API");
+            error_result(format!("Neighborhood API unavailable: {}", e))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -475,7 +681,7 @@ mod tests {
     #[test]
     fn test_list_tools_count() {
         let tools = list_tools();
-        assert_eq!(tools.len(), 5);
+        assert_eq!(tools.len(), 7);
     }
 
     #[test]
@@ -487,6 +693,8 @@ mod tests {
         assert!(names.contains(&"generate_code"));
         assert!(names.contains(&"give_feedback"));
         assert!(names.contains(&"get_graph_stats"));
+        assert!(names.contains(&"translate_component"));
+        assert!(names.contains(&"get_graph_neighborhood"));
     }
 
     #[test]
@@ -84,6 +84,22 @@ impl ReasoningPipeline {
         Ok(())
     }
 
+    /// Input Narsese beliefs (e.g. an imported token palette) into ONA,
+    /// loading the ontology first
+    ///
+    /// Returns the number of statements sent, 0 when ONA is disabled.
+    pub fn add_beliefs(&mut self, statements: &[String]) -> anyhow::Result<usize> {
+        if !self.ona_enabled {
+            debug!("ONA disabled, not inputting {} beliefs", statements.len());
+            return Ok(0);
+        }
+        self.initialize()?;
+        for statement in statements {
+            self.ona.input_statement(statement)?;
+        }
+        Ok(statements.len())
+    }
+
     /// Process a natural language query through the full pipeline
     ///
     /// If ONA is disabled or unavailable, automatically falls back to offline mode.
@@ -2,6 +2,10 @@
 //!
 //! Weights: vector similarity (40%) + fulltext (30%) + pattern matching (30%)
 //!
+//! The fulltext channel searches the graph store's fulltext index by default;
+//! with [`FulltextSource::Sparse`] it searches the vector store's sparse
+//! lexical vectors instead, saving a round trip to the graph database.
+//!
 //! ## Metrics Exposed
 //!
 //! - `retrieval_vector_latency_ms` - Vector search latency histogram
@@ -17,9 +21,9 @@ use anyhow::Result;
 use tracing::{debug, error, info, warn};
 
 use codegraph_extraction::embedding::EmbeddingGenerator;
-use codegraph_graph::Neo4jRepository;
+use codegraph_graph::{GraphStore, Neo4jRepository};
 use codegraph_reasoning::{ReasoningPipeline, ReasoningResult};
-use codegraph_vector::{QdrantRepository, SearchResult};
+use codegraph_vector::{QdrantRepository, SearchResult, VectorStore};
 
 use crate::query::{ProcessedQuery, QueryProcessor};
 use crate::ranker::{Ranker, ResultSource, ScoredElement};
@@ -57,6 +61,28 @@ pub struct RetrievalResult {
     pub latency_ms: u64,
 }
 
+/// Store searched by the fulltext channel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FulltextSource {
+    /// Fulltext index of the graph store
+    #[default]
+    Graph,
+    /// Sparse lexical vectors of the vector store
+    Sparse,
+}
+
+impl std::str::FromStr for FulltextSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "graph" | "neo4j" => Ok(Self::Graph),
+            "sparse" | "vector" | "qdrant" => Ok(Self::Sparse),
+            other => Err(format!("unknown fulltext source '{}'", other)),
+        }
+    }
+}
+
 /// Hybrid retriever combining multiple search strategies
 pub struct HybridRetriever {
     query_processor: QueryProcessor,
@@ -65,10 +91,12 @@ pub struct HybridRetriever {
     hybrid_weights: HybridWeights,
     max_results: usize,
     latency_target_ms: u64,
-    /// Qdrant repository for vector searches
-    qdrant_repository: Option<Arc<QdrantRepository>>,
-    /// Neo4j repository for fulltext and graph searches
-    neo4j_repository: Option<Arc<Neo4jRepository>>,
+    /// Vector store for similarity searches
+    vector_store: Option<Arc<dyn VectorStore>>,
+    /// Graph store for fulltext and graph searches
+    graph_store: Option<Arc<dyn GraphStore>>,
+    /// Store searched by the fulltext channel
+    fulltext_source: FulltextSource,
     /// Embedding generator for query vectorization
     embedding_generator: Arc<EmbeddingGenerator>,
 }
@@ -82,24 +110,41 @@ impl HybridRetriever {
             hybrid_weights: HybridWeights::default(),
             max_results: 10,
             latency_target_ms: 2000, // 2 seconds target
-            qdrant_repository: None,
-            neo4j_repository: None,
+            vector_store: None,
+            graph_store: None,
+            fulltext_source: FulltextSource::default(),
             embedding_generator: Arc::new(EmbeddingGenerator::new()),
         }
     }
 
-    /// Create a new HybridRetriever with Qdrant repository for vector search
-    pub fn with_qdrant(mut self, repository: Arc<QdrantRepository>) -> Self {
-        self.qdrant_repository = Some(repository);
+    /// Use a vector store for similarity search
+    pub fn with_vector_store(mut self, store: Arc<dyn VectorStore>) -> Self {
+        self.vector_store = Some(store);
         self
     }
 
-    /// Create a new HybridRetriever with Neo4j repository for fulltext and graph search
-    pub fn with_neo4j(mut self, repository: Arc<Neo4jRepository>) -> Self {
-        self.neo4j_repository = Some(repository);
+    /// Use a graph store for fulltext and graph search
+    pub fn with_graph_store(mut self, store: Arc<dyn GraphStore>) -> Self {
+        self.graph_store = Some(store);
         self
     }
 
+    /// Choose the store searched by the fulltext channel
+    pub fn with_fulltext_source(mut self, source: FulltextSource) -> Self {
+        self.fulltext_source = source;
+        self
+    }
+
+    /// Create a new HybridRetriever with Qdrant repository for vector search
+    pub fn with_qdrant(self, repository: Arc<QdrantRepository>) -> Self {
+        self.with_vector_store(repository)
+    }
+
+    /// Create a new HybridRetriever with Neo4j repository for fulltext and graph search
+    pub fn with_neo4j(self, repository: Arc<Neo4jRepository>) -> Self {
+        self.with_graph_store(repository)
+    }
+
     /// Set the embedding generator
     pub fn with_embedding_generator(mut self, generator: Arc<EmbeddingGenerator>) -> Self {
         self.embedding_generator = generator;
@@ -143,10 +188,11 @@ impl HybridRetriever {
 
         // Step 3: Perform parallel searches (vector, fulltext, graph)
         // In production, these would be actual database calls
-        let (vector_results, fulltext_results, graph_results) = tokio::join!(
+        let (vector_results, fulltext_results, graph_results, token_results) = tokio::join!(
             self.search_vector(&reasoning.search_terms),
             self.search_fulltext(&processed.search_terms),
             self.search_graph(&processed.component_types, &processed.attributes),
+            self.search_tokens(&processed.design_tokens),
         );
 
         // Step 4: Combine results with hybrid weights
@@ -162,7 +208,11 @@ impl HybridRetriever {
             all_elements.push(elem);
         }
 
-        for mut elem in graph_results.unwrap_or_default() {
+        for mut elem in graph_results
+            .unwrap_or_default()
+            .into_iter()
+            .chain(token_results.unwrap_or_default())
+        {
             elem.semantic_similarity *= self.hybrid_weights.pattern_matching;
             all_elements.push(elem);
         }
@@ -218,16 +268,16 @@ impl HybridRetriever {
         })
     }
 
-    /// Vector similarity search via Qdrant
+    /// Vector similarity search via the vector store
     async fn search_vector(&self, terms: &[String]) -> Result<Vec<ScoredElement>> {
         let start = Instant::now();
         debug!("Vector search for terms: {:?}", terms);
 
-        // Check if Qdrant repository is available
-        let repository = match &self.qdrant_repository {
+        // Check if a vector store is available
+        let repository = match &self.vector_store {
             Some(repo) => repo,
             None => {
-                warn!("Qdrant repository not configured, skipping vector search");
+                warn!("Vector store not configured, skipping vector search");
                 return Ok(vec![]);
             }
         };
@@ -263,7 +313,7 @@ impl HybridRetriever {
         {
             Ok(results) => results,
             Err(e) => {
-                error!("Qdrant search failed: {}", e);
+                error!("Vector search failed: {}", e);
                 return Ok(vec![]);
             }
         };
@@ -292,21 +342,26 @@ impl HybridRetriever {
             narsese_confidence: result.payload.confidence,
             semantic_similarity: result.score,
             graph_degree: 0.0, // Will be updated by graph search if available
+            a11y_score: None,
             final_score: 0.0,  // Will be calculated by ranker
             source: ResultSource::Vector,
         }
     }
 
-    /// Fulltext search via Neo4j
+    /// Fulltext search via the configured [`FulltextSource`]
     async fn search_fulltext(&self, terms: &[String]) -> Result<Vec<ScoredElement>> {
+        if self.fulltext_source == FulltextSource::Sparse {
+            return self.search_sparse(terms).await;
+        }
+
         let start = Instant::now();
         debug!("Fulltext search for terms: {:?}", terms);
 
-        // Check if Neo4j repository is available
-        let repository = match &self.neo4j_repository {
+        // Check if a graph store is available
+        let repository = match &self.graph_store {
             Some(repo) => repo,
             None => {
-                warn!("Neo4j repository not configured, skipping fulltext search");
+                warn!("Graph store not configured, skipping fulltext search");
                 return Ok(vec![]);
             }
         };
@@ -325,7 +380,7 @@ impl HybridRetriever {
         let results = match repository.fulltext_search(&search_term, self.max_results).await {
             Ok(results) => results,
             Err(e) => {
-                error!("Neo4j fulltext search failed: {}", e);
+                error!("Fulltext search failed: {}", e);
                 return Ok(vec![]);
             }
         };
@@ -344,6 +399,43 @@ impl HybridRetriever {
         Ok(scored_elements)
     }
 
+    /// Fulltext search via the vector store's sparse vectors
+    async fn search_sparse(&self, terms: &[String]) -> Result<Vec<ScoredElement>> {
+        let start = Instant::now();
+        debug!("Sparse fulltext search for terms: {:?}", terms);
+
+        let repository = match &self.vector_store {
+            Some(repo) => repo,
+            None => {
+                warn!("Vector store not configured, skipping sparse fulltext search");
+                return Ok(vec![]);
+            }
+        };
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let results = match repository.search_sparse(&terms.join(" "), self.max_results as u64, None).await {
+            Ok(results) => results,
+            Err(e) => {
+                error!("Sparse fulltext search failed: {}", e);
+                return Ok(vec![]);
+            }
+        };
+
+        let latency_ms = start.elapsed().as_millis() as f64;
+        metrics::histogram!("retrieval_fulltext_latency_ms").record(latency_ms);
+        debug!("Sparse fulltext search returned {} results in {}ms", results.len(), latency_ms);
+
+        Ok(results
+            .into_iter()
+            .map(|result| ScoredElement {
+                source: ResultSource::Fulltext,
+                ..self.search_result_to_scored_element(result)
+            })
+            .collect())
+    }
+
     /// Convert a Neo4j SimilarElement to a ScoredElement
     fn similar_element_to_scored_element(
         &self,
@@ -358,12 +450,13 @@ impl HybridRetriever {
             narsese_confidence: 0.5, // Default, will be updated by NARS reasoning
             semantic_similarity: result.similarity,
             graph_degree: 0.0, // Will be updated by graph search if available
+            a11y_score: result.element.a11y_score,
             final_score: 0.0,  // Will be calculated by ranker
             source,
         }
     }
 
-    /// Graph pattern matching via Neo4j Cypher
+    /// Graph pattern matching via the graph store
     async fn search_graph(
         &self,
         component_types: &[String],
@@ -375,11 +468,11 @@ impl HybridRetriever {
             component_types, attributes
         );
 
-        // Check if Neo4j repository is available
-        let repository = match &self.neo4j_repository {
+        // Check if a graph store is available
+        let repository = match &self.graph_store {
             Some(repo) => repo,
             None => {
-                warn!("Neo4j repository not configured, skipping graph search");
+                warn!("Graph store not configured, skipping graph search");
                 return Ok(vec![]);
             }
         };
@@ -397,7 +490,6 @@ impl HybridRetriever {
                     for element in elements {
                         // Get graph degree for connectivity scoring
                         let degree = repository
-                            .relations()
                             .get_degree(element.id)
                             .await
                             .unwrap_or(0);
@@ -413,6 +505,7 @@ impl HybridRetriever {
                             narsese_confidence: 0.5, // Default
                             semantic_similarity: 0.8, // High score for category match
                             graph_degree: normalized_degree,
+                            a11y_score: element.a11y_score,
                             final_score: 0.0,
                             source: ResultSource::Graph,
                         };
@@ -448,6 +541,47 @@ impl HybridRetriever {
 
         Ok(all_elements)
     }
+
+    /// Graph search for elements using design tokens (via USES_TOKEN/ALIAS_OF)
+    async fn search_tokens(&self, tokens: &[String]) -> Result<Vec<ScoredElement>> {
+        if tokens.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let repository = match &self.graph_store {
+            Some(repo) => repo,
+            None => {
+                warn!("Graph store not configured, skipping token search");
+                return Ok(vec![]);
+            }
+        };
+
+        let mut all_elements = Vec::new();
+        for token in tokens {
+            match repository.find_elements_using_token(token).await {
+                Ok(elements) => {
+                    all_elements.extend(elements.into_iter().map(|element| ScoredElement {
+                        element_id: element.id.to_string(),
+                        name: element.name,
+                        category: element.category,
+                        tags: element.tags,
+                        narsese_confidence: 0.5,
+                        semantic_similarity: 0.9, // Exact token usage is a strong signal
+                        graph_degree: 0.0,
+                        a11y_score: element.a11y_score,
+                        final_score: 0.0,
+                        source: ResultSource::Graph,
+                    }));
+                }
+                Err(e) => {
+                    warn!("Failed to search by token '{}': {}", token, e);
+                }
+            }
+        }
+
+        all_elements.truncate(self.max_results);
+        Ok(all_elements)
+    }
 }
 
 impl Default for HybridRetriever {
@@ -473,7 +607,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_search_vector_without_repository() {
-        // When no Qdrant repository is configured, vector search should return empty
+        // When no vector store is configured, vector search should return empty
         let retriever = HybridRetriever::new();
         let terms = vec!["button".to_string(), "primary".to_string()];
         let result = retriever.search_vector(&terms).await;
@@ -494,7 +628,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_search_fulltext_without_repository() {
-        // When no Neo4j repository is configured, fulltext search should return empty
+        // When no graph store is configured, fulltext search should return empty
         let retriever = HybridRetriever::new();
         let terms = vec!["button".to_string()];
         let result = retriever.search_fulltext(&terms).await;
@@ -503,6 +637,16 @@ mod tests {
         assert!(result.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_sparse_fulltext_without_vector_store() {
+        let retriever = HybridRetriever::new().with_fulltext_source(FulltextSource::Sparse);
+        let terms = vec!["button".to_string()];
+        let result = retriever.search_fulltext(&terms).await;
+
+        assert!(result.unwrap().is_empty());
+        assert_eq!("qdrant".parse::<FulltextSource>(), Ok(FulltextSource::Sparse));
+    }
+
     #[tokio::test]
     async fn test_search_fulltext_with_empty_terms() {
         let retriever = HybridRetriever::new();
@@ -515,7 +659,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_search_graph_without_repository() {
-        // When no Neo4j repository is configured, graph search should return empty
+        // When no graph store is configured, graph search should return empty
         let retriever = HybridRetriever::new();
         let components = vec!["button".to_string()];
         let attributes = vec!["primary".to_string()];
@@ -536,6 +680,16 @@ mod tests {
         assert!(result.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_search_tokens_without_repository() {
+        let retriever = HybridRetriever::new();
+        let tokens = vec!["--color-primary".to_string()];
+        let result = retriever.search_tokens(&tokens).await;
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_hybrid_weights_default() {
         let weights = HybridWeights::default();
@@ -7,6 +7,6 @@ pub mod hybrid;
 pub mod query;
 pub mod ranker;
 
-pub use hybrid::HybridRetriever;
+pub use hybrid::{FulltextSource, HybridRetriever};
 pub use query::QueryProcessor;
 pub use ranker::Ranker;
@@ -32,6 +32,9 @@ pub struct ProcessedQuery {
     pub context: Vec<String>,
     /// Search terms for retrieval
     pub search_terms: Vec<String>,
+    /// Design tokens mentioned (e.g., "--color-primary")
+    #[serde(default)]
+    pub design_tokens: Vec<String>,
 }
 
 /// Extracts structured information from natural language queries
@@ -39,6 +42,7 @@ pub struct QueryProcessor {
     intent_patterns: IntentPatterns,
     component_patterns: Vec<(&'static str, Regex)>,
     attribute_patterns: Vec<(&'static str, Regex)>,
+    token_pattern: Regex,
 }
 
 struct IntentPatterns {
@@ -116,6 +120,7 @@ impl QueryProcessor {
                 ("fullwidth", Regex::new(r"(?i)\b(full\s*width|full-width)\b").unwrap()),
                 ("transparent", Regex::new(r"(?i)\btransparent\b").unwrap()),
             ],
+            token_pattern: Regex::new(r"--[a-zA-Z][\w-]*").unwrap(),
         }
     }
 
@@ -125,6 +130,7 @@ impl QueryProcessor {
         let component_types = self.extract_components(query);
         let attributes = self.extract_attributes(query);
         let context = self.extract_context(query, &component_types, &attributes);
+        let design_tokens = self.extract_tokens(query);
 
         // Combine all terms for search
         let mut search_terms = Vec::new();
@@ -139,6 +145,7 @@ impl QueryProcessor {
             attributes,
             context,
             search_terms,
+            design_tokens,
         }
     }
 
@@ -168,6 +175,16 @@ impl QueryProcessor {
             .collect()
     }
 
+    fn extract_tokens(&self, query: &str) -> Vec<String> {
+        let mut tokens: Vec<String> = self
+            .token_pattern
+            .find_iter(query)
+            .map(|m| m.as_str().trim_end_matches('-').to_string())
+            .collect();
+        tokens.dedup();
+        tokens
+    }
+
     fn extract_context(&self, query: &str, components: &[String], attributes: &[String]) -> Vec<String> {
         // Extract additional meaningful words not already captured
         let stop_words = [
@@ -214,4 +231,12 @@ mod tests {
         assert!(result.attributes.contains(&"responsive".to_string()));
         assert!(result.attributes.contains(&"dark".to_string()));
     }
+
+    #[test]
+    fn test_extract_design_tokens() {
+        let processor = QueryProcessor::new();
+        let result = processor.process("components using --color-primary");
+
+        assert_eq!(result.design_tokens, vec!["--color-primary".to_string()]);
+    }
 }
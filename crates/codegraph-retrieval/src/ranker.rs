@@ -1,6 +1,7 @@
 //! Ranker - scores and ranks retrieval results
 //!
-//! Formula: 0.5*narsese_confidence + 0.3*semantic_similarity + 0.2*graph_degree
+//! Formula: 0.5*narsese_confidence + 0.3*semantic_similarity + 0.2*graph_degree,
+//! scaled down by up to `accessibility` for elements with a low a11y score.
 
 use std::collections::HashMap;
 
@@ -23,6 +24,9 @@ pub struct ScoredElement {
     pub semantic_similarity: f32,
     /// Graph connectivity degree (normalized 0.0-1.0)
     pub graph_degree: f32,
+    /// Accessibility audit score (0.0-1.0, `None` if not audited)
+    #[serde(default)]
+    pub a11y_score: Option<f32>,
     /// Combined final score
     pub final_score: f32,
     /// Source of the result (vector, graph, fulltext)
@@ -44,6 +48,8 @@ pub struct RankingWeights {
     pub narsese_confidence: f32,
     pub semantic_similarity: f32,
     pub graph_degree: f32,
+    /// Maximum penalty for a fully inaccessible element (0.0 disables)
+    pub accessibility: f32,
 }
 
 impl Default for RankingWeights {
@@ -52,6 +58,7 @@ impl Default for RankingWeights {
             narsese_confidence: 0.5,
             semantic_similarity: 0.3,
             graph_degree: 0.2,
+            accessibility: 0.2,
         }
     }
 }
@@ -90,6 +97,14 @@ impl Ranker {
             + self.weights.graph_degree * graph_degree
     }
 
+    /// Multiplier for an element's a11y score; unaudited elements are not penalized
+    pub fn accessibility_factor(&self, a11y_score: Option<f32>) -> f32 {
+        match a11y_score {
+            Some(score) => 1.0 - self.weights.accessibility * (1.0 - score.clamp(0.0, 1.0)),
+            None => 1.0,
+        }
+    }
+
     /// Rank a list of elements by their scores
     pub fn rank(&self, mut elements: Vec<ScoredElement>) -> Vec<ScoredElement> {
         // Calculate final scores
@@ -98,7 +113,7 @@ impl Ranker {
                 elem.narsese_confidence,
                 elem.semantic_similarity,
                 elem.graph_degree,
-            );
+            ) * self.accessibility_factor(elem.a11y_score);
         }
 
         // Sort by final score descending
@@ -166,6 +181,7 @@ mod tests {
                 narsese_confidence: 0.8,
                 semantic_similarity: 0.6,
                 graph_degree: 0.4,
+                a11y_score: None,
                 final_score: 0.5,
                 source: ResultSource::Vector,
             },
@@ -177,6 +193,7 @@ mod tests {
                 narsese_confidence: 0.9,
                 semantic_similarity: 0.7,
                 graph_degree: 0.5,
+                a11y_score: None,
                 final_score: 0.7,
                 source: ResultSource::Graph,
             },
@@ -186,4 +203,27 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].final_score, 0.7);
     }
+
+    #[test]
+    fn test_accessibility_penalty() {
+        let ranker = Ranker::new();
+        let element = |id: &str, a11y_score| ScoredElement {
+            element_id: id.to_string(),
+            name: id.to_string(),
+            category: "button".to_string(),
+            tags: vec![],
+            narsese_confidence: 0.8,
+            semantic_similarity: 0.6,
+            graph_degree: 0.4,
+            a11y_score,
+            final_score: 0.0,
+            source: ResultSource::Graph,
+        };
+
+        let ranked = ranker.rank(vec![element("poor", Some(0.2)), element("unaudited", None), element("good", Some(1.0))]);
+        assert_eq!(ranked[2].element_id, "poor");
+        assert_eq!(ranked[0].final_score, ranked[1].final_score);
+        // 0.66 * (1 - 0.2 * 0.8)
+        assert!((ranked[2].final_score - 0.5544).abs() < 0.001);
+    }
 }
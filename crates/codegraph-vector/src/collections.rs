@@ -1,7 +1,8 @@
 //! Collection definitions for design system embeddings
 //!
 //! Each design system has its own Qdrant collection to enable
-//! efficient filtering and targeted searches.
+//! efficient filtering and targeted searches. Design systems without a
+//! predefined collection get one named after them (see [`collection_name`]).
 
 use serde::{Deserialize, Serialize};
 
@@ -10,14 +11,21 @@ pub const COLLECTION_MATERIAL: &str = "ui_material";
 pub const COLLECTION_TAILWIND: &str = "ui_tailwind";
 pub const COLLECTION_CHAKRA: &str = "ui_chakra";
 pub const COLLECTION_BOOTSTRAP: &str = "ui_bootstrap";
+pub const COLLECTION_ANT_DESIGN: &str = "ui_ant_design";
+pub const COLLECTION_SHADCN: &str = "ui_shadcn";
 pub const COLLECTION_CUSTOM: &str = "ui_custom";
 
-/// All available collections
+/// Prefix shared by every design system collection
+pub const COLLECTION_PREFIX: &str = "ui_";
+
+/// Predefined collections, created on startup
 pub const COLLECTIONS: &[&str] = &[
     COLLECTION_MATERIAL,
     COLLECTION_TAILWIND,
     COLLECTION_CHAKRA,
     COLLECTION_BOOTSTRAP,
+    COLLECTION_ANT_DESIGN,
+    COLLECTION_SHADCN,
     COLLECTION_CUSTOM,
 ];
 
@@ -65,6 +73,16 @@ impl Collection {
                 "Bootstrap",
                 "Popular CSS framework for responsive layouts",
             ),
+            Collection::new(
+                COLLECTION_ANT_DESIGN,
+                "Ant Design",
+                "Enterprise-class React component library",
+            ),
+            Collection::new(
+                COLLECTION_SHADCN,
+                "shadcn/ui",
+                "Radix primitives styled with Tailwind CSS",
+            ),
             Collection::new(
                 COLLECTION_CUSTOM,
                 "Custom",
@@ -87,11 +105,32 @@ pub fn design_system_to_collection(design_system: &str) -> Option<&'static str>
         "tailwind" | "tailwindcss" | "tailwind-css" => Some(COLLECTION_TAILWIND),
         "chakra" | "chakra-ui" => Some(COLLECTION_CHAKRA),
         "bootstrap" => Some(COLLECTION_BOOTSTRAP),
+        "ant-design" | "antdesign" | "antd" | "ant" => Some(COLLECTION_ANT_DESIGN),
+        "shadcn" | "shadcn-ui" | "shadcn/ui" => Some(COLLECTION_SHADCN),
         "custom" | "" => Some(COLLECTION_CUSTOM),
         _ => None,
     }
 }
 
+/// Collection holding a design system's embeddings: the predefined one, else
+/// `ui_` followed by the design system name in snake case
+pub fn collection_name(design_system: &str) -> String {
+    if let Some(name) = design_system_to_collection(design_system) {
+        return name.to_string();
+    }
+    let slug = design_system
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if slug.is_empty() {
+        COLLECTION_CUSTOM.to_string()
+    } else {
+        format!("{}{}", COLLECTION_PREFIX, slug)
+    }
+}
+
 /// Map collection name back to canonical design system name
 pub fn collection_to_design_system(collection: &str) -> Option<&'static str> {
     match collection {
@@ -99,6 +138,8 @@ pub fn collection_to_design_system(collection: &str) -> Option<&'static str> {
         COLLECTION_TAILWIND => Some("tailwind"),
         COLLECTION_CHAKRA => Some("chakra"),
         COLLECTION_BOOTSTRAP => Some("bootstrap"),
+        COLLECTION_ANT_DESIGN => Some("ant-design"),
+        COLLECTION_SHADCN => Some("shadcn"),
         COLLECTION_CUSTOM => Some("custom"),
         _ => None,
     }
@@ -110,8 +151,8 @@ mod tests {
 
     #[test]
     fn test_collections_count() {
-        assert_eq!(COLLECTIONS.len(), 5);
-        assert_eq!(Collection::all().len(), 5);
+        assert_eq!(COLLECTIONS.len(), 7);
+        assert_eq!(Collection::all().len(), 7);
     }
 
     #[test]
@@ -124,9 +165,19 @@ mod tests {
         assert_eq!(design_system_to_collection("bootstrap"), Some(COLLECTION_BOOTSTRAP));
         assert_eq!(design_system_to_collection("custom"), Some(COLLECTION_CUSTOM));
         assert_eq!(design_system_to_collection(""), Some(COLLECTION_CUSTOM));
+        assert_eq!(design_system_to_collection("antd"), Some(COLLECTION_ANT_DESIGN));
+        assert_eq!(design_system_to_collection("shadcn/ui"), Some(COLLECTION_SHADCN));
         assert_eq!(design_system_to_collection("unknown"), None);
     }
 
+    #[test]
+    fn test_collection_name_for_unknown_design_systems() {
+        assert_eq!(collection_name("Tailwind"), COLLECTION_TAILWIND);
+        assert_eq!(collection_name("Radix Themes"), "ui_radix_themes");
+        assert_eq!(collection_name("@acme/ui-kit"), "ui_acme_ui_kit");
+        assert_eq!(collection_name("--"), COLLECTION_CUSTOM);
+    }
+
     #[test]
     fn test_collection_to_design_system() {
         assert_eq!(collection_to_design_system(COLLECTION_TAILWIND), Some("tailwind"));
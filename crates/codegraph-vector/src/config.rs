@@ -10,6 +10,12 @@ pub const DEFAULT_VECTOR_SIZE: u64 = 1536;
 /// Default distance metric
 pub const DEFAULT_DISTANCE: Distance = Distance::Cosine;
 
+/// Name of the dense embedding vector of every point
+pub const DENSE_VECTOR: &str = "dense";
+
+/// Name of the sparse lexical vector of every point
+pub const SPARSE_VECTOR: &str = "sparse";
+
 /// Distance metrics for vector similarity
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -80,6 +86,15 @@ impl VectorConfig {
         }
     }
 
+    /// Create config sized from `QDRANT_VECTOR_SIZE`, falling back to
+    /// `OPENAI_EMBEDDING_DIMENSIONS`
+    pub fn from_env() -> Self {
+        Self {
+            size: codegraph_core::config::QdrantConfig::from_env().vector_size,
+            ..Default::default()
+        }
+    }
+
     /// Create config optimized for large collections (100k+ vectors)
     pub fn large_scale() -> Self {
         Self {
@@ -123,6 +138,101 @@ impl HnswConfig {
     }
 }
 
+/// How design systems map onto Qdrant collections
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionLayout {
+    /// One Qdrant collection per design system (`ui_tailwind`, `ui_material`, ...)
+    #[default]
+    PerDesignSystem,
+    /// A single [`PARTITIONED_COLLECTION`] whose points carry their logical
+    /// collection in the `collection` payload field
+    Partitioned,
+}
+
+/// Physical collection used by [`CollectionLayout::Partitioned`]
+pub const PARTITIONED_COLLECTION: &str = "ui_elements";
+
+impl std::str::FromStr for CollectionLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "per_design_system" | "per-design-system" | "collections" => Ok(Self::PerDesignSystem),
+            "partitioned" | "single" => Ok(Self::Partitioned),
+            other => Err(format!("unknown collection layout '{}'", other)),
+        }
+    }
+}
+
+/// How scores from different collections are made comparable before merging
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// Keep raw similarity scores
+    #[default]
+    None,
+    /// Rescale each collection's hits to 0.0-1.0 (best hit 1.0)
+    ///
+    /// A collection whose hits all score the same keeps the raw score, so a
+    /// lone weak hit does not rank level with the best hits of others.
+    MinMax,
+}
+
+impl std::str::FromStr for ScoreNormalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "raw" => Ok(Self::None),
+            "min_max" | "minmax" | "min-max" => Ok(Self::MinMax),
+            other => Err(format!("unknown score normalization '{}'", other)),
+        }
+    }
+}
+
+/// Multi-collection search settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchConfig {
+    /// Time allowed for each collection; slower collections are left out of the results
+    pub budget_ms: u64,
+    /// Hits kept from every collection that has them, before filling by score
+    pub min_per_collection: usize,
+    /// Score normalization applied per collection before merging
+    pub normalization: ScoreNormalization,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            budget_ms: 500,
+            min_per_collection: 0,
+            normalization: ScoreNormalization::None,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Create config from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            budget_ms: std::env::var("QDRANT_SEARCH_BUDGET_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.budget_ms),
+            min_per_collection: std::env::var("QDRANT_MIN_PER_COLLECTION")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.min_per_collection),
+            normalization: std::env::var("QDRANT_SCORE_NORMALIZATION")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(defaults.normalization),
+        }
+    }
+}
+
 /// Qdrant connection configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QdrantConfig {
@@ -134,6 +244,10 @@ pub struct QdrantConfig {
     pub timeout_secs: u64,
     /// Vector configuration
     pub vector_config: VectorConfig,
+    /// Collection layout
+    pub layout: CollectionLayout,
+    /// Multi-collection search settings
+    pub search: SearchConfig,
 }
 
 impl Default for QdrantConfig {
@@ -143,6 +257,8 @@ impl Default for QdrantConfig {
             api_key: None,
             timeout_secs: 30,
             vector_config: VectorConfig::default(),
+            layout: CollectionLayout::default(),
+            search: SearchConfig::default(),
         }
     }
 }
@@ -157,7 +273,12 @@ impl QdrantConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(30),
-            vector_config: VectorConfig::default(),
+            vector_config: VectorConfig::from_env(),
+            layout: std::env::var("QDRANT_LAYOUT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_default(),
+            search: SearchConfig::from_env(),
         }
     }
 
@@ -178,6 +299,18 @@ impl QdrantConfig {
         self.vector_config = config;
         self
     }
+
+    /// Set collection layout
+    pub fn with_layout(mut self, layout: CollectionLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set multi-collection search settings
+    pub fn with_search_config(mut self, search: SearchConfig) -> Self {
+        self.search = search;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -218,6 +351,14 @@ mod tests {
         assert_eq!(config.api_key, Some("secret".to_string()));
     }
 
+    #[test]
+    fn test_layout_and_normalization_parse() {
+        assert_eq!("partitioned".parse::<CollectionLayout>(), Ok(CollectionLayout::Partitioned));
+        assert_eq!("per-design-system".parse::<CollectionLayout>(), Ok(CollectionLayout::PerDesignSystem));
+        assert_eq!("min-max".parse::<ScoreNormalization>(), Ok(ScoreNormalization::MinMax));
+        assert!("zscore".parse::<ScoreNormalization>().is_err());
+    }
+
     #[test]
     fn test_distance_to_qdrant() {
         assert_eq!(
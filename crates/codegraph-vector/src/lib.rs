@@ -5,7 +5,10 @@
 //!
 //! ## Features
 //!
-//! - Separate collections per design system (Material, Tailwind, Chakra, Bootstrap, Custom)
+//! - Separate collections per design system (Material, Tailwind, Chakra, Bootstrap,
+//!   Ant Design, shadcn/ui, Custom), created on demand for any other design system
+//! - Optional single partitioned collection instead of one collection per design system
+//! - Concurrent multi-collection search with per-collection quotas and score normalization
 //! - OpenAI-compatible embeddings (1536 dimensions, Cosine distance)
 //! - Payload indexing for fast filtered searches
 //! - Redis caching with 1-hour TTL
@@ -17,10 +20,12 @@ pub mod error;
 pub mod models;
 pub mod cache;
 pub mod repository;
+pub mod search;
 
-pub use collections::{Collection, COLLECTIONS};
-pub use config::{QdrantConfig, VectorConfig};
+pub use collections::{collection_name, Collection, COLLECTIONS};
+pub use config::{CollectionLayout, QdrantConfig, ScoreNormalization, SearchConfig, VectorConfig};
 pub use error::VectorError;
 pub use models::{EmbeddingPoint, SearchFilter, SearchResult};
-pub use repository::QdrantRepository;
+pub use repository::{QdrantMigration, QdrantRepository, MIGRATIONS_COLLECTION, QDRANT_MIGRATIONS};
+pub use codegraph_core::traits::VectorStore;
 pub use cache::EmbeddingCache;
@@ -1,23 +1,89 @@
 //! QdrantRepository - Vector storage operations
 //!
 //! Implements CRUD operations for embedding points in Qdrant collections.
+//!
+//! Collections are logical: with [`CollectionLayout::PerDesignSystem`] each one
+//! is a Qdrant collection, with [`CollectionLayout::Partitioned`] they are
+//! partitions of [`PARTITIONED_COLLECTION`] keyed by the `collection` payload
+//! field. Collections beyond the predefined ones are created on first upsert.
+//!
+//! Every point carries two named vectors: the dense embedding
+//! ([`DENSE_VECTOR`]) and a sparse lexical vector ([`SPARSE_VECTOR`]) scored
+//! with Qdrant's IDF modifier. Collections created before sparse vectors
+//! existed have a single unnamed vector; migration 2 rebuilds them in place.
 
 use qdrant_client::qdrant::{
     Condition, CreateCollectionBuilder, Filter, HnswConfigDiff,
     PointId, PointStruct, Range, SearchPointsBuilder,
     UpsertPointsBuilder, VectorParamsBuilder,
     CreateFieldIndexCollectionBuilder, FieldType,
-    DeletePointsBuilder,
+    DeletePointsBuilder, FacetCountsBuilder, CountPointsBuilder,
+    Fusion, Modifier, PrefetchQueryBuilder, Query, QueryPointsBuilder, RetrievedPoint, ScoredPoint,
+    ScrollPointsBuilder, SparseVectorParamsBuilder, SparseVectorsConfigBuilder, Vector, VectorInput, VectorsConfigBuilder,
+    vector_output, vectors_config,
 };
+use futures::future::join_all;
+use async_trait::async_trait;
+use codegraph_core::migrations::{self, AppliedMigration, Migration};
+use codegraph_core::traits::VectorStore;
 use qdrant_client::Qdrant;
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
-use crate::collections::COLLECTIONS;
-use crate::config::{QdrantConfig, VectorConfig};
+use crate::collections::{COLLECTIONS, COLLECTION_PREFIX};
+use crate::config::{
+    CollectionLayout, QdrantConfig, SearchConfig, VectorConfig, DENSE_VECTOR, PARTITIONED_COLLECTION, SPARSE_VECTOR,
+};
 use crate::error::{Result, VectorError};
-use crate::models::{EmbeddingPoint, PointPayload, SearchFilter, SearchResult};
+use crate::models::{EmbeddingPoint, PointPayload, SearchFilter, SearchResult, SparseVector};
+use codegraph_core::sparse;
+use crate::search;
+
+pub use codegraph_core::entities::CollectionInfo;
+
+/// How long the list of collections is trusted before asking Qdrant again
+const COLLECTION_REFRESH: Duration = Duration::from_secs(60);
+
+/// Upper bound on partitions listed in the partitioned layout
+const MAX_PARTITIONS: u64 = 1000;
+
+/// Points read or written per request when rebuilding a collection
+const MIGRATION_BATCH: u32 = 256;
+
+/// Prefix of the copy a collection is rebuilt through by migration 2
+///
+/// It lacks [`COLLECTION_PREFIX`], so a copy left behind by an interrupted
+/// migration is never taken for a logical collection.
+const MIGRATION_COPY_PREFIX: &str = "codegraph_migrating_";
+
+/// Collection recording applied migrations, one marker point per version
+pub const MIGRATIONS_COLLECTION: &str = "codegraph_migrations";
+
+/// What a Qdrant migration does
+#[derive(Debug, Clone, Copy)]
+pub enum QdrantMigration {
+    /// Create the predefined collections with their payload indexes
+    PredefinedCollections,
+    /// Rebuild collections holding a single unnamed vector with named dense and sparse vectors
+    NamedVectors,
+}
+
+/// Ordered Qdrant migrations
+pub const QDRANT_MIGRATIONS: &[Migration<QdrantMigration>] = &[
+    Migration {
+        version: 1,
+        description: "predefined collections and payload indexes",
+        steps: QdrantMigration::PredefinedCollections,
+    },
+    Migration {
+        version: 2,
+        description: "named dense and sparse vectors",
+        steps: QdrantMigration::NamedVectors,
+    },
+];
 
 /// Repository for Qdrant vector operations
 #[derive(Clone)]
@@ -26,6 +92,19 @@ pub struct QdrantRepository {
     client: Arc<Qdrant>,
     /// Vector configuration
     vector_config: VectorConfig,
+    /// Collection layout
+    layout: CollectionLayout,
+    /// Multi-collection search settings
+    search_config: SearchConfig,
+    /// Logical collections known to exist
+    known_collections: Arc<RwLock<KnownCollections>>,
+}
+
+/// Collections seen so far and when they were last listed from Qdrant
+#[derive(Default)]
+struct KnownCollections {
+    names: BTreeSet<String>,
+    listed_at: Option<Instant>,
 }
 
 impl QdrantRepository {
@@ -39,20 +118,340 @@ impl QdrantRepository {
         Ok(Self {
             client: Arc::new(client),
             vector_config: config.vector_config,
+            layout: config.layout,
+            search_config: config.search,
+            known_collections: Arc::default(),
         })
     }
 
-    /// Initialize all collections
+    /// Initialize all predefined collections
     #[instrument(skip(self))]
     pub async fn init_collections(&self) -> Result<()> {
         for collection_name in COLLECTIONS {
-            self.create_collection(collection_name).await?;
+            self.ensure_collection(collection_name).await?;
         }
 
-        info!("Initialized {} collections", COLLECTIONS.len());
+        info!("Initialized {} collections ({:?} layout)", COLLECTIONS.len(), self.layout);
         Ok(())
     }
 
+    /// Run pending migrations, returning those applied
+    ///
+    /// Applied versions are recorded as points of [`MIGRATIONS_COLLECTION`].
+    /// Fails if the server was migrated by a newer build, or if an existing
+    /// collection's dense vectors have another size than configured.
+    #[instrument(skip(self))]
+    pub async fn migrate(&self) -> Result<Vec<AppliedMigration>> {
+        if !self.client.collection_exists(MIGRATIONS_COLLECTION).await? {
+            self.client
+                .create_collection(
+                    CreateCollectionBuilder::new(MIGRATIONS_COLLECTION)
+                        .vectors_config(VectorParamsBuilder::new(1, qdrant_client::qdrant::Distance::Dot)),
+                )
+                .await?;
+        }
+        let recorded = self
+            .client
+            .scroll(ScrollPointsBuilder::new(MIGRATIONS_COLLECTION).limit(1000).with_payload(false))
+            .await?;
+        let applied_versions: Vec<u32> = recorded
+            .result
+            .into_iter()
+            .filter_map(|point| match point.id?.point_id_options? {
+                qdrant_client::qdrant::point_id::PointIdOptions::Num(version) => Some(version as u32),
+                _ => None,
+            })
+            .collect();
+
+        let pending = migrations::pending("Qdrant", QDRANT_MIGRATIONS, &applied_versions)
+            .map_err(|e| VectorError::Config(e.to_string()))?;
+        let mut applied = Vec::new();
+        for migration in pending {
+            match migration.steps {
+                QdrantMigration::PredefinedCollections => self.init_collections().await?,
+                QdrantMigration::NamedVectors => self.name_vectors().await?,
+            }
+            let marker = PointStruct::new(
+                migration.version as u64,
+                vec![0.0],
+                HashMap::from([
+                    ("version".to_string(), serde_json::json!(migration.version)),
+                    ("description".to_string(), serde_json::json!(migration.description)),
+                    ("applied_at".to_string(), serde_json::json!(chrono::Utc::now().to_rfc3339())),
+                ]),
+            );
+            self.client
+                .upsert_points(UpsertPointsBuilder::new(MIGRATIONS_COLLECTION, vec![marker]).wait(true))
+                .await?;
+            info!(version = migration.version, "Applied Qdrant migration: {}", migration.description);
+            applied.push(AppliedMigration { version: migration.version, description: migration.description.to_string() });
+        }
+
+        self.check_vector_size().await?;
+        Ok(applied)
+    }
+
+    /// Rebuild every collection with a single unnamed vector in the named layout
+    ///
+    /// Each collection is copied to `codegraph_migrating_<name>` in the named
+    /// layout, with its dense vector under [`DENSE_VECTOR`] and a sparse
+    /// vector encoded from the payload; IDs and payloads are kept as stored.
+    /// Only once the copy holds every point is the original deleted,
+    /// recreated and filled from the copy, which is then dropped. A rerun
+    /// after an interruption resumes from whichever of the two is complete.
+    /// Collections whose vectors have another size than configured, or with
+    /// points that cannot be converted, are refused before anything is deleted.
+    async fn name_vectors(&self) -> Result<()> {
+        let response = self.client.list_collections().await?;
+        let existing: BTreeSet<String> = response.collections.into_iter().map(|c| c.name).collect();
+        let names: BTreeSet<&str> = existing
+            .iter()
+            .map(|name| name.strip_prefix(MIGRATION_COPY_PREFIX).unwrap_or(name))
+            .filter(|name| name.starts_with(COLLECTION_PREFIX))
+            .collect();
+
+        for name in names {
+            let copy = format!("{}{}", MIGRATION_COPY_PREFIX, name);
+            if existing.contains(name) {
+                let Some(size) = self.unnamed_vector_size(name).await? else {
+                    if existing.contains(&copy) {
+                        // The original was being refilled from the copy
+                        self.restore_from_copy(name, &copy).await?;
+                    }
+                    continue;
+                };
+                if size != self.vector_config.size {
+                    return Err(VectorError::Config(format!(
+                        "collection {} stores {}-dimensional vectors but {} are configured; \
+                         set QDRANT_VECTOR_SIZE={} so it can be migrated",
+                        name, size, self.vector_config.size, size
+                    )));
+                }
+
+                // A copy next to an unmigrated original is left from an interrupted copy
+                if existing.contains(&copy) {
+                    self.client.delete_collection(copy.as_str()).await?;
+                }
+                self.create_collection(&copy).await?;
+                if let Err(e) = self.copy_named(name, &copy).await {
+                    self.client.delete_collection(copy.as_str()).await?;
+                    return Err(e);
+                }
+                self.client.delete_collection(name).await?;
+            }
+            self.restore_from_copy(name, &copy).await?;
+        }
+        Ok(())
+    }
+
+    /// Size of a collection's vectors if it stores a single unnamed one
+    async fn unnamed_vector_size(&self, name: &str) -> Result<Option<u64>> {
+        let info = self.client.collection_info(name).await?;
+        let vectors = info
+            .result
+            .and_then(|r| r.config)
+            .and_then(|c| c.params)
+            .and_then(|p| p.vectors_config)
+            .and_then(|v| v.config);
+        Ok(match vectors {
+            Some(vectors_config::Config::Params(params)) => Some(params.size),
+            _ => None,
+        })
+    }
+
+    /// Recreate `name` in the named layout from its complete copy, then drop the copy
+    async fn restore_from_copy(&self, name: &str, copy: &str) -> Result<()> {
+        if self.client.collection_exists(name).await? {
+            self.client.delete_collection(name).await?;
+        }
+        self.create_collection(name).await?;
+        let count = self.copy_named(copy, name).await?;
+        self.client.delete_collection(copy).await?;
+        info!(collection = %name, count = count, "Rebuilt collection with named vectors");
+        Ok(())
+    }
+
+    /// Copy every point of `from` into `to` in the named layout, returning the count
+    ///
+    /// Fails, listing their IDs, if any point has no dense vector or ID, and
+    /// if `to` does not end up with as many points as `from`.
+    async fn copy_named(&self, from: &str, to: &str) -> Result<u64> {
+        let mut unconvertible = Vec::new();
+        let mut offset = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(from)
+                .limit(MIGRATION_BATCH)
+                .with_payload(true)
+                .with_vectors(true);
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+            let response = self.client.scroll(request).await?;
+            let mut batch = Vec::with_capacity(response.result.len());
+            for point in response.result {
+                match named_point(point) {
+                    Ok(point) => batch.push(point),
+                    Err(id) => unconvertible.push(id),
+                }
+            }
+            if !batch.is_empty() {
+                self.client
+                    .upsert_points(UpsertPointsBuilder::new(to, batch).wait(true))
+                    .await?;
+            }
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+        if !unconvertible.is_empty() {
+            return Err(VectorError::Config(format!(
+                "collection {} has {} points without a dense vector or ID, which must be deleted before it can be migrated: {}",
+                from,
+                unconvertible.len(),
+                unconvertible.join(", ")
+            )));
+        }
+
+        let expected = self.exact_count(from).await?;
+        let copied = self.exact_count(to).await?;
+        if copied != expected {
+            return Err(VectorError::Config(format!(
+                "copying collection {} to {} wrote {} of its {} points",
+                from, to, copied, expected
+            )));
+        }
+        Ok(copied)
+    }
+
+    async fn exact_count(&self, name: &str) -> Result<u64> {
+        let response = self.client.count(CountPointsBuilder::new(name).exact(true)).await?;
+        Ok(response.result.map(|r| r.count).unwrap_or_default())
+    }
+
+    /// Fail if an existing collection stores dense vectors of another size
+    async fn check_vector_size(&self) -> Result<()> {
+        let physical: BTreeSet<String> = match self.layout {
+            CollectionLayout::PerDesignSystem => self.list_remote_collections().await?.into_iter().collect(),
+            CollectionLayout::Partitioned => BTreeSet::from([PARTITIONED_COLLECTION.to_string()]),
+        };
+        for name in physical {
+            let info = self.client.collection_info(&name).await?;
+            let vectors = info
+                .result
+                .and_then(|r| r.config)
+                .and_then(|c| c.params)
+                .and_then(|p| p.vectors_config)
+                .and_then(|v| v.config);
+            let size = match vectors {
+                Some(vectors_config::Config::ParamsMap(params)) => params.map.get(DENSE_VECTOR).map(|p| p.size),
+                _ => None,
+            };
+            match size {
+                Some(size) if size == self.vector_config.size => {}
+                Some(size) => {
+                    return Err(VectorError::Config(format!(
+                        "collection {} stores {}-dimensional vectors but {} are configured; \
+                         set QDRANT_VECTOR_SIZE={} or recreate the collection and re-embed",
+                        name, size, self.vector_config.size, size
+                    )))
+                }
+                None => {
+                    return Err(VectorError::Config(format!(
+                        "collection {} has no named '{}' vector and must be recreated",
+                        name, DENSE_VECTOR
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a logical collection unless it is already known
+    pub async fn ensure_collection(&self, collection: &str) -> Result<()> {
+        if self.is_known(collection) {
+            return Ok(());
+        }
+        self.create_collection(self.physical(collection)).await?;
+        self.known_collections
+            .write()
+            .expect("collection registry lock poisoned")
+            .names
+            .insert(collection.to_string());
+        Ok(())
+    }
+
+    /// Every logical collection, including ones created by other processes
+    pub async fn collections(&self) -> Vec<String> {
+        let stale = self
+            .known_collections
+            .read()
+            .expect("collection registry lock poisoned")
+            .listed_at
+            .is_none_or(|at| at.elapsed() > COLLECTION_REFRESH);
+
+        if stale {
+            match self.list_remote_collections().await {
+                Ok(names) => {
+                    let mut known = self.known_collections.write().expect("collection registry lock poisoned");
+                    known.names.extend(names);
+                    known.listed_at = Some(Instant::now());
+                }
+                Err(e) => warn!(error = %e, "Failed to list collections, using known ones"),
+            }
+        }
+
+        let known = self.known_collections.read().expect("collection registry lock poisoned");
+        let mut names: BTreeSet<String> = COLLECTIONS.iter().map(|c| c.to_string()).collect();
+        names.extend(known.names.iter().cloned());
+        names.into_iter().collect()
+    }
+
+    /// Logical collections present in Qdrant
+    async fn list_remote_collections(&self) -> Result<Vec<String>> {
+        match self.layout {
+            CollectionLayout::PerDesignSystem => {
+                let response = self.client.list_collections().await?;
+                Ok(response
+                    .collections
+                    .into_iter()
+                    .map(|c| c.name)
+                    .filter(|name| name.starts_with(COLLECTION_PREFIX) && name != PARTITIONED_COLLECTION)
+                    .collect())
+            }
+            CollectionLayout::Partitioned => {
+                let response = self
+                    .client
+                    .facet(FacetCountsBuilder::new(PARTITIONED_COLLECTION, "collection").limit(MAX_PARTITIONS))
+                    .await?;
+                Ok(response
+                    .hits
+                    .into_iter()
+                    .filter_map(|hit| match hit.value?.variant? {
+                        qdrant_client::qdrant::facet_value::Variant::StringValue(name) => Some(name),
+                        _ => None,
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    fn is_known(&self, collection: &str) -> bool {
+        self.known_collections
+            .read()
+            .expect("collection registry lock poisoned")
+            .names
+            .contains(collection)
+    }
+
+    /// Qdrant collection storing a logical collection
+    fn physical<'a>(&self, collection: &'a str) -> &'a str {
+        match self.layout {
+            CollectionLayout::PerDesignSystem => collection,
+            CollectionLayout::Partitioned => PARTITIONED_COLLECTION,
+        }
+    }
+
     /// Create a collection with proper configuration
     #[instrument(skip(self))]
     pub async fn create_collection(&self, name: &str) -> Result<()> {
@@ -64,18 +463,24 @@ impl QdrantRepository {
             return Ok(());
         }
 
-        // Create collection with vector config
-        let mut vectors_config = VectorParamsBuilder::new(
+        // Create collection with named dense and sparse vectors
+        let mut dense_params = VectorParamsBuilder::new(
             self.vector_config.size,
             self.vector_config.distance.to_qdrant(),
         );
 
         if self.vector_config.on_disk {
-            vectors_config = vectors_config.on_disk(true);
+            dense_params = dense_params.on_disk(true);
         }
 
+        let mut vectors_config = VectorsConfigBuilder::default();
+        vectors_config.add_named_vector_params(DENSE_VECTOR, dense_params);
+        let mut sparse_config = SparseVectorsConfigBuilder::default();
+        sparse_config.add_named_vector_params(SPARSE_VECTOR, SparseVectorParamsBuilder::default().modifier(Modifier::Idf));
+
         let mut create_builder = CreateCollectionBuilder::new(name)
-            .vectors_config(vectors_config);
+            .vectors_config(vectors_config)
+            .sparse_vectors_config(sparse_config);
 
         // Add HNSW config if specified
         if let Some(hnsw) = &self.vector_config.hnsw_config {
@@ -130,6 +535,13 @@ impl QdrantRepository {
             )
             .await?;
 
+        // Index on the logical collection (keyword), used by the partitioned layout
+        self.client
+            .create_field_index(
+                CreateFieldIndexCollectionBuilder::new(collection, "collection", FieldType::Keyword)
+            )
+            .await?;
+
         debug!(collection = collection, "Created payload indexes");
 
         Ok(())
@@ -146,10 +558,11 @@ impl QdrantRepository {
             });
         }
 
-        let point_struct = self.embedding_to_point_struct(&point)?;
+        self.ensure_collection(collection).await?;
+        let point_struct = self.embedding_to_point_struct(collection, &point)?;
 
         self.client
-            .upsert_points(UpsertPointsBuilder::new(collection, vec![point_struct]))
+            .upsert_points(UpsertPointsBuilder::new(self.physical(collection), vec![point_struct]))
             .await?;
 
         debug!(collection = collection, id = %point.id, "Upserted point");
@@ -175,14 +588,16 @@ impl QdrantRepository {
             }
         }
 
+        self.ensure_collection(collection).await?;
+
         let count = points.len();
         let point_structs: Vec<_> = points
             .into_iter()
-            .filter_map(|p| self.embedding_to_point_struct(&p).ok())
+            .filter_map(|p| self.embedding_to_point_struct(collection, &p).ok())
             .collect();
 
         self.client
-            .upsert_points(UpsertPointsBuilder::new(collection, point_structs))
+            .upsert_points(UpsertPointsBuilder::new(self.physical(collection), point_structs))
             .await?;
 
         info!(collection = collection, count = count, "Batch upserted points");
@@ -208,15 +623,13 @@ impl QdrantRepository {
             });
         }
 
-        let mut search_builder = SearchPointsBuilder::new(collection, vector, limit)
+        let mut search_builder = SearchPointsBuilder::new(self.physical(collection), vector, limit)
+            .vector_name(DENSE_VECTOR)
             .with_payload(true);
 
         // Apply filters
-        if let Some(f) = filter {
-            if f.is_active() {
-                let qdrant_filter = self.build_filter(&f);
-                search_builder = search_builder.filter(qdrant_filter);
-            }
+        if let Some(f) = self.filter(collection, filter.as_ref()) {
+            search_builder = search_builder.filter(f);
         }
 
         let response = self.client.search_points(search_builder).await?;
@@ -235,6 +648,10 @@ impl QdrantRepository {
     }
 
     /// Search across all collections
+    ///
+    /// Collections are queried concurrently. One that fails or misses the
+    /// search budget contributes no hits; the rest are merged according to the
+    /// [`SearchConfig`].
     #[instrument(skip(self, vector))]
     pub async fn search_all(
         &self,
@@ -242,24 +659,168 @@ impl QdrantRepository {
         limit: u64,
         filter: Option<SearchFilter>,
     ) -> Result<Vec<SearchResult>> {
-        let mut all_results = Vec::new();
+        self.search_every_collection(limit, |collection| {
+            let vector = vector.clone();
+            let filter = filter.clone();
+            async move { self.search(&collection, vector, limit, filter).await }
+        })
+        .await
+    }
 
-        for collection in COLLECTIONS {
-            match self.search(collection, vector.clone(), limit, filter.clone()).await {
-                Ok(results) => all_results.extend(results),
-                Err(e) => {
-                    warn!(collection = collection, error = %e, "Search failed in collection");
-                }
+    /// Keyword search of one collection through its sparse vectors
+    #[instrument(skip(self))]
+    pub async fn search_sparse(
+        &self,
+        collection: &str,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> Result<Vec<SearchResult>> {
+        let query = sparse::encode_query(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = QueryPointsBuilder::new(self.physical(collection))
+            .query(Query::new_nearest(VectorInput::new_sparse(query.indices, query.values)))
+            .using(SPARSE_VECTOR)
+            .limit(limit)
+            .with_payload(true);
+        let request = match self.filter(collection, filter.as_ref()) {
+            Some(f) => request.filter(f),
+            None => request,
+        };
+
+        self.query(collection, request).await
+    }
+
+    /// Dense and sparse search of one collection in a single query, fused by reciprocal rank
+    #[instrument(skip(self, vector))]
+    pub async fn search_hybrid(
+        &self,
+        collection: &str,
+        vector: Vec<f32>,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> Result<Vec<SearchResult>> {
+        if vector.len() != self.vector_config.size as usize {
+            return Err(VectorError::InvalidDimension {
+                expected: self.vector_config.size as usize,
+                actual: vector.len(),
+            });
+        }
+
+        let filter = self.filter(collection, filter.as_ref());
+        let mut dense = PrefetchQueryBuilder::default()
+            .query(Query::new_nearest(vector))
+            .using(DENSE_VECTOR)
+            .limit(limit);
+        let mut prefetch = Vec::new();
+        let sparse = sparse::encode_query(query);
+        if !sparse.is_empty() {
+            let mut lexical = PrefetchQueryBuilder::default()
+                .query(Query::new_nearest(VectorInput::new_sparse(sparse.indices, sparse.values)))
+                .using(SPARSE_VECTOR)
+                .limit(limit);
+            if let Some(f) = &filter {
+                lexical = lexical.filter(f.clone());
             }
+            prefetch.push(lexical.build());
+        }
+        if let Some(f) = &filter {
+            dense = dense.filter(f.clone());
         }
+        prefetch.push(dense.build());
+
+        let request = QueryPointsBuilder::new(self.physical(collection))
+            .prefetch(prefetch)
+            .query(Query::new_fusion(Fusion::Rrf))
+            .limit(limit)
+            .with_payload(true);
+
+        self.query(collection, request).await
+    }
 
-        // Sort by score descending
-        all_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    /// Keyword search across all collections
+    #[instrument(skip(self))]
+    pub async fn search_sparse_all(&self, query: &str, limit: u64, filter: Option<SearchFilter>) -> Result<Vec<SearchResult>> {
+        self.search_every_collection(limit, |collection| {
+            let filter = filter.clone();
+            async move { self.search_sparse(&collection, query, limit, filter).await }
+        })
+        .await
+    }
+
+    /// Hybrid search across all collections
+    #[instrument(skip(self, vector))]
+    pub async fn search_hybrid_all(
+        &self,
+        vector: Vec<f32>,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> Result<Vec<SearchResult>> {
+        self.search_every_collection(limit, |collection| {
+            let vector = vector.clone();
+            let filter = filter.clone();
+            async move { self.search_hybrid(&collection, vector, query, limit, filter).await }
+        })
+        .await
+    }
 
-        // Limit total results
-        all_results.truncate(limit as usize);
+    /// Run `search` on every collection concurrently within the search budget and merge the hits
+    async fn search_every_collection<F, Fut>(&self, limit: u64, search: F) -> Result<Vec<SearchResult>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<SearchResult>>>,
+    {
+        let collections = self.collections().await;
+        let budget = Duration::from_millis(self.search_config.budget_ms);
+
+        let searches = collections.into_iter().map(|collection| {
+            let search = search(collection.clone());
+            async move {
+                let started = Instant::now();
+                let outcome = tokio::time::timeout(budget, search).await;
+                metrics::histogram!("vector_collection_search_latency_ms", "collection" => collection.clone())
+                    .record(started.elapsed().as_secs_f64() * 1000.0);
+
+                let hits = match outcome {
+                    Ok(Ok(results)) => results,
+                    Ok(Err(e)) => {
+                        warn!(collection = %collection, error = %e, "Search failed in collection");
+                        Vec::new()
+                    }
+                    Err(_) => {
+                        warn!(collection = %collection, budget_ms = budget.as_millis() as u64, "Search timed out in collection");
+                        metrics::counter!("vector_collection_search_timeouts", "collection" => collection.clone())
+                            .increment(1);
+                        Vec::new()
+                    }
+                };
+                (collection, hits)
+            }
+        });
 
-        Ok(all_results)
+        let per_collection = join_all(searches).await;
+        Ok(search::merge(per_collection, limit as usize, &self.search_config))
+    }
+
+    /// Run a query request and convert its hits
+    async fn query(&self, collection: &str, request: QueryPointsBuilder) -> Result<Vec<SearchResult>> {
+        let response = self.client.query(request).await?;
+        let results: Vec<SearchResult> = response
+            .result
+            .into_iter()
+            .filter_map(|p| self.scored_point_to_result(p))
+            .collect();
+
+        debug!(collection = collection, results = results.len(), "Query completed");
+        metrics::counter!("vector_searches").increment(1);
+        metrics::histogram!("vector_search_results").record(results.len() as f64);
+
+        Ok(results)
     }
 
     /// Delete a point by ID
@@ -269,7 +830,7 @@ impl QdrantRepository {
 
         self.client
             .delete_points(
-                DeletePointsBuilder::new(collection)
+                DeletePointsBuilder::new(self.physical(collection))
                     .points(vec![point_id])
             )
             .await?;
@@ -292,7 +853,7 @@ impl QdrantRepository {
 
         self.client
             .delete_points(
-                DeletePointsBuilder::new(collection)
+                DeletePointsBuilder::new(self.physical(collection))
                     .points(point_ids)
             )
             .await?;
@@ -303,8 +864,54 @@ impl QdrantRepository {
         Ok(count)
     }
 
+    /// Page of a collection's points in ID order, vectors included
+    #[instrument(skip(self))]
+    pub async fn scroll_points(
+        &self,
+        collection: &str,
+        offset: Option<Uuid>,
+        limit: usize,
+    ) -> Result<(Vec<EmbeddingPoint>, Option<Uuid>)> {
+        let mut request = ScrollPointsBuilder::new(self.physical(collection))
+            .limit(limit as u32)
+            .with_payload(true)
+            .with_vectors(true);
+        if let Some(f) = self.filter(collection, None) {
+            request = request.filter(f);
+        }
+        if let Some(offset) = offset {
+            request = request.offset(PointId::from(offset.to_string()));
+        }
+
+        let response = self.client.scroll(request).await?;
+        let points = response
+            .result
+            .into_iter()
+            .filter_map(|p| self.retrieved_point_to_embedding(p))
+            .collect();
+        Ok((points, response.next_page_offset.and_then(point_uuid)))
+    }
+
     /// Get collection info
     pub async fn collection_info(&self, name: &str) -> Result<CollectionInfo> {
+        if self.layout == CollectionLayout::Partitioned {
+            let response = self
+                .client
+                .count(
+                    CountPointsBuilder::new(PARTITIONED_COLLECTION)
+                        .filter(Filter::must(self.filter_conditions(name, None)))
+                        .exact(true),
+                )
+                .await?;
+            let count = response.result.map(|r| r.count).unwrap_or(0);
+            return Ok(CollectionInfo {
+                name: name.to_string(),
+                points_count: count,
+                vectors_count: count,
+                indexed_vectors_count: count,
+            });
+        }
+
         let info = self.client.collection_info(name).await?;
         let result = info.result.ok_or_else(|| VectorError::CollectionNotFound(name.to_string()))?;
 
@@ -321,8 +928,8 @@ impl QdrantRepository {
     pub async fn all_collections_info(&self) -> Result<Vec<CollectionInfo>> {
         let mut infos = Vec::new();
 
-        for name in COLLECTIONS {
-            match self.collection_info(name).await {
+        for name in self.collections().await {
+            match self.collection_info(&name).await {
                 Ok(info) => infos.push(info),
                 Err(e) => {
                     warn!(collection = name, error = %e, "Failed to get collection info");
@@ -334,10 +941,20 @@ impl QdrantRepository {
     }
 
     /// Convert EmbeddingPoint to Qdrant PointStruct
-    fn embedding_to_point_struct(&self, point: &EmbeddingPoint) -> Result<PointStruct> {
+    fn embedding_to_point_struct(&self, collection: &str, point: &EmbeddingPoint) -> Result<PointStruct> {
         let id = PointId::from(point.id.to_string());
 
+        let sparse: SparseVector = point
+            .sparse
+            .clone()
+            .unwrap_or_else(|| sparse::encode_document(&point.payload, None));
+        let vectors = std::collections::HashMap::from([
+            (DENSE_VECTOR.to_string(), Vector::new_dense(point.vector.clone())),
+            (SPARSE_VECTOR.to_string(), Vector::new_sparse(sparse.indices, sparse.values)),
+        ]);
+
         let mut payload = std::collections::HashMap::new();
+        payload.insert("collection".to_string(), serde_json::json!(collection));
         payload.insert("name".to_string(), serde_json::json!(point.payload.name));
         payload.insert("category".to_string(), serde_json::json!(point.payload.category));
         payload.insert("element_type".to_string(), serde_json::json!(point.payload.element_type));
@@ -346,56 +963,58 @@ impl QdrantRepository {
         payload.insert("css_classes".to_string(), serde_json::json!(point.payload.css_classes));
         payload.insert("tags".to_string(), serde_json::json!(point.payload.tags));
 
-        Ok(PointStruct::new(id, point.vector.clone(), payload))
+        Ok(PointStruct::new(id, vectors, payload))
     }
 
     /// Convert Qdrant ScoredPoint to SearchResult
-    fn scored_point_to_result(&self, point: qdrant_client::qdrant::ScoredPoint) -> Option<SearchResult> {
-        let id_str = match point.id? {
-            PointId { point_id_options: Some(qdrant_client::qdrant::point_id::PointIdOptions::Uuid(s)) } => s,
-            _ => return None,
-        };
-
-        let id = Uuid::parse_str(&id_str).ok()?;
-        let payload = point.payload;
-
-        let name = payload.get("name")?.as_str()?.to_string();
-        let category = payload.get("category")?.as_str()?.to_string();
-        let element_type = payload.get("element_type")?.as_str()?.to_string();
-        let design_system = payload.get("design_system")?.as_str()?.to_string();
-        let confidence = payload.get("confidence")?.as_double()? as f32;
-
-        let css_classes: Vec<String> = payload
-            .get("css_classes")
-            .and_then(|v| v.as_list())
-            .map(|l| l.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-            .unwrap_or_default();
-
-        let tags: Vec<String> = payload
-            .get("tags")
-            .and_then(|v| v.as_list())
-            .map(|l| l.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-            .unwrap_or_default();
-
+    fn scored_point_to_result(&self, point: ScoredPoint) -> Option<SearchResult> {
         Some(SearchResult {
-            id,
+            id: point_uuid(point.id?)?,
             score: point.score,
-            payload: PointPayload {
-                name,
-                category,
-                element_type,
-                design_system,
-                confidence,
-                css_classes,
-                tags,
-            },
+            payload: payload_from_qdrant(&point.payload)?,
         })
     }
 
-    /// Build Qdrant filter from SearchFilter
-    fn build_filter(&self, filter: &SearchFilter) -> Filter {
+    /// Convert a scrolled Qdrant point back to the EmbeddingPoint it was stored from
+    fn retrieved_point_to_embedding(&self, point: RetrievedPoint) -> Option<EmbeddingPoint> {
+        let vectors = point.vectors?;
+        let vector = match vectors.get_vector_by_name(DENSE_VECTOR)? {
+            vector_output::Vector::Dense(dense) => dense.data,
+            _ => return None,
+        };
+        let sparse = match vectors.get_vector_by_name(SPARSE_VECTOR) {
+            Some(vector_output::Vector::Sparse(sparse)) => Some(SparseVector {
+                indices: sparse.indices,
+                values: sparse.values,
+            }),
+            _ => None,
+        };
+        Some(EmbeddingPoint {
+            id: point_uuid(point.id?)?,
+            vector,
+            payload: payload_from_qdrant(&point.payload)?,
+            sparse,
+        })
+    }
+
+    /// Qdrant filter for a search in `collection`, if anything needs filtering
+    fn filter(&self, collection: &str, filter: Option<&SearchFilter>) -> Option<Filter> {
+        let conditions = self.filter_conditions(collection, filter);
+        (!conditions.is_empty()).then(|| Filter::must(conditions))
+    }
+
+    /// Build Qdrant filter conditions for a search in `collection`
+    fn filter_conditions(&self, collection: &str, filter: Option<&SearchFilter>) -> Vec<Condition> {
         let mut conditions = Vec::new();
 
+        if self.layout == CollectionLayout::Partitioned {
+            conditions.push(Condition::matches("collection", collection.to_string()));
+        }
+
+        let Some(filter) = filter.filter(|f| f.is_active()) else {
+            return conditions;
+        };
+
         if let Some(ref category) = filter.category {
             conditions.push(Condition::matches("category", category.clone()));
         }
@@ -424,21 +1043,132 @@ impl QdrantRepository {
             }
         }
 
-        Filter::must(conditions)
+        conditions
     }
 }
 
-/// Collection statistics
-#[derive(Debug, Clone)]
-pub struct CollectionInfo {
-    /// Collection name
-    pub name: String,
-    /// Number of points
-    pub points_count: u64,
-    /// Number of vectors
-    pub vectors_count: u64,
-    /// Number of indexed vectors
-    pub indexed_vectors_count: u64,
+/// A stored point in the named dense and sparse layout, or the ID of one without a dense vector
+///
+/// The dense vector is read from either layout; the sparse one is encoded from the payload.
+fn named_point(point: RetrievedPoint) -> std::result::Result<PointStruct, String> {
+    let Some(id) = point.id else {
+        return Err("(no ID)".to_string());
+    };
+    let label = match &id.point_id_options {
+        Some(qdrant_client::qdrant::point_id::PointIdOptions::Uuid(id)) => id.clone(),
+        Some(qdrant_client::qdrant::point_id::PointIdOptions::Num(id)) => id.to_string(),
+        None => return Err("(no ID)".to_string()),
+    };
+    let dense = point
+        .vectors
+        .as_ref()
+        .and_then(|vectors| vectors.get_vector().or_else(|| vectors.get_vector_by_name(DENSE_VECTOR)));
+    let Some(vector_output::Vector::Dense(dense)) = dense else {
+        return Err(label);
+    };
+    let dense = dense.data;
+    let sparse = payload_from_qdrant(&point.payload)
+        .map(|payload| sparse::encode_document(&payload, None))
+        .unwrap_or_default();
+    let vectors = HashMap::from([
+        (DENSE_VECTOR.to_string(), Vector::new_dense(dense)),
+        (SPARSE_VECTOR.to_string(), Vector::new_sparse(sparse.indices, sparse.values)),
+    ]);
+    Ok(PointStruct::new(id, vectors, point.payload))
+}
+
+/// UUID of a point ID (every point is stored under its element's UUID)
+fn point_uuid(id: PointId) -> Option<Uuid> {
+    match id.point_id_options? {
+        qdrant_client::qdrant::point_id::PointIdOptions::Uuid(s) => Uuid::parse_str(&s).ok(),
+        _ => None,
+    }
+}
+
+/// Payload of a stored point, `None` if a required field is missing
+fn payload_from_qdrant(payload: &HashMap<String, qdrant_client::qdrant::Value>) -> Option<PointPayload> {
+    let strings = |key: &str| -> Vec<String> {
+        payload
+            .get(key)
+            .and_then(|v| v.as_list())
+            .map(|l| l.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default()
+    };
+
+    Some(PointPayload {
+        name: payload.get("name")?.as_str()?.to_string(),
+        category: payload.get("category")?.as_str()?.to_string(),
+        element_type: payload.get("element_type")?.as_str()?.to_string(),
+        design_system: payload.get("design_system")?.as_str()?.to_string(),
+        confidence: payload.get("confidence")?.as_double()? as f32,
+        css_classes: strings("css_classes"),
+        tags: strings("tags"),
+    })
+}
+
+#[async_trait]
+impl VectorStore for QdrantRepository {
+    async fn init_collections(&self) -> anyhow::Result<()> {
+        QdrantRepository::migrate(self).await?;
+        Ok(())
+    }
+
+    async fn upsert_point(&self, collection: &str, point: EmbeddingPoint) -> anyhow::Result<()> {
+        Ok(QdrantRepository::upsert_point(self, collection, point).await?)
+    }
+
+    async fn upsert_batch(&self, collection: &str, points: Vec<EmbeddingPoint>) -> anyhow::Result<usize> {
+        Ok(QdrantRepository::upsert_batch(self, collection, points).await?)
+    }
+
+    async fn search(
+        &self,
+        collection: &str,
+        vector: Vec<f32>,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        Ok(QdrantRepository::search(self, collection, vector, limit, filter).await?)
+    }
+
+    async fn search_all(&self, vector: Vec<f32>, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>> {
+        Ok(QdrantRepository::search_all(self, vector, limit, filter).await?)
+    }
+
+    async fn search_sparse(&self, query: &str, limit: u64, filter: Option<SearchFilter>) -> anyhow::Result<Vec<SearchResult>> {
+        Ok(QdrantRepository::search_sparse_all(self, query, limit, filter).await?)
+    }
+
+    async fn search_hybrid(
+        &self,
+        vector: Vec<f32>,
+        query: &str,
+        limit: u64,
+        filter: Option<SearchFilter>,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        Ok(QdrantRepository::search_hybrid_all(self, vector, query, limit, filter).await?)
+    }
+
+    async fn delete_point(&self, collection: &str, id: Uuid) -> anyhow::Result<()> {
+        Ok(QdrantRepository::delete_point(self, collection, id).await?)
+    }
+
+    async fn delete_batch(&self, collection: &str, ids: Vec<Uuid>) -> anyhow::Result<usize> {
+        Ok(QdrantRepository::delete_batch(self, collection, ids).await?)
+    }
+
+    async fn all_collections_info(&self) -> anyhow::Result<Vec<CollectionInfo>> {
+        Ok(QdrantRepository::all_collections_info(self).await?)
+    }
+
+    async fn scroll_points(
+        &self,
+        collection: &str,
+        offset: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<EmbeddingPoint>, Option<Uuid>)> {
+        Ok(QdrantRepository::scroll_points(self, collection, offset, limit).await?)
+    }
 }
 
 #[cfg(test)]
@@ -461,4 +1191,62 @@ mod tests {
         assert!(filter.is_active());
         assert_eq!(filter.category, Some("button".to_string()));
     }
+
+    #[test]
+    fn test_named_point_keeps_id_and_payload() {
+        use qdrant_client::qdrant::{vectors_output::VectorsOptions, VectorOutput, VectorsOutput};
+
+        let id = Uuid::new_v4();
+        let payload: HashMap<String, qdrant_client::qdrant::Value> = HashMap::from([
+            ("name".to_string(), "Primary Button".into()),
+            ("category".to_string(), "button".into()),
+            ("element_type".to_string(), "component".into()),
+            ("design_system".to_string(), "tailwind".into()),
+            ("confidence".to_string(), 0.9.into()),
+        ]);
+        #[allow(deprecated)]
+        let vector = VectorOutput { data: vec![0.1, 0.2, 0.3], ..Default::default() };
+        let point = RetrievedPoint {
+            id: Some(PointId::from(id.to_string())),
+            payload: payload.clone(),
+            vectors: Some(VectorsOutput { vectors_options: Some(VectorsOptions::Vector(vector)) }),
+            ..Default::default()
+        };
+
+        let named = named_point(point).unwrap();
+        assert_eq!(named.id.and_then(point_uuid), Some(id));
+        assert_eq!(named.payload, payload);
+        let vectors = match named.vectors.and_then(|v| v.vectors_options) {
+            Some(qdrant_client::qdrant::vectors::VectorsOptions::Vectors(named)) => named.vectors,
+            other => panic!("expected named vectors, got {:?}", other),
+        };
+        assert!(vectors.contains_key(DENSE_VECTOR));
+        assert!(vectors.contains_key(SPARSE_VECTOR));
+    }
+
+    #[test]
+    fn test_named_point_reads_named_layout_and_reports_missing_vectors() {
+        use qdrant_client::qdrant::{vectors_output::VectorsOptions, NamedVectorsOutput, VectorOutput, VectorsOutput};
+
+        let id = Uuid::new_v4();
+        #[allow(deprecated)]
+        let dense = VectorOutput { data: vec![0.1, 0.2, 0.3], ..Default::default() };
+        let named = RetrievedPoint {
+            id: Some(PointId::from(id.to_string())),
+            vectors: Some(VectorsOutput {
+                vectors_options: Some(VectorsOptions::Vectors(NamedVectorsOutput {
+                    vectors: HashMap::from([(DENSE_VECTOR.to_string(), dense)]),
+                })),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(named_point(named).unwrap().id.and_then(point_uuid), Some(id));
+
+        let missing = RetrievedPoint {
+            id: Some(PointId::from(id.to_string())),
+            ..Default::default()
+        };
+        assert_eq!(named_point(missing).unwrap_err(), id.to_string());
+        assert_eq!(named_point(RetrievedPoint::default()).unwrap_err(), "(no ID)");
+    }
 }
@@ -0,0 +1,125 @@
+//! Merging of per-collection search results
+//!
+//! [`merge`] turns the hits of several collections into one ranking: scores
+//! are optionally normalized per collection, every collection keeps up to
+//! `min_per_collection` of its best hits, and the remaining slots go to the
+//! best remaining hits overall.
+
+use crate::config::{ScoreNormalization, SearchConfig};
+use crate::models::SearchResult;
+
+/// Merge per-collection hits into the best `limit` results, highest score first
+pub fn merge(per_collection: Vec<(String, Vec<SearchResult>)>, limit: usize, config: &SearchConfig) -> Vec<SearchResult> {
+    let mut reserved = Vec::new();
+    let mut rest = Vec::new();
+
+    for (_, mut hits) in per_collection {
+        normalize(&mut hits, config.normalization);
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let quota = config.min_per_collection.min(hits.len());
+        rest.extend(hits.split_off(quota));
+        reserved.extend(hits);
+    }
+
+    // Quotas can exceed the limit on their own; the best reserved hits win
+    reserved.sort_by(|a, b| b.score.total_cmp(&a.score));
+    reserved.truncate(limit);
+
+    rest.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let free = limit - reserved.len();
+    reserved.extend(rest.into_iter().take(free));
+
+    reserved.sort_by(|a, b| b.score.total_cmp(&a.score));
+    reserved
+}
+
+fn normalize(hits: &mut [SearchResult], normalization: ScoreNormalization) {
+    match normalization {
+        ScoreNormalization::None => {}
+        ScoreNormalization::MinMax => {
+            let max = hits.iter().map(|h| h.score).fold(f32::MIN, f32::max);
+            let min = hits.iter().map(|h| h.score).fold(f32::MAX, f32::min);
+            let range = max - min;
+            // Without a spread there is nothing to rescale against
+            if range <= f32::EPSILON {
+                return;
+            }
+            for hit in hits {
+                hit.score = (hit.score - min) / range;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PointPayload;
+    use uuid::Uuid;
+
+    fn hits(design_system: &str, scores: &[f32]) -> (String, Vec<SearchResult>) {
+        let results = scores
+            .iter()
+            .map(|score| {
+                SearchResult::new(
+                    Uuid::new_v4(),
+                    *score,
+                    PointPayload::new("el", "button", "atomic", design_system),
+                )
+            })
+            .collect();
+        (format!("ui_{}", design_system), results)
+    }
+
+    #[test]
+    fn test_merge_without_quota_ranks_by_score() {
+        let merged = merge(
+            vec![hits("tailwind", &[0.9, 0.8, 0.7]), hits("material", &[0.6])],
+            3,
+            &SearchConfig::default(),
+        );
+        let scores: Vec<f32> = merged.iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![0.9, 0.8, 0.7]);
+    }
+
+    #[test]
+    fn test_merge_keeps_quota_per_collection() {
+        let config = SearchConfig {
+            min_per_collection: 1,
+            ..SearchConfig::default()
+        };
+        let merged = merge(
+            vec![hits("tailwind", &[0.9, 0.8, 0.7]), hits("material", &[0.6]), hits("chakra", &[])],
+            3,
+            &config,
+        );
+        assert_eq!(merged.len(), 3);
+        assert!(merged.iter().any(|r| r.payload.design_system == "material"));
+        assert_eq!(merged[0].score, 0.9);
+        assert_eq!(merged[2].score, 0.6);
+    }
+
+    #[test]
+    fn test_min_max_normalization_per_collection() {
+        let config = SearchConfig {
+            normalization: ScoreNormalization::MinMax,
+            ..SearchConfig::default()
+        };
+        let merged = merge(vec![hits("tailwind", &[0.9, 0.5]), hits("material", &[0.3, 0.1])], 10, &config);
+        let scores: Vec<f32> = merged.iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![1.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_min_max_keeps_raw_score_of_lone_hit() {
+        let config = SearchConfig {
+            normalization: ScoreNormalization::MinMax,
+            ..SearchConfig::default()
+        };
+        let merged = merge(vec![hits("tailwind", &[0.95, 0.6]), hits("material", &[0.2])], 2, &config);
+        assert_eq!(merged[0].payload.design_system, "tailwind");
+        assert_eq!(merged[0].score, 1.0);
+        assert_eq!(merged[1].payload.design_system, "material");
+        assert_eq!(merged[1].score, 0.2);
+    }
+}
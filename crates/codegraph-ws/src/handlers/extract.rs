@@ -2,10 +2,104 @@
 
 use crate::protocol::*;
 use crate::state::SharedState;
-use codegraph_extraction::ExtractionInput;
+use codegraph_core::{DesignToken, Snippet, UIElement};
+use codegraph_extraction::{ComponentSource, ExtractionInput, ExtractionResult, Framework};
+use std::collections::HashMap;
 use std::sync::Arc;
+use uuid::Uuid;
 use tracing::{error, info};
 
+/// Tag added to the composite element of a segment
+pub const SEGMENT_TAG: &str = "segment";
+
+/// Composite and atomic elements built from the segments of an extraction
+#[derive(Debug, Default)]
+pub struct SegmentElements {
+    /// Composites followed by their atoms, in segment order
+    pub elements: Vec<UIElement>,
+    /// `HAS_CHILD` edges: owners to composites, composites to atoms
+    pub child_links: Vec<(Uuid, Uuid)>,
+    /// `USES_TOKEN` links of the built elements
+    pub token_links: Vec<(Uuid, String)>,
+}
+
+/// Turn the segments of `result` into composite elements owning their atoms
+///
+/// Top-level segments belong to the root element at the start of their
+/// path (`root_ids` are the IDs of `result.ontology.elements`), nested ones
+/// to their enclosing segment. `id_for` receives a key unique within the
+/// snippet (`segment:0.1` for a composite, `segment:0.1/button:0` for an
+/// atom) and `decorate` applies the fields shared with the snippet's other
+/// elements, such as design system, tags and source. Composites also carry
+/// [`SEGMENT_TAG`], their template and, when embedded, the detector
+/// confidence.
+pub fn segment_elements(
+    result: &ExtractionResult,
+    root_ids: &[Uuid],
+    mut id_for: impl FnMut(&str) -> Uuid,
+    decorate: impl Fn(UIElement) -> UIElement,
+) -> SegmentElements {
+    let mut built = SegmentElements::default();
+    let mut segment_ids: Vec<Uuid> = Vec::new();
+    for segment in &result.ontology.segments {
+        let path: Vec<String> = segment.path.iter().map(usize::to_string).collect();
+        let key = format!("segment:{}", path.join("."));
+        let composite_id = id_for(&key);
+        let owner = match segment.parent {
+            Some(parent) => segment_ids.get(parent),
+            None => segment.path.first().and_then(|root| root_ids.get(*root)),
+        };
+        if let Some(owner) = owner {
+            built.child_links.push((*owner, composite_id));
+        }
+        segment_ids.push(composite_id);
+
+        let mapped = &segment.element;
+        let mut composite = decorate(
+            UIElement::new(&segment.name, mapped.category.as_str())
+                .with_id(composite_id)
+                .with_element_type(&mapped.element_type)
+                .with_css_classes(mapped.classes.clone())
+                .with_a11y_score(result.a11y.score),
+        )
+        .with_html_template(&segment.html);
+        composite.tags.push(SEGMENT_TAG.to_string());
+        if let Some(fingerprint) = &mapped.fingerprint {
+            composite = composite.with_fingerprint(&fingerprint.shape, fingerprint.signature.clone());
+        }
+        if let Some(embedding) = &segment.embedding {
+            composite = composite
+                .with_embedding(embedding.clone())
+                .with_confidence(result.design_system.confidence);
+        }
+        built.token_links.extend(mapped.tokens_used.iter().map(|token| (composite_id, token.clone())));
+        built.elements.push(composite);
+
+        let mut ordinals: HashMap<&str, usize> = HashMap::new();
+        for atom in &segment.children {
+            let category = atom.category.as_str();
+            let ordinal = ordinals.entry(category).or_default();
+            let atom_id = id_for(&format!("{}/{}:{}", key, category, ordinal));
+            let mut element = decorate(
+                UIElement::new(format!("{}-{}-{}", segment.name, category, ordinal), category)
+                    .with_id(atom_id)
+                    .with_element_type(&atom.element_type)
+                    .with_css_classes(atom.classes.clone())
+                    .with_a11y_score(result.a11y.score),
+            );
+            *ordinal += 1;
+            if let Some(fingerprint) = &atom.fingerprint {
+                element = element.with_fingerprint(&fingerprint.shape, fingerprint.signature.clone());
+            }
+
+            built.child_links.push((composite_id, atom_id));
+            built.token_links.extend(atom.tokens_used.iter().map(|token| (atom_id, token.clone())));
+            built.elements.push(element);
+        }
+    }
+    built
+}
+
 pub async fn handle_extract(state: Arc<SharedState>, msg: WsMessage) -> Option<WsMessage> {
     let request: ExtractRequest = match serde_json::from_value(msg.payload.clone()) {
         Ok(req) => req,
@@ -29,17 +123,58 @@ pub async fn handle_extract(state: Arc<SharedState>, msg: WsMessage) -> Option<W
     let start = std::time::Instant::now();
 
     // Build extraction input
-    let mut input = ExtractionInput::new(&request.html);
-    if let Some(css) = &request.css {
-        input = input.with_css(css);
+    let mut input = match (request.framework.as_deref(), request.component.as_deref()) {
+        (Some(name), Some(component)) => {
+            let Some(framework) = Framework::parse(name) else {
+                return Some(WsMessage::error(
+                    msg.id,
+                    ErrorPayload::new(
+                        error_codes::INVALID_MESSAGE,
+                        format!("Unsupported framework: {}", name),
+                    ),
+                ));
+            };
+            let source = match framework {
+                Framework::Vue => ComponentSource::from_vue(component),
+                Framework::Svelte => ComponentSource::from_svelte(component),
+                Framework::Angular => ComponentSource::from_angular(
+                    component,
+                    Some(request.html.as_str()).filter(|html| !html.is_empty()),
+                    request.css.as_deref(),
+                ),
+            };
+            ExtractionInput::from(source)
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            return Some(WsMessage::error(
+                msg.id,
+                ErrorPayload::new(
+                    error_codes::INVALID_MESSAGE,
+                    "`framework` and `component` must be given together",
+                ),
+            ));
+        }
+        (None, None) => {
+            let mut input = ExtractionInput::new(&request.html);
+            if let Some(css) = &request.css {
+                input = input.with_css(css);
+            }
+            input
+        }
+    };
+    if let Some(lang) = &request.style_lang {
+        input = input.with_style_lang(lang);
     }
     if let Some(js) = &request.js {
         input = input.with_js(js);
     }
+    if let Some(jsx) = &request.jsx {
+        input = input.with_jsx(jsx);
+    }
 
-    // Run extraction pipeline
-    let mut extraction = state.extraction.write().await;
-    let result = match extraction.extract(input).await {
+    // Run extraction pipeline, releasing it before the slower store writes
+    let extracted = state.extraction.write().await.extract(input).await;
+    let result = match extracted {
         Ok(result) => result,
         Err(e) => {
             error!("Extraction failed: {}", e);
@@ -53,13 +188,24 @@ pub async fn handle_extract(state: Arc<SharedState>, msg: WsMessage) -> Option<W
     let snippet_id = uuid::Uuid::new_v4();
 
     // Generate element IDs based on ontology mapping
-    let element_ids: Vec<uuid::Uuid> = result
+    let root_ids: Vec<uuid::Uuid> = result
         .ontology
         .elements
         .iter()
         .map(|_| uuid::Uuid::new_v4())
         .collect();
 
+    let element_ids = match persist(&state, &request, &result, snippet_id, &root_ids).await {
+        Ok(element_ids) => element_ids,
+        Err(e) => {
+            error!("Failed to store extraction: {}", e);
+            return Some(WsMessage::error(
+                msg.id,
+                ErrorPayload::new(error_codes::EXTRACTION_FAILED, format!("Failed to store extraction: {}", e)),
+            ));
+        }
+    };
+
     // Get narsese statements as strings
     let narsese_statements: Vec<String> = result
         .narsese
@@ -79,6 +225,180 @@ pub async fn handle_extract(state: Arc<SharedState>, msg: WsMessage) -> Option<W
             narsese_statements,
             design_system: result.design_system.design_system.as_str().to_string(),
             processing_time_ms: processing_time,
+            a11y_score: Some(result.a11y.score),
+            a11y_issues: result.a11y.describe_issues(),
         },
     ))
 }
+
+/// Store the snippet, its elements and segments, its design tokens (with
+/// aliases) and the `HAS_CHILD` and `USES_TOKEN` links of the elements,
+/// returning the IDs of every stored element
+async fn persist(
+    state: &SharedState,
+    request: &ExtractRequest,
+    result: &ExtractionResult,
+    snippet_id: Uuid,
+    root_ids: &[Uuid],
+) -> anyhow::Result<Vec<Uuid>> {
+    let design_system = request
+        .design_system
+        .clone()
+        .unwrap_or_else(|| result.design_system.design_system.as_str().to_string());
+    let name = request.name.clone().unwrap_or_else(|| "snippet".to_string());
+    // Framework uploads carry their markup in the component source
+    let source = request.component.as_deref().unwrap_or(&request.html);
+
+    let mut elements = Vec::new();
+    let mut token_links = Vec::new();
+    for (index, (mapped, id)) in result.ontology.elements.iter().zip(root_ids).enumerate() {
+        let category = mapped.category.as_str();
+        let element_name = if index == 0 { name.clone() } else { format!("{}-{}-{}", name, category, index) };
+        let mut element = UIElement::new(element_name, category)
+            .with_id(*id)
+            .with_element_type(&mapped.element_type)
+            .with_design_system(&design_system)
+            .with_css_classes(mapped.classes.clone())
+            .with_tags(request.tags.clone())
+            .with_a11y_score(result.a11y.score);
+        if let Some(fingerprint) = &mapped.fingerprint {
+            element = element.with_fingerprint(&fingerprint.shape, fingerprint.signature.clone());
+        }
+        if index == 0 {
+            element = element.with_html_template(source);
+            if let Some(embedding) = &result.embedding {
+                element = element
+                    .with_embedding(embedding.embedding.clone())
+                    .with_confidence(result.design_system.confidence);
+            }
+        }
+        token_links.extend(mapped.tokens_used.iter().map(|token| (*id, token.clone())));
+        elements.push(element);
+    }
+
+    let segments = segment_elements(
+        result,
+        root_ids,
+        |_| Uuid::new_v4(),
+        |element| element.with_design_system(&design_system).with_tags(request.tags.clone()),
+    );
+    elements.extend(segments.elements);
+    token_links.extend(segments.token_links);
+    let element_ids: Vec<Uuid> = elements.iter().map(|element| element.id).collect();
+
+    let tokens = &result.ontology.token_graph.tokens;
+    for token in tokens {
+        let mut node = DesignToken::new(&token.name, &token.value, token.category.as_str())
+            .with_theme(&token.theme)
+            .with_design_system(&design_system);
+        if let Some(resolved) = &token.resolved_value {
+            node = node.with_resolved_value(resolved);
+        }
+        if let Some(alias_of) = &token.alias_of {
+            node = node.with_alias_of(alias_of);
+        }
+        state.repository.save_design_token(&node).await?;
+    }
+    // Aliases are linked once all targets exist
+    for token in tokens {
+        if let Some(alias_of) = &token.alias_of {
+            state.repository.link_token_alias(&token.name, alias_of, &token.theme).await?;
+        }
+    }
+
+    state.repository.save_batch(&elements).await?;
+    let mut snippet = Snippet::new(source)
+        .with_id(snippet_id)
+        .with_name(name)
+        .with_design_system(&design_system)
+        .with_tags(request.tags.clone())
+        .with_element_ids(element_ids.clone());
+    if let Some(css) = &request.css {
+        snippet = snippet.with_css(css);
+    }
+    if let Some(js) = request.js.as_ref().or(request.jsx.as_ref()) {
+        snippet = snippet.with_js(js);
+    }
+    state.repository.save_snippet(&snippet).await?;
+    state.repository.link_children(&segments.child_links).await?;
+    for (element_id, token) in &token_links {
+        state.repository.link_to_token(*element_id, token).await?;
+    }
+    Ok(element_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codegraph_core::{GraphNodeRef, GraphStore, MemoryGraphStore};
+    use codegraph_extraction::ExtractionPipeline;
+    use codegraph_generation::VanillaCodeGenerator;
+    use codegraph_retrieval::HybridRetriever;
+
+    #[tokio::test]
+    async fn test_extract_stores_segments_as_composites() {
+        let store = Arc::new(MemoryGraphStore::new());
+        let state = Arc::new(SharedState::new(
+            store.clone(),
+            HybridRetriever::new(),
+            VanillaCodeGenerator::new(),
+            ExtractionPipeline::new(),
+        ));
+        let request = serde_json::json!({
+            "name": "shop",
+            "html": r#"<body>
+                <nav class="navbar"><a href="/">Home</a><button class="btn">Menu</button></nav>
+                <div class="grid">
+                    <div class="product-card"><img src="a.png"><h3>A</h3><button>Buy</button></div>
+                    <div class="product-card"><img src="b.png"><h3>B</h3><button>Buy</button></div>
+                </div>
+            </body>"#,
+            "css": ".btn { color: var(--brand); } :root { --brand: #3366ff; }",
+        });
+
+        let response = handle_extract(state, WsMessage::new(MessageType::ExtractRequest, request))
+            .await
+            .unwrap();
+        assert_eq!(response.msg_type, MessageType::ExtractComplete);
+        let complete: ExtractComplete = serde_json::from_value(response.payload).unwrap();
+
+        let dump = store.export_graph().await.unwrap();
+        let composites: Vec<&UIElement> = dump
+            .elements
+            .iter()
+            .filter(|e| e.tags.iter().any(|t| t == SEGMENT_TAG))
+            .collect();
+        assert_eq!(composites.len(), 3);
+        assert!(composites.iter().all(|c| c.html_template.is_some() && complete.element_ids.contains(&c.id)));
+
+        let children = |rel_type: &str| -> Vec<(Uuid, Uuid)> {
+            dump.relations
+                .iter()
+                .filter(|r| r.rel_type == rel_type)
+                .filter_map(|r| match (r.from.clone(), r.to.clone()) {
+                    (GraphNodeRef::Element { id: from }, GraphNodeRef::Element { id: to }) => Some((from, to)),
+                    _ => None,
+                })
+                .collect()
+        };
+        let has_child = children("HAS_CHILD");
+        // Every composite has an owner and owns its atoms
+        for composite in &composites {
+            assert!(has_child.iter().any(|(_, to)| *to == composite.id));
+            assert!(has_child.iter().any(|(from, _)| *from == composite.id));
+        }
+
+        // Atoms keep the snippet's a11y score, and the nav button its token
+        let nav = composites.iter().find(|c| c.name == "nav").unwrap();
+        let atoms: Vec<&UIElement> = has_child
+            .iter()
+            .filter(|(from, _)| *from == nav.id)
+            .filter_map(|(_, to)| dump.elements.iter().find(|e| e.id == *to))
+            .collect();
+        assert!(atoms.iter().all(|atom| atom.a11y_score == complete.a11y_score));
+        let button = atoms.iter().find(|atom| atom.category == "button").unwrap();
+        assert!(dump.relations.iter().any(|r| {
+            r.rel_type == "USES_TOKEN" && matches!(r.from, GraphNodeRef::Element { id } if id == button.id)
+        }));
+    }
+}
@@ -2,9 +2,12 @@
 
 use crate::protocol::*;
 use crate::state::SharedState;
-use codegraph_generation::{GenerationRequest as GenRequest, SimilarElement as GenSimilarElement};
+use codegraph_extraction::css::TokenCategory;
+use codegraph_generation::{
+    DesignSystemConstraints, GenerationRequest as GenRequest, SimilarElement as GenSimilarElement,
+};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub async fn handle_generate(state: Arc<SharedState>, msg: WsMessage) -> Option<WsMessage> {
     let request: GenerateRequest = match serde_json::from_value(msg.payload.clone()) {
@@ -50,6 +53,18 @@ pub async fn handle_generate(state: Arc<SharedState>, msg: WsMessage) -> Option<
         Vec::new()
     };
 
+    // Load design system constraints from the graph
+    let constraints = match &request.design_system {
+        Some(ds) => match load_constraints(&state, ds).await {
+            Ok(constraints) => Some(constraints),
+            Err(e) => {
+                warn!("Failed to load constraints for {}: {}", ds, e);
+                None
+            }
+        },
+        None => None,
+    };
+
     // Generate code
     // TODO: Implement streaming generation
     // For now, we'll do synchronous generation
@@ -66,6 +81,8 @@ pub async fn handle_generate(state: Arc<SharedState>, msg: WsMessage) -> Option<
             .collect(),
         reasoning: None,
         categories: Vec::new(),
+        constraints,
+        max_repair_attempts: request.max_repair_attempts,
     };
 
     let result = match state.generator.generate(generation_request).await {
@@ -99,6 +116,31 @@ pub async fn handle_generate(state: Arc<SharedState>, msg: WsMessage) -> Option<
             reference_elements,
             narsese_reasoning: Vec::new(),
             generation_time_ms: generation_time,
+            validation_errors: result.validation_errors,
+            a11y_score: result.a11y.as_ref().map(|r| r.score),
+            a11y_issues: result.a11y.map(|r| r.describe_issues()).unwrap_or_default(),
         },
     ))
 }
+
+/// Build design system constraints from the classes, tokens and categories in the graph
+pub(crate) async fn load_constraints(state: &SharedState, design_system: &str) -> anyhow::Result<DesignSystemConstraints> {
+    let classes = state.repository.list_css_classes(design_system).await?;
+    let tokens = state
+        .repository
+        .find_design_tokens_by_design_system(design_system)
+        .await?;
+    let categories = state.repository.count_by_category().await?;
+
+    let mut constraints = DesignSystemConstraints::new(design_system)
+        .with_known_classes(classes)
+        .with_known_categories(categories.into_iter().map(|(category, _)| category));
+
+    for token in tokens {
+        let category = TokenCategory::from_name(&token.category);
+        let value = token.resolved_value.unwrap_or(token.value);
+        constraints = constraints.with_token(token.name, value, category);
+    }
+
+    Ok(constraints)
+}
@@ -1,7 +1,11 @@
-//! Graph handlers - processes graph statistics and element queries
+//! Graph handlers - processes graph statistics, element and neighbourhood queries
+//!
+//! Neighbourhoods are shared by the `graph_neighborhood` WebSocket message and
+//! `POST /api/graph/neighborhood`.
 
 use crate::protocol::*;
 use crate::state::SharedState;
+use codegraph_graph::{ElementQuery, NeighborhoodQuery, NeighborhoodRoot};
 use std::sync::Arc;
 use tracing::{error, info};
 
@@ -67,13 +71,22 @@ pub async fn handle_graph_elements(state: Arc<SharedState>, msg: WsMessage) -> O
     };
 
     info!(
-        "Processing graph elements: page={}, per_page={}, category={:?}",
-        request.page, request.per_page, request.category
+        "Processing graph elements: page={}, per_page={}, category={:?}, sort={:?}",
+        request.page, request.per_page, request.category, request.sort
     );
 
-    // Fetch elements using available method
-    let elements_raw = match state.repository.list_all_elements(request.per_page).await {
-        Ok(elements) => elements,
+    let query = ElementQuery {
+        category: request.category,
+        design_system: request.design_system,
+        tags: request.tags,
+        name_prefix: request.name_prefix,
+        sort: request.sort,
+        direction: request.direction,
+        page: u32::try_from(request.page).unwrap_or(u32::MAX).max(1),
+        per_page: u32::try_from(request.per_page).unwrap_or(u32::MAX),
+    };
+    let page = match state.repository.list_elements(&query).await {
+        Ok(page) => page,
         Err(e) => {
             error!("Failed to fetch elements: {}", e);
             return Some(WsMessage::error(
@@ -83,30 +96,16 @@ pub async fn handle_graph_elements(state: Arc<SharedState>, msg: WsMessage) -> O
         }
     };
 
-    let total = state.repository.count().await.unwrap_or(0);
-
-    let elements: Vec<GraphElement> = elements_raw
+    let total = page.total;
+    let elements: Vec<GraphElement> = page
+        .elements
         .into_iter()
-        .filter(|e| {
-            // Apply filters if present
-            let category_match = request
-                .category
-                .as_ref()
-                .map(|c| e.category == *c)
-                .unwrap_or(true);
-            let ds_match = request
-                .design_system
-                .as_ref()
-                .map(|ds| e.design_system.as_ref().map(|eds| eds == ds).unwrap_or(false))
-                .unwrap_or(true);
-            category_match && ds_match
-        })
-        .map(|e| GraphElement {
-            id: e.id,
-            name: e.name,
-            category: e.category,
-            design_system: e.design_system.unwrap_or_default(),
-            connections: 0, // Would need graph query to get this
+        .map(|listed| GraphElement {
+            id: listed.element.id,
+            name: listed.element.name,
+            category: listed.element.category,
+            design_system: listed.element.design_system.unwrap_or_default(),
+            connections: listed.degree,
         })
         .collect();
 
@@ -116,8 +115,100 @@ pub async fn handle_graph_elements(state: Arc<SharedState>, msg: WsMessage) -> O
         GraphElementsResult {
             elements,
             total,
-            page: request.page,
-            per_page: request.per_page,
+            page: query.page as usize,
+            per_page: query.limit() as usize,
         },
     ))
 }
+
+pub async fn handle_graph_neighborhood(state: Arc<SharedState>, msg: WsMessage) -> Option<WsMessage> {
+    let request: GraphNeighborhoodRequest = match serde_json::from_value(msg.payload.clone()) {
+        Ok(req) => req,
+        Err(e) => {
+            return Some(WsMessage::error(
+                msg.id,
+                ErrorPayload::new(
+                    error_codes::PARSE_ERROR,
+                    format!("Invalid graph neighborhood request: {}", e),
+                ),
+            ));
+        }
+    };
+
+    match neighborhood(&state, request).await {
+        Ok(result) => Some(WsMessage::response(msg.id, MessageType::GraphNeighborhoodResult, result)),
+        Err(error) => Some(WsMessage::error(msg.id, error)),
+    }
+}
+
+/// Nodes and relationships around an element or design system
+pub async fn neighborhood(
+    state: &SharedState,
+    request: GraphNeighborhoodRequest,
+) -> Result<GraphNeighborhoodResult, ErrorPayload> {
+    let root = match (request.element_id, request.design_system) {
+        (Some(id), None) => NeighborhoodRoot::Element(id),
+        (None, Some(name)) => NeighborhoodRoot::DesignSystem(name),
+        _ => {
+            return Err(ErrorPayload::new(
+                error_codes::INVALID_MESSAGE,
+                "Exactly one of element_id and design_system is required",
+            ));
+        }
+    };
+
+    info!(
+        "Processing graph neighborhood: root={:?}, depth={}, max_nodes={}",
+        root, request.depth, request.max_nodes
+    );
+
+    let query = NeighborhoodQuery::new(root.clone())
+        .with_depth(request.depth)
+        .with_relation_types(request.relation_types)
+        .with_max_nodes(request.max_nodes);
+    let hood = match state.repository.neighborhood(&query).await {
+        Ok(Some(hood)) => hood,
+        Ok(None) => {
+            return Err(ErrorPayload::new(
+                error_codes::NOT_FOUND,
+                format!("Neighborhood root not found: {:?}", root),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to fetch neighborhood: {}", e);
+            return Err(ErrorPayload::new(error_codes::INTERNAL_ERROR, e.to_string()));
+        }
+    };
+
+    let nodes = hood
+        .nodes
+        .into_iter()
+        .map(|node| GraphNeighborhoodNode {
+            id: node.id,
+            kind: node.kind,
+            name: node.name,
+            category: node.category.unwrap_or_default(),
+            design_system: node.design_system.unwrap_or_default(),
+            connections: node.degree,
+            depth: node.depth,
+            truncated: node.truncated,
+        })
+        .collect();
+    let links = hood
+        .edges
+        .into_iter()
+        .map(|edge| GraphNeighborhoodLink {
+            source: edge.source,
+            target: edge.target,
+            rel_type: edge.rel_type,
+            score: edge.score,
+            created_at: edge.created_at,
+        })
+        .collect();
+
+    Ok(GraphNeighborhoodResult {
+        nodes,
+        links,
+        truncated: hood.truncated,
+    })
+}
@@ -6,6 +6,7 @@ mod generate;
 mod graph;
 mod metrics;
 mod query;
+mod translate;
 
 pub use extract::*;
 pub use feedback::*;
@@ -13,6 +14,7 @@ pub use generate::*;
 pub use graph::*;
 pub use metrics::*;
 pub use query::*;
+pub use translate::*;
 
 use crate::protocol::*;
 use crate::state::SharedState;
@@ -30,9 +32,11 @@ pub async fn route_message(state: Arc<SharedState>, msg: WsMessage) -> Option<Ws
         MessageType::ExtractRequest => handle_extract(state, msg).await,
         MessageType::QueryRequest => handle_query(state, msg).await,
         MessageType::GenerateRequest => handle_generate(state, msg).await,
+        MessageType::TranslateRequest => handle_translate(state, msg).await,
         MessageType::FeedbackSubmit => handle_feedback(state, msg).await,
         MessageType::GraphStats => handle_graph_stats(state, msg).await,
         MessageType::GraphElements => handle_graph_elements(state, msg).await,
+        MessageType::GraphNeighborhood => handle_graph_neighborhood(state, msg).await,
         MessageType::MetricsSubscribe => handle_metrics_subscribe(state, msg).await,
         MessageType::MetricsUnsubscribe => handle_metrics_unsubscribe(state, msg).await,
         MessageType::Ping => Some(WsMessage::response(msg.id, MessageType::Pong, ())),
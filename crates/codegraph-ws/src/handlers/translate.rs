@@ -0,0 +1,184 @@
+//! Translate handler - rewrites a component into another design system
+//!
+//! Shared by the `translate_request` WebSocket message and `POST /api/translate`.
+
+use crate::handlers::generate::load_constraints;
+use crate::protocol::*;
+use crate::state::SharedState;
+use codegraph_extraction::css::TokenCategory;
+use codegraph_extraction::design_system::DesignSystemType;
+use codegraph_generation::{map_tokens, ReferenceComponent, TokenRef, TranslationRequest};
+use codegraph_graph::DesignToken;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Same-category elements used as references when no counterparts are linked
+const FALLBACK_REFERENCES: usize = 3;
+
+pub async fn handle_translate(state: Arc<SharedState>, msg: WsMessage) -> Option<WsMessage> {
+    let request: TranslateRequest = match serde_json::from_value(msg.payload.clone()) {
+        Ok(req) => req,
+        Err(e) => {
+            return Some(WsMessage::error(
+                msg.id,
+                ErrorPayload::new(error_codes::PARSE_ERROR, format!("Invalid translate request: {}", e)),
+            ));
+        }
+    };
+
+    match translate(&state, request).await {
+        Ok(complete) => Some(WsMessage::response(msg.id, MessageType::TranslateComplete, complete)),
+        Err(error) => Some(WsMessage::error(msg.id, error)),
+    }
+}
+
+/// Translate an element into the requested design system
+pub async fn translate(state: &SharedState, request: TranslateRequest) -> Result<TranslateComplete, ErrorPayload> {
+    let target = DesignSystemType::from_name(&request.target_design_system);
+    if matches!(target, DesignSystemType::Unknown | DesignSystemType::Custom) {
+        return Err(ErrorPayload::new(
+            error_codes::INVALID_MESSAGE,
+            format!("Unsupported target design system: {}", request.target_design_system),
+        ));
+    }
+
+    info!("Translating element {} to {}", request.element_id, target.as_str());
+    let start = std::time::Instant::now();
+
+    let element = match state.repository.find_by_id(request.element_id).await {
+        Ok(Some(element)) => element,
+        Ok(None) => {
+            return Err(ErrorPayload::new(
+                error_codes::NOT_FOUND,
+                format!("Element not found: {}", request.element_id),
+            ));
+        }
+        Err(e) => return Err(ErrorPayload::new(error_codes::INTERNAL_ERROR, e.to_string())),
+    };
+    let Some(source_html) = element.html_template.clone() else {
+        return Err(ErrorPayload::new(
+            error_codes::TRANSLATION_FAILED,
+            format!("Element {} has no HTML template", element.id),
+        ));
+    };
+
+    // Counterparts in the target system, falling back to elements of the same category
+    let mut references: Vec<ReferenceComponent> = Vec::new();
+    let mut reference_elements: Vec<ElementWithScore> = Vec::new();
+    let counterparts = state
+        .repository
+        .find_counterparts(element.id, target.as_str(), request.max_references)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load counterparts of {}: {}", element.id, e);
+            Vec::new()
+        });
+    let candidates: Vec<_> = if counterparts.is_empty() {
+        state
+            .repository
+            .find_by_category(&element.category)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|e| e.design_system.as_deref() == Some(target.as_str()))
+            .take(FALLBACK_REFERENCES.min(request.max_references))
+            .map(|e| (e, "SAME_CATEGORY".to_string(), 0.0))
+            .collect()
+    } else {
+        counterparts
+            .into_iter()
+            .map(|c| (c.element, c.relation, c.score))
+            .collect()
+    };
+    for (reference, relation, score) in candidates {
+        reference_elements.push(ElementWithScore {
+            id: reference.id,
+            name: reference.name.clone(),
+            category: reference.category.clone(),
+            design_system: reference.design_system.clone().unwrap_or_default(),
+            score: score as f64,
+            match_reason: relation.clone(),
+        });
+        if let Some(html) = reference.html_template {
+            references.push(ReferenceComponent {
+                name: reference.name,
+                html,
+                relation,
+                score,
+            });
+        }
+    }
+
+    // Map the source's tokens onto the target's
+    let source_tokens = state
+        .repository
+        .find_tokens_used_by(element.id)
+        .await
+        .unwrap_or_default();
+    let target_tokens = state
+        .repository
+        .find_design_tokens_by_design_system(target.as_str())
+        .await
+        .unwrap_or_default();
+    let token_mappings = map_tokens(&token_refs(source_tokens), &token_refs(target_tokens));
+
+    let constraints = match load_constraints(state, target.as_str()).await {
+        Ok(constraints) => Some(constraints),
+        Err(e) => {
+            warn!("Failed to load constraints for {}: {}", target.as_str(), e);
+            None
+        }
+    };
+
+    let translation_request = TranslationRequest {
+        name: element.name.clone(),
+        category: element.category.clone(),
+        source_html,
+        source_css: None,
+        source_design_system: element.design_system.clone(),
+        target_design_system: target,
+        references,
+        token_mappings: token_mappings.clone(),
+        constraints,
+        max_repair_attempts: request.max_repair_attempts,
+    };
+
+    let result = match state.generator.translate(translation_request).await {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Translation failed: {}", e);
+            return Err(ErrorPayload::new(error_codes::TRANSLATION_FAILED, e.to_string()));
+        }
+    };
+
+    let generation_time = start.elapsed().as_millis() as u64;
+    state.metrics.write().await.record_generation(generation_time as f64);
+
+    let code = result.generation.code;
+    Ok(TranslateComplete {
+        element_id: element.id,
+        source_design_system: element.design_system,
+        target_design_system: target.as_str().to_string(),
+        html: code.html.unwrap_or_default(),
+        css: code.css.filter(|_| request.include_css),
+        javascript: code.javascript.filter(|_| request.include_js),
+        reference_elements,
+        token_mappings,
+        detected_design_system: result.detection.design_system.as_str().to_string(),
+        detection_confidence: result.detection.confidence,
+        valid: result.valid,
+        validation_errors: result.generation.validation_errors,
+        attempts: result.generation.attempts,
+        generation_time_ms: generation_time,
+    })
+}
+
+fn token_refs(tokens: Vec<DesignToken>) -> Vec<TokenRef> {
+    tokens
+        .into_iter()
+        .map(|token| {
+            let category = TokenCategory::from_name(&token.category);
+            TokenRef::new(token.name, token.resolved_value.unwrap_or(token.value), category)
+        })
+        .collect()
+}
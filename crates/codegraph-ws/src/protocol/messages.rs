@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub use codegraph_generation::TokenMapping;
+pub use codegraph_graph::{ElementSort, NeighborhoodNodeKind, SortDirection};
+
 /// Base message envelope for all WebSocket communications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WsMessage {
@@ -49,9 +52,11 @@ pub enum MessageType {
     ExtractRequest,
     QueryRequest,
     GenerateRequest,
+    TranslateRequest,
     FeedbackSubmit,
     GraphStats,
     GraphElements,
+    GraphNeighborhood,
     MetricsSubscribe,
     MetricsUnsubscribe,
     Ping,
@@ -62,9 +67,11 @@ pub enum MessageType {
     QueryResult,
     GenerateStreaming,
     GenerateComplete,
+    TranslateComplete,
     FeedbackAck,
     GraphStatsResult,
     GraphElementsResult,
+    GraphNeighborhoodResult,
     MetricsUpdate,
     Pong,
     Error,
@@ -76,11 +83,26 @@ pub enum MessageType {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractRequest {
+    #[serde(default)]
     pub html: String,
     #[serde(default)]
     pub css: Option<String>,
+    /// Stylesheet language of `css` (`scss`, `sass`, `less`); plain CSS if absent
+    #[serde(default)]
+    pub style_lang: Option<String>,
     #[serde(default)]
     pub js: Option<String>,
+    /// React component source (JSX/TSX)
+    #[serde(default)]
+    pub jsx: Option<String>,
+    /// Component framework (`vue`, `svelte`, `angular`) for `component`;
+    /// required together with it
+    #[serde(default)]
+    pub framework: Option<String>,
+    /// Vue/Svelte single-file component, or Angular component class
+    /// (with `html`/`css` holding its external template and styles)
+    #[serde(default)]
+    pub component: Option<String>,
     #[serde(default)]
     pub name: Option<String>,
     #[serde(default)]
@@ -114,6 +136,10 @@ pub struct ExtractComplete {
     pub narsese_statements: Vec<String>,
     pub design_system: String,
     pub processing_time_ms: u64,
+    #[serde(default)]
+    pub a11y_score: Option<f32>,
+    #[serde(default)]
+    pub a11y_issues: Vec<String>,
 }
 
 // ============================================================================
@@ -168,6 +194,9 @@ pub struct GenerateRequest {
     pub include_js: bool,
     #[serde(default)]
     pub use_references: bool,
+    /// Regenerate up to N times when the output violates the design system
+    #[serde(default)]
+    pub max_repair_attempts: u32,
 }
 
 fn default_true() -> bool {
@@ -197,6 +226,57 @@ pub struct GenerateComplete {
     pub reference_elements: Vec<ElementWithScore>,
     pub narsese_reasoning: Vec<String>,
     pub generation_time_ms: u64,
+    #[serde(default)]
+    pub validation_errors: Vec<String>,
+    #[serde(default)]
+    pub a11y_score: Option<f32>,
+    #[serde(default)]
+    pub a11y_issues: Vec<String>,
+}
+
+// ============================================================================
+// Translate Messages
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslateRequest {
+    pub element_id: Uuid,
+    pub target_design_system: String,
+    #[serde(default = "default_true")]
+    pub include_css: bool,
+    #[serde(default = "default_true")]
+    pub include_js: bool,
+    /// Target-system counterparts given to the LLM as references
+    #[serde(default = "default_max_references")]
+    pub max_references: usize,
+    /// Regenerate up to N times when the output is not detected as the target
+    #[serde(default)]
+    pub max_repair_attempts: u32,
+}
+
+fn default_max_references() -> usize {
+    5
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslateComplete {
+    pub element_id: Uuid,
+    pub source_design_system: Option<String>,
+    pub target_design_system: String,
+    pub html: String,
+    pub css: Option<String>,
+    pub javascript: Option<String>,
+    pub reference_elements: Vec<ElementWithScore>,
+    pub token_mappings: Vec<TokenMapping>,
+    /// Design system detected on the generated code
+    pub detected_design_system: String,
+    pub detection_confidence: f32,
+    /// Whether the detected design system is the requested target
+    pub valid: bool,
+    #[serde(default)]
+    pub validation_errors: Vec<String>,
+    pub attempts: u32,
+    pub generation_time_ms: u64,
 }
 
 // ============================================================================
@@ -267,6 +347,16 @@ pub struct GraphElementsRequest {
     pub category: Option<String>,
     #[serde(default)]
     pub design_system: Option<String>,
+    /// Only elements carrying every tag
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Case-insensitive prefix of the element name
+    #[serde(default)]
+    pub name_prefix: Option<String>,
+    #[serde(default)]
+    pub sort: ElementSort,
+    #[serde(default)]
+    pub direction: SortDirection,
 }
 
 fn default_page() -> usize {
@@ -294,6 +384,68 @@ pub struct GraphElement {
     pub connections: u32,
 }
 
+/// Nodes and relationships within `depth` hops of an element or design system
+///
+/// Exactly one of `element_id` and `design_system` names the root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNeighborhoodRequest {
+    #[serde(default)]
+    pub element_id: Option<Uuid>,
+    #[serde(default)]
+    pub design_system: Option<String>,
+    #[serde(default = "default_depth")]
+    pub depth: u32,
+    /// Relationship types to follow (all if empty)
+    #[serde(default)]
+    pub relation_types: Vec<String>,
+    #[serde(default = "default_max_nodes")]
+    pub max_nodes: usize,
+}
+
+fn default_depth() -> u32 {
+    1
+}
+
+fn default_max_nodes() -> usize {
+    200
+}
+
+/// Neighbourhood as force-directed graph data: `links` reference node IDs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNeighborhoodResult {
+    pub nodes: Vec<GraphNeighborhoodNode>,
+    pub links: Vec<GraphNeighborhoodLink>,
+    /// The node cap stopped the traversal before the requested depth
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNeighborhoodNode {
+    /// Element and snippet UUIDs; `design_system:<name>` and
+    /// `design_token:<name>:<theme>` for the other kinds
+    pub id: String,
+    pub kind: NeighborhoodNodeKind,
+    pub name: String,
+    pub category: String,
+    pub design_system: String,
+    pub connections: u32,
+    /// Hops from the root
+    pub depth: u32,
+    /// Some of the node's neighbours were left out by the depth or node cap
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNeighborhoodLink {
+    pub source: String,
+    pub target: String,
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    /// Similarity score of `SIMILAR_TO` / `CAN_REPLACE`
+    pub score: Option<f32>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 // ============================================================================
 // Metrics Messages
 // ============================================================================
@@ -361,6 +513,7 @@ pub mod error_codes {
     pub const EXTRACTION_FAILED: &str = "extraction_failed";
     pub const QUERY_FAILED: &str = "query_failed";
     pub const GENERATION_FAILED: &str = "generation_failed";
+    pub const TRANSLATION_FAILED: &str = "translation_failed";
     pub const FEEDBACK_FAILED: &str = "feedback_failed";
     pub const TIMEOUT: &str = "timeout";
     pub const INTERNAL_ERROR: &str = "internal_error";
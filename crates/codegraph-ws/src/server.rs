@@ -1,16 +1,20 @@
 //! WebSocket server implementation
 
-use crate::handlers::route_message;
-use crate::protocol::WsMessage;
+use crate::handlers::{neighborhood, route_message, translate};
+use crate::protocol::{
+    error_codes, ErrorPayload, GraphNeighborhoodRequest, GraphNeighborhoodResult, TranslateComplete, TranslateRequest,
+    WsMessage,
+};
 use crate::state::SharedState;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
         State, WebSocketUpgrade,
     },
+    http::StatusCode,
     response::IntoResponse,
-    routing::get,
-    Router,
+    routing::{get, post},
+    Json, Router,
 };
 use futures::{SinkExt, StreamExt};
 use std::net::SocketAddr;
@@ -30,6 +34,8 @@ pub fn create_router(state: Arc<SharedState>) -> Router {
     Router::new()
         .route("/ws", get(ws_handler))
         .route("/health", get(health_handler))
+        .route("/api/translate", post(translate_handler))
+        .route("/api/graph/neighborhood", post(neighborhood_handler))
         .layer(cors)
         .with_state(state)
 }
@@ -39,6 +45,32 @@ async fn health_handler() -> &'static str {
     "OK"
 }
 
+/// REST counterpart of the `translate_request` message
+async fn translate_handler(
+    State(state): State<Arc<SharedState>>,
+    Json(request): Json<TranslateRequest>,
+) -> Result<Json<TranslateComplete>, (StatusCode, Json<ErrorPayload>)> {
+    translate(&state, request).await.map(Json).map_err(rest_error)
+}
+
+/// REST counterpart of the `graph_neighborhood` message
+async fn neighborhood_handler(
+    State(state): State<Arc<SharedState>>,
+    Json(request): Json<GraphNeighborhoodRequest>,
+) -> Result<Json<GraphNeighborhoodResult>, (StatusCode, Json<ErrorPayload>)> {
+    neighborhood(&state, request).await.map(Json).map_err(rest_error)
+}
+
+/// HTTP status and body of a handler error
+fn rest_error(error: ErrorPayload) -> (StatusCode, Json<ErrorPayload>) {
+    let status = match error.code.as_str() {
+        error_codes::NOT_FOUND => StatusCode::NOT_FOUND,
+        error_codes::INVALID_MESSAGE | error_codes::PARSE_ERROR => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(error))
+}
+
 /// WebSocket upgrade handler
 async fn ws_handler(
     ws: WebSocketUpgrade,
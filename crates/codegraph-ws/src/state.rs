@@ -2,15 +2,15 @@
 
 use codegraph_extraction::ExtractionPipeline;
 use codegraph_generation::VanillaCodeGenerator;
-use codegraph_graph::Neo4jRepository;
+use codegraph_graph::GraphStore;
 use codegraph_retrieval::HybridRetriever;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 /// Shared state across all WebSocket connections
 pub struct SharedState {
-    /// Neo4j repository for graph operations
-    pub repository: Arc<Neo4jRepository>,
+    /// Graph store (Neo4j or in-memory)
+    pub repository: Arc<dyn GraphStore>,
 
     /// Hybrid retriever for vector+graph+NARS search
     pub retriever: Arc<RwLock<HybridRetriever>>,
@@ -27,13 +27,13 @@ pub struct SharedState {
 
 impl SharedState {
     pub fn new(
-        repository: Neo4jRepository,
+        repository: Arc<dyn GraphStore>,
         retriever: HybridRetriever,
         generator: VanillaCodeGenerator,
         extraction: ExtractionPipeline,
     ) -> Self {
         Self {
-            repository: Arc::new(repository),
+            repository,
             retriever: Arc::new(RwLock::new(retriever)),
             generator: Arc::new(generator),
             extraction: Arc::new(RwLock::new(extraction)),
@@ -8,6 +8,7 @@ export type MessageType =
   | 'feedback_submit'
   | 'graph_stats'
   | 'graph_elements'
+  | 'graph_neighborhood'
   | 'metrics_subscribe'
   | 'metrics_unsubscribe'
   | 'ping'
@@ -20,6 +21,7 @@ export type MessageType =
   | 'feedback_ack'
   | 'graph_stats_result'
   | 'graph_elements_result'
+  | 'graph_neighborhood_result'
   | 'metrics_update'
   | 'pong'
   | 'error';
@@ -68,6 +70,19 @@ export interface GraphElementsRequest {
   per_page: number;
   category?: string;
   design_system?: string;
+  tags?: string[];
+  name_prefix?: string;
+  sort?: 'name' | 'created' | 'confidence' | 'degree';
+  direction?: 'asc' | 'desc';
+}
+
+export interface GraphNeighborhoodRequest {
+  // Exactly one of element_id and design_system
+  element_id?: string;
+  design_system?: string;
+  depth?: number;
+  relation_types?: string[];
+  max_nodes?: number;
 }
 
 // Response payloads
@@ -148,6 +163,26 @@ export interface GraphElement {
   connections: number;
 }
 
+export interface GraphNeighborhoodNode extends GraphElement {
+  kind: 'element' | 'snippet' | 'design_token' | 'design_system';
+  depth: number;
+  truncated: boolean;
+}
+
+export interface GraphNeighborhoodLink {
+  source: string;
+  target: string;
+  type: string;
+  score: number | null;
+  created_at: string | null;
+}
+
+export interface GraphNeighborhoodResult {
+  nodes: GraphNeighborhoodNode[];
+  links: GraphNeighborhoodLink[];
+  truncated: boolean;
+}
+
 export interface GraphElementsResult {
   elements: GraphElement[];
   total: number;
@@ -28,6 +28,8 @@ fn create_test_request(description: &str) -> GenerationRequest {
         ],
         reasoning: Some("User needs a clickable button element".to_string()),
         categories: vec!["button".to_string()],
+        constraints: None,
+        max_repair_attempts: 0,
     }
 }
 
@@ -88,6 +90,8 @@ async fn test_generate_card_component() {
         ],
         reasoning: Some("Display product information in a card layout".to_string()),
         categories: vec!["card".to_string(), "container".to_string()],
+        constraints: None,
+        max_repair_attempts: 0,
     };
 
     let result = generator
@@ -114,6 +118,8 @@ async fn test_generate_with_css() {
         similar_elements: vec![],
         reasoning: None,
         categories: vec!["navigation".to_string(), "layout".to_string()],
+        constraints: None,
+        max_repair_attempts: 0,
     };
 
     let result = generator
@@ -287,6 +293,8 @@ async fn test_generation_request_creation() {
         ],
         reasoning: Some("User wants a button".to_string()),
         categories: vec!["button".to_string()],
+        constraints: None,
+        max_repair_attempts: 0,
     };
 
     assert_eq!(request.description, "A simple button");
@@ -331,6 +339,8 @@ async fn test_generate_with_context_from_similar_elements() {
         ],
         reasoning: Some("Form needs input validation for email addresses".to_string()),
         categories: vec!["input".to_string(), "form".to_string()],
+        constraints: None,
+        max_repair_attempts: 0,
     };
 
     let result = generator
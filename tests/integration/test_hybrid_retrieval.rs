@@ -3,20 +3,20 @@
 //! This test validates the hybrid retrieval returns non-empty results
 //! when the database is seeded with known UI elements.
 //!
-//! Prerequisites:
-//! - Neo4j running at localhost:7687
-//! - Qdrant running at localhost:6334
+//! Runs against the in-memory graph and vector stores, so no external
+//! services are required. The `_external` variant runs the same checks
+//! against Neo4j and Qdrant and is ignored by default.
 //!
-//! Run with: cargo test --test test_hybrid_retrieval -- --ignored
+//! Neo4j + Qdrant: cargo test --test test_hybrid_retrieval -- --ignored
 
 use std::sync::Arc;
 
+use codegraph_core::{GraphStore, MemoryGraphStore, MemoryVectorStore, VectorStore};
 use codegraph_extraction::embedding::EmbeddingGenerator;
 use codegraph_graph::{Neo4jRepository, UIElement};
-use codegraph_retrieval::HybridRetriever;
-use codegraph_vector::{
-    models::PointPayload, EmbeddingPoint, QdrantConfig, QdrantRepository,
-};
+use codegraph_retrieval::hybrid::{HybridWeights, RetrievalResult};
+use codegraph_retrieval::{FulltextSource, HybridRetriever};
+use codegraph_vector::{models::PointPayload, EmbeddingPoint, QdrantConfig, QdrantRepository};
 use uuid::Uuid;
 
 /// Test collection name
@@ -34,24 +34,58 @@ fn create_test_element(name: &str, category: &str, html: &str) -> UIElement {
         css_classes: vec!["bg-blue-500".to_string(), "rounded".to_string()],
         tags: vec!["test".to_string()],
         embedding: None,
+        a11y_score: None,
+        confidence: None,
+        source_path: None,
+        content_hash: None,
+        shape: None,
+        fingerprint: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     }
 }
 
 #[tokio::test]
-#[ignore = "requires Neo4j and Qdrant"]
 async fn test_hybrid_retrieval_returns_results() {
-    // Setup: Connect to databases
-    let neo4j = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j");
-    neo4j.initialize_schema().await.ok();
+    // Setup: In-memory stores
+    let graph: Arc<dyn GraphStore> = Arc::new(MemoryGraphStore::new());
+    let vectors: Arc<dyn VectorStore> = Arc::new(MemoryVectorStore::new());
 
-    let qdrant = QdrantRepository::new(QdrantConfig::default())
-        .await
-        .expect("Failed to connect to Qdrant");
-    qdrant.init_collections().await.ok();
+    let (element_ids, result) = check_hybrid_retrieval(graph, vectors).await;
+
+    // Every result comes from the seeded elements
+    let seeded: Vec<String> = element_ids.iter().map(Uuid::to_string).collect();
+    assert!(result.elements.iter().all(|e| seeded.contains(&e.element_id)));
+}
+
+#[tokio::test]
+#[ignore = "requires Neo4j and Qdrant connections"]
+async fn test_hybrid_retrieval_returns_results_external() {
+    let graph = Arc::new(
+        Neo4jRepository::new()
+            .await
+            .expect("Failed to connect to Neo4j - is it running?"),
+    );
+    let vectors = Arc::new(
+        QdrantRepository::new(QdrantConfig::from_env())
+            .await
+            .expect("Failed to connect to Qdrant - is it running?"),
+    );
+
+    let (element_ids, _) = check_hybrid_retrieval(graph.clone(), vectors.clone()).await;
+
+    // Cleanup
+    graph.delete_batch(&element_ids).await.unwrap();
+    vectors.delete_batch(TEST_COLLECTION, element_ids).await.unwrap();
+}
+
+/// Seed three elements, then check a query finds the relevant one
+async fn check_hybrid_retrieval(
+    graph: Arc<dyn GraphStore>,
+    vectors: Arc<dyn VectorStore>,
+) -> (Vec<Uuid>, RetrievalResult) {
+    graph.initialize_schema().await.expect("Failed to initialize schema");
+    vectors.init_collections().await.expect("Failed to initialize collections");
 
     let embedding_generator = Arc::new(EmbeddingGenerator::new());
 
@@ -68,10 +102,10 @@ async fn test_hybrid_retrieval_returns_results() {
         let element = create_test_element(name, category, html);
         element_ids.push(element.id);
 
-        // Save to Neo4j
-        neo4j.save(&element).await.expect("Failed to save to Neo4j");
+        // Save to the graph store
+        graph.save(&element).await.expect("Failed to save element");
 
-        // Generate embedding and save to Qdrant
+        // Generate embedding and save to the vector store
         let embedding = embedding_generator
             .generate_text_embedding(&format!("{} {} {}", name, category, html))
             .await
@@ -79,18 +113,16 @@ async fn test_hybrid_retrieval_returns_results() {
 
         let payload = PointPayload::new(name.to_string(), category.to_string(), "component", "tailwind");
         let point = EmbeddingPoint::new(element.id, embedding.embedding, payload);
-        qdrant.upsert_point(TEST_COLLECTION, point).await.ok();
+        vectors
+            .upsert_point(TEST_COLLECTION, point)
+            .await
+            .expect("Failed to save embedding");
     }
 
-    // Step 2: Create HybridRetriever with repositories
-    // Need to create a new Neo4j connection since Neo4jRepository doesn't implement Clone
-    let neo4j_for_retriever = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j for retriever");
-
+    // Step 2: Create HybridRetriever with both stores
     let mut retriever = HybridRetriever::new()
-        .with_qdrant(Arc::new(qdrant))
-        .with_neo4j(Arc::new(neo4j_for_retriever))
+        .with_vector_store(vectors)
+        .with_graph_store(graph.clone())
         .with_embedding_generator(embedding_generator)
         .with_max_results(10);
 
@@ -107,35 +139,21 @@ async fn test_hybrid_retrieval_returns_results() {
     );
 
     // Step 5: Verify result quality
-    assert!(
-        result.latency_ms > 0,
-        "Should track latency"
-    );
-
     // The results should contain elements matching our query
     // Check that at least one result is related to buttons
     let found_relevant = result.elements.iter().any(|e| {
         e.name.to_lowercase().contains("button") || e.category.to_lowercase() == "button"
     });
     assert!(found_relevant, "Should find relevant results");
-
-    // Cleanup
-    for id in element_ids {
-        neo4j.delete(id).await.ok();
-    }
+    (element_ids, result)
 }
 
 #[tokio::test]
-#[ignore = "requires Neo4j and Qdrant"]
 async fn test_vector_search_returns_results() {
-    let qdrant = QdrantRepository::new(QdrantConfig::default())
-        .await
-        .expect("Failed to connect to Qdrant");
-    qdrant.init_collections().await.ok();
-
+    let vectors: Arc<dyn VectorStore> = Arc::new(MemoryVectorStore::new());
     let embedding_generator = Arc::new(EmbeddingGenerator::new());
 
-    // Seed Qdrant with test data
+    // Seed the vector store with test data
     let test_id = Uuid::new_v4();
     let embedding = embedding_generator
         .generate_text_embedding("a blue button with rounded corners")
@@ -144,11 +162,11 @@ async fn test_vector_search_returns_results() {
 
     let payload = PointPayload::new("Test Button", "button", "component", "tailwind");
     let point = EmbeddingPoint::new(test_id, embedding.embedding.clone(), payload);
-    qdrant.upsert_point(TEST_COLLECTION, point).await.ok();
+    vectors.upsert_point(TEST_COLLECTION, point).await.unwrap();
 
-    // Create retriever with only Qdrant
+    // Create retriever with only the vector store
     let mut retriever = HybridRetriever::new()
-        .with_qdrant(Arc::new(qdrant.clone()))
+        .with_vector_store(vectors)
         .with_embedding_generator(embedding_generator.clone())
         .with_max_results(10);
 
@@ -163,36 +181,26 @@ async fn test_vector_search_returns_results() {
         !result.elements.is_empty(),
         "Vector search should return results"
     );
-
-    // Cleanup
-    qdrant.delete_point(TEST_COLLECTION, test_id).await.ok();
+    assert_eq!(result.elements[0].element_id, test_id.to_string());
 }
 
 #[tokio::test]
-#[ignore = "requires Neo4j"]
 async fn test_fulltext_search_returns_results() {
-    let neo4j = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j");
-    neo4j.initialize_schema().await.ok();
+    let graph: Arc<dyn GraphStore> = Arc::new(MemoryGraphStore::new());
 
-    // Seed Neo4j with test data
+    // Seed the graph store with test data
     let test_element = create_test_element(
         "Primary Action Button",
         "button",
         "<button class=\"btn-primary\">Submit</button>",
     );
     let test_id = test_element.id;
-    neo4j.save(&test_element).await.expect("Failed to save");
+    graph.save(&test_element).await.expect("Failed to save");
 
-    // Create retriever with only Neo4j
-    // Need new connection since Neo4jRepository doesn't implement Clone
-    let neo4j_for_retriever = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j for retriever");
+    // Create retriever with only the graph store
     let embedding_generator = Arc::new(EmbeddingGenerator::new());
     let mut retriever = HybridRetriever::new()
-        .with_neo4j(Arc::new(neo4j_for_retriever))
+        .with_graph_store(graph)
         .with_embedding_generator(embedding_generator)
         .with_max_results(10);
 
@@ -202,21 +210,16 @@ async fn test_fulltext_search_returns_results() {
         .await
         .expect("Retrieval should succeed");
 
-    // Fulltext search may or may not return results depending on index
-    // The important thing is that retrieval completes without error
-    assert!(result.latency_ms > 0, "Should track latency");
-
-    // Cleanup
-    neo4j.delete(test_id).await.ok();
+    // Fulltext search should surface the seeded element
+    assert!(
+        result.elements.iter().any(|e| e.element_id == test_id.to_string()),
+        "Fulltext search should find the seeded element"
+    );
 }
 
 #[tokio::test]
-#[ignore = "requires Neo4j"]
 async fn test_graph_search_by_category() {
-    let neo4j = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j");
-    neo4j.initialize_schema().await.ok();
+    let graph = MemoryGraphStore::new();
 
     // Seed multiple buttons
     let mut button_ids = Vec::new();
@@ -231,44 +234,72 @@ async fn test_graph_search_by_category() {
             css_classes: vec!["btn".to_string()],
             tags: vec!["test".to_string()],
             embedding: None,
+            a11y_score: None,
+            confidence: None,
+            source_path: None,
+            content_hash: None,
+            shape: None,
+            fingerprint: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
         button_ids.push(element.id);
-        neo4j.save(&element).await.ok();
+        graph.save(&element).await.unwrap();
     }
 
     // Query by category
-    let buttons = neo4j
+    let buttons = graph
         .find_by_category("button")
         .await
         .expect("Category search should succeed");
 
-    assert!(
-        buttons.len() >= 3,
-        "Should find at least 3 buttons by category"
-    );
+    assert_eq!(buttons.len(), 3, "Should find the 3 buttons by category");
+    assert!(buttons.iter().all(|b| button_ids.contains(&b.id)));
+}
 
-    // Cleanup
-    for id in button_ids {
-        neo4j.delete(id).await.ok();
+#[tokio::test]
+async fn test_sparse_fulltext_replaces_graph_fulltext() {
+    // No graph store: the fulltext channel runs on the vector store's sparse vectors
+    let vectors: Arc<dyn VectorStore> = Arc::new(MemoryVectorStore::new());
+    let embedding_generator = Arc::new(EmbeddingGenerator::new());
+
+    let templates = [
+        ("Checkout Button", "<button class=\"btn\">Checkout</button>"),
+        ("Login Button", "<button class=\"btn\">Sign in</button>"),
+    ];
+    let mut checkout_id = None;
+    for (name, html) in templates {
+        let element = create_test_element(name, "button", html);
+        checkout_id.get_or_insert(element.id);
+        let embedding = embedding_generator.generate_text_embedding(name).await.unwrap();
+        let payload = PointPayload::new(name, "button", "component", "tailwind");
+        let lexical = codegraph_core::sparse::encode_document(&payload, Some(html));
+        let point = EmbeddingPoint::new(element.id, embedding.embedding, payload).with_sparse(lexical);
+        vectors.upsert_point(TEST_COLLECTION, point).await.unwrap();
     }
+
+    let mut retriever = HybridRetriever::new()
+        .with_vector_store(vectors)
+        .with_embedding_generator(embedding_generator)
+        .with_fulltext_source(FulltextSource::Sparse)
+        .with_hybrid_weights(HybridWeights {
+            vector_similarity: 0.0,
+            fulltext: 1.0,
+            pattern_matching: 0.0,
+        });
+
+    let result = retriever.retrieve("checkout").await.expect("Retrieval should succeed");
+    assert_eq!(result.elements.len(), 2);
+    assert_eq!(result.elements[0].element_id, checkout_id.unwrap().to_string());
+    assert!(result.elements[0].final_score > result.elements[1].final_score);
 }
 
 #[tokio::test]
-#[ignore = "requires Neo4j and Qdrant"]
 async fn test_retrieval_with_empty_query() {
-    let neo4j = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j");
-    let qdrant = QdrantRepository::new(QdrantConfig::default())
-        .await
-        .expect("Failed to connect to Qdrant");
-
     let embedding_generator = Arc::new(EmbeddingGenerator::new());
     let mut retriever = HybridRetriever::new()
-        .with_qdrant(Arc::new(qdrant))
-        .with_neo4j(Arc::new(neo4j))
+        .with_vector_store(Arc::new(MemoryVectorStore::new()))
+        .with_graph_store(Arc::new(MemoryGraphStore::new()))
         .with_embedding_generator(embedding_generator)
         .with_max_results(10);
 
@@ -279,7 +310,7 @@ async fn test_retrieval_with_empty_query() {
     match result {
         Ok(r) => {
             // Empty results are acceptable for empty query
-            assert!(r.latency_ms >= 0);
+            assert!(r.elements.is_empty());
         }
         Err(_) => {
             // Error is also acceptable for invalid query
@@ -302,25 +333,16 @@ async fn test_retriever_without_repositories() {
 
     // Results will be empty since no repositories are configured
     // But retrieval should complete without error
-    assert!(result.latency_ms >= 0);
+    assert!(result.elements.is_empty());
 }
 
 #[tokio::test]
-#[ignore = "requires Neo4j and Qdrant"]
 async fn test_result_ranking_and_deduplication() {
-    let neo4j = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j");
-    neo4j.initialize_schema().await.ok();
-
-    let qdrant = QdrantRepository::new(QdrantConfig::default())
-        .await
-        .expect("Failed to connect to Qdrant");
-    qdrant.init_collections().await.ok();
-
+    let graph: Arc<dyn GraphStore> = Arc::new(MemoryGraphStore::new());
+    let vectors: Arc<dyn VectorStore> = Arc::new(MemoryVectorStore::new());
     let embedding_generator = Arc::new(EmbeddingGenerator::new());
 
-    // Create element in both databases
+    // Create element in both stores
     let element_id = Uuid::new_v4();
     let element = UIElement {
         id: element_id,
@@ -332,30 +354,31 @@ async fn test_result_ranking_and_deduplication() {
         css_classes: vec!["btn".to_string()],
         tags: vec!["test".to_string()],
         embedding: None,
+        a11y_score: None,
+        confidence: None,
+        source_path: None,
+        content_hash: None,
+        shape: None,
+        fingerprint: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
 
-    // Save to Neo4j
-    neo4j.save(&element).await.ok();
+    // Save to the graph store
+    graph.save(&element).await.unwrap();
 
-    // Save to Qdrant
+    // Save to the vector store
     let embedding = embedding_generator
         .generate_text_embedding("duplicate test button")
         .await
         .unwrap();
     let payload = PointPayload::new("Duplicate Test Button", "button", "component", "tailwind");
     let point = EmbeddingPoint::new(element_id, embedding.embedding, payload);
-    qdrant.upsert_point(TEST_COLLECTION, point).await.ok();
-
-    // Query - need to create new Neo4j connection since it doesn't implement Clone
-    let neo4j2 = Neo4jRepository::new()
-        .await
-        .expect("Failed to connect to Neo4j");
+    vectors.upsert_point(TEST_COLLECTION, point).await.unwrap();
 
     let mut retriever = HybridRetriever::new()
-        .with_qdrant(Arc::new(qdrant.clone()))
-        .with_neo4j(Arc::new(neo4j2))
+        .with_vector_store(vectors)
+        .with_graph_store(graph)
         .with_embedding_generator(embedding_generator)
         .with_max_results(10);
 
@@ -368,8 +391,8 @@ async fn test_result_ranking_and_deduplication() {
     let element_id_str = element_id.to_string();
     let count = result.elements.iter().filter(|e| e.element_id == element_id_str).count();
     assert!(
-        count <= 1,
-        "Same element should not appear more than once (found {} times)",
+        count == 1,
+        "Same element should appear exactly once (found {} times)",
         count
     );
 
@@ -380,8 +403,4 @@ async fn test_result_ranking_and_deduplication() {
             "Results should be ranked by score descending"
         );
     }
-
-    // Cleanup
-    neo4j.delete(element_id).await.ok();
-    qdrant.delete_point(TEST_COLLECTION, element_id).await.ok();
 }